@@ -0,0 +1,46 @@
+-- ============================================
+-- MAILING LISTS
+-- ============================================
+
+-- ============================================
+-- 21. MAILING_LISTS TABLE
+-- ============================================
+CREATE TABLE IF NOT EXISTS mailing_lists (
+    id VARCHAR(36) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    domain_id VARCHAR(36) NOT NULL,
+    list_name VARCHAR(64) NOT NULL,
+    list_address VARCHAR(255) NOT NULL UNIQUE,
+    description VARCHAR(255),
+    list_type VARCHAR(20) NOT NULL DEFAULT 'discussion',
+    moderation VARCHAR(20) NOT NULL DEFAULT 'non_members',
+    reply_to_list BOOLEAN NOT NULL DEFAULT TRUE,
+    is_active BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    CONSTRAINT fk_mailing_lists_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    CONSTRAINT fk_mailing_lists_domain FOREIGN KEY (domain_id) REFERENCES domains(id) ON DELETE CASCADE,
+    INDEX idx_mailing_lists_user_id (user_id),
+    INDEX idx_mailing_lists_domain_id (domain_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- ============================================
+-- 22. MAILING_LIST_SUBSCRIBERS TABLE
+-- ============================================
+CREATE TABLE IF NOT EXISTS mailing_list_subscribers (
+    id VARCHAR(36) PRIMARY KEY,
+    list_id VARCHAR(36) NOT NULL,
+    email VARCHAR(255) NOT NULL,
+    name VARCHAR(255),
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    confirm_token VARCHAR(64) NULL,
+    token_expires_at TIMESTAMP NULL,
+    confirmed_at TIMESTAMP NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    UNIQUE KEY unique_list_subscriber (list_id, email),
+    CONSTRAINT fk_mailing_list_subscribers_list FOREIGN KEY (list_id) REFERENCES mailing_lists(id) ON DELETE CASCADE,
+    INDEX idx_mailing_list_subscribers_list_id (list_id),
+    INDEX idx_mailing_list_subscribers_token (confirm_token)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,29 @@
+-- ============================================
+-- EMAIL SPAM POLICY
+-- ============================================
+
+-- Override per mailbox (NULL = ikut kebijakan domain)
+ALTER TABLE email_accounts
+    ADD COLUMN spam_enabled BOOLEAN NULL,
+    ADD COLUMN spam_threshold DOUBLE NULL,
+    ADD COLUMN spam_action VARCHAR(20) NULL,
+    ADD COLUMN spam_whitelist TEXT NULL,
+    ADD COLUMN spam_blacklist TEXT NULL;
+
+-- ============================================
+-- 23. EMAIL_DOMAIN_SPAM_POLICIES TABLE
+-- ============================================
+CREATE TABLE IF NOT EXISTS email_domain_spam_policies (
+    domain_id VARCHAR(36) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    threshold DOUBLE NOT NULL DEFAULT 5.0,
+    action VARCHAR(20) NOT NULL DEFAULT 'junk',
+    whitelist TEXT,
+    blacklist TEXT,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    CONSTRAINT fk_email_domain_spam_policies_domain FOREIGN KEY (domain_id) REFERENCES domains(id) ON DELETE CASCADE,
+    CONSTRAINT fk_email_domain_spam_policies_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    INDEX idx_email_domain_spam_policies_user_id (user_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
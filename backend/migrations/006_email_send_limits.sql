@@ -0,0 +1,53 @@
+-- ============================================
+-- EMAIL OUTBOUND SEND LIMITS
+-- ============================================
+
+-- Limit per mailbox (NULL = pakai default server)
+ALTER TABLE email_accounts
+    ADD COLUMN send_limit_hourly INT NULL,
+    ADD COLUMN send_limit_daily INT NULL;
+
+-- ============================================
+-- 24. EMAIL_DOMAIN_SEND_LIMITS TABLE
+-- ============================================
+CREATE TABLE IF NOT EXISTS email_domain_send_limits (
+    domain_id VARCHAR(36) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    hourly_limit INT NULL,
+    daily_limit INT NULL,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    CONSTRAINT fk_email_domain_send_limits_domain FOREIGN KEY (domain_id) REFERENCES domains(id) ON DELETE CASCADE,
+    CONSTRAINT fk_email_domain_send_limits_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    INDEX idx_email_domain_send_limits_user_id (user_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- ============================================
+-- 25. EMAIL_SEND_USAGE TABLE
+-- ============================================
+-- Jumlah penerima per jam (scope_key: "account:<email>" atau "domain:<domain_id>")
+CREATE TABLE IF NOT EXISTS email_send_usage (
+    scope_key VARCHAR(300) NOT NULL,
+    bucket_start DATETIME NOT NULL,
+    recipients INT NOT NULL DEFAULT 0,
+
+    PRIMARY KEY (scope_key, bucket_start),
+    INDEX idx_email_send_usage_bucket (bucket_start)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- ============================================
+-- 26. EMAIL_SEND_LIMIT_ALERTS TABLE
+-- ============================================
+CREATE TABLE IF NOT EXISTS email_send_limit_alerts (
+    id VARCHAR(36) PRIMARY KEY,
+    email_address VARCHAR(255) NOT NULL,
+    domain_id VARCHAR(36) NOT NULL,
+    scope VARCHAR(20) NOT NULL,
+    period VARCHAR(20) NOT NULL,
+    limit_value INT NOT NULL,
+    client_address VARCHAR(45),
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT fk_email_send_limit_alerts_domain FOREIGN KEY (domain_id) REFERENCES domains(id) ON DELETE CASCADE,
+    INDEX idx_email_send_limit_alerts_created_at (created_at)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,8 @@
+-- ============================================
+-- FTP BACKEND (PURE-FTPD MYSQL AUTH)
+-- ============================================
+
+-- uid/gid system user pemilik, dibaca Pure-FTPd (MYSQLGetUID / MYSQLGetGID)
+ALTER TABLE ftp_accounts
+    ADD COLUMN system_uid INT NULL,
+    ADD COLUMN system_gid INT NULL;
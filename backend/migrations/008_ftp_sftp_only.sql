@@ -0,0 +1,7 @@
+-- ============================================
+-- SFTP-ONLY FTP ACCOUNTS
+-- ============================================
+
+-- Akun SFTP-only dilayani OpenSSH (Match User + ChrootDirectory), bukan Pure-FTPd
+ALTER TABLE ftp_accounts
+    ADD COLUMN sftp_only BOOLEAN NOT NULL DEFAULT FALSE;
@@ -0,0 +1,25 @@
+-- ============================================
+-- DATABASE IMPORTS (dump upload & restore)
+-- ============================================
+
+-- ============================================
+-- 27. DATABASE_IMPORTS TABLE
+-- ============================================
+CREATE TABLE IF NOT EXISTS database_imports (
+    id VARCHAR(36) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    database_id VARCHAR(36) NOT NULL,
+    source VARCHAR(20) NOT NULL DEFAULT 'upload',
+    filename VARCHAR(255) NOT NULL,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    total_bytes BIGINT NOT NULL DEFAULT 0,
+    processed_bytes BIGINT NOT NULL DEFAULT 0,
+    error_message TEXT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    completed_at TIMESTAMP NULL,
+
+    CONSTRAINT fk_database_imports_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    CONSTRAINT fk_database_imports_db FOREIGN KEY (database_id) REFERENCES managed_databases(id) ON DELETE CASCADE,
+    INDEX idx_database_imports_user_id (user_id),
+    INDEX idx_database_imports_database_id (database_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
@@ -0,0 +1,26 @@
+-- ============================================
+-- DATABASE USER GRANTS (satu user, banyak database)
+-- ============================================
+
+-- ============================================
+-- 28. DATABASE_USER_GRANTS TABLE
+-- ============================================
+CREATE TABLE IF NOT EXISTS database_user_grants (
+    id VARCHAR(36) PRIMARY KEY,
+    database_user_id VARCHAR(36) NOT NULL,
+    database_id VARCHAR(36) NOT NULL,
+    privileges VARCHAR(255) NOT NULL DEFAULT 'ALL PRIVILEGES',
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP ON UPDATE CURRENT_TIMESTAMP,
+
+    CONSTRAINT fk_database_user_grants_user FOREIGN KEY (database_user_id) REFERENCES database_users(id) ON DELETE CASCADE,
+    CONSTRAINT fk_database_user_grants_db FOREIGN KEY (database_id) REFERENCES managed_databases(id) ON DELETE CASCADE,
+    UNIQUE KEY uk_database_user_grants (database_user_id, database_id),
+    INDEX idx_database_user_grants_database_id (database_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Pindahkan assignment lama (database_users.database_id) ke tabel grants
+INSERT IGNORE INTO database_user_grants (id, database_user_id, database_id, privileges, created_at, updated_at)
+SELECT UUID(), id, database_id, COALESCE(privileges, 'ALL PRIVILEGES'), created_at, updated_at
+FROM database_users
+WHERE database_id IS NOT NULL;
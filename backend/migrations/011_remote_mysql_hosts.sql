@@ -0,0 +1,21 @@
+-- ============================================
+-- REMOTE MYSQL ACCESS (allow-list IP/CIDR per akun)
+-- ============================================
+
+-- ============================================
+-- 29. REMOTE_MYSQL_HOSTS TABLE
+-- ============================================
+CREATE TABLE IF NOT EXISTS remote_mysql_hosts (
+    id VARCHAR(36) PRIMARY KEY,
+    user_id VARCHAR(36) NOT NULL,
+    host VARCHAR(64) NOT NULL,
+    description VARCHAR(255) NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+
+    CONSTRAINT fk_remote_mysql_hosts_user FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE CASCADE,
+    UNIQUE KEY uk_remote_mysql_hosts (user_id, host),
+    INDEX idx_remote_mysql_hosts_user_id (user_id)
+) ENGINE=InnoDB DEFAULT CHARSET=utf8mb4 COLLATE=utf8mb4_unicode_ci;
+
+-- Database user baru dibuat sebagai user@localhost (remote lewat allow-list)
+ALTER TABLE database_users MODIFY host VARCHAR(255) NOT NULL DEFAULT 'localhost';
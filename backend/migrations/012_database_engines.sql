@@ -0,0 +1,11 @@
+-- ============================================
+-- DATABASE ENGINES (MySQL / PostgreSQL)
+-- ============================================
+
+-- Managed database dan database user yang sudah ada tetap MySQL
+ALTER TABLE managed_databases
+    ADD COLUMN engine VARCHAR(20) NOT NULL DEFAULT 'mysql' AFTER user_id;
+
+ALTER TABLE database_users
+    ADD COLUMN engine VARCHAR(20) NOT NULL DEFAULT 'mysql' AFTER database_id;
+
@@ -0,0 +1,7 @@
+-- ============================================
+-- DATABASE STATS (ukuran managed database)
+-- ============================================
+
+-- Waktu terakhir size_bytes dihitung oleh stats collector
+ALTER TABLE managed_databases
+    ADD COLUMN size_updated_at TIMESTAMP NULL AFTER size_bytes;
@@ -0,0 +1,8 @@
+-- ============================================
+-- DATABASE USER SECRETS (phpMyAdmin SSO)
+-- ============================================
+
+-- Password database user terenkripsi AES-256-GCM dengan master key panel
+-- Format: v1:<key_id>:<base64(nonce || ciphertext)>
+ALTER TABLE database_users
+    ADD COLUMN password_encrypted TEXT NULL AFTER password_hash;
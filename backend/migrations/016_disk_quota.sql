@@ -0,0 +1,7 @@
+-- ============================================
+-- DISK QUOTA PER USER
+-- ============================================
+
+-- Batas disk user dalam MB: NULL = DISK_QUOTA_DEFAULT_MB, 0 = tanpa batas
+ALTER TABLE users
+    ADD COLUMN disk_quota_mb BIGINT NULL;
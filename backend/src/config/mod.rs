@@ -31,9 +31,15 @@ pub struct AppConfig {
     /// Konfigurasi keamanan
     pub security: SecurityConfig,
 
+    /// Master key enkripsi kredensial
+    pub secrets: SecretsConfig,
+
     /// Konfigurasi file management
     pub file: FileConfig,
 
+    /// Informasi koneksi FTP server
+    pub ftp: FtpConfig,
+
     /// URL phpMyAdmin
     pub phpmyadmin_url: String,
 }
@@ -77,6 +83,24 @@ pub struct SecurityConfig {
     pub password_require_special: bool,
 }
 
+/// Konfigurasi master key untuk enkripsi kredensial (AES-256-GCM)
+///
+/// Rotasi: set key baru sebagai `SECRETS_MASTER_KEY` dengan ID baru, pindahkan
+/// key lama ke `SECRETS_PREVIOUS_KEYS` sampai semua kredensial dienkripsi ulang.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretsConfig {
+    /// Master key aktif (base64, 32 bytes); kosong = enkripsi nonaktif
+    #[serde(skip_serializing)]
+    pub master_key: Option<String>,
+
+    /// ID master key aktif (disimpan bersama ciphertext)
+    pub master_key_id: String,
+
+    /// Key lama untuk dekripsi, format `id:base64`
+    #[serde(skip_serializing)]
+    pub previous_keys: Vec<String>,
+}
+
 /// Konfigurasi file management
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileConfig {
@@ -88,6 +112,62 @@ pub struct FileConfig {
 
     /// Ekstensi file yang dilarang untuk upload
     pub forbidden_extensions: Vec<String>,
+
+    /// Group web server yang boleh dipakai untuk chown
+    pub web_group: String,
+}
+
+/// Informasi koneksi FTP server yang ditampilkan ke user
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FtpConfig {
+    /// Hostname publik FTP server
+    pub host: String,
+
+    /// Port FTP (plain / explicit TLS)
+    pub port: u16,
+
+    /// Port FTPS implicit
+    pub ftps_port: u16,
+
+    /// Port SFTP
+    pub sftp_port: u16,
+
+    /// Awal range port passive mode
+    pub passive_port_min: u16,
+
+    /// Akhir range port passive mode
+    pub passive_port_max: u16,
+
+    /// Mode TLS FTP
+    pub tls_mode: FtpTlsMode,
+
+    /// Path sertifikat TLS FTP daemon
+    pub tls_cert_path: String,
+
+    /// Fingerprint SHA-256 sertifikat (kosong = dibaca dari sertifikat)
+    pub cert_fingerprint: Option<String>,
+}
+
+/// Mode TLS FTP server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FtpTlsMode {
+    /// Tanpa TLS (plain FTP)
+    None,
+    /// AUTH TLS di port FTP (FTPES)
+    Explicit,
+    /// TLS langsung di port FTPS
+    Implicit,
+}
+
+impl From<String> for FtpTlsMode {
+    fn from(s: String) -> Self {
+        match s.to_lowercase().as_str() {
+            "none" | "off" | "plain" => Self::None,
+            "implicit" => Self::Implicit,
+            _ => Self::Explicit,
+        }
+    }
 }
 
 /// Mode environment aplikasi
@@ -174,6 +254,22 @@ impl AppConfig {
                 password_require_special: true,
             },
 
+            secrets: SecretsConfig {
+                master_key: env::var("SECRETS_MASTER_KEY")
+                    .ok()
+                    .filter(|v| !v.trim().is_empty()),
+                master_key_id: env::var("SECRETS_MASTER_KEY_ID")
+                    .ok()
+                    .filter(|v| !v.trim().is_empty())
+                    .unwrap_or_else(|| "1".to_string()),
+                previous_keys: env::var("SECRETS_PREVIOUS_KEYS")
+                    .unwrap_or_default()
+                    .split(',')
+                    .map(|s| s.trim().to_string())
+                    .filter(|s| !s.is_empty())
+                    .collect(),
+            },
+
             file: FileConfig {
                 user_home_base: env::var("USER_HOME_BASE")
                     .unwrap_or_else(|_| "/tmp/nusa-panel-users".to_string()),
@@ -189,6 +285,39 @@ impl AppConfig {
                     "ps1".to_string(),
                     "vbs".to_string(),
                 ],
+                web_group: env::var("FILE_WEB_GROUP").unwrap_or_else(|_| "www-data".to_string()),
+            },
+
+            ftp: FtpConfig {
+                host: env::var("FTP_HOST").unwrap_or_else(|_| "localhost".to_string()),
+                port: env::var("FTP_PORT")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(21),
+                ftps_port: env::var("FTP_FTPS_PORT")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(990),
+                sftp_port: env::var("FTP_SFTP_PORT")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(22),
+                passive_port_min: env::var("FTP_PASSIVE_PORT_MIN")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(49152),
+                passive_port_max: env::var("FTP_PASSIVE_PORT_MAX")
+                    .ok()
+                    .and_then(|v| v.parse().ok())
+                    .unwrap_or(65534),
+                tls_mode: env::var("FTP_TLS_MODE")
+                    .unwrap_or_else(|_| "explicit".to_string())
+                    .into(),
+                tls_cert_path: env::var("FTP_TLS_CERT")
+                    .unwrap_or_else(|_| "/etc/ssl/private/pure-ftpd.pem".to_string()),
+                cert_fingerprint: env::var("FTP_TLS_CERT_FINGERPRINT")
+                    .ok()
+                    .filter(|v| !v.trim().is_empty()),
             },
 
             phpmyadmin_url: env::var("PHPMYADMIN_URL")
@@ -224,4 +353,12 @@ mod tests {
         assert_eq!(Environment::from("development".to_string()), Environment::Development);
         assert_eq!(Environment::from("unknown".to_string()), Environment::Development);
     }
+
+    #[test]
+    fn test_ftp_tls_mode_from_string() {
+        assert_eq!(FtpTlsMode::from("implicit".to_string()), FtpTlsMode::Implicit);
+        assert_eq!(FtpTlsMode::from("NONE".to_string()), FtpTlsMode::None);
+        assert_eq!(FtpTlsMode::from("explicit".to_string()), FtpTlsMode::Explicit);
+        assert_eq!(FtpTlsMode::from("".to_string()), FtpTlsMode::Explicit);
+    }
 }
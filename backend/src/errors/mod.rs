@@ -92,6 +92,14 @@ pub enum ApiError {
     #[error("{0} sedang digunakan dan tidak bisa dihapus")]
     InUse(String),
 
+    /// Offset chunk resumable upload tidak sesuai dengan data yang diterima
+    #[error("Offset upload tidak sesuai, lanjutkan dari offset {0}")]
+    UploadOffsetMismatch(u64),
+
+    /// Background job dibatalkan user
+    #[error("Job dibatalkan")]
+    JobCancelled,
+
     // ==========================================
     // Server Errors (500, 503)
     // ==========================================
@@ -137,6 +145,14 @@ pub enum ApiError {
     /// Permission denied untuk file operation
     #[error("Tidak memiliki izin untuk mengakses file/folder ini")]
     FilePermissionDenied,
+
+    /// Range download di luar ukuran file
+    #[error("Range tidak valid untuk file berukuran {0} bytes")]
+    RangeNotSatisfiable(u64),
+
+    /// Kuota disk user terlampaui
+    #[error("Kuota disk terlampaui (batas {0} bytes)")]
+    QuotaExceeded(u64),
 }
 
 impl From<String> for ApiError {
@@ -169,7 +185,10 @@ impl ApiError {
             Self::NotFound(_) | Self::FileNotFound(_) => Status::NotFound,
 
             // 409 Conflict
-            Self::AlreadyExists(_) | Self::InUse(_) => Status::Conflict,
+            Self::AlreadyExists(_)
+            | Self::InUse(_)
+            | Self::UploadOffsetMismatch(_)
+            | Self::JobCancelled => Status::Conflict,
 
             // 413 Payload Too Large
             Self::FileTooLarge(_) => Status::PayloadTooLarge,
@@ -177,9 +196,15 @@ impl ApiError {
             // 415 Unsupported Media Type
             Self::FileTypeNotAllowed(_) => Status::UnsupportedMediaType,
 
+            // 416 Range Not Satisfiable
+            Self::RangeNotSatisfiable(_) => Status::RangeNotSatisfiable,
+
             // 429 Too Many Requests
             Self::RateLimitExceeded(_) => Status::TooManyRequests,
 
+            // 507 Insufficient Storage
+            Self::QuotaExceeded(_) => Status::InsufficientStorage,
+
             // 500 Internal Server Error
             Self::DatabaseError(_) | Self::InternalError(_) | Self::IoError(_) => Status::InternalServerError,
 
@@ -203,6 +228,8 @@ impl ApiError {
             Self::NotFound(_) => "NOT_FOUND",
             Self::AlreadyExists(_) => "ALREADY_EXISTS",
             Self::InUse(_) => "IN_USE",
+            Self::UploadOffsetMismatch(_) => "UPLOAD_OFFSET_MISMATCH",
+            Self::JobCancelled => "JOB_CANCELLED",
             Self::DatabaseError(_) => "DATABASE_ERROR",
             Self::InternalError(_) => "INTERNAL_ERROR",
             Self::IoError(_) => "IO_ERROR",
@@ -212,6 +239,8 @@ impl ApiError {
             Self::FileTooLarge(_) => "FILE_TOO_LARGE",
             Self::FileTypeNotAllowed(_) => "FILE_TYPE_NOT_ALLOWED",
             Self::FilePermissionDenied => "FILE_PERMISSION_DENIED",
+            Self::RangeNotSatisfiable(_) => "RANGE_NOT_SATISFIABLE",
+            Self::QuotaExceeded(_) => "QUOTA_EXCEEDED",
         }
     }
 }
@@ -13,6 +13,10 @@ use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 use nusa_panel::config::AppConfig;
 use nusa_panel::database::Database;
 use nusa_panel::routes;
+use nusa_panel::services::{
+    ChunkedUploadService, DatabaseStatsService, JobService, MailPolicyServer,
+    RemoteMysqlService, SftpService, TrashService,
+};
 use rocket::fs::{FileServer, NamedFile};
 use std::path::PathBuf;
 
@@ -92,6 +96,20 @@ async fn rocket() -> _ {
         .attach(cors)
         // Attach database connection pool
         .attach(Database::fairing())
+        // Attach Postfix policy server (send limits)
+        .attach(MailPolicyServer::fairing())
+        // Attach managed database size collector
+        .attach(DatabaseStatsService::fairing())
+        // Attach garbage collector resumable upload
+        .attach(ChunkedUploadService::fairing())
+        // Attach background job runner (recovery & retention)
+        .attach(JobService::fairing())
+        // Attach retention trash bin
+        .attach(TrashService::fairing())
+        // Attach bind mount akun SFTP-only
+        .attach(SftpService::fairing())
+        // Attach pemulihan firewall remote MySQL
+        .attach(RemoteMysqlService::fairing())
         // Attach application config
         // Attach application config
         // .attach(AdHoc::config::<AppConfig>())
@@ -102,9 +120,11 @@ async fn rocket() -> _ {
         .mount("/api/users", routes::user_routes())
         .mount("/api/domains", routes::domain_routes())
         .mount("/api/files", routes::file_routes())
+        .mount("/api/jobs", routes::job_routes())
         .mount("/api/ftp", routes::ftp_routes())
         .mount("/api/databases", routes::database_routes())
         .mount("/api/emails", routes::email_routes())
+        .mount("/api/mailing-lists", routes::mailing_list_routes())
         .mount("/api/web-server", routes::web_server_routes())
         .mount("/api/security", routes::security_routes())
         .mount("/api/system", routes::system_routes())
@@ -112,6 +132,7 @@ async fn rocket() -> _ {
         .mount("/api/redis", routes::redis_routes())
         .mount("/api/nodejs", routes::nodejs_routes())
         .mount("/api/phpmyadmin", routes::phpmyadmin_routes())
+        .mount("/api/webmail", routes::webmail_routes())
         // Serve Static Files for Frontend
         .mount("/", FileServer::from(&frontend_path))
         // Frontend
@@ -8,6 +8,8 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use validator::Validate;
 
+use crate::models::WebmailConfig;
+
 /// Email Account entity dari database
 #[derive(Debug, Clone, FromRow, Serialize)]
 pub struct EmailAccount {
@@ -44,6 +46,40 @@ pub struct EmailAccount {
 
     /// Login terakhir
     pub last_login: Option<DateTime<Utc>>,
+
+    /// Override filter spam (NULL = ikut kebijakan domain)
+    pub spam_enabled: Option<bool>,
+
+    /// Override threshold skor spam
+    pub spam_threshold: Option<f64>,
+
+    /// Override aksi spam (junk, discard)
+    pub spam_action: Option<String>,
+
+    /// Whitelist pengirim (satu pattern per baris)
+    pub spam_whitelist: Option<String>,
+
+    /// Blacklist pengirim (satu pattern per baris)
+    pub spam_blacklist: Option<String>,
+
+    /// Limit penerima per jam (NULL = default server)
+    pub send_limit_hourly: Option<i32>,
+
+    /// Limit penerima per hari (NULL = default server)
+    pub send_limit_daily: Option<i32>,
+}
+
+impl EmailAccount {
+    /// Override kebijakan spam milik mailbox ini
+    pub fn spam_override(&self) -> SpamPolicyOverride {
+        SpamPolicyOverride {
+            enabled: self.spam_enabled,
+            threshold: self.spam_threshold,
+            action: self.spam_action.clone(),
+            whitelist: split_patterns(self.spam_whitelist.as_deref()),
+            blacklist: split_patterns(self.spam_blacklist.as_deref()),
+        }
+    }
 }
 
 /// Response DTO untuk Email Account
@@ -62,6 +98,12 @@ pub struct EmailAccountResponse {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub last_login: Option<DateTime<Utc>>,
+    /// Override kebijakan spam mailbox (field kosong = ikut domain)
+    pub spam_policy: SpamPolicyOverride,
+    /// Limit penerima per jam (null = default server)
+    pub send_limit_hourly: Option<i32>,
+    /// Limit penerima per hari (null = default server)
+    pub send_limit_daily: Option<i32>,
     /// Info untuk webmail
     pub webmail_info: WebmailInfo,
 }
@@ -74,6 +116,9 @@ impl From<EmailAccount> for EmailAccountResponse {
             0.0
         };
 
+        let webmail = WebmailConfig::default();
+        let spam_policy = account.spam_override();
+
         Self {
             id: account.id.clone(),
             user_id: account.user_id,
@@ -88,13 +133,16 @@ impl From<EmailAccount> for EmailAccountResponse {
             created_at: account.created_at,
             updated_at: account.updated_at,
             last_login: account.last_login,
+            spam_policy,
+            send_limit_hourly: account.send_limit_hourly,
+            send_limit_daily: account.send_limit_daily,
             webmail_info: WebmailInfo {
-                url: "https://webmail.example.com".to_string(),
+                url: webmail.base_url,
                 username: account.email_address,
-                imap_server: "mail.example.com".to_string(),
-                imap_port: 993,
-                smtp_server: "mail.example.com".to_string(),
-                smtp_port: 587,
+                imap_server: webmail.imap_host,
+                imap_port: webmail.imap_port,
+                smtp_server: webmail.smtp_host,
+                smtp_port: webmail.smtp_port,
             },
         }
     }
@@ -151,6 +199,220 @@ pub struct UpdateEmailAccountRequest {
 
     /// Status aktif
     pub is_active: Option<bool>,
+
+    /// Override kebijakan spam mailbox
+    #[validate]
+    pub spam_policy: Option<UpdateSpamPolicyRequest>,
+
+    /// Limit penerima per jam
+    #[validate(range(min = 1, max = 100000, message = "Limit per jam harus 1-100000"))]
+    pub send_limit_hourly: Option<i32>,
+
+    /// Limit penerima per hari
+    #[validate(range(min = 1, max = 1000000, message = "Limit per hari harus 1-1000000"))]
+    pub send_limit_daily: Option<i32>,
+}
+
+// ==========================================
+// SPAM POLICY
+// ==========================================
+
+/// Aksi yang didukung untuk email spam
+pub const SPAM_ACTIONS: &[&str] = &["junk", "discard"];
+
+/// Jumlah maksimum pattern whitelist/blacklist
+pub const MAX_SPAM_PATTERNS: usize = 200;
+
+/// Kebijakan spam efektif untuk mailbox/domain
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SpamPolicy {
+    /// Filter spam aktif
+    pub enabled: bool,
+
+    /// Skor minimum untuk dianggap spam
+    pub threshold: f64,
+
+    /// Aksi untuk spam: "junk" (pindah ke folder Junk) atau "discard"
+    pub action: String,
+
+    /// Pattern pengirim yang selalu diterima (e.g., "*@partner.com")
+    pub whitelist: Vec<String>,
+
+    /// Pattern pengirim yang selalu dibuang
+    pub blacklist: Vec<String>,
+}
+
+impl Default for SpamPolicy {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            threshold: 5.0,
+            action: "junk".to_string(),
+            whitelist: Vec::new(),
+            blacklist: Vec::new(),
+        }
+    }
+}
+
+impl SpamPolicy {
+    /// Terapkan override mailbox di atas kebijakan ini
+    ///
+    /// Field skalar diganti jika di-set, whitelist/blacklist digabung.
+    pub fn with_override(&self, overrides: &SpamPolicyOverride) -> SpamPolicy {
+        let mut whitelist = self.whitelist.clone();
+        for pattern in &overrides.whitelist {
+            if !whitelist.contains(pattern) {
+                whitelist.push(pattern.clone());
+            }
+        }
+
+        let mut blacklist = self.blacklist.clone();
+        for pattern in &overrides.blacklist {
+            if !blacklist.contains(pattern) {
+                blacklist.push(pattern.clone());
+            }
+        }
+
+        SpamPolicy {
+            enabled: overrides.enabled.unwrap_or(self.enabled),
+            threshold: overrides.threshold.unwrap_or(self.threshold),
+            action: overrides.action.clone().unwrap_or_else(|| self.action.clone()),
+            whitelist,
+            blacklist,
+        }
+    }
+}
+
+/// Override kebijakan spam per mailbox (None = ikut domain)
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SpamPolicyOverride {
+    pub enabled: Option<bool>,
+    pub threshold: Option<f64>,
+    pub action: Option<String>,
+    pub whitelist: Vec<String>,
+    pub blacklist: Vec<String>,
+}
+
+/// Kebijakan spam per domain entity
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct DomainSpamPolicy {
+    /// Domain ID
+    pub domain_id: String,
+
+    /// User ID pemilik
+    pub user_id: String,
+
+    /// Filter spam aktif
+    pub enabled: bool,
+
+    /// Skor minimum untuk dianggap spam
+    pub threshold: f64,
+
+    /// Aksi untuk spam (junk, discard)
+    pub action: String,
+
+    /// Whitelist pengirim (satu pattern per baris)
+    pub whitelist: Option<String>,
+
+    /// Blacklist pengirim (satu pattern per baris)
+    pub blacklist: Option<String>,
+
+    /// Waktu update terakhir
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<DomainSpamPolicy> for SpamPolicy {
+    fn from(policy: DomainSpamPolicy) -> Self {
+        Self {
+            enabled: policy.enabled,
+            threshold: policy.threshold,
+            action: policy.action,
+            whitelist: split_patterns(policy.whitelist.as_deref()),
+            blacklist: split_patterns(policy.blacklist.as_deref()),
+        }
+    }
+}
+
+/// Response DTO kebijakan spam efektif mailbox
+#[derive(Debug, Serialize)]
+pub struct EmailSpamPolicyResponse {
+    /// Alamat email
+    pub email_address: String,
+    /// Kebijakan yang benar-benar diterapkan
+    pub effective: SpamPolicy,
+    /// Kebijakan domain
+    pub domain_policy: SpamPolicy,
+    /// Override mailbox
+    pub account_override: SpamPolicyOverride,
+}
+
+/// DTO untuk update kebijakan spam (mailbox atau domain)
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateSpamPolicyRequest {
+    /// Filter spam aktif
+    pub enabled: Option<bool>,
+
+    /// Skor minimum untuk dianggap spam
+    #[validate(range(min = 1.0, max = 50.0, message = "Threshold harus 1-50"))]
+    pub threshold: Option<f64>,
+
+    /// Aksi untuk spam (junk, discard)
+    #[validate(custom = "validate_spam_action")]
+    pub action: Option<String>,
+
+    /// Whitelist pengirim
+    #[validate(custom = "validate_sender_patterns")]
+    pub whitelist: Option<Vec<String>>,
+
+    /// Blacklist pengirim
+    #[validate(custom = "validate_sender_patterns")]
+    pub blacklist: Option<Vec<String>>,
+}
+
+fn validate_spam_action(action: &str) -> Result<(), validator::ValidationError> {
+    if SPAM_ACTIONS.contains(&action) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_spam_action"))
+    }
+}
+
+fn validate_sender_patterns(patterns: &[String]) -> Result<(), validator::ValidationError> {
+    if patterns.len() > MAX_SPAM_PATTERNS {
+        return Err(validator::ValidationError::new("too_many_patterns"));
+    }
+    if patterns
+        .iter()
+        .any(|p| p.len() > 255 || !SENDER_PATTERN_REGEX.is_match(p))
+    {
+        return Err(validator::ValidationError::new("invalid_sender_pattern"));
+    }
+    Ok(())
+}
+
+/// Pecah kolom pattern (satu per baris) menjadi list
+pub fn split_patterns(value: Option<&str>) -> Vec<String> {
+    value
+        .unwrap_or_default()
+        .lines()
+        .map(|l| l.trim().to_lowercase())
+        .filter(|l| !l.is_empty())
+        .collect()
+}
+
+/// Gabungkan list pattern menjadi kolom (satu per baris), None jika kosong
+pub fn join_patterns(patterns: &[String]) -> Option<String> {
+    let normalized: Vec<String> = patterns
+        .iter()
+        .map(|p| p.trim().to_lowercase())
+        .filter(|p| !p.is_empty())
+        .collect();
+
+    if normalized.is_empty() {
+        None
+    } else {
+        Some(normalized.join("\n"))
+    }
 }
 
 /// Email Forwarder entity
@@ -317,6 +579,39 @@ pub static EMAIL_USERNAME_REGEX: once_cell::sync::Lazy<regex::Regex> =
         regex::Regex::new(r"^[a-zA-Z0-9][a-zA-Z0-9._-]*$").unwrap()
     });
 
+/// Konfigurasi filter spam (Sieve script per mailbox via Dovecot `sieve_before`)
+#[derive(Debug, Clone)]
+pub struct SpamFilterConfig {
+    /// Direktori script Sieve yang di-generate panel
+    pub sieve_dir: String,
+
+    /// Binary compiler Sieve
+    pub sievec_bin: String,
+}
+
+impl Default for SpamFilterConfig {
+    fn default() -> Self {
+        Self {
+            sieve_dir: std::env::var("MAIL_SIEVE_DIR")
+                .unwrap_or_else(|_| "/var/mail/sieve/nusa".to_string()),
+            sievec_bin: std::env::var("MAIL_SIEVEC_BIN").unwrap_or_else(|_| "sievec".to_string()),
+        }
+    }
+}
+
+impl SpamFilterConfig {
+    /// Path script Sieve untuk mailbox (cocok dengan `sieve_before = <dir>/%u.sieve`)
+    pub fn script_path(&self, email_address: &str) -> String {
+        format!("{}/{}.sieve", self.sieve_dir.trim_end_matches('/'), email_address)
+    }
+}
+
+/// Regex untuk pattern pengirim whitelist/blacklist (wildcard `*` dan `?` diizinkan)
+pub static SENDER_PATTERN_REGEX: once_cell::sync::Lazy<regex::Regex> =
+    once_cell::sync::Lazy::new(|| {
+        regex::Regex::new(r"^[a-zA-Z0-9._%+*?-]+@[a-zA-Z0-9*?-]+(\.[a-zA-Z0-9*?-]+)*$").unwrap()
+    });
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -345,9 +640,54 @@ mod tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             last_login: None,
+            spam_enabled: None,
+            spam_threshold: None,
+            spam_action: None,
+            spam_whitelist: None,
+            spam_blacklist: None,
+            send_limit_hourly: None,
+            send_limit_daily: None,
         };
 
         let response = EmailAccountResponse::from(account);
         assert!((response.quota_percentage - 25.0).abs() < 0.01);
     }
+
+    #[test]
+    fn test_sender_pattern_regex() {
+        assert!(SENDER_PATTERN_REGEX.is_match("john@example.com"));
+        assert!(SENDER_PATTERN_REGEX.is_match("*@partner.co.id"));
+        assert!(SENDER_PATTERN_REGEX.is_match("news-*@*.example.com"));
+        assert!(!SENDER_PATTERN_REGEX.is_match("example.com"));
+        assert!(!SENDER_PATTERN_REGEX.is_match("a\"b@example.com"));
+        assert!(!SENDER_PATTERN_REGEX.is_match("john@example.com\nstop;"));
+    }
+
+    #[test]
+    fn test_spam_policy_override() {
+        let domain = SpamPolicy {
+            whitelist: vec!["*@partner.com".to_string()],
+            ..SpamPolicy::default()
+        };
+        let overrides = SpamPolicyOverride {
+            threshold: Some(8.0),
+            action: Some("discard".to_string()),
+            whitelist: vec!["boss@example.com".to_string(), "*@partner.com".to_string()],
+            ..SpamPolicyOverride::default()
+        };
+
+        let effective = domain.with_override(&overrides);
+        assert!(effective.enabled);
+        assert_eq!(effective.threshold, 8.0);
+        assert_eq!(effective.action, "discard");
+        assert_eq!(effective.whitelist, vec!["*@partner.com", "boss@example.com"]);
+    }
+
+    #[test]
+    fn test_split_and_join_patterns() {
+        let joined = join_patterns(&["A@Example.com".to_string(), " ".to_string()]);
+        assert_eq!(joined.as_deref(), Some("a@example.com"));
+        assert_eq!(split_patterns(Some("a@x.com\n\nb@y.com")), vec!["a@x.com", "b@y.com"]);
+        assert_eq!(join_patterns(&[]), None);
+    }
 }
@@ -6,6 +6,9 @@ use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
+use crate::errors::ApiError;
+use crate::models::DiskQuota;
+
 /// Tipe file entry
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -57,9 +60,18 @@ pub struct FileInfo {
     /// Extension file (untuk files)
     pub extension: Option<String>,
 
-    /// MIME type (untuk files)
+    /// MIME type (untuk files, dari magic bytes dengan fallback ekstensi)
     pub mime_type: Option<String>,
 
+    /// Apakah konten berupa text dan bisa dibuka di editor
+    pub is_text: bool,
+
+    /// Lebar gambar dalam pixel (untuk file gambar)
+    pub image_width: Option<u32>,
+
+    /// Tinggi gambar dalam pixel (untuk file gambar)
+    pub image_height: Option<u32>,
+
     /// Apakah hidden file (dimulai dengan .)
     pub is_hidden: bool,
 }
@@ -169,6 +181,9 @@ pub struct DeleteRequest {
 
     /// Recursive delete untuk directory
     pub recursive: Option<bool>,
+
+    /// Hapus permanen tanpa melewati trash (default false)
+    pub permanent: Option<bool>,
 }
 
 /// Request untuk read file content
@@ -228,12 +243,36 @@ pub struct ChangePermissionsRequest {
     #[validate(length(min = 1, max = 500, message = "Path harus 1-500 karakter"))]
     pub path: String,
 
-    /// Permissions dalam format octal (e.g., "755", "644")
-    #[validate(regex(
-        path = "crate::models::file::PERMISSIONS_REGEX",
-        message = "Format permissions tidak valid (gunakan format octal seperti 755)"
-    ))]
-    pub permissions: String,
+    /// Mode octal (e.g., "755", "644") atau simbolik (e.g., "u+x,go-w")
+    #[validate(custom = "validate_file_mode")]
+    pub permissions: Option<String>,
+
+    /// Mode untuk file saat recursive (default: `permissions`)
+    #[validate(custom = "validate_file_mode")]
+    pub file_permissions: Option<String>,
+
+    /// Mode untuk directory saat recursive (default: `permissions`)
+    #[validate(custom = "validate_file_mode")]
+    pub dir_permissions: Option<String>,
+
+    /// Recursive untuk directory
+    pub recursive: Option<bool>,
+}
+
+/// Request untuk change owner/group
+#[derive(Debug, Deserialize, Validate)]
+pub struct ChangeOwnerRequest {
+    /// Path file/directory
+    #[validate(length(min = 1, max = 500, message = "Path harus 1-500 karakter"))]
+    pub path: String,
+
+    /// Owner baru (hanya system user milik akun)
+    #[validate(length(min = 1, max = 32, message = "Owner harus 1-32 karakter"))]
+    pub owner: Option<String>,
+
+    /// Group baru (group milik akun atau group web server)
+    #[validate(length(min = 1, max = 32, message = "Group harus 1-32 karakter"))]
+    pub group: Option<String>,
 
     /// Recursive untuk directory
     pub recursive: Option<bool>,
@@ -249,7 +288,7 @@ pub struct CompressRequest {
     #[validate(length(min = 1, max = 255, message = "Nama archive harus 1-255 karakter"))]
     pub archive_name: String,
 
-    /// Format: zip, tar.gz, tar.bz2
+    /// Format: zip (default), tar, tar.gz, tar.bz2, tar.xz, tar.zst
     pub format: Option<String>,
 }
 
@@ -268,6 +307,726 @@ pub struct ExtractRequest {
     pub overwrite: Option<bool>,
 }
 
+/// Format archive yang didukung compress/extract
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveFormat {
+    /// Zip (deflate)
+    Zip,
+    /// Tar tanpa kompresi
+    Tar,
+    /// Tar + gzip
+    TarGz,
+    /// Tar + bzip2
+    TarBz2,
+    /// Tar + xz
+    TarXz,
+    /// Tar + zstd
+    TarZst,
+}
+
+impl ArchiveFormat {
+    /// Semua format, urut dari ekstensi terpanjang untuk pencocokan nama file
+    const ALL: [Self; 6] = [
+        Self::TarGz,
+        Self::TarBz2,
+        Self::TarXz,
+        Self::TarZst,
+        Self::Tar,
+        Self::Zip,
+    ];
+
+    /// Parse nama format dari request (e.g., "tar.gz", "tgz")
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().trim_start_matches('.').to_lowercase().as_str() {
+            "zip" => Some(Self::Zip),
+            "tar" => Some(Self::Tar),
+            "tar.gz" | "tgz" | "gz" => Some(Self::TarGz),
+            "tar.bz2" | "tbz2" | "bz2" => Some(Self::TarBz2),
+            "tar.xz" | "txz" | "xz" => Some(Self::TarXz),
+            "tar.zst" | "tzst" | "zst" => Some(Self::TarZst),
+            _ => None,
+        }
+    }
+
+    /// Ekstensi file archive
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Zip => ".zip",
+            Self::Tar => ".tar",
+            Self::TarGz => ".tar.gz",
+            Self::TarBz2 => ".tar.bz2",
+            Self::TarXz => ".tar.xz",
+            Self::TarZst => ".tar.zst",
+        }
+    }
+
+    /// Deteksi format dari nama file
+    pub fn from_filename(name: &str) -> Option<Self> {
+        let name = name.to_lowercase();
+        let aliases = [(".tgz", Self::TarGz), (".tbz2", Self::TarBz2), (".txz", Self::TarXz)];
+
+        Self::ALL
+            .iter()
+            .map(|format| (format.extension(), *format))
+            .chain(aliases)
+            .find(|(extension, _)| name.ends_with(extension))
+            .map(|(_, format)| format)
+    }
+
+    /// Deteksi format dari magic bytes awal file
+    pub fn from_magic(head: &[u8]) -> Option<Self> {
+        if head.starts_with(b"PK\x03\x04") || head.starts_with(b"PK\x05\x06") {
+            Some(Self::Zip)
+        } else if head.starts_with(&[0x1F, 0x8B]) {
+            Some(Self::TarGz)
+        } else if head.starts_with(b"BZh") {
+            Some(Self::TarBz2)
+        } else if head.starts_with(&[0xFD, b'7', b'z', b'X', b'Z', 0x00]) {
+            Some(Self::TarXz)
+        } else if head.starts_with(&[0x28, 0xB5, 0x2F, 0xFD]) {
+            Some(Self::TarZst)
+        } else if head.get(257..262) == Some(b"ustar") {
+            Some(Self::Tar)
+        } else {
+            None
+        }
+    }
+}
+
+/// Konfigurasi batas ekstraksi archive (perlindungan archive bomb)
+#[derive(Debug, Clone)]
+pub struct ArchiveConfig {
+    /// Total ukuran maksimum hasil ekstraksi dalam bytes
+    pub max_extract_size: u64,
+
+    /// Jumlah entry maksimum dalam satu archive
+    pub max_entries: u64,
+
+    /// Kuota disk user tujuan ekstraksi, `None` = tidak dicek
+    pub quota: Option<DiskQuota>,
+}
+
+impl Default for ArchiveConfig {
+    fn default() -> Self {
+        Self {
+            max_extract_size: std::env::var("ARCHIVE_MAX_EXTRACT_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10 * 1024 * 1024 * 1024), // 10GB
+            max_entries: std::env::var("ARCHIVE_MAX_ENTRIES")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(100_000),
+            quota: None,
+        }
+    }
+}
+
+/// Mode pencarian file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchMode {
+    /// Cocokkan nama file (default)
+    Name,
+    /// Cari teks literal di dalam isi file
+    Literal,
+    /// Cari regular expression di dalam isi file
+    Regex,
+}
+
+impl SearchMode {
+    /// Parse mode dari query parameter
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "name" => Some(SearchMode::Name),
+            "literal" => Some(SearchMode::Literal),
+            "regex" => Some(SearchMode::Regex),
+            _ => None,
+        }
+    }
+}
+
+/// Request untuk search files
+#[derive(Debug, Clone, Deserialize, Validate)]
+pub struct SearchRequest {
+    /// Query pencarian (nama file, teks literal atau regex)
+    #[validate(length(min = 1, max = 500, message = "Query harus 1-500 karakter"))]
+    pub query: String,
+
+    /// Path untuk memulai pencarian
+    pub path: Option<String>,
+
+    /// Mode: name (default), literal, regex
+    pub mode: Option<String>,
+
+    /// Pencarian case-sensitive (default false)
+    #[serde(default)]
+    pub case_sensitive: bool,
+
+    /// Glob file yang dicari, dipisah koma (e.g., "*.php,*.env")
+    pub include: Option<String>,
+
+    /// Glob file/directory yang dilewati, dipisah koma (e.g., "node_modules,*.min.js")
+    pub exclude: Option<String>,
+
+    /// Jumlah baris konteks sebelum dan sesudah match
+    #[validate(range(max = 5, message = "Context maksimal 5 baris"))]
+    #[serde(default)]
+    pub context: usize,
+
+    /// Maximum file dalam hasil (default 50, max 100)
+    pub limit: Option<usize>,
+}
+
+/// Baris yang cocok pada pencarian isi file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SearchMatch {
+    /// Nomor baris (mulai dari 1)
+    pub line_number: usize,
+
+    /// Isi baris (dipotong jika terlalu panjang)
+    pub line: String,
+
+    /// Baris konteks sebelum match
+    pub before: Vec<String>,
+
+    /// Baris konteks sesudah match
+    pub after: Vec<String>,
+}
+
+/// File hasil pencarian
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResult {
+    #[serde(flatten)]
+    pub file: FileInfo,
+
+    /// Baris yang cocok (kosong untuk pencarian nama)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub matches: Vec<SearchMatch>,
+}
+
+/// Response pencarian
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResponse {
+    /// File yang cocok
+    pub results: Vec<SearchResult>,
+
+    /// Jumlah file yang diperiksa isinya
+    pub files_scanned: usize,
+
+    /// Jumlah file yang dilewati (binary atau melebihi batas ukuran)
+    pub files_skipped: usize,
+
+    /// Hasil dipotong karena mencapai limit
+    pub truncated: bool,
+
+    /// Pencarian dihentikan karena timeout (hasil parsial)
+    pub timed_out: bool,
+}
+
+/// Konfigurasi pencarian isi file
+#[derive(Debug, Clone)]
+pub struct SearchConfig {
+    /// Ukuran file maksimum yang diperiksa isinya dalam bytes
+    pub max_file_size: u64,
+
+    /// Batas waktu satu pencarian dalam detik
+    pub timeout_secs: u64,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            max_file_size: std::env::var("SEARCH_MAX_FILE_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5 * 1024 * 1024), // 5MB
+            timeout_secs: std::env::var("SEARCH_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+        }
+    }
+}
+
+/// Jumlah maksimum baris yang cocok per file
+pub const SEARCH_MAX_MATCHES_PER_FILE: usize = 20;
+
+/// Panjang maksimum baris dalam hasil pencarian (karakter)
+pub const SEARCH_MAX_LINE_LENGTH: usize = 500;
+
+/// Jumlah maksimum file dalam satu request upload multipart
+pub const MAX_UPLOAD_FILES: u64 = 20;
+
+/// Range bytes (inklusif) dari header HTTP `Range`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ByteRange {
+    /// Offset awal
+    pub start: u64,
+
+    /// Offset akhir (inklusif)
+    pub end: u64,
+}
+
+impl ByteRange {
+    /// Parse header `Range` untuk file berukuran `total` bytes
+    ///
+    /// Hanya satu range yang didukung (`bytes=0-99`, `bytes=100-`, `bytes=-500`).
+    /// Header dengan unit lain atau multi-range diabaikan (`Ok(None)`) sehingga
+    /// file dikirim utuh.
+    ///
+    /// # Errors
+    /// Returns `ApiError::RangeNotSatisfiable` jika range di luar ukuran file
+    pub fn parse(header: &str, total: u64) -> Result<Option<Self>, ApiError> {
+        let unsatisfiable = || ApiError::RangeNotSatisfiable(total);
+
+        let Some(spec) = header.trim().strip_prefix("bytes=") else {
+            return Ok(None);
+        };
+        if spec.contains(',') {
+            return Ok(None);
+        }
+        let Some((start, end)) = spec.trim().split_once('-') else {
+            return Ok(None);
+        };
+
+        let range = match (start.trim(), end.trim()) {
+            ("", "") => return Ok(None),
+            // Suffix range: N bytes terakhir
+            ("", suffix) => {
+                let suffix: u64 = suffix.parse().map_err(|_| unsatisfiable())?;
+                if suffix == 0 || total == 0 {
+                    return Err(unsatisfiable());
+                }
+                Self {
+                    start: total.saturating_sub(suffix),
+                    end: total - 1,
+                }
+            }
+            (start, end) => {
+                let start: u64 = start.parse().map_err(|_| unsatisfiable())?;
+                let end = if end.is_empty() {
+                    total.saturating_sub(1)
+                } else {
+                    end.parse::<u64>()
+                        .map_err(|_| unsatisfiable())?
+                        .min(total.saturating_sub(1))
+                };
+                if start >= total || end < start {
+                    return Err(unsatisfiable());
+                }
+                Self { start, end }
+            }
+        };
+
+        Ok(Some(range))
+    }
+
+    /// Jumlah bytes dalam range
+    pub fn length(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// Nilai header `Content-Range`
+    pub fn content_range(&self, total: u64) -> String {
+        format!("bytes {}-{}/{}", self.start, self.end, total)
+    }
+}
+
+/// Request untuk memulai resumable upload
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateChunkedUploadRequest {
+    /// Direktori tujuan (relatif dari user home)
+    #[validate(length(max = 500, message = "Path maksimal 500 karakter"))]
+    pub path: String,
+
+    /// Nama file tujuan
+    #[validate(length(min = 1, max = 255, message = "Nama file harus 1-255 karakter"))]
+    pub filename: String,
+
+    /// Ukuran total file dalam bytes
+    pub size: u64,
+
+    /// SHA-256 (hex) file lengkap, diverifikasi saat finalize
+    #[validate(regex(
+        path = "crate::models::file::SHA256_REGEX",
+        message = "Checksum harus SHA-256 hex (64 karakter)"
+    ))]
+    pub checksum: Option<String>,
+
+    /// Timpa file yang sudah ada
+    pub overwrite: Option<bool>,
+}
+
+/// State resumable upload (disimpan sebagai `upload.json` di staging area)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChunkedUpload {
+    /// Upload ID (UUID)
+    pub id: String,
+
+    /// Direktori tujuan (relatif dari user home)
+    pub directory: String,
+
+    /// Nama file tujuan
+    pub filename: String,
+
+    /// Ukuran total file dalam bytes
+    pub size: u64,
+
+    /// SHA-256 (hex) yang diharapkan
+    pub checksum: Option<String>,
+
+    /// Timpa file yang sudah ada
+    pub overwrite: bool,
+
+    /// Waktu pembuatan
+    pub created_at: DateTime<Utc>,
+
+    /// Waktu chunk terakhir diterima
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Response status resumable upload
+#[derive(Debug, Serialize)]
+pub struct ChunkedUploadResponse {
+    pub id: String,
+    /// Path file tujuan (relatif dari user home)
+    pub path: String,
+    pub filename: String,
+    pub size: u64,
+    /// Jumlah bytes yang sudah diterima (offset chunk berikutnya)
+    pub offset: u64,
+    pub progress_percent: f64,
+    pub created_at: DateTime<Utc>,
+    /// Upload dihapus otomatis jika tidak ada aktivitas sampai waktu ini
+    pub expires_at: DateTime<Utc>,
+}
+
+impl ChunkedUploadResponse {
+    /// Build response dari state upload dan offset saat ini
+    pub fn new(upload: &ChunkedUpload, offset: u64, ttl_secs: u64) -> Self {
+        let path = if upload.directory.trim_matches('/').is_empty() {
+            upload.filename.clone()
+        } else {
+            format!("{}/{}", upload.directory.trim_matches('/'), upload.filename)
+        };
+        let progress_percent = if upload.size == 0 {
+            100.0
+        } else {
+            ((offset as f64 / upload.size as f64) * 10000.0).round() / 100.0
+        };
+
+        Self {
+            id: upload.id.clone(),
+            path,
+            filename: upload.filename.clone(),
+            size: upload.size,
+            offset,
+            progress_percent,
+            created_at: upload.created_at,
+            expires_at: upload.updated_at + chrono::Duration::seconds(ttl_secs as i64),
+        }
+    }
+}
+
+/// Konfigurasi resumable upload
+#[derive(Debug, Clone)]
+pub struct ChunkedUploadConfig {
+    /// Ukuran maksimum file dalam bytes
+    pub max_size: u64,
+
+    /// Ukuran maksimum satu chunk (body PATCH) dalam bytes
+    pub max_chunk_size: u64,
+
+    /// Upload tanpa aktivitas lebih lama dari ini (detik) dihapus
+    pub ttl_secs: u64,
+}
+
+impl Default for ChunkedUploadConfig {
+    fn default() -> Self {
+        Self {
+            max_size: std::env::var("UPLOAD_MAX_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10 * 1024 * 1024 * 1024), // 10GB
+            max_chunk_size: std::env::var("UPLOAD_CHUNK_MAX_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(64 * 1024 * 1024), // 64MB
+            ttl_secs: std::env::var("UPLOAD_SESSION_TTL")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|v| *v > 0)
+                .unwrap_or(86400), // 24 jam
+        }
+    }
+}
+
+/// Nama staging area resumable upload di dalam user home
+pub const UPLOAD_STAGING_DIR: &str = ".nusa-uploads";
+
+/// Nama trash bin di dalam user home
+pub const TRASH_DIR: &str = ".trash";
+
+/// Item di trash (disimpan sebagai `trash.json` di samping data item)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrashItem {
+    /// ID item trash
+    pub id: String,
+
+    /// Nama asli file/directory
+    pub name: String,
+
+    /// Path asli relatif dari user home (tujuan restore)
+    pub original_path: String,
+
+    /// Tipe item
+    pub file_type: FileType,
+
+    /// Total ukuran item dalam bytes
+    pub size: u64,
+
+    /// Waktu dihapus
+    pub deleted_at: DateTime<Utc>,
+}
+
+/// Response list trash
+#[derive(Debug, Serialize)]
+pub struct TrashListResponse {
+    /// Item di trash, terbaru lebih dulu
+    pub items: Vec<TrashItem>,
+
+    /// Total ukuran trash dalam bytes (dihitung dalam disk usage user)
+    pub total_size: u64,
+
+    /// Item lebih lama dari ini (hari) dihapus otomatis, 0 = tidak pernah
+    pub retention_days: u32,
+}
+
+/// Konfigurasi trash bin
+#[derive(Debug, Clone)]
+pub struct TrashConfig {
+    /// Item trash lebih lama dari ini (hari) dihapus permanen, 0 = nonaktif
+    pub retention_days: u32,
+}
+
+impl Default for TrashConfig {
+    fn default() -> Self {
+        Self {
+            retention_days: std::env::var("TRASH_RETENTION_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+        }
+    }
+}
+
+/// Nama directory riwayat versi file di dalam user home (di luar document root)
+pub const VERSIONS_DIR: &str = ".nusa-versions";
+
+/// Area internal di user home yang tidak bisa diakses lewat file manager
+pub const INTERNAL_DIRS: [&str; 3] = [UPLOAD_STAGING_DIR, TRASH_DIR, VERSIONS_DIR];
+
+/// Satu revisi file yang disimpan sebelum ditimpa lewat editor
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileRevision {
+    /// ID revisi
+    pub id: String,
+
+    /// Ukuran konten dalam bytes (sebelum dikompresi)
+    pub size: u64,
+
+    /// Checksum SHA-256 konten (hex)
+    pub sha256: String,
+
+    /// Waktu revisi disimpan
+    pub created_at: DateTime<Utc>,
+}
+
+/// Response daftar revisi file
+#[derive(Debug, Serialize)]
+pub struct FileVersionsResponse {
+    /// Path file relatif dari user home
+    pub path: String,
+
+    /// Revisi, terbaru lebih dulu
+    pub revisions: Vec<FileRevision>,
+
+    /// Jumlah revisi maksimum yang disimpan per file
+    pub max_revisions: usize,
+}
+
+/// Response diff antar revisi
+#[derive(Debug, Serialize)]
+pub struct FileDiffResponse {
+    /// Path file relatif dari user home
+    pub path: String,
+
+    /// Revisi asal
+    pub from: String,
+
+    /// Revisi tujuan, `None` = konten file saat ini
+    pub to: Option<String>,
+
+    /// Unified diff
+    pub diff: String,
+}
+
+/// Request untuk restore revisi file
+#[derive(Debug, Deserialize, Validate)]
+pub struct RestoreVersionRequest {
+    /// Path file
+    #[validate(length(min = 1, max = 500, message = "Path harus 1-500 karakter"))]
+    pub path: String,
+
+    /// ID revisi yang dikembalikan
+    #[validate(length(min = 1, max = 64, message = "Revisi harus 1-64 karakter"))]
+    pub revision: String,
+}
+
+/// Konfigurasi versioning file
+#[derive(Debug, Clone)]
+pub struct VersionConfig {
+    /// Jumlah revisi yang disimpan per file, 0 = nonaktif
+    pub max_revisions: usize,
+
+    /// File lebih besar dari ini (bytes) tidak disimpan revisinya
+    pub max_file_size: u64,
+}
+
+impl Default for VersionConfig {
+    fn default() -> Self {
+        Self {
+            max_revisions: std::env::var("FILE_VERSION_MAX_REVISIONS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+            max_file_size: std::env::var("FILE_VERSION_MAX_FILE_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5 * 1024 * 1024), // 5MB
+        }
+    }
+}
+
+/// Regex untuk validasi checksum SHA-256 (hex)
+pub static SHA256_REGEX: once_cell::sync::Lazy<regex::Regex> =
+    once_cell::sync::Lazy::new(|| regex::Regex::new(r"^[0-9a-fA-F]{64}$").unwrap());
+
+/// Ukuran sisi thumbnail default (pixel)
+pub const THUMBNAIL_DEFAULT_SIZE: u32 = 256;
+
+/// Ukuran sisi thumbnail maksimum (pixel)
+pub const THUMBNAIL_MAX_SIZE: u32 = 1024;
+
+/// Ukuran file gambar maksimum yang dibuatkan thumbnail (20MB)
+pub const THUMBNAIL_MAX_SOURCE_SIZE: u64 = 20 * 1024 * 1024;
+
+/// Bit permission yang ditolak policy: setuid, setgid dan world-writable
+pub const FORBIDDEN_MODE_BITS: u32 = 0o4000 | 0o2000 | 0o002;
+
+/// Regex untuk validasi mode simbolik (e.g., "u+x,go-w", "a=rX")
+pub static SYMBOLIC_MODE_REGEX: once_cell::sync::Lazy<regex::Regex> =
+    once_cell::sync::Lazy::new(|| {
+        regex::Regex::new(r"^[ugoa]*[-+=][rwxXst]*(,[ugoa]*[-+=][rwxXst]*)*$").unwrap()
+    });
+
+/// Mode chmod: octal atau daftar klausa simbolik
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileMode {
+    /// Mode absolut (e.g., 0o755)
+    Octal(u32),
+    /// Klausa simbolik `(who, op, perms)`, who kosong berarti semua (`a`)
+    Symbolic(Vec<(String, char, String)>),
+}
+
+impl FileMode {
+    /// Parse mode octal atau simbolik
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+
+        if PERMISSIONS_REGEX.is_match(value) {
+            return u32::from_str_radix(value, 8).ok().map(Self::Octal);
+        }
+        if !SYMBOLIC_MODE_REGEX.is_match(value) {
+            return None;
+        }
+
+        let clauses = value
+            .split(',')
+            .map(|clause| {
+                let op_index = clause.find(['+', '-', '=']).unwrap_or(0);
+                let (who, rest) = clause.split_at(op_index);
+                let op = rest.chars().next().unwrap_or('=');
+                (who.to_string(), op, rest[1..].to_string())
+            })
+            .collect();
+
+        Some(Self::Symbolic(clauses))
+    }
+
+    /// Bit kelas user yang dipengaruhi (termasuk setuid/setgid/sticky)
+    fn who_mask(who: &str) -> u32 {
+        if who.is_empty() || who.contains('a') {
+            return 0o7777;
+        }
+        who.chars().fold(0, |mask, c| match c {
+            'u' => mask | 0o4700,
+            'g' => mask | 0o2070,
+            'o' => mask | 0o1007,
+            _ => mask,
+        })
+    }
+
+    /// Bit yang diminta oleh satu klausa untuk kelas `who`
+    fn clause_bits(who: &str, perms: &str, current: u32, is_dir: bool) -> u32 {
+        let bits = perms.chars().fold(0, |bits, c| match c {
+            'r' => bits | 0o444,
+            'w' => bits | 0o222,
+            'x' => bits | 0o111,
+            'X' if is_dir || current & 0o111 != 0 => bits | 0o111,
+            's' => bits | 0o6000,
+            't' => bits | 0o1000,
+            _ => bits,
+        });
+        bits & Self::who_mask(who)
+    }
+
+    /// Hitung mode baru dari mode saat ini
+    pub fn apply(&self, current: u32, is_dir: bool) -> u32 {
+        let current = current & 0o7777;
+
+        match self {
+            Self::Octal(mode) => *mode,
+            Self::Symbolic(clauses) => clauses.iter().fold(current, |mode, (who, op, perms)| {
+                let bits = Self::clause_bits(who, perms, mode, is_dir);
+                match op {
+                    '+' => mode | bits,
+                    '-' => mode & !bits,
+                    _ => (mode & !Self::who_mask(who)) | bits,
+                }
+            }),
+        }
+    }
+
+    /// Cek apakah mode memberikan bit yang dilarang policy
+    pub fn grants_forbidden_bits(&self) -> bool {
+        match self {
+            Self::Octal(mode) => mode & FORBIDDEN_MODE_BITS != 0,
+            Self::Symbolic(clauses) => clauses.iter().any(|(who, op, perms)| {
+                *op != '-' && Self::clause_bits(who, perms, 0o111, true) & FORBIDDEN_MODE_BITS != 0
+            }),
+        }
+    }
+}
+
+/// Validasi mode chmod pada request
+pub fn validate_file_mode(value: &str) -> Result<(), validator::ValidationError> {
+    match FileMode::parse(value) {
+        Some(_) => Ok(()),
+        None => Err(validator::ValidationError::new("invalid_file_mode")),
+    }
+}
+
 /// Regex untuk validasi permissions octal
 pub static PERMISSIONS_REGEX: once_cell::sync::Lazy<regex::Regex> =
     once_cell::sync::Lazy::new(|| regex::Regex::new(r"^[0-7]{3,4}$").unwrap());
@@ -341,6 +1100,111 @@ mod tests {
         assert_eq!(permissions_to_octal(0o644), "644");
     }
 
+    #[test]
+    fn test_byte_range_parse() {
+        let parse = |header| ByteRange::parse(header, 1000).unwrap();
+        let range = |start, end| Some(ByteRange { start, end });
+
+        assert_eq!(parse("bytes=0-99"), range(0, 99));
+        assert_eq!(parse("bytes=900-"), range(900, 999));
+        assert_eq!(parse("bytes=-100"), range(900, 999));
+        assert_eq!(parse("bytes=500-5000"), range(500, 999));
+        assert_eq!(parse("bytes=-5000"), range(0, 999));
+        assert_eq!(parse("bytes=0-99").unwrap().length(), 100);
+
+        assert!(ByteRange::parse("bytes=1000-", 1000).is_err());
+        assert!(ByteRange::parse("bytes=5-1", 1000).is_err());
+        assert!(ByteRange::parse("bytes=-0", 1000).is_err());
+        assert!(ByteRange::parse("bytes=0-", 0).is_err());
+
+        assert_eq!(parse("items=0-1"), None);
+        assert_eq!(parse("bytes=0-1,5-6"), None);
+    }
+
+    #[test]
+    fn test_archive_format_detection() {
+        assert_eq!(ArchiveFormat::parse("tar.gz"), Some(ArchiveFormat::TarGz));
+        assert_eq!(ArchiveFormat::parse(".TZST"), Some(ArchiveFormat::TarZst));
+        assert_eq!(ArchiveFormat::parse("rar"), None);
+
+        assert_eq!(ArchiveFormat::from_filename("backup.TAR.GZ"), Some(ArchiveFormat::TarGz));
+        assert_eq!(ArchiveFormat::from_filename("site.tgz"), Some(ArchiveFormat::TarGz));
+        assert_eq!(ArchiveFormat::from_filename("site.tar"), Some(ArchiveFormat::Tar));
+        assert_eq!(ArchiveFormat::from_filename("site.tar.zst"), Some(ArchiveFormat::TarZst));
+        assert_eq!(ArchiveFormat::from_filename("notes.txt"), None);
+
+        assert_eq!(ArchiveFormat::from_magic(b"PK\x03\x04rest"), Some(ArchiveFormat::Zip));
+        assert_eq!(ArchiveFormat::from_magic(&[0x1F, 0x8B, 0x08]), Some(ArchiveFormat::TarGz));
+        let mut tar_header = vec![0u8; 512];
+        tar_header[257..262].copy_from_slice(b"ustar");
+        assert_eq!(ArchiveFormat::from_magic(&tar_header), Some(ArchiveFormat::Tar));
+        assert_eq!(ArchiveFormat::from_magic(b"plain text"), None);
+    }
+
+    #[test]
+    fn test_file_mode_apply() {
+        let mode = |value| FileMode::parse(value).unwrap();
+
+        assert_eq!(mode("755"), FileMode::Octal(0o755));
+        assert_eq!(mode("0644").apply(0o100777, false), 0o644);
+        assert_eq!(mode("u+x").apply(0o644, false), 0o744);
+        assert_eq!(mode("go-w").apply(0o775, true), 0o755);
+        assert_eq!(mode("u=rwx,g=rx,o=").apply(0o777, true), 0o750);
+        assert_eq!(mode("a=rX").apply(0o600, false), 0o444);
+        assert_eq!(mode("a=rX").apply(0o700, true), 0o555);
+        assert_eq!(mode("+x").apply(0o644, false), 0o755);
+
+        assert!(FileMode::parse("999").is_none());
+        assert!(FileMode::parse("u+q").is_none());
+        assert!(FileMode::parse("rwx").is_none());
+    }
+
+    #[test]
+    fn test_file_mode_policy() {
+        let forbidden = |value| FileMode::parse(value).unwrap().grants_forbidden_bits();
+
+        assert!(forbidden("777"));
+        assert!(forbidden("4755"));
+        assert!(forbidden("2755"));
+        assert!(forbidden("o+w"));
+        assert!(forbidden("+w"));
+        assert!(forbidden("u+s"));
+        assert!(forbidden("g=rws"));
+
+        assert!(!forbidden("755"));
+        assert!(!forbidden("1755"));
+        assert!(!forbidden("ug+w"));
+        assert!(!forbidden("o-w"));
+        assert!(!forbidden("a-s"));
+    }
+
+    #[test]
+    fn test_chunked_upload_response() {
+        let now = Utc::now();
+        let upload = ChunkedUpload {
+            id: "upload-1".to_string(),
+            directory: "/public_html/".to_string(),
+            filename: "site.tar.gz".to_string(),
+            size: 3000,
+            checksum: None,
+            overwrite: false,
+            created_at: now,
+            updated_at: now,
+        };
+
+        let response = ChunkedUploadResponse::new(&upload, 1000, 60);
+        assert_eq!(response.path, "public_html/site.tar.gz");
+        assert_eq!(response.progress_percent, 33.33);
+        assert_eq!(response.expires_at, now + chrono::Duration::seconds(60));
+
+        let root = ChunkedUpload {
+            directory: "".to_string(),
+            ..upload
+        };
+        assert_eq!(ChunkedUploadResponse::new(&root, 3000, 60).path, "site.tar.gz");
+        assert_eq!(ChunkedUploadResponse::new(&root, 3000, 60).progress_percent, 100.0);
+    }
+
     #[test]
     fn test_permissions_regex() {
         assert!(PERMISSIONS_REGEX.is_match("755"));
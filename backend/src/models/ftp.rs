@@ -7,6 +7,9 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use validator::Validate;
 
+use crate::config::{FtpConfig, FtpTlsMode};
+use crate::utils::password;
+
 /// FTP Account entity dari database
 #[derive(Debug, Clone, FromRow, Serialize)]
 pub struct FtpAccount {
@@ -43,6 +46,17 @@ pub struct FtpAccount {
 
     /// Login terakhir
     pub last_login: Option<DateTime<Utc>>,
+
+    /// UID system user pemilik (dipakai FTP daemon)
+    #[serde(skip_serializing)]
+    pub system_uid: Option<i32>,
+
+    /// GID system user pemilik (dipakai FTP daemon)
+    #[serde(skip_serializing)]
+    pub system_gid: Option<i32>,
+
+    /// Hanya boleh login via SFTP (OpenSSH chroot)
+    pub sftp_only: bool,
 }
 
 /// Response DTO untuk FTP Account
@@ -56,6 +70,10 @@ pub struct FtpAccountResponse {
     pub quota_bytes: i64,
     pub used_bytes: i64,
     pub quota_percentage: f64,
+    pub sftp_only: bool,
+    /// Password masih berupa hash Argon2 lama yang tidak bisa diverifikasi
+    /// Pure-FTPd; login FTP gagal sampai password diganti
+    pub password_reset_required: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub last_login: Option<DateTime<Utc>>,
@@ -78,6 +96,9 @@ impl From<FtpAccount> for FtpAccountResponse {
             quota_bytes: account.quota_bytes,
             used_bytes: account.used_bytes,
             quota_percentage,
+            sftp_only: account.sftp_only,
+            password_reset_required: !account.sftp_only
+                && !password::is_crypt_hash(&account.password_hash),
             created_at: account.created_at,
             updated_at: account.updated_at,
             last_login: account.last_login,
@@ -98,6 +119,7 @@ pub struct CreateFtpAccountRequest {
 
     /// Password untuk FTP account
     #[validate(length(min = 8, max = 128, message = "Password harus 8-128 karakter"))]
+    #[validate(custom = "validate_ftp_password")]
     pub password: String,
 
     /// Home directory (relatif dari user home, opsional)
@@ -106,6 +128,9 @@ pub struct CreateFtpAccountRequest {
 
     /// Quota dalam MB (0 = unlimited)
     pub quota_mb: Option<i64>,
+
+    /// Hanya izinkan SFTP (default: false)
+    pub sftp_only: Option<bool>,
 }
 
 /// DTO untuk update FTP account
@@ -113,6 +138,7 @@ pub struct CreateFtpAccountRequest {
 pub struct UpdateFtpAccountRequest {
     /// Password baru (opsional)
     #[validate(length(min = 8, max = 128, message = "Password harus 8-128 karakter"))]
+    #[validate(custom = "validate_ftp_password")]
     pub password: Option<String>,
 
     /// Home directory baru (opsional)
@@ -131,6 +157,7 @@ pub struct UpdateFtpAccountRequest {
 pub struct ChangeFtpPasswordRequest {
     /// Password baru
     #[validate(length(min = 8, max = 128, message = "Password harus 8-128 karakter"))]
+    #[validate(custom = "validate_ftp_password")]
     pub new_password: String,
 }
 
@@ -140,6 +167,132 @@ pub static FTP_USERNAME_REGEX: once_cell::sync::Lazy<regex::Regex> =
         regex::Regex::new(r"^[a-zA-Z][a-zA-Z0-9_-]*$").unwrap()
     });
 
+/// Validasi password FTP
+///
+/// Password diteruskan ke `chpasswd` (format `user:password` per baris) untuk
+/// akun SFTP-only, sehingga karakter kontrol dan `:` ditolak.
+pub fn validate_ftp_password(value: &str) -> Result<(), validator::ValidationError> {
+    if value.chars().any(|c| c.is_control() || c == ':') {
+        return Err(validator::ValidationError::new("invalid_ftp_password"));
+    }
+    Ok(())
+}
+
+/// Konfigurasi backend FTP daemon (Pure-FTPd dengan MySQL auth)
+///
+/// Pure-FTPd membaca `ftp_accounts` langsung (hash SHA-512 crypt diverifikasi via
+/// `crypt(3)` libc), sehingga create/update/toggle/delete langsung berlaku.
+#[derive(Debug, Clone)]
+pub struct FtpBackendConfig {
+    /// Path konfigurasi MySQL Pure-FTPd yang di-generate panel
+    pub mysql_conf_path: String,
+
+    /// Nama service systemd FTP daemon
+    pub service_name: String,
+
+    /// Host MySQL untuk Pure-FTPd
+    pub db_host: String,
+
+    /// Port MySQL untuk Pure-FTPd
+    pub db_port: u16,
+
+    /// Nama database panel
+    pub db_name: String,
+
+    /// User MySQL read-only untuk Pure-FTPd
+    pub db_user: String,
+
+    /// Password user MySQL Pure-FTPd
+    pub db_password: String,
+
+    /// Internal key untuk endpoint event dari FTP server
+    pub internal_key: String,
+}
+
+impl Default for FtpBackendConfig {
+    fn default() -> Self {
+        Self {
+            mysql_conf_path: std::env::var("FTP_PUREFTPD_MYSQL_CONF")
+                .unwrap_or_else(|_| "/etc/pure-ftpd/db/mysql.conf".to_string()),
+            service_name: std::env::var("FTP_SERVICE_NAME")
+                .unwrap_or_else(|_| "pure-ftpd-mysql".to_string()),
+            db_host: std::env::var("FTP_DB_HOST").unwrap_or_else(|_| "127.0.0.1".to_string()),
+            db_port: std::env::var("FTP_DB_PORT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(3306),
+            db_name: std::env::var("FTP_DB_NAME").unwrap_or_else(|_| "nusa_panel".to_string()),
+            db_user: std::env::var("FTP_DB_USER").unwrap_or_else(|_| "nusa_ftp".to_string()),
+            db_password: std::env::var("FTP_DB_PASSWORD").unwrap_or_default(),
+            internal_key: std::env::var("FTP_INTERNAL_KEY")
+                .unwrap_or_else(|_| "change-me-in-production".to_string()),
+        }
+    }
+}
+
+/// Konfigurasi akses SFTP-only (OpenSSH `Match User` + `ChrootDirectory`)
+///
+/// OpenSSH mensyaratkan chroot dimiliki root, jadi setiap akun mendapat
+/// `<chroot_base>/<ftp_username>` milik root dengan home FTP di-bind mount
+/// ke `<chroot>/files`.
+#[derive(Debug, Clone)]
+pub struct SftpConfig {
+    /// File konfigurasi sshd yang di-generate panel (di-include sshd_config)
+    pub sshd_config_path: String,
+
+    /// Base direktori chroot milik root
+    pub chroot_base: String,
+
+    /// Group system untuk akun SFTP-only
+    pub group: String,
+
+    /// Nama service systemd OpenSSH (ssh di Debian/Ubuntu, sshd di RHEL)
+    pub service_name: String,
+}
+
+impl Default for SftpConfig {
+    fn default() -> Self {
+        Self {
+            sshd_config_path: std::env::var("SFTP_SSHD_CONFIG")
+                .unwrap_or_else(|_| "/etc/ssh/sshd_config.d/nusa-sftp.conf".to_string()),
+            chroot_base: std::env::var("SFTP_CHROOT_BASE")
+                .unwrap_or_else(|_| "/var/sftp".to_string()),
+            group: std::env::var("SFTP_GROUP").unwrap_or_else(|_| "sftponly".to_string()),
+            service_name: std::env::var("SFTP_SSHD_SERVICE")
+                .unwrap_or_else(|_| "ssh".to_string()),
+        }
+    }
+}
+
+impl SftpConfig {
+    /// Direktori chroot untuk akun
+    pub fn chroot_dir(&self, ftp_username: &str) -> String {
+        format!("{}/{}", self.chroot_base.trim_end_matches('/'), ftp_username)
+    }
+
+    /// Mount point home FTP di dalam chroot
+    pub fn files_dir(&self, ftp_username: &str) -> String {
+        format!("{}/files", self.chroot_dir(ftp_username))
+    }
+}
+
+/// DTO event dari FTP server (baris log Pure-FTPd via syslog/omprog)
+#[derive(Debug, Deserialize, Validate)]
+pub struct FtpLogEventsRequest {
+    /// Baris log mentah
+    #[validate(length(min = 1, max = 5000, message = "Lines harus 1-5000 baris"))]
+    pub lines: Vec<String>,
+}
+
+/// Hasil pemrosesan event FTP
+#[derive(Debug, Default, Serialize)]
+pub struct FtpLogEventsResult {
+    /// Jumlah login yang dicatat
+    pub logins: usize,
+    /// Jumlah akun yang pemakaian disk-nya diperbarui
+    pub usage_updated: usize,
+}
+
 /// FTP Server configuration
 #[derive(Debug, Clone, Serialize)]
 pub struct FtpServerInfo {
@@ -153,16 +306,31 @@ pub struct FtpServerInfo {
     pub sftp_port: u16,
     /// Passive mode port range
     pub passive_ports: String,
+    /// Mode TLS (none, explicit, implicit)
+    pub tls_mode: FtpTlsMode,
+    /// FTP daemon yang sedang berjalan (None = tidak terdeteksi)
+    pub daemon: Option<String>,
+    /// Fingerprint SHA-256 sertifikat TLS
+    pub cert_fingerprint: Option<String>,
+    /// Waktu kedaluwarsa sertifikat TLS
+    pub cert_expires_at: Option<DateTime<Utc>>,
+    /// Sisa hari sebelum sertifikat kedaluwarsa
+    pub cert_days_remaining: Option<i64>,
 }
 
-impl Default for FtpServerInfo {
-    fn default() -> Self {
+impl From<&FtpConfig> for FtpServerInfo {
+    fn from(config: &FtpConfig) -> Self {
         Self {
-            hostname: "ftp.example.com".to_string(),
-            port: 21,
-            ftps_port: 990,
-            sftp_port: 22,
-            passive_ports: "49152-65534".to_string(),
+            hostname: config.host.clone(),
+            port: config.port,
+            ftps_port: config.ftps_port,
+            sftp_port: config.sftp_port,
+            passive_ports: format!("{}-{}", config.passive_port_min, config.passive_port_max),
+            tls_mode: config.tls_mode,
+            daemon: None,
+            cert_fingerprint: config.cert_fingerprint.clone(),
+            cert_expires_at: None,
+            cert_days_remaining: None,
         }
     }
 }
@@ -181,6 +349,14 @@ mod tests {
         assert!(!FTP_USERNAME_REGEX.is_match("user.name")); // No dot allowed
     }
 
+    #[test]
+    fn test_validate_ftp_password() {
+        assert!(validate_ftp_password("s3cret pass!").is_ok());
+        assert!(validate_ftp_password("secret\nroot:x").is_err());
+        assert!(validate_ftp_password("pass:word").is_err());
+        assert!(validate_ftp_password("pass\tword").is_err());
+    }
+
     #[test]
     fn test_quota_percentage() {
         let account = FtpAccount {
@@ -195,9 +371,20 @@ mod tests {
             created_at: Utc::now(),
             updated_at: Utc::now(),
             last_login: None,
+            system_uid: None,
+            system_gid: None,
+            sftp_only: false,
         };
 
-        let response = FtpAccountResponse::from(account);
+        let response = FtpAccountResponse::from(account.clone());
         assert!((response.quota_percentage - 50.0).abs() < 0.01);
+        // Hash bukan SHA-512 crypt (mis. Argon2 lama) harus diganti
+        assert!(response.password_reset_required);
+
+        let response = FtpAccountResponse::from(FtpAccount {
+            password_hash: "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl".to_string(),
+            ..account
+        });
+        assert!(!response.password_reset_required);
     }
 }
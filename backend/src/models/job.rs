@@ -0,0 +1,165 @@
+//! # Job Model
+//!
+//! Model dan DTO untuk background job (operasi file yang berjalan lama).
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use sqlx::FromRow;
+
+/// Jenis background job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobKind {
+    /// Compress files ke archive
+    Compress,
+    /// Extract archive
+    Extract,
+    /// Copy file/directory
+    Copy,
+    /// Pencarian file
+    Search,
+}
+
+impl JobKind {
+    /// Nilai kolom `kind`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobKind::Compress => "compress",
+            JobKind::Extract => "extract",
+            JobKind::Copy => "copy",
+            JobKind::Search => "search",
+        }
+    }
+}
+
+/// Status background job
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JobStatus {
+    /// Menunggu worker
+    Pending,
+    /// Sedang berjalan
+    Running,
+    /// Selesai dengan sukses
+    Completed,
+    /// Gagal
+    Failed,
+    /// Dibatalkan user
+    Cancelled,
+}
+
+impl JobStatus {
+    /// Nilai kolom `status`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobStatus::Pending => "pending",
+            JobStatus::Running => "running",
+            JobStatus::Completed => "completed",
+            JobStatus::Failed => "failed",
+            JobStatus::Cancelled => "cancelled",
+        }
+    }
+
+    /// Apakah status sudah final (job tidak akan berubah lagi)
+    pub fn is_final(status: &str) -> bool {
+        matches!(status, "completed" | "failed" | "cancelled")
+    }
+}
+
+/// Background job
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct Job {
+    /// Unique identifier
+    pub id: String,
+
+    /// User ID pemilik
+    pub user_id: String,
+
+    /// Jenis job (compress, extract, copy, search)
+    pub kind: String,
+
+    /// Deskripsi singkat untuk ditampilkan
+    pub description: String,
+
+    /// Status (pending, running, completed, failed, cancelled)
+    pub status: String,
+
+    /// Total bytes yang akan diproses (0 jika belum diketahui)
+    pub total_bytes: i64,
+
+    /// Bytes yang sudah diproses
+    pub processed_bytes: i64,
+
+    /// Hasil job dalam format JSON
+    #[serde(skip_serializing)]
+    pub result: Option<String>,
+
+    /// Pesan error jika gagal
+    pub error_message: Option<String>,
+
+    /// Waktu job dibuat
+    pub created_at: DateTime<Utc>,
+
+    /// Waktu job mulai dijalankan worker
+    pub started_at: Option<DateTime<Utc>>,
+
+    /// Waktu selesai
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+/// Response DTO untuk background job
+#[derive(Debug, Serialize)]
+pub struct JobResponse {
+    #[serde(flatten)]
+    pub job: Job,
+    /// Progress dalam persen (0-100)
+    pub progress_percent: f64,
+    /// Hasil job (FileInfo atau daftar FileInfo) jika sudah selesai
+    pub result: Option<serde_json::Value>,
+}
+
+impl From<Job> for JobResponse {
+    fn from(job: Job) -> Self {
+        let progress_percent = if job.status == "completed" {
+            100.0
+        } else if job.total_bytes > 0 {
+            (job.processed_bytes as f64 / job.total_bytes as f64 * 100.0).min(100.0)
+        } else {
+            0.0
+        };
+        let result = job
+            .result
+            .as_deref()
+            .and_then(|result| serde_json::from_str(result).ok());
+
+        Self {
+            job,
+            progress_percent,
+            result,
+        }
+    }
+}
+
+/// Konfigurasi job runner
+#[derive(Debug, Clone)]
+pub struct JobConfig {
+    /// Jumlah job yang boleh berjalan bersamaan
+    pub workers: usize,
+
+    /// Lama riwayat job yang sudah selesai disimpan (hari)
+    pub retention_days: u32,
+}
+
+impl Default for JobConfig {
+    fn default() -> Self {
+        Self {
+            workers: std::env::var("JOB_WORKERS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .filter(|workers| *workers > 0)
+                .unwrap_or(2),
+            retention_days: std::env::var("JOB_RETENTION_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(7),
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! # Mail Queue Model
+//!
+//! Model dan DTO untuk inspeksi dan manajemen antrian Postfix (admin only).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// Pesan dalam antrian Postfix
+#[derive(Debug, Clone, Serialize)]
+pub struct MailQueueMessage {
+    /// Queue ID Postfix
+    pub queue_id: String,
+
+    /// Nama antrian (active, deferred, hold, incoming, maildrop)
+    pub queue_name: String,
+
+    /// Alamat pengirim (MAILER-DAEMON untuk bounce)
+    pub sender: String,
+
+    /// Daftar penerima
+    pub recipients: Vec<MailQueueRecipient>,
+
+    /// Ukuran pesan dalam bytes
+    pub size: u64,
+
+    /// Waktu pesan masuk antrian
+    pub arrival_time: DateTime<Utc>,
+
+    /// Umur pesan dalam detik
+    pub age_seconds: i64,
+
+    /// Alasan penundaan (dari penerima pertama yang tertunda)
+    pub deferral_reason: Option<String>,
+}
+
+/// Penerima pesan dalam antrian
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MailQueueRecipient {
+    /// Alamat penerima
+    pub address: String,
+
+    /// Alasan penundaan untuk penerima ini
+    #[serde(default)]
+    pub delay_reason: Option<String>,
+}
+
+/// Jumlah pesan antrian per pengirim
+#[derive(Debug, Clone, Serialize)]
+pub struct MailQueueSenderCount {
+    /// Alamat pengirim
+    pub sender: String,
+    /// Jumlah pesan
+    pub count: usize,
+    /// Jumlah penerima
+    pub recipient_count: usize,
+    /// Total ukuran dalam bytes
+    pub total_size: u64,
+}
+
+/// Ringkasan antrian
+#[derive(Debug, Serialize)]
+pub struct MailQueueSummary {
+    /// Total pesan
+    pub total: usize,
+    /// Total ukuran dalam bytes
+    pub total_size: u64,
+    /// Pesan di antrian active
+    pub active: usize,
+    /// Pesan di antrian deferred
+    pub deferred: usize,
+    /// Pesan di antrian hold
+    pub hold: usize,
+}
+
+/// Response DTO untuk list antrian
+#[derive(Debug, Serialize)]
+pub struct MailQueueResponse {
+    pub summary: MailQueueSummary,
+    pub messages: Vec<MailQueueMessage>,
+}
+
+/// Aksi yang didukung untuk pesan antrian
+pub const MAIL_QUEUE_ACTIONS: &[&str] = &["hold", "release", "requeue", "delete"];
+
+/// DTO untuk aksi terhadap pesan antrian
+///
+/// Target dipilih lewat `queue_ids` atau `sender` (semua pesan dari pengirim).
+#[derive(Debug, Deserialize, Validate)]
+pub struct MailQueueActionRequest {
+    /// Aksi: hold, release, requeue, delete
+    #[validate(custom = "validate_queue_action")]
+    pub action: String,
+
+    /// Queue ID target
+    #[validate(length(max = 1000, message = "Maksimal 1000 queue ID per request"))]
+    #[validate(custom = "validate_queue_ids")]
+    pub queue_ids: Option<Vec<String>>,
+
+    /// Alamat pengirim target
+    #[validate(length(min = 1, max = 255, message = "Sender harus 1-255 karakter"))]
+    pub sender: Option<String>,
+}
+
+/// Hasil aksi antrian
+#[derive(Debug, Serialize)]
+pub struct MailQueueActionResult {
+    /// Aksi yang dijalankan
+    pub action: String,
+    /// Jumlah pesan yang diproses
+    pub affected: usize,
+}
+
+fn validate_queue_action(action: &str) -> Result<(), validator::ValidationError> {
+    if MAIL_QUEUE_ACTIONS.contains(&action) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_queue_action"))
+    }
+}
+
+fn validate_queue_ids(ids: &[String]) -> Result<(), validator::ValidationError> {
+    if ids.iter().all(|id| is_valid_queue_id(id)) {
+        Ok(())
+    } else {
+        Err(validator::ValidationError::new("invalid_queue_id"))
+    }
+}
+
+/// Cek format queue ID Postfix (short hex atau long alphanumeric)
+pub fn is_valid_queue_id(id: &str) -> bool {
+    (6..=32).contains(&id.len()) && id.chars().all(|c| c.is_ascii_alphanumeric())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_valid_queue_id() {
+        assert!(is_valid_queue_id("3F1A2B4C5D"));
+        assert!(is_valid_queue_id("4Xk9Ld2Xyzz1Nq"));
+        assert!(!is_valid_queue_id("ALL"));
+        assert!(!is_valid_queue_id("3F1A2B -d ALL"));
+        assert!(!is_valid_queue_id("../etc"));
+    }
+
+    #[test]
+    fn test_validate_queue_action() {
+        assert!(validate_queue_action("hold").is_ok());
+        assert!(validate_queue_action("delete").is_ok());
+        assert!(validate_queue_action("flush").is_err());
+    }
+}
@@ -0,0 +1,336 @@
+//! # Mailing List Model
+//!
+//! Model dan DTO untuk Mailing List (announcement/discussion) per domain.
+//! Includes subscribers dengan double opt-in dan konfigurasi transport MTA.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::Validate;
+
+/// Mailing List entity dari database
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct MailingList {
+    /// Unique identifier
+    pub id: String,
+
+    /// User ID pemilik
+    pub user_id: String,
+
+    /// Domain ID
+    pub domain_id: String,
+
+    /// Nama list (bagian sebelum @)
+    pub list_name: String,
+
+    /// Alamat list lengkap (e.g., news@example.com)
+    pub list_address: String,
+
+    /// Deskripsi list
+    pub description: Option<String>,
+
+    /// Tipe list (announcement, discussion)
+    pub list_type: String,
+
+    /// Mode moderasi (none, non_members, all)
+    pub moderation: String,
+
+    /// Set Reply-To ke alamat list
+    pub reply_to_list: bool,
+
+    /// Status aktif
+    pub is_active: bool,
+
+    /// Waktu pembuatan
+    pub created_at: DateTime<Utc>,
+
+    /// Waktu update terakhir
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Response DTO untuk Mailing List
+#[derive(Debug, Serialize)]
+pub struct MailingListResponse {
+    pub id: String,
+    pub user_id: String,
+    pub domain_id: String,
+    pub list_name: String,
+    pub list_address: String,
+    pub description: Option<String>,
+    pub list_type: String,
+    pub moderation: String,
+    pub reply_to_list: bool,
+    pub is_active: bool,
+    /// Jumlah subscriber aktif
+    pub subscriber_count: i64,
+    /// Jumlah subscriber yang menunggu konfirmasi
+    pub pending_count: i64,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl MailingListResponse {
+    /// Membuat response dari entity dan jumlah subscriber
+    pub fn from_list(list: MailingList, subscriber_count: i64, pending_count: i64) -> Self {
+        Self {
+            id: list.id,
+            user_id: list.user_id,
+            domain_id: list.domain_id,
+            list_name: list.list_name,
+            list_address: list.list_address,
+            description: list.description,
+            list_type: list.list_type,
+            moderation: list.moderation,
+            reply_to_list: list.reply_to_list,
+            is_active: list.is_active,
+            subscriber_count,
+            pending_count,
+            created_at: list.created_at,
+            updated_at: list.updated_at,
+        }
+    }
+}
+
+/// DTO untuk membuat mailing list
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateMailingListRequest {
+    /// Domain ID
+    pub domain_id: String,
+
+    /// Nama list (bagian sebelum @)
+    #[validate(length(min = 1, max = 64, message = "Nama list harus 1-64 karakter"))]
+    #[validate(regex(
+        path = "crate::models::email::EMAIL_USERNAME_REGEX",
+        message = "Nama list hanya boleh huruf, angka, titik, dash, dan underscore"
+    ))]
+    pub list_name: String,
+
+    /// Deskripsi (opsional)
+    #[validate(length(max = 255, message = "Deskripsi maksimal 255 karakter"))]
+    pub description: Option<String>,
+
+    /// Tipe list: announcement atau discussion (default: discussion)
+    #[validate(custom = "validate_list_type")]
+    pub list_type: Option<String>,
+
+    /// Mode moderasi: none, non_members, all (default: non_members)
+    #[validate(custom = "validate_moderation")]
+    pub moderation: Option<String>,
+
+    /// Set Reply-To ke alamat list (default: true)
+    pub reply_to_list: Option<bool>,
+}
+
+/// DTO untuk update mailing list
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateMailingListRequest {
+    /// Deskripsi baru
+    #[validate(length(max = 255, message = "Deskripsi maksimal 255 karakter"))]
+    pub description: Option<String>,
+
+    /// Tipe list baru
+    #[validate(custom = "validate_list_type")]
+    pub list_type: Option<String>,
+
+    /// Mode moderasi baru
+    #[validate(custom = "validate_moderation")]
+    pub moderation: Option<String>,
+
+    /// Set Reply-To ke alamat list
+    pub reply_to_list: Option<bool>,
+
+    /// Status aktif
+    pub is_active: Option<bool>,
+}
+
+fn validate_list_type(list_type: &str) -> Result<(), validator::ValidationError> {
+    match list_type {
+        "announcement" | "discussion" => Ok(()),
+        _ => Err(validator::ValidationError::new("invalid_list_type")),
+    }
+}
+
+fn validate_moderation(moderation: &str) -> Result<(), validator::ValidationError> {
+    match moderation {
+        "none" | "non_members" | "all" => Ok(()),
+        _ => Err(validator::ValidationError::new("invalid_moderation")),
+    }
+}
+
+/// Mailing List Subscriber entity
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct MailingListSubscriber {
+    /// Unique identifier
+    pub id: String,
+
+    /// Mailing List ID
+    pub list_id: String,
+
+    /// Alamat email subscriber
+    pub email: String,
+
+    /// Nama subscriber (opsional)
+    pub name: Option<String>,
+
+    /// Status (pending, active, unsubscribed)
+    pub status: String,
+
+    /// Token konfirmasi double opt-in
+    #[serde(skip_serializing)]
+    pub confirm_token: Option<String>,
+
+    /// Waktu kadaluarsa token konfirmasi
+    pub token_expires_at: Option<DateTime<Utc>>,
+
+    /// Waktu konfirmasi
+    pub confirmed_at: Option<DateTime<Utc>>,
+
+    /// Waktu pembuatan
+    pub created_at: DateTime<Utc>,
+}
+
+impl MailingListSubscriber {
+    /// Masa berlaku token konfirmasi (48 jam)
+    pub fn confirm_token_ttl() -> Duration {
+        Duration::hours(48)
+    }
+
+    /// Cek apakah token konfirmasi sudah kadaluarsa
+    pub fn is_token_expired(&self) -> bool {
+        self.token_expires_at
+            .map(|expires_at| Utc::now() > expires_at)
+            .unwrap_or(true)
+    }
+}
+
+/// DTO untuk menambah subscriber
+#[derive(Debug, Deserialize, Validate)]
+pub struct AddSubscriberRequest {
+    /// Alamat email subscriber
+    #[validate(email(message = "Format email tidak valid"))]
+    pub email: String,
+
+    /// Nama subscriber (opsional)
+    #[validate(length(max = 255, message = "Nama maksimal 255 karakter"))]
+    pub name: Option<String>,
+
+    /// Kirim email konfirmasi double opt-in (default: true)
+    pub require_confirmation: Option<bool>,
+}
+
+/// DTO untuk import subscriber dari CSV
+///
+/// Format CSV: `email[,name]` per baris, header `email,name` opsional.
+#[derive(Debug, Deserialize, Validate)]
+pub struct ImportSubscribersRequest {
+    /// Konten CSV
+    #[validate(length(min = 1, max = 5242880, message = "CSV harus 1 byte - 5MB"))]
+    pub csv: String,
+
+    /// Kirim email konfirmasi double opt-in (default: true)
+    pub require_confirmation: Option<bool>,
+}
+
+/// Hasil import subscriber
+#[derive(Debug, Default, Serialize)]
+pub struct ImportSubscribersResult {
+    /// Jumlah subscriber yang berhasil ditambahkan
+    pub imported: usize,
+    /// Jumlah baris yang dilewati karena sudah terdaftar
+    pub skipped: usize,
+    /// Baris yang tidak valid
+    pub invalid: Vec<String>,
+}
+
+/// Konfigurasi list manager (Mailman 3 via LMTP) dan map Postfix
+#[derive(Debug, Clone)]
+pub struct MailingListConfig {
+    /// Transport Postfix untuk list manager (e.g., "lmtp:[127.0.0.1]:8024")
+    pub transport: String,
+
+    /// Path transport map yang di-generate panel
+    pub transport_map_path: String,
+
+    /// Path virtual alias map yang di-generate panel
+    pub virtual_map_path: String,
+
+    /// Base URL panel untuk link konfirmasi
+    pub confirm_base_url: String,
+
+    /// Alamat pengirim email konfirmasi
+    pub sender_address: String,
+
+    /// Base URL Mailman 3 REST API (e.g., "http://127.0.0.1:8001/3.1")
+    pub mailman_api_url: String,
+
+    /// User Mailman REST API
+    pub mailman_api_user: String,
+
+    /// Password Mailman REST API
+    pub mailman_api_password: String,
+}
+
+impl Default for MailingListConfig {
+    fn default() -> Self {
+        Self {
+            transport: std::env::var("MAILING_LIST_TRANSPORT")
+                .unwrap_or_else(|_| "lmtp:[127.0.0.1]:8024".to_string()),
+            transport_map_path: std::env::var("MAILING_LIST_TRANSPORT_MAP")
+                .unwrap_or_else(|_| "/etc/postfix/nusa_mailing_list_transport".to_string()),
+            virtual_map_path: std::env::var("MAILING_LIST_VIRTUAL_MAP")
+                .unwrap_or_else(|_| "/etc/postfix/nusa_mailing_list_virtual".to_string()),
+            confirm_base_url: std::env::var("PANEL_URL")
+                .unwrap_or_else(|_| "http://localhost:8095".to_string()),
+            sender_address: std::env::var("MAILING_LIST_SENDER")
+                .unwrap_or_else(|_| "no-reply@localhost".to_string()),
+            mailman_api_url: std::env::var("MAILMAN_API_URL")
+                .unwrap_or_else(|_| "http://127.0.0.1:8001/3.1".to_string()),
+            mailman_api_user: std::env::var("MAILMAN_API_USER")
+                .unwrap_or_else(|_| "restadmin".to_string()),
+            mailman_api_password: std::env::var("MAILMAN_API_PASSWORD")
+                .unwrap_or_else(|_| "restpass".to_string()),
+        }
+    }
+}
+
+/// Suffix alamat tambahan yang dilayani list manager (Mailman 3)
+pub const LIST_ADDRESS_SUFFIXES: &[&str] = &[
+    "", "-bounces", "-confirm", "-join", "-leave", "-owner", "-request", "-subscribe",
+    "-unsubscribe",
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_list_type_and_moderation() {
+        assert!(validate_list_type("announcement").is_ok());
+        assert!(validate_list_type("discussion").is_ok());
+        assert!(validate_list_type("newsletter").is_err());
+        assert!(validate_moderation("non_members").is_ok());
+        assert!(validate_moderation("everyone").is_err());
+    }
+
+    #[test]
+    fn test_subscriber_token_expiry() {
+        let mut subscriber = MailingListSubscriber {
+            id: "1".to_string(),
+            list_id: "1".to_string(),
+            email: "john@example.com".to_string(),
+            name: None,
+            status: "pending".to_string(),
+            confirm_token: Some("token".to_string()),
+            token_expires_at: Some(Utc::now() + MailingListSubscriber::confirm_token_ttl()),
+            confirmed_at: None,
+            created_at: Utc::now(),
+        };
+        assert!(!subscriber.is_token_expired());
+
+        subscriber.token_expires_at = Some(Utc::now() - Duration::seconds(1));
+        assert!(subscriber.is_token_expired());
+
+        subscriber.token_expires_at = None;
+        assert!(subscriber.is_token_expired());
+    }
+}
@@ -6,24 +6,36 @@ pub mod app_installer;
 pub mod domain;
 pub mod email;
 pub mod file;
+pub mod job;
+pub mod mail_queue;
 pub mod ftp;
+pub mod mailing_list;
 pub mod managed_db;
 pub mod phpmyadmin_signon;
+pub mod quota;
 pub mod redis;
 pub mod security;
+pub mod send_limit;
 pub mod system;
 pub mod user;
 pub mod web_server;
+pub mod webmail_signon;
 
 pub use app_installer::*;
 pub use domain::*;
 pub use email::*;
 pub use file::*;
+pub use job::*;
+pub use mail_queue::*;
 pub use ftp::*;
+pub use mailing_list::*;
 pub use managed_db::*;
 pub use phpmyadmin_signon::*;
+pub use quota::*;
 pub use redis::*;
 pub use security::*;
+pub use send_limit::*;
 pub use system::*;
 pub use user::*;
 pub use web_server::*;
+pub use webmail_signon::*;
@@ -0,0 +1,143 @@
+//! # Disk Quota Model
+//!
+//! Model dan DTO untuk kuota disk per user.
+
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::errors::{ApiError, ApiResult};
+
+/// Sumber data pemakaian disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UsageSource {
+    /// Linux filesystem quota (`repquota`)
+    Quota,
+    /// Fallback `du` pada home user
+    Du,
+}
+
+/// Kuota disk user beserta pemakaiannya
+#[derive(Debug, Clone)]
+pub struct DiskQuota {
+    /// Pemakaian disk dalam bytes
+    pub used_bytes: u64,
+
+    /// Batas disk dalam bytes, `None` = tanpa batas
+    pub limit_bytes: Option<u64>,
+
+    /// Sumber data pemakaian
+    pub source: UsageSource,
+}
+
+impl DiskQuota {
+    /// Sisa kuota dalam bytes, `None` = tanpa batas
+    pub fn available_bytes(&self) -> Option<u64> {
+        self.limit_bytes
+            .map(|limit| limit.saturating_sub(self.used_bytes))
+    }
+
+    /// Pastikan `additional` bytes masih muat di dalam kuota
+    pub fn ensure_available(&self, additional: u64) -> ApiResult<()> {
+        match self.limit_bytes {
+            Some(limit) if self.used_bytes.saturating_add(additional) > limit => {
+                Err(ApiError::QuotaExceeded(limit))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Response pemakaian disk user
+#[derive(Debug, Serialize)]
+pub struct DiskUsageResponse {
+    /// Pemakaian disk dalam bytes
+    pub used_bytes: u64,
+
+    /// Batas disk dalam bytes, `null` = tanpa batas
+    pub limit_bytes: Option<u64>,
+
+    /// Sisa kuota dalam bytes, `null` = tanpa batas
+    pub available_bytes: Option<u64>,
+
+    /// Persentase pemakaian, `null` = tanpa batas
+    pub usage_percent: Option<f64>,
+
+    /// Sumber data pemakaian
+    pub source: UsageSource,
+}
+
+impl From<DiskQuota> for DiskUsageResponse {
+    fn from(quota: DiskQuota) -> Self {
+        Self {
+            used_bytes: quota.used_bytes,
+            limit_bytes: quota.limit_bytes,
+            available_bytes: quota.available_bytes(),
+            usage_percent: quota
+                .limit_bytes
+                .filter(|limit| *limit > 0)
+                .map(|limit| quota.used_bytes as f64 / limit as f64 * 100.0),
+            source: quota.source,
+        }
+    }
+}
+
+/// Request untuk mengubah kuota disk user (admin)
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateDiskQuotaRequest {
+    /// Batas disk dalam MB: `null` = default server, 0 = tanpa batas
+    #[validate(range(min = 0, max = 104857600, message = "Kuota harus 0-104857600 MB"))]
+    pub disk_quota_mb: Option<i64>,
+}
+
+/// Konfigurasi kuota disk
+#[derive(Debug, Clone)]
+pub struct QuotaConfig {
+    /// Kuota default dalam MB untuk user tanpa kuota khusus, 0 = tanpa batas
+    pub default_limit_mb: u64,
+
+    /// Lama pemakaian disk di-cache (detik)
+    pub usage_cache_secs: u64,
+}
+
+impl Default for QuotaConfig {
+    fn default() -> Self {
+        Self {
+            default_limit_mb: std::env::var("DISK_QUOTA_DEFAULT_MB")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0),
+            usage_cache_secs: std::env::var("DISK_QUOTA_CACHE_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ensure_available() {
+        let quota = DiskQuota {
+            used_bytes: 900,
+            limit_bytes: Some(1000),
+            source: UsageSource::Du,
+        };
+        assert_eq!(quota.available_bytes(), Some(100));
+        assert!(quota.ensure_available(100).is_ok());
+        assert!(matches!(
+            quota.ensure_available(101),
+            Err(ApiError::QuotaExceeded(1000))
+        ));
+
+        let unlimited = DiskQuota {
+            limit_bytes: None,
+            ..quota
+        };
+        assert!(unlimited.ensure_available(u64::MAX).is_ok());
+        assert_eq!(DiskUsageResponse::from(unlimited).usage_percent, None);
+    }
+}
@@ -0,0 +1,259 @@
+//! # Send Limit Model
+//!
+//! Model dan DTO untuk limit pengiriman email keluar per mailbox dan per domain.
+//! Limit ditegakkan oleh policy server (Postfix policy delegation).
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::Validate;
+
+use crate::errors::{ApiError, ApiResult};
+
+/// Limit pengiriman per domain entity
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct DomainSendLimit {
+    /// Domain ID
+    pub domain_id: String,
+
+    /// User ID pemilik
+    pub user_id: String,
+
+    /// Total penerima per jam untuk seluruh domain (NULL = tanpa limit)
+    pub hourly_limit: Option<i32>,
+
+    /// Total penerima per hari untuk seluruh domain (NULL = tanpa limit)
+    pub daily_limit: Option<i32>,
+
+    /// Waktu update terakhir
+    pub updated_at: DateTime<Utc>,
+}
+
+/// DTO untuk update limit domain (field kosong = tanpa limit)
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateDomainSendLimitRequest {
+    /// Total penerima per jam
+    #[validate(range(min = 1, max = 1000000, message = "Limit per jam harus 1-1000000"))]
+    pub hourly_limit: Option<i32>,
+
+    /// Total penerima per hari
+    #[validate(range(min = 1, max = 10000000, message = "Limit per hari harus 1-10000000"))]
+    pub daily_limit: Option<i32>,
+}
+
+/// Response DTO pemakaian dan limit
+#[derive(Debug, Serialize)]
+pub struct SendLimitUsage {
+    /// Mailbox atau domain
+    pub scope: String,
+    /// Limit per jam (null = tanpa limit)
+    pub hourly_limit: Option<i64>,
+    /// Limit per hari (null = tanpa limit)
+    pub daily_limit: Option<i64>,
+    /// Penerima dalam jam berjalan
+    pub sent_last_hour: i64,
+    /// Penerima dalam 24 jam terakhir
+    pub sent_last_day: i64,
+}
+
+/// Alert saat limit terlampaui
+#[derive(Debug, Clone, FromRow, Serialize)]
+pub struct SendLimitAlert {
+    /// Unique identifier
+    pub id: String,
+
+    /// Mailbox pengirim
+    pub email_address: String,
+
+    /// Domain ID
+    pub domain_id: String,
+
+    /// Scope limit (account, domain)
+    pub scope: String,
+
+    /// Periode limit (hourly, daily)
+    pub period: String,
+
+    /// Nilai limit yang terlampaui
+    pub limit_value: i32,
+
+    /// IP client SMTP
+    pub client_address: Option<String>,
+
+    /// Waktu alert
+    pub created_at: DateTime<Utc>,
+}
+
+/// Konfigurasi policy server
+#[derive(Debug, Clone)]
+pub struct SendLimitConfig {
+    /// Jalankan policy server saat startup
+    pub enabled: bool,
+
+    /// Alamat listen (e.g., "127.0.0.1:10031" atau "unix:/var/spool/postfix/private/nusa-policy")
+    pub listen: String,
+
+    /// Group yang boleh terhubung ke unix socket (mode 0660)
+    pub socket_group: String,
+
+    /// Default limit per jam per mailbox
+    pub default_hourly: i64,
+
+    /// Default limit per hari per mailbox
+    pub default_daily: i64,
+
+    /// Alamat pengirim email alert
+    pub alert_sender: String,
+}
+
+impl Default for SendLimitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: std::env::var("MAIL_POLICY_ENABLED")
+                .map(|v| v == "true" || v == "1")
+                .unwrap_or(false),
+            listen: std::env::var("MAIL_POLICY_LISTEN")
+                .unwrap_or_else(|_| "127.0.0.1:10031".to_string()),
+            socket_group: std::env::var("MAIL_POLICY_SOCKET_GROUP")
+                .unwrap_or_else(|_| "postfix".to_string()),
+            default_hourly: std::env::var("MAIL_SEND_LIMIT_HOURLY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(200),
+            default_daily: std::env::var("MAIL_SEND_LIMIT_DAILY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1000),
+            alert_sender: std::env::var("MAIL_POLICY_ALERT_SENDER")
+                .unwrap_or_else(|_| "no-reply@localhost".to_string()),
+        }
+    }
+}
+
+impl SendLimitConfig {
+    /// Pastikan limit mailbox yang diminta user (non-admin) tidak melebihi default server
+    pub fn ensure_within_default(&self, hourly: Option<i32>, daily: Option<i32>) -> ApiResult<()> {
+        if hourly.is_some_and(|limit| i64::from(limit) > self.default_hourly) {
+            return Err(ApiError::ValidationError(format!(
+                "Limit per jam maksimal {}",
+                self.default_hourly
+            )));
+        }
+        if daily.is_some_and(|limit| i64::from(limit) > self.default_daily) {
+            return Err(ApiError::ValidationError(format!(
+                "Limit per hari maksimal {}",
+                self.default_daily
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Limit yang terlampaui
+#[derive(Debug, Clone, PartialEq)]
+pub struct SendLimitExceeded {
+    /// Scope limit (account, domain)
+    pub scope: &'static str,
+    /// Periode limit (hourly, daily)
+    pub period: &'static str,
+    /// Nilai limit
+    pub limit: i64,
+}
+
+/// Cek apakah satu penerima tambahan melampaui limit
+///
+/// Urutan pengecekan: mailbox per jam, mailbox per hari, domain per jam, domain per hari.
+pub fn check_send_limits(
+    account: &SendLimitUsage,
+    domain: &SendLimitUsage,
+) -> Option<SendLimitExceeded> {
+    let checks = [
+        (
+            "account",
+            "hourly",
+            account.hourly_limit,
+            account.sent_last_hour,
+        ),
+        (
+            "account",
+            "daily",
+            account.daily_limit,
+            account.sent_last_day,
+        ),
+        (
+            "domain",
+            "hourly",
+            domain.hourly_limit,
+            domain.sent_last_hour,
+        ),
+        ("domain", "daily", domain.daily_limit, domain.sent_last_day),
+    ];
+
+    checks
+        .into_iter()
+        .find_map(|(scope, period, limit, sent)| match limit {
+            Some(limit) if sent >= limit => Some(SendLimitExceeded {
+                scope,
+                period,
+                limit,
+            }),
+            _ => None,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usage(
+        hourly_limit: Option<i64>,
+        daily_limit: Option<i64>,
+        hour: i64,
+        day: i64,
+    ) -> SendLimitUsage {
+        SendLimitUsage {
+            scope: "test".to_string(),
+            hourly_limit,
+            daily_limit,
+            sent_last_hour: hour,
+            sent_last_day: day,
+        }
+    }
+
+    #[test]
+    fn test_check_send_limits() {
+        let domain = usage(None, Some(500), 10, 100);
+
+        let account = usage(Some(200), Some(1000), 5, 50);
+        assert_eq!(check_send_limits(&account, &domain), None);
+
+        let account = usage(Some(200), Some(1000), 200, 250);
+        let exceeded = check_send_limits(&account, &domain).unwrap();
+        assert_eq!(
+            (exceeded.scope, exceeded.period, exceeded.limit),
+            ("account", "hourly", 200)
+        );
+
+        let domain = usage(None, Some(500), 10, 500);
+        let account = usage(Some(200), Some(1000), 5, 50);
+        let exceeded = check_send_limits(&account, &domain).unwrap();
+        assert_eq!((exceeded.scope, exceeded.period), ("domain", "daily"));
+    }
+
+    #[test]
+    fn test_ensure_within_default() {
+        let config = SendLimitConfig {
+            enabled: false,
+            listen: "127.0.0.1:10031".to_string(),
+            socket_group: "postfix".to_string(),
+            default_hourly: 200,
+            default_daily: 1000,
+            alert_sender: "no-reply@localhost".to_string(),
+        };
+
+        assert!(config.ensure_within_default(None, None).is_ok());
+        assert!(config.ensure_within_default(Some(200), Some(1000)).is_ok());
+        assert!(config.ensure_within_default(Some(201), None).is_err());
+        assert!(config.ensure_within_default(None, Some(5000)).is_err());
+    }
+}
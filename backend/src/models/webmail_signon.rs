@@ -0,0 +1,206 @@
+//! # Webmail Signon Model
+//!
+//! Model dan DTO untuk Webmail SSO (Roundcube / SnappyMail).
+//! Memungkinkan user untuk login ke webmail tanpa mengetik ulang password mailbox.
+
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Token untuk SSO webmail
+/// Token ini memiliki masa hidup singkat (30 detik) dan hanya bisa digunakan sekali
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WebmailSignonToken {
+    /// Unique identifier token
+    pub id: String,
+
+    /// ID email account yang akan di-login
+    pub email_account_id: String,
+
+    /// ID panel user yang melakukan request
+    pub panel_user_id: String,
+
+    /// Alamat email mailbox
+    pub email_address: String,
+
+    /// Waktu pembuatan token
+    pub created_at: DateTime<Utc>,
+
+    /// Waktu kadaluarsa token
+    pub expires_at: DateTime<Utc>,
+}
+
+impl WebmailSignonToken {
+    /// Membuat token baru dengan TTL 30 detik
+    ///
+    /// # Arguments
+    /// * `email_account_id` - ID email account yang akan di-SSO
+    /// * `panel_user_id` - ID panel user yang melakukan request
+    /// * `email_address` - Alamat email mailbox
+    pub fn new(email_account_id: &str, panel_user_id: &str, email_address: &str) -> Self {
+        let now = Utc::now();
+        Self {
+            id: Uuid::new_v4().to_string(),
+            email_account_id: email_account_id.to_string(),
+            panel_user_id: panel_user_id.to_string(),
+            email_address: email_address.to_string(),
+            created_at: now,
+            expires_at: now + Duration::seconds(30),
+        }
+    }
+
+    /// Cek apakah token sudah kadaluarsa
+    pub fn is_expired(&self) -> bool {
+        Utc::now() > self.expires_at
+    }
+}
+
+/// Response DTO untuk generate webmail signon token
+#[derive(Debug, Serialize)]
+pub struct WebmailSignonResponse {
+    /// URL lengkap untuk SSO ke webmail
+    pub signon_url: String,
+
+    /// Detik tersisa sampai token kadaluarsa
+    pub expires_in: i64,
+}
+
+/// Response DTO berisi kredensial untuk plugin webmail
+/// Hanya dikembalikan jika token valid
+///
+/// Karena password mailbox disimpan sebagai hash, login dilakukan
+/// melalui Dovecot master user (`mailbox*master`).
+#[derive(Debug, Serialize)]
+pub struct WebmailCredentials {
+    /// Alamat email mailbox (untuk ditampilkan di webmail)
+    pub email: String,
+
+    /// Login IMAP (format master user, e.g. "info@example.com*nusapanel")
+    pub login: String,
+
+    /// Password master user
+    pub password: String,
+
+    /// IMAP server
+    pub imap_host: String,
+
+    /// IMAP port
+    pub imap_port: u16,
+
+    /// SMTP server
+    pub smtp_host: String,
+
+    /// SMTP port
+    pub smtp_port: u16,
+}
+
+/// Konfigurasi webmail
+#[derive(Debug, Clone)]
+pub struct WebmailConfig {
+    /// Base URL webmail (e.g., "https://webmail.example.com")
+    pub base_url: String,
+
+    /// Path ke signon script/plugin
+    pub signon_path: String,
+
+    /// Internal API key untuk validasi dari plugin webmail
+    pub internal_key: String,
+
+    /// Dovecot master user (kosong = SSO tidak tersedia)
+    pub master_user: String,
+
+    /// Password Dovecot master user
+    pub master_password: String,
+
+    /// Separator master user Dovecot (`auth_master_user_separator`)
+    pub master_separator: String,
+
+    /// IMAP server
+    pub imap_host: String,
+
+    /// IMAP port (SSL)
+    pub imap_port: u16,
+
+    /// SMTP server
+    pub smtp_host: String,
+
+    /// SMTP port (TLS)
+    pub smtp_port: u16,
+}
+
+impl Default for WebmailConfig {
+    fn default() -> Self {
+        Self {
+            base_url: std::env::var("WEBMAIL_URL")
+                .unwrap_or_else(|_| "https://webmail.example.com".to_string()),
+            signon_path: std::env::var("WEBMAIL_SIGNON_PATH")
+                .unwrap_or_else(|_| "/?_action=nusa_signon".to_string()),
+            internal_key: std::env::var("WEBMAIL_INTERNAL_KEY")
+                .unwrap_or_else(|_| "change-me-in-production".to_string()),
+            master_user: std::env::var("WEBMAIL_MASTER_USER").unwrap_or_default(),
+            master_password: std::env::var("WEBMAIL_MASTER_PASSWORD").unwrap_or_default(),
+            master_separator: std::env::var("WEBMAIL_MASTER_SEPARATOR")
+                .unwrap_or_else(|_| "*".to_string()),
+            imap_host: std::env::var("MAIL_IMAP_HOST")
+                .unwrap_or_else(|_| "mail.example.com".to_string()),
+            imap_port: std::env::var("MAIL_IMAP_PORT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(993),
+            smtp_host: std::env::var("MAIL_SMTP_HOST")
+                .unwrap_or_else(|_| "mail.example.com".to_string()),
+            smtp_port: std::env::var("MAIL_SMTP_PORT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(587),
+        }
+    }
+}
+
+impl WebmailConfig {
+    /// Cek apakah master user sudah dikonfigurasi
+    pub fn has_master_user(&self) -> bool {
+        !self.master_user.is_empty() && !self.master_password.is_empty()
+    }
+
+    /// Build login IMAP dengan format master user
+    ///
+    /// # Example
+    /// `info@example.com` + `*` + `nusapanel` => `info@example.com*nusapanel`
+    pub fn master_login(&self, email_address: &str) -> String {
+        format!("{}{}{}", email_address, self.master_separator, self.master_user)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_webmail_signon_token_expiry() {
+        let mut token = WebmailSignonToken::new("email-123", "panel-user-456", "info@example.com");
+
+        assert!(!token.id.is_empty());
+        assert_eq!(token.email_address, "info@example.com");
+        assert!(!token.is_expired());
+
+        token.expires_at = Utc::now() - Duration::seconds(1);
+        assert!(token.is_expired());
+    }
+
+    #[test]
+    fn test_master_login() {
+        let config = WebmailConfig {
+            master_user: "nusapanel".to_string(),
+            master_password: "secret".to_string(),
+            master_separator: "*".to_string(),
+            ..WebmailConfig::default()
+        };
+
+        assert!(config.has_master_user());
+        assert_eq!(
+            config.master_login("info@example.com"),
+            "info@example.com*nusapanel"
+        );
+    }
+}
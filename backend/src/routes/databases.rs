@@ -2,17 +2,27 @@
 //!
 //! Route handlers untuk managed database operations.
 
+use rocket::data::{Data, ToByteUnit};
+use rocket::http::{ContentType, Header};
+use rocket::request::Request;
+use rocket::response::{self, Responder, Response};
 use rocket::serde::json::Json;
 use rocket::{delete, get, post, put, routes, Route, State};
 
 use crate::database::Database;
-use crate::errors::ApiResult;
+use crate::errors::{ApiError, ApiResult};
 use crate::guards::AuthenticatedUser;
 use crate::models::{
-    CreateDatabaseRequest, CreateDatabaseUserRequest, DatabaseUserResponse,
-    ManagedDatabaseResponse, UpdateDatabaseRequest, UpdateDatabaseUserRequest,
+    CreateDatabaseGrantRequest, CreateDatabaseRequest, CreateDatabaseUserRequest,
+    DatabaseDumpConfig, DatabaseGrantResponse, DatabaseStatsResponse, DatabaseImportResponse, DatabaseUserResponse,
+    CreateRemoteMysqlHostRequest, ManagedDatabaseResponse, RemoteMysqlHost,
+    RestoreDatabaseRequest, UpdateDatabaseGrantRequest, UpdateDatabaseRequest,
+    UpdateDatabaseUserRequest,
+};
+use crate::services::{
+    DatabaseBackupService, DatabaseExport, DatabaseService, DatabaseStatsService,
+    RemoteMysqlService,
 };
-use crate::services::DatabaseService;
 use crate::utils::response::{success, success_message, ApiResponse};
 
 // ==========================================
@@ -49,9 +59,29 @@ pub async fn get_database(
     Ok(success(database))
 }
 
+/// Get database statistics
+///
+/// Jumlah tabel, estimasi baris, ukuran data/index per tabel, engine dan
+/// fragmentasi. Ukuran database tersimpan ikut diperbarui.
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Database ID
+#[get("/<id>/stats", rank = 2)]
+pub async fn database_stats(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<Json<ApiResponse<DatabaseStatsResponse>>> {
+    let stats = DatabaseStatsService::get_stats(db.get_pool(), id, &user.id).await?;
+    Ok(success(stats))
+}
+
 /// Create new database
 ///
-/// Membuat database MySQL baru yang terpisah dari database system.
+/// Membuat database MySQL atau PostgreSQL baru yang terpisah dari database system.
 /// Nama database akan di-prefix dengan user ID untuk isolasi.
 ///
 /// # Headers
@@ -62,8 +92,9 @@ pub async fn get_database(
 /// {
 ///   "name": "wordpress",
 ///   "description": "WordPress database",  // optional
-///   "charset": "utf8mb4",  // optional
-///   "collation": "utf8mb4_unicode_ci"  // optional
+///   "engine": "mysql",  // optional: mysql | postgresql, default: mysql
+///   "charset": "utf8mb4",  // optional (PostgreSQL: UTF8, LATIN1, SQL_ASCII)
+///   "collation": "utf8mb4_unicode_ci"  // optional (PostgreSQL: default)
 /// }
 /// ```
 #[post("/", format = "json", data = "<request>")]
@@ -122,6 +153,128 @@ pub async fn delete_database(
     Ok(success_message("Database berhasil dihapus"))
 }
 
+// ==========================================
+// EXPORT / IMPORT / RESTORE ENDPOINTS
+// ==========================================
+
+/// Response streaming untuk file export (`.sql.gz`)
+impl<'r> Responder<'r, 'static> for DatabaseExport {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        Response::build()
+            .header(ContentType::new("application", "gzip"))
+            .header(Header::new(
+                "Content-Disposition",
+                format!("attachment; filename=\"{}\"", self.filename),
+            ))
+            .streamed_body(self.stream)
+            .ok()
+    }
+}
+
+/// Export database sebagai gzip SQL dump
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Database ID
+#[get("/export/<id>")]
+pub async fn export_database(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<DatabaseExport> {
+    DatabaseBackupService::export(db.get_pool(), id, &user.id).await
+}
+
+/// Import file `.sql` / `.sql.gz` ke database
+///
+/// Body request adalah isi file mentah (bukan JSON). Import berjalan di
+/// background; pantau progress via `GET /imports/<import_id>`.
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Database ID
+///
+/// # Query Parameters
+/// - filename: Nama file asli (e.g., backup.sql.gz)
+#[post("/import/<id>?<filename>", data = "<data>")]
+pub async fn import_database(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+    filename: &str,
+    data: Data<'_>,
+) -> ApiResult<Json<ApiResponse<DatabaseImportResponse>>> {
+    let path = DatabaseBackupService::prepare_import(db.get_pool(), id, &user.id, filename).await?;
+    let max_size = DatabaseDumpConfig::default().max_import_size;
+
+    let file = data
+        .open(max_size.bytes())
+        .into_file(&path)
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Upload error: {}", e)))?;
+
+    if !file.is_complete() {
+        let _ = std::fs::remove_file(&path);
+        return Err(ApiError::FileTooLarge(max_size));
+    }
+
+    let import =
+        DatabaseBackupService::start_import(db.get_pool(), id, &user.id, filename, path).await?;
+    Ok(success(import))
+}
+
+/// Status dan progress import database
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - import_id: Import ID
+#[get("/imports/<import_id>")]
+pub async fn get_database_import(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    import_id: &str,
+) -> ApiResult<Json<ApiResponse<DatabaseImportResponse>>> {
+    let import = DatabaseBackupService::get_import(db.get_pool(), import_id, &user.id).await?;
+    Ok(success(import))
+}
+
+/// Restore database dari system backup (tipe database atau full)
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Database ID
+///
+/// # Request Body
+/// ```json
+/// {
+///   "backup_id": "backup-uuid-here"
+/// }
+/// ```
+#[post("/restore/<id>", format = "json", data = "<request>")]
+pub async fn restore_database(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+    request: Json<RestoreDatabaseRequest>,
+) -> ApiResult<Json<ApiResponse<DatabaseImportResponse>>> {
+    let import = DatabaseBackupService::restore_from_backup(
+        db.get_pool(),
+        id,
+        &user.id,
+        request.into_inner(),
+    )
+    .await?;
+    Ok(success(import))
+}
+
 // ==========================================
 // DATABASE USER ENDPOINTS
 // ==========================================
@@ -162,6 +315,8 @@ pub async fn get_database_user(
 ///
 /// User ini bisa digunakan untuk login ke phpMyAdmin.
 /// User hanya akan memiliki akses ke database yang ditentukan.
+/// Account dibuat sebagai `user@localhost` ditambah satu account per host
+/// di allow-list remote MySQL.
 ///
 /// # Headers
 /// - Authorization: Bearer <access_token>
@@ -172,7 +327,7 @@ pub async fn get_database_user(
 ///   "username": "wpuser",
 ///   "password": "SecureP@ss123",
 ///   "database_id": "database-uuid-here",
-///   "host": "%",  // optional, default: % (semua host)
+///   "engine": "mysql",  // optional, default: engine database
 ///   "privileges": "ALL"  // optional, default: ALL
 /// }
 /// ```
@@ -238,6 +393,186 @@ pub async fn delete_database_user(
     Ok(success_message("Database user berhasil dihapus"))
 }
 
+// ==========================================
+// REMOTE MYSQL ENDPOINTS
+// ==========================================
+
+/// List allow-list host remote MySQL
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+#[get("/remote-hosts")]
+pub async fn list_remote_hosts(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+) -> ApiResult<Json<ApiResponse<Vec<RemoteMysqlHost>>>> {
+    let hosts = RemoteMysqlService::list_hosts(db.get_pool(), &user.id).await?;
+    Ok(success(hosts))
+}
+
+/// Tambah host ke allow-list remote MySQL
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Request Body
+/// ```json
+/// {
+///   "host": "203.0.113.0/24",  // IP atau CIDR IPv4
+///   "description": "Kantor"  // optional
+/// }
+/// ```
+#[post("/remote-hosts", format = "json", data = "<request>")]
+pub async fn add_remote_host(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    request: Json<CreateRemoteMysqlHostRequest>,
+) -> ApiResult<Json<ApiResponse<RemoteMysqlHost>>> {
+    let host =
+        RemoteMysqlService::add_host(db.get_pool(), &user.id, request.into_inner()).await?;
+    Ok(success(host))
+}
+
+/// Sinkronkan ulang account MySQL dan firewall dengan allow-list
+///
+/// Database user lama dengan host `%` dipindah ke `localhost`.
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+#[post("/remote-hosts/sync")]
+pub async fn sync_remote_hosts(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+) -> ApiResult<Json<ApiResponse<()>>> {
+    RemoteMysqlService::sync(db.get_pool(), &user.id).await?;
+    Ok(success_message("Remote MySQL berhasil disinkronkan"))
+}
+
+/// Hapus host dari allow-list remote MySQL
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Remote MySQL Host ID
+#[delete("/remote-hosts/<id>")]
+pub async fn remove_remote_host(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<Json<ApiResponse<()>>> {
+    RemoteMysqlService::remove_host(db.get_pool(), id, &user.id).await?;
+    Ok(success_message("Remote MySQL host berhasil dihapus"))
+}
+
+// ==========================================
+// DATABASE USER GRANT ENDPOINTS
+// ==========================================
+
+/// List grants database user
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Database User ID
+#[get("/users/<id>/grants")]
+pub async fn list_database_user_grants(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<Json<ApiResponse<Vec<DatabaseGrantResponse>>>> {
+    let grants = DatabaseService::get_database_user_grants(db.get_pool(), id, &user.id).await?;
+    Ok(success(grants))
+}
+
+/// Beri akses database user ke database lain
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Database User ID
+///
+/// # Request Body
+/// ```json
+/// {
+///   "database_id": "database-uuid-here",
+///   "privileges": "SELECT, INSERT"  // optional, default: ALL
+/// }
+/// ```
+#[post("/users/<id>/grants", format = "json", data = "<request>")]
+pub async fn add_database_user_grant(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+    request: Json<CreateDatabaseGrantRequest>,
+) -> ApiResult<Json<ApiResponse<DatabaseUserResponse>>> {
+    let db_user = DatabaseService::add_database_user_grant(
+        db.get_pool(),
+        id,
+        &user.id,
+        request.into_inner(),
+    )
+    .await?;
+    Ok(success(db_user))
+}
+
+/// Ubah privileges database user pada database
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Database User ID
+/// - database_id: Database ID
+///
+/// # Request Body
+/// ```json
+/// {
+///   "privileges": "SELECT, INSERT, UPDATE"
+/// }
+/// ```
+#[put("/users/<id>/grants/<database_id>", format = "json", data = "<request>")]
+pub async fn update_database_user_grant(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+    database_id: &str,
+    request: Json<UpdateDatabaseGrantRequest>,
+) -> ApiResult<Json<ApiResponse<DatabaseUserResponse>>> {
+    let db_user = DatabaseService::update_database_user_grant(
+        db.get_pool(),
+        id,
+        database_id,
+        &user.id,
+        request.into_inner(),
+    )
+    .await?;
+    Ok(success(db_user))
+}
+
+/// Cabut akses database user dari database
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Database User ID
+/// - database_id: Database ID
+#[delete("/users/<id>/grants/<database_id>")]
+pub async fn remove_database_user_grant(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+    database_id: &str,
+) -> ApiResult<Json<ApiResponse<DatabaseUserResponse>>> {
+    let db_user =
+        DatabaseService::remove_database_user_grant(db.get_pool(), id, database_id, &user.id)
+            .await?;
+    Ok(success(db_user))
+}
+
 /// Mendapatkan routes untuk databases
 pub fn database_routes() -> Vec<Route> {
     routes![
@@ -247,11 +582,27 @@ pub fn database_routes() -> Vec<Route> {
         create_database,
         update_database,
         delete_database,
+        database_stats,
+        // Export / Import / Restore
+        export_database,
+        import_database,
+        get_database_import,
+        restore_database,
         // Database Users
         list_database_users,
         get_database_user,
         create_database_user,
         update_database_user,
-        delete_database_user
+        delete_database_user,
+        // Database User Grants
+        list_database_user_grants,
+        add_database_user_grant,
+        update_database_user_grant,
+        remove_database_user_grant,
+        // Remote MySQL
+        list_remote_hosts,
+        add_remote_host,
+        sync_remote_hosts,
+        remove_remote_host
     ]
 }
@@ -7,13 +7,16 @@ use rocket::{delete, get, post, put, routes, Route, State};
 
 use crate::database::Database;
 use crate::errors::ApiResult;
-use crate::guards::AuthenticatedUser;
+use crate::guards::{AdminUser, AuthenticatedUser};
 use crate::models::{
     AutoresponderResponse, CreateAutoresponderRequest, CreateEmailAccountRequest,
     CreateEmailForwarderRequest, EmailAccountResponse, EmailForwarderResponse,
-    UpdateAutoresponderRequest, UpdateEmailAccountRequest,
+    EmailSpamPolicyResponse, MailQueueActionRequest, MailQueueActionResult, MailQueueResponse,
+    MailQueueSenderCount, SendLimitAlert, SendLimitConfig, SendLimitUsage, SpamPolicy,
+    UpdateAutoresponderRequest, UpdateDomainSendLimitRequest, UpdateEmailAccountRequest,
+    UpdateSpamPolicyRequest,
 };
-use crate::services::EmailService;
+use crate::services::{EmailService, MailQueueService, SendLimitService, SpamFilterService};
 use crate::utils::response::{success, success_message, ApiResponse};
 
 // ==========================================
@@ -88,7 +91,16 @@ pub async fn create_email_account(
 /// {
 ///   "password": "NewP@ssword",  // optional
 ///   "quota_mb": 2048,  // optional
-///   "is_active": true  // optional
+///   "is_active": true,  // optional
+///   "send_limit_hourly": 100,  // optional, penerima per jam (non-admin: maks default server)
+///   "send_limit_daily": 500,  // optional, penerima per hari (non-admin: maks default server)
+///   "spam_policy": {  // optional, override kebijakan domain
+///     "enabled": true,
+///     "threshold": 6.0,
+///     "action": "junk",  // junk, discard
+///     "whitelist": ["*@partner.com"],
+///     "blacklist": ["promo@spammer.com"]
+///   }
 /// }
 /// ```
 #[put("/<id>", format = "json", data = "<request>")]
@@ -98,6 +110,11 @@ pub async fn update_email_account(
     id: &str,
     request: Json<UpdateEmailAccountRequest>,
 ) -> ApiResult<Json<ApiResponse<EmailAccountResponse>>> {
+    if !user.is_admin() {
+        SendLimitConfig::default()
+            .ensure_within_default(request.send_limit_hourly, request.send_limit_daily)?;
+    }
+
     let account =
         EmailService::update_account(db.get_pool(), id, &user.id, request.into_inner()).await?;
     Ok(success(account))
@@ -120,6 +137,95 @@ pub async fn delete_email_account(
     Ok(success_message("Email account berhasil dihapus"))
 }
 
+// ==========================================
+// SPAM POLICY ENDPOINTS
+// ==========================================
+
+/// Get kebijakan spam efektif mailbox
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Email Account ID
+#[get("/spam-policy/accounts/<id>")]
+pub async fn get_email_spam_policy(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<Json<ApiResponse<EmailSpamPolicyResponse>>> {
+    let policy = SpamFilterService::get_account_policy(db.get_pool(), id, &user.id).await?;
+    Ok(success(policy))
+}
+
+/// Reset override spam mailbox ke kebijakan domain
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Email Account ID
+#[delete("/spam-policy/accounts/<id>")]
+pub async fn reset_email_spam_policy(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<Json<ApiResponse<EmailSpamPolicyResponse>>> {
+    let policy = SpamFilterService::reset_account_policy(db.get_pool(), id, &user.id).await?;
+    Ok(success(policy))
+}
+
+/// Get kebijakan spam domain
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - domain_id: Domain ID
+#[get("/spam-policy/domains/<domain_id>")]
+pub async fn get_domain_spam_policy(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    domain_id: &str,
+) -> ApiResult<Json<ApiResponse<SpamPolicy>>> {
+    let policy = SpamFilterService::get_domain_policy(db.get_pool(), domain_id, &user.id).await?;
+    Ok(success(policy))
+}
+
+/// Update kebijakan spam domain
+///
+/// Script Sieve semua mailbox di domain akan di-render ulang.
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Request Body
+/// ```json
+/// {
+///   "enabled": true,
+///   "threshold": 5.0,
+///   "action": "junk",  // junk, discard
+///   "whitelist": ["*@partner.com"],
+///   "blacklist": []
+/// }
+/// ```
+#[put("/spam-policy/domains/<domain_id>", format = "json", data = "<request>")]
+pub async fn update_domain_spam_policy(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    domain_id: &str,
+    request: Json<UpdateSpamPolicyRequest>,
+) -> ApiResult<Json<ApiResponse<SpamPolicy>>> {
+    let policy = SpamFilterService::update_domain_policy(
+        db.get_pool(),
+        domain_id,
+        &user.id,
+        request.into_inner(),
+    )
+    .await?;
+    Ok(success(policy))
+}
+
 // ==========================================
 // EMAIL FORWARDER ENDPOINTS
 // ==========================================
@@ -271,6 +377,148 @@ pub async fn delete_autoresponder(
     Ok(success_message("Autoresponder berhasil dihapus"))
 }
 
+// ==========================================
+// SEND LIMIT ENDPOINTS
+// ==========================================
+
+/// Get limit dan pemakaian pengiriman mailbox
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Email Account ID
+#[get("/send-limits/accounts/<id>")]
+pub async fn get_email_send_limit(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<Json<ApiResponse<SendLimitUsage>>> {
+    let usage = SendLimitService::get_account_usage(db.get_pool(), id, &user.id).await?;
+    Ok(success(usage))
+}
+
+/// Get limit dan pemakaian pengiriman domain
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - domain_id: Domain ID
+#[get("/send-limits/domains/<domain_id>")]
+pub async fn get_domain_send_limit(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    domain_id: &str,
+) -> ApiResult<Json<ApiResponse<SendLimitUsage>>> {
+    let usage = SendLimitService::get_domain_usage(db.get_pool(), domain_id, &user.id).await?;
+    Ok(success(usage))
+}
+
+/// Update limit pengiriman domain (total semua mailbox, admin)
+///
+/// # Headers
+/// - Authorization: Bearer <admin_token>
+///
+/// # Request Body
+/// ```json
+/// {
+///   "hourly_limit": 500,  // null = tanpa limit
+///   "daily_limit": 5000  // null = tanpa limit
+/// }
+/// ```
+#[put("/send-limits/domains/<domain_id>", format = "json", data = "<request>")]
+pub async fn update_domain_send_limit(
+    db: &State<Database>,
+    _admin: AdminUser,
+    domain_id: &str,
+    request: Json<UpdateDomainSendLimitRequest>,
+) -> ApiResult<Json<ApiResponse<SendLimitUsage>>> {
+    let usage =
+        SendLimitService::update_domain_limit(db.get_pool(), domain_id, request.into_inner())
+            .await?;
+    Ok(success(usage))
+}
+
+/// List alert limit pengiriman terbaru
+///
+/// # Headers
+/// - Authorization: Bearer <admin_token>
+#[get("/send-limits/alerts")]
+pub async fn list_send_limit_alerts(
+    db: &State<Database>,
+    _admin: AdminUser,
+) -> ApiResult<Json<ApiResponse<Vec<SendLimitAlert>>>> {
+    let alerts = SendLimitService::get_recent_alerts(db.get_pool(), 100).await?;
+    Ok(success(alerts))
+}
+
+// ==========================================
+// MAIL QUEUE ENDPOINTS (ADMIN)
+// ==========================================
+
+/// List pesan di antrian Postfix
+///
+/// # Headers
+/// - Authorization: Bearer <admin_token>
+///
+/// # Query Parameters
+/// - sender: Filter alamat pengirim (optional)
+/// - queue_name: Filter antrian: active, deferred, hold, incoming (optional)
+#[get("/queue?<sender>&<queue_name>")]
+pub async fn list_mail_queue(
+    _admin: AdminUser,
+    sender: Option<&str>,
+    queue_name: Option<&str>,
+) -> ApiResult<Json<ApiResponse<MailQueueResponse>>> {
+    let queue = MailQueueService::list_messages(sender, queue_name).await?;
+    Ok(success(queue))
+}
+
+/// Jumlah pesan antrian per pengirim
+///
+/// # Headers
+/// - Authorization: Bearer <admin_token>
+#[get("/queue/senders")]
+pub async fn list_mail_queue_senders(
+    _admin: AdminUser,
+) -> ApiResult<Json<ApiResponse<Vec<MailQueueSenderCount>>>> {
+    let counts = MailQueueService::get_sender_counts().await?;
+    Ok(success(counts))
+}
+
+/// Flush antrian (kirim ulang pesan deferred)
+///
+/// # Headers
+/// - Authorization: Bearer <admin_token>
+#[post("/queue/flush")]
+pub async fn flush_mail_queue(_admin: AdminUser) -> ApiResult<Json<ApiResponse<()>>> {
+    MailQueueService::flush().await?;
+    Ok(success_message("Antrian email berhasil di-flush"))
+}
+
+/// Hold, release, requeue, atau delete pesan antrian
+///
+/// # Headers
+/// - Authorization: Bearer <admin_token>
+///
+/// # Request Body
+/// ```json
+/// {
+///   "action": "delete",  // hold, release, requeue, delete
+///   "queue_ids": ["4XyZ1k2Lm3"],  // optional
+///   "sender": "spam@example.com"  // optional, semua pesan dari pengirim ini
+/// }
+/// ```
+#[post("/queue/actions", format = "json", data = "<request>")]
+pub async fn mail_queue_action(
+    _admin: AdminUser,
+    request: Json<MailQueueActionRequest>,
+) -> ApiResult<Json<ApiResponse<MailQueueActionResult>>> {
+    let result = MailQueueService::apply_action(request.into_inner()).await?;
+    Ok(success(result))
+}
+
 /// Mendapatkan routes untuk emails
 pub fn email_routes() -> Vec<Route> {
     routes![
@@ -280,6 +528,11 @@ pub fn email_routes() -> Vec<Route> {
         create_email_account,
         update_email_account,
         delete_email_account,
+        // Spam Policy
+        get_email_spam_policy,
+        reset_email_spam_policy,
+        get_domain_spam_policy,
+        update_domain_spam_policy,
         // Forwarders
         list_email_forwarders,
         create_email_forwarder,
@@ -288,6 +541,16 @@ pub fn email_routes() -> Vec<Route> {
         list_autoresponders,
         create_autoresponder,
         update_autoresponder,
-        delete_autoresponder
+        delete_autoresponder,
+        // Send Limits
+        get_email_send_limit,
+        get_domain_send_limit,
+        update_domain_send_limit,
+        list_send_limit_alerts,
+        // Mail Queue (admin)
+        list_mail_queue,
+        list_mail_queue_senders,
+        flush_mail_queue,
+        mail_queue_action
     ]
 }
@@ -2,20 +2,38 @@
 //!
 //! Route handlers untuk file management.
 
+use std::convert::Infallible;
+use std::path::Path;
+
+use multer::Multipart;
+use rocket::data::{Data, ToByteUnit};
 use rocket::form::FromForm;
+use rocket::http::{ContentType, Header, Status};
+use rocket::request::{FromRequest, Outcome, Request};
+use rocket::response::{self, Responder, Response};
 use rocket::serde::json::Json;
-use rocket::{get, post, put, routes, Route, State};
+use rocket::{delete, get, head, patch, post, put, routes, Route, State};
 use serde::Deserialize;
+use tokio::io::AsyncReadExt;
+use tokio_util::io::ReaderStream;
 
+use crate::config::CONFIG;
 use crate::database::Database;
-use crate::errors::ApiResult;
+use crate::errors::{ApiError, ApiResult};
 use crate::guards::AuthenticatedUser;
 use crate::models::{
-    CompressRequest, CopyRequest, CreateFileRequest, DeleteRequest, ExtractRequest,
-    FileContentResponse, FileInfo, FileListResponse, MoveRequest, RenameRequest, WriteFileRequest,
+    ChangeOwnerRequest, ChangePermissionsRequest, ChunkedUploadConfig, ChunkedUploadResponse,
+    CompressRequest, CopyRequest, CreateChunkedUploadRequest, CreateFileRequest, DeleteRequest,
+    DiskUsageResponse, ExtractRequest,
+    FileContentResponse, FileDiffResponse, FileInfo, FileListResponse, FileVersionsResponse,
+    JobResponse, MoveRequest, RenameRequest, RestoreVersionRequest, SearchRequest, TrashItem,
+    TrashListResponse, WriteFileRequest, MAX_UPLOAD_FILES,
+};
+use crate::services::{
+    ChunkedUploadService, FileDownload, FileDownloadBody, FileService, JobService, QuotaService,
+    TrashService, VersionService,
 };
-use crate::services::FileService;
-use crate::utils::response::{success, success_message, ApiResponse};
+use crate::utils::response::{success, success_message, success_with_message, ApiResponse};
 
 /// Query parameters untuk search files
 #[derive(Debug, Deserialize, FromForm)]
@@ -24,6 +42,18 @@ pub struct SearchFilesParams {
     pub query: String,
     /// Path untuk memulai pencarian
     pub path: Option<String>,
+    /// Mode: name, literal, regex
+    pub mode: Option<String>,
+    /// Pencarian case-sensitive
+    #[field(default = false)]
+    pub case_sensitive: bool,
+    /// Glob file yang dicari (dipisah koma)
+    pub include: Option<String>,
+    /// Glob file/directory yang dilewati (dipisah koma)
+    pub exclude: Option<String>,
+    /// Jumlah baris konteks
+    #[field(default = 0)]
+    pub context: usize,
     /// Maximum hasil
     #[field(default = 50)]
     pub limit: usize,
@@ -101,11 +131,12 @@ pub async fn get_file_content(
 /// ```
 #[post("/create", format = "json", data = "<request>")]
 pub async fn create_file(
-    _db: &State<Database>,
+    db: &State<Database>,
     user: AuthenticatedUser,
     request: Json<CreateFileRequest>,
 ) -> ApiResult<Json<ApiResponse<FileInfo>>> {
-    let file = FileService::create(&user.username, request.into_inner()).await?;
+    let quota = QuotaService::get(db.get_pool(), &user.id).await?;
+    let file = FileService::create(&user.username, request.into_inner(), &quota).await?;
     Ok(success(file))
 }
 
@@ -125,11 +156,12 @@ pub async fn create_file(
 /// ```
 #[put("/content", format = "json", data = "<request>")]
 pub async fn write_file_content(
-    _db: &State<Database>,
+    db: &State<Database>,
     user: AuthenticatedUser,
     request: Json<WriteFileRequest>,
 ) -> ApiResult<Json<ApiResponse<FileInfo>>> {
-    let file = FileService::write_file(&user.username, request.into_inner()).await?;
+    let quota = QuotaService::get(db.get_pool(), &user.id).await?;
+    let file = FileService::write_file(&user.username, request.into_inner(), &quota).await?;
     Ok(success(file))
 }
 
@@ -170,12 +202,14 @@ pub async fn rename_file(
 /// ```
 #[post("/copy", format = "json", data = "<request>")]
 pub async fn copy_file(
-    _db: &State<Database>,
+    db: &State<Database>,
     user: AuthenticatedUser,
     request: Json<CopyRequest>,
-) -> ApiResult<Json<ApiResponse<FileInfo>>> {
-    let file = FileService::copy(&user.username, request.into_inner()).await?;
-    Ok(success(file))
+) -> ApiResult<Json<ApiResponse<JobResponse>>> {
+    let quota = QuotaService::get(db.get_pool(), &user.id).await?;
+    let task = FileService::copy(&user.username, request.into_inner(), quota).await?;
+    let job = JobService::enqueue(db.get_pool(), &user.id, task).await?;
+    Ok(success(job))
 }
 
 /// Move file or directory
@@ -201,8 +235,58 @@ pub async fn move_file(
     Ok(success(file))
 }
 
+/// Change permissions file or directory
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Request Body
+/// ```json
+/// {
+///   "path": "/public_html",
+///   "file_permissions": "644",
+///   "dir_permissions": "u=rwx,go=rx",
+///   "recursive": true
+/// }
+/// ```
+#[put("/permissions", format = "json", data = "<request>")]
+pub async fn change_permissions(
+    _db: &State<Database>,
+    user: AuthenticatedUser,
+    request: Json<ChangePermissionsRequest>,
+) -> ApiResult<Json<ApiResponse<FileInfo>>> {
+    let file = FileService::change_permissions(&user.username, request.into_inner()).await?;
+    Ok(success(file))
+}
+
+/// Change owner/group file or directory
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Request Body
+/// ```json
+/// {
+///   "path": "/public_html/uploads",
+///   "group": "www-data",
+///   "recursive": true
+/// }
+/// ```
+#[put("/owner", format = "json", data = "<request>")]
+pub async fn change_owner(
+    _db: &State<Database>,
+    user: AuthenticatedUser,
+    request: Json<ChangeOwnerRequest>,
+) -> ApiResult<Json<ApiResponse<FileInfo>>> {
+    let file = FileService::change_owner(&user.username, request.into_inner()).await?;
+    Ok(success(file))
+}
+
 /// Delete file or directory
 ///
+/// Default-nya item dipindahkan ke trash (`data` berisi item trash);
+/// `permanent=true` menghapus langsung.
+///
 /// # Headers
 /// - Authorization: Bearer <access_token>
 ///
@@ -210,7 +294,8 @@ pub async fn move_file(
 /// ```json
 /// {
 ///   "path": "/path/to/delete",
-///   "recursive": false  // required true for non-empty directories
+///   "recursive": false,  // required true for non-empty directories
+///   "permanent": false   // true = skip trash
 /// }
 /// ```
 #[post("/delete", format = "json", data = "<request>")]
@@ -218,16 +303,172 @@ pub async fn delete_file(
     _db: &State<Database>,
     user: AuthenticatedUser,
     request: Json<DeleteRequest>,
+) -> ApiResult<Json<ApiResponse<Option<TrashItem>>>> {
+    let trashed = FileService::delete(&user.username, request.into_inner()).await?;
+    let message = if trashed.is_some() {
+        "File/directory dipindahkan ke trash"
+    } else {
+        "File/directory berhasil dihapus permanen"
+    };
+    Ok(success_with_message(trashed, message))
+}
+
+// ==========================================
+// TRASH ENDPOINTS
+// ==========================================
+
+/// List isi trash (terbaru lebih dulu)
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+#[get("/trash")]
+pub async fn list_trash(
+    _db: &State<Database>,
+    user: AuthenticatedUser,
+) -> ApiResult<Json<ApiResponse<TrashListResponse>>> {
+    let trash = TrashService::list(&user.username)?;
+    Ok(success(trash))
+}
+
+/// Kembalikan item trash ke path asli
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Trash item ID
+#[post("/trash/<id>/restore")]
+pub async fn restore_trash_item(
+    _db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<Json<ApiResponse<FileInfo>>> {
+    let file = TrashService::restore(&user.username, id)?;
+    Ok(success_with_message(file, "Item berhasil dikembalikan"))
+}
+
+/// Hapus permanen satu item trash
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Trash item ID
+#[delete("/trash/<id>")]
+pub async fn purge_trash_item(
+    _db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<Json<ApiResponse<()>>> {
+    TrashService::purge(&user.username, id)?;
+    Ok(success_message("Item trash dihapus permanen"))
+}
+
+/// Kosongkan trash
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+#[delete("/trash")]
+pub async fn empty_trash(
+    _db: &State<Database>,
+    user: AuthenticatedUser,
 ) -> ApiResult<Json<ApiResponse<()>>> {
-    FileService::delete(&user.username, request.into_inner()).await?;
-    Ok(success_message("File/directory berhasil dihapus"))
+    let removed = TrashService::empty(&user.username)?;
+    Ok(success_message(format!("{} item trash dihapus permanen", removed)))
+}
+
+// ==========================================
+// QUOTA ENDPOINT
+// ==========================================
+
+/// Pemakaian dan kuota disk user
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+#[get("/quota")]
+pub async fn get_disk_quota(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+) -> ApiResult<Json<ApiResponse<DiskUsageResponse>>> {
+    let quota = QuotaService::get(db.get_pool(), &user.id).await?;
+    Ok(success(DiskUsageResponse::from(quota)))
+}
+
+// ==========================================
+// VERSION ENDPOINTS
+// ==========================================
+
+/// List revisi file yang disimpan lewat editor (terbaru lebih dulu)
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Query Parameters
+/// - path: Path file
+#[get("/versions?<path>")]
+pub async fn list_file_versions(
+    _db: &State<Database>,
+    user: AuthenticatedUser,
+    path: &str,
+) -> ApiResult<Json<ApiResponse<FileVersionsResponse>>> {
+    let versions = VersionService::list(&user.username, path)?;
+    Ok(success(versions))
+}
+
+/// Unified diff antar revisi atau terhadap konten saat ini
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Query Parameters
+/// - path: Path file
+/// - from: ID revisi asal
+/// - to: ID revisi tujuan (optional, default konten file saat ini)
+#[get("/versions/diff?<path>&<from>&<to>")]
+pub async fn diff_file_versions(
+    _db: &State<Database>,
+    user: AuthenticatedUser,
+    path: &str,
+    from: &str,
+    to: Option<&str>,
+) -> ApiResult<Json<ApiResponse<FileDiffResponse>>> {
+    let diff = VersionService::diff(&user.username, path, from, to)?;
+    Ok(success(diff))
+}
+
+/// Kembalikan file ke revisi sebelumnya
+///
+/// Konten saat ini disimpan sebagai revisi baru sebelum ditimpa.
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Request Body
+/// ```json
+/// {
+///   "path": "/public_html/.htaccess",
+///   "revision": "revision-id"
+/// }
+/// ```
+#[post("/versions/restore", format = "json", data = "<request>")]
+pub async fn restore_file_version(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    request: Json<RestoreVersionRequest>,
+) -> ApiResult<Json<ApiResponse<FileInfo>>> {
+    let quota = QuotaService::get(db.get_pool(), &user.id).await?;
+    let file = VersionService::restore(&user.username, request.into_inner(), &quota)?;
+    Ok(success_with_message(file, "Revisi berhasil dikembalikan"))
 }
 
 // ==========================================
 // COMPRESS & EXTRACT ENDPOINTS
 // ==========================================
 
-/// Compress files/directories ke zip archive
+/// Compress files/directories ke archive
+///
+/// Format: zip, tar, tar.gz, tar.bz2, tar.xz, tar.zst. Berjalan sebagai
+/// background job; progress di `GET /api/jobs/<id>`.
 ///
 /// # Headers
 /// - Authorization: Bearer <access_token>
@@ -237,20 +478,23 @@ pub async fn delete_file(
 /// {
 ///   "paths": ["/path/to/file1", "/path/to/folder"],
 ///   "archive_name": "backup",
-///   "format": "zip"
+///   "format": "tar.gz"
 /// }
 /// ```
 #[post("/compress", format = "json", data = "<request>")]
 pub async fn compress_files(
-    _db: &State<Database>,
+    db: &State<Database>,
     user: AuthenticatedUser,
     request: Json<CompressRequest>,
-) -> ApiResult<Json<ApiResponse<FileInfo>>> {
-    let file = FileService::compress(&user.username, request.into_inner()).await?;
-    Ok(success(file))
+) -> ApiResult<Json<ApiResponse<JobResponse>>> {
+    let task = FileService::compress(&user.username, request.into_inner()).await?;
+    let job = JobService::enqueue(db.get_pool(), &user.id, task).await?;
+    Ok(success(job))
 }
 
-/// Extract zip archive
+/// Extract archive (zip atau keluarga tar)
+///
+/// Berjalan sebagai background job; progress di `GET /api/jobs/<id>`.
 ///
 /// # Headers
 /// - Authorization: Bearer <access_token>
@@ -258,26 +502,339 @@ pub async fn compress_files(
 /// # Request Body
 /// ```json
 /// {
-///   "archive_path": "/path/to/archive.zip",
+///   "archive_path": "/path/to/archive.tar.gz",
 ///   "destination": "/path/to/extract",
 ///   "overwrite": false
 /// }
 /// ```
 #[post("/extract", format = "json", data = "<request>")]
 pub async fn extract_archive(
-    _db: &State<Database>,
+    db: &State<Database>,
     user: AuthenticatedUser,
     request: Json<ExtractRequest>,
+) -> ApiResult<Json<ApiResponse<JobResponse>>> {
+    let quota = QuotaService::get(db.get_pool(), &user.id).await?;
+    let task = FileService::extract(&user.username, request.into_inner(), quota).await?;
+    let job = JobService::enqueue(db.get_pool(), &user.id, task).await?;
+    Ok(success(job))
+}
+
+// ==========================================
+// UPLOAD & DOWNLOAD ENDPOINTS
+// ==========================================
+
+/// Header HTTP `Range` (opsional)
+pub struct RangeHeader(pub Option<String>);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for RangeHeader {
+    type Error = Infallible;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        Outcome::Success(RangeHeader(
+            request.headers().get_one("Range").map(|v| v.to_string()),
+        ))
+    }
+}
+
+/// Response streaming untuk download file / directory zip
+impl<'r> Responder<'r, 'static> for FileDownload {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        let disposition = format!(
+            "attachment; filename=\"{}\"",
+            self.filename.replace(['"', '\\'], "_")
+        );
+
+        match self.body {
+            FileDownloadBody::File {
+                file,
+                length,
+                total,
+                range,
+            } => {
+                let content_type = Path::new(&self.filename)
+                    .extension()
+                    .and_then(|e| e.to_str())
+                    .and_then(ContentType::from_extension)
+                    .unwrap_or(ContentType::Binary);
+
+                let mut response = Response::build();
+                response
+                    .header(content_type)
+                    .header(Header::new("Content-Disposition", disposition))
+                    .header(Header::new("Accept-Ranges", "bytes"))
+                    .header(Header::new("Content-Length", length.to_string()));
+
+                if let Some(range) = range {
+                    response
+                        .status(Status::PartialContent)
+                        .header(Header::new("Content-Range", range.content_range(total)));
+                }
+
+                response.streamed_body(file.take(length)).ok()
+            }
+            FileDownloadBody::Archive(stream) => Response::build()
+                .header(ContentType::ZIP)
+                .header(Header::new("Content-Disposition", disposition))
+                .streamed_body(stream)
+                .ok(),
+        }
+    }
+}
+
+/// Upload files (multipart/form-data, streaming)
+///
+/// Semua part yang memiliki filename disimpan ke direktori tujuan. Ukuran per
+/// file dibatasi `MAX_UPLOAD_SIZE`, maksimal `MAX_UPLOAD_FILES` file per request.
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+/// - Content-Type: multipart/form-data; boundary=...
+///
+/// # Query Parameters
+/// - path: Direktori tujuan (default: root)
+/// - overwrite: Timpa file yang sudah ada (default: false)
+#[post("/upload?<path>&<overwrite>", data = "<data>")]
+pub async fn upload_files(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    content_type: &ContentType,
+    path: Option<String>,
+    overwrite: Option<bool>,
+    data: Data<'_>,
+) -> ApiResult<Json<ApiResponse<Vec<FileInfo>>>> {
+    let boundary = content_type
+        .params()
+        .find(|(name, _)| name == "boundary")
+        .filter(|_| content_type.is_form_data())
+        .map(|(_, value)| value.to_string())
+        .ok_or_else(|| {
+            ApiError::ValidationError("Content-Type harus multipart/form-data".to_string())
+        })?;
+
+    // Batas total body; batas per file dicek saat streaming
+    let limit = CONFIG.file.max_upload_size.saturating_mul(MAX_UPLOAD_FILES);
+    let body = ReaderStream::new(data.open(limit.bytes()));
+
+    let quota = QuotaService::get(db.get_pool(), &user.id).await?;
+    let files = FileService::upload(
+        &user.username,
+        path.as_deref().unwrap_or(""),
+        overwrite.unwrap_or(false),
+        &quota,
+        Multipart::new(body, boundary),
+    )
+    .await?;
+    Ok(success(files))
+}
+
+/// Download file atau directory (directory dikirim sebagai zip)
+///
+/// Mendukung header `Range` (satu range) untuk resume download file.
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+/// - Range: bytes=<start>-<end> (optional)
+///
+/// # Query Parameters
+/// - path: Path file/directory
+#[get("/download?<path>")]
+pub async fn download_file(
+    _db: &State<Database>,
+    user: AuthenticatedUser,
+    range: RangeHeader,
+    path: String,
+) -> ApiResult<FileDownload> {
+    FileService::download(&user.username, &path, range.0.as_deref()).await
+}
+
+/// Thumbnail PNG untuk file gambar
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Query Parameters
+/// - path: Path file gambar
+/// - size: Sisi maksimum thumbnail dalam pixel (default: 256, max: 1024)
+#[get("/thumbnail?<path>&<size>")]
+pub async fn file_thumbnail(
+    _db: &State<Database>,
+    user: AuthenticatedUser,
+    path: String,
+    size: Option<u32>,
+) -> ApiResult<(ContentType, Vec<u8>)> {
+    let thumbnail = FileService::thumbnail(&user.username, &path, size).await?;
+    Ok((ContentType::PNG, thumbnail))
+}
+
+// ==========================================
+// RESUMABLE UPLOAD ENDPOINTS
+// ==========================================
+
+/// Header `Upload-Offset` untuk chunk resumable upload
+pub struct UploadOffset(pub u64);
+
+#[rocket::async_trait]
+impl<'r> FromRequest<'r> for UploadOffset {
+    type Error = ApiError;
+
+    async fn from_request(request: &'r Request<'_>) -> Outcome<Self, Self::Error> {
+        match request
+            .headers()
+            .get_one("Upload-Offset")
+            .and_then(|v| v.trim().parse().ok())
+        {
+            Some(offset) => Outcome::Success(UploadOffset(offset)),
+            None => Outcome::Error((
+                Status::BadRequest,
+                ApiError::MissingParameter("Upload-Offset".to_string()),
+            )),
+        }
+    }
+}
+
+/// Response HEAD resumable upload (progress via header)
+pub struct UploadProgress(ChunkedUploadResponse);
+
+impl<'r> Responder<'r, 'static> for UploadProgress {
+    fn respond_to(self, _: &'r Request<'_>) -> response::Result<'static> {
+        Response::build()
+            .header(Header::new("Upload-Offset", self.0.offset.to_string()))
+            .header(Header::new("Upload-Length", self.0.size.to_string()))
+            .header(Header::new("Cache-Control", "no-store"))
+            .ok()
+    }
+}
+
+/// Mulai resumable upload
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Request Body
+/// ```json
+/// {
+///   "path": "/public_html",
+///   "filename": "site-backup.tar.gz",
+///   "size": 5368709120,
+///   "checksum": "<sha256 hex>",  // optional, diverifikasi saat complete
+///   "overwrite": false
+/// }
+/// ```
+#[post("/uploads", format = "json", data = "<request>")]
+pub async fn create_upload(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    request: Json<CreateChunkedUploadRequest>,
+) -> ApiResult<Json<ApiResponse<ChunkedUploadResponse>>> {
+    let quota = QuotaService::get(db.get_pool(), &user.id).await?;
+    let upload =
+        ChunkedUploadService::create(&user.username, request.into_inner(), &quota).await?;
+    Ok(success(upload))
+}
+
+/// Status resumable upload (JSON)
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Upload ID
+#[get("/uploads/<id>")]
+pub async fn get_upload(
+    _db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<Json<ApiResponse<ChunkedUploadResponse>>> {
+    let upload = ChunkedUploadService::status(&user.username, id).await?;
+    Ok(success(upload))
+}
+
+/// Offset resumable upload via header `Upload-Offset` dan `Upload-Length`
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Upload ID
+#[head("/uploads/<id>")]
+pub async fn head_upload(
+    _db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<UploadProgress> {
+    let upload = ChunkedUploadService::status(&user.username, id).await?;
+    Ok(UploadProgress(upload))
+}
+
+/// Kirim chunk resumable upload
+///
+/// Body request adalah bytes mentah chunk. Offset yang tidak sesuai dibalas
+/// 409 beserta offset yang benar.
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+/// - Upload-Offset: Offset awal chunk
+///
+/// # Path Parameters
+/// - id: Upload ID
+#[patch("/uploads/<id>", data = "<data>")]
+pub async fn upload_chunk(
+    _db: &State<Database>,
+    user: AuthenticatedUser,
+    offset: UploadOffset,
+    id: &str,
+    data: Data<'_>,
+) -> ApiResult<Json<ApiResponse<ChunkedUploadResponse>>> {
+    let limit = ChunkedUploadConfig::default().max_chunk_size;
+    let upload =
+        ChunkedUploadService::write_chunk(&user.username, id, offset.0, data.open(limit.bytes()))
+            .await?;
+    Ok(success(upload))
+}
+
+/// Finalize resumable upload (verifikasi ukuran & checksum, pindahkan ke tujuan)
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Upload ID
+#[post("/uploads/<id>/complete")]
+pub async fn complete_upload(
+    _db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
 ) -> ApiResult<Json<ApiResponse<FileInfo>>> {
-    let file = FileService::extract(&user.username, request.into_inner()).await?;
+    let file = ChunkedUploadService::complete(&user.username, id).await?;
     Ok(success(file))
 }
 
+/// Batalkan resumable upload
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Upload ID
+#[delete("/uploads/<id>")]
+pub async fn abort_upload(
+    _db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<Json<ApiResponse<()>>> {
+    ChunkedUploadService::abort(&user.username, id).await?;
+    Ok(success_message("Upload dibatalkan"))
+}
+
 // ==========================================
 // SEARCH ENDPOINT
 // ==========================================
 
-/// Search files by name
+/// Search files by name atau isi file
+///
+/// Berjalan sebagai background job; hasil (`SearchResponse`) ada di `result`
+/// job setelah selesai.
 ///
 /// # Headers
 /// - Authorization: Bearer <access_token>
@@ -285,21 +842,31 @@ pub async fn extract_archive(
 /// # Query Parameters
 /// - query: Search query string
 /// - path: Optional path to start search from
+/// - mode: name (default), literal atau regex (isi file)
+/// - case_sensitive: Pencarian case-sensitive (default false)
+/// - include: Glob file yang dicari, dipisah koma (e.g., `*.php,*.env`)
+/// - exclude: Glob file/directory yang dilewati (e.g., `node_modules,.git`)
+/// - context: Jumlah baris konteks sekitar match (default 0, max 5)
 /// - limit: Maximum results (default 50)
 #[get("/search?<params..>")]
 pub async fn search_files(
-    _db: &State<Database>,
+    db: &State<Database>,
     user: AuthenticatedUser,
     params: SearchFilesParams,
-) -> ApiResult<Json<ApiResponse<Vec<FileInfo>>>> {
-    let results = FileService::search(
-        &user.username,
-        &params.query,
-        params.path.as_deref(),
-        Some(params.limit),
-    )
-    .await?;
-    Ok(success(results))
+) -> ApiResult<Json<ApiResponse<JobResponse>>> {
+    let request = SearchRequest {
+        query: params.query,
+        path: params.path,
+        mode: params.mode,
+        case_sensitive: params.case_sensitive,
+        include: params.include,
+        exclude: params.exclude,
+        context: params.context,
+        limit: Some(params.limit),
+    };
+    let task = FileService::search(&user.username, request).await?;
+    let job = JobService::enqueue(db.get_pool(), &user.id, task).await?;
+    Ok(success(job))
 }
 
 /// Mendapatkan routes untuk files
@@ -312,7 +879,26 @@ pub fn file_routes() -> Vec<Route> {
         rename_file,
         copy_file,
         move_file,
+        change_permissions,
+        change_owner,
         delete_file,
+        list_trash,
+        restore_trash_item,
+        purge_trash_item,
+        empty_trash,
+        get_disk_quota,
+        list_file_versions,
+        diff_file_versions,
+        restore_file_version,
+        upload_files,
+        download_file,
+        file_thumbnail,
+        create_upload,
+        get_upload,
+        head_upload,
+        upload_chunk,
+        complete_upload,
+        abort_upload,
         compress_files,
         extract_archive,
         search_files
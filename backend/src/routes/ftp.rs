@@ -2,17 +2,19 @@
 //!
 //! Route handlers untuk FTP account management.
 
+use rocket::http::ContentType;
 use rocket::serde::json::Json;
 use rocket::{delete, get, post, put, routes, Route, State};
 
 use crate::database::Database;
 use crate::errors::ApiResult;
-use crate::guards::AuthenticatedUser;
+use crate::guards::{AdminUser, AuthenticatedUser};
 use crate::models::{
-    ChangeFtpPasswordRequest, CreateFtpAccountRequest, FtpAccountResponse, FtpServerInfo,
-    UpdateFtpAccountRequest,
+    ChangeFtpPasswordRequest, CreateFtpAccountRequest, FtpAccountResponse, FtpLogEventsRequest,
+    FtpLogEventsResult, FtpServerInfo, UpdateFtpAccountRequest,
 };
-use crate::services::FtpService;
+use crate::routes::phpmyadmin::InternalKeyGuard;
+use crate::services::{FtpService, SftpService};
 use crate::utils::response::{success, success_message, ApiResponse};
 
 // ==========================================
@@ -60,7 +62,8 @@ pub async fn get_ftp_account(
 ///   "username": "myftpuser",
 ///   "password": "SecureP@ss123",
 ///   "home_directory": "/public_html",  // optional
-///   "quota_mb": 1024  // optional, 0 = unlimited
+///   "quota_mb": 1024,  // optional, 0 = unlimited
+///   "sftp_only": false  // optional, true = hanya SFTP (OpenSSH chroot)
 /// }
 /// ```
 #[post("/", format = "json", data = "<request>")]
@@ -171,6 +174,77 @@ pub async fn get_ftp_server_info(
     success(FtpService::get_server_info())
 }
 
+/// Download Site Manager XML FileZilla untuk FTP account
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: FTP Account ID
+#[get("/<id>/filezilla")]
+pub async fn get_ftp_filezilla_config(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<(ContentType, String)> {
+    let xml = FtpService::get_filezilla_xml(db.get_pool(), id, &user.id).await?;
+    Ok((ContentType::XML, xml))
+}
+
+// ==========================================
+// FTP DAEMON INTEGRATION
+// ==========================================
+
+/// Terima baris log dari FTP daemon (login & aktivitas upload)
+///
+/// Endpoint internal, dipanggil oleh rsyslog (omprog) di server yang sama.
+///
+/// # Headers
+/// - X-Internal-Key: <internal_key>
+///
+/// # Request Body
+/// ```json
+/// {
+///   "lines": [
+///     "pure-ftpd: (?@203.0.113.5) [INFO] alice_1234abcd is now logged in"
+///   ]
+/// }
+/// ```
+#[post("/internal/events", format = "json", data = "<request>")]
+pub async fn ftp_log_events(
+    db: &State<Database>,
+    internal_key: InternalKeyGuard,
+    request: Json<FtpLogEventsRequest>,
+) -> ApiResult<Json<ApiResponse<FtpLogEventsResult>>> {
+    let result =
+        FtpService::process_log_events(db.get_pool(), request.into_inner(), &internal_key.0)
+            .await?;
+    Ok(success(result))
+}
+
+/// Generate ulang konfigurasi FTP daemon dan restart service
+///
+/// # Headers
+/// - Authorization: Bearer <admin_token>
+#[post("/backend/sync")]
+pub async fn sync_ftp_backend(_admin: AdminUser) -> ApiResult<Json<ApiResponse<()>>> {
+    FtpService::sync_backend_config().await?;
+    Ok(success_message("Konfigurasi FTP server berhasil diperbarui"))
+}
+
+/// Generate ulang blok `Match User` sshd untuk akun SFTP-only dan reload sshd
+///
+/// # Headers
+/// - Authorization: Bearer <admin_token>
+#[post("/backend/sftp-sync")]
+pub async fn sync_sftp_backend(
+    db: &State<Database>,
+    _admin: AdminUser,
+) -> ApiResult<Json<ApiResponse<usize>>> {
+    let count = SftpService::sync_sshd_config(db.get_pool()).await?;
+    Ok(success(count))
+}
+
 /// Mendapatkan routes untuk FTP accounts
 pub fn ftp_routes() -> Vec<Route> {
     routes![
@@ -181,6 +255,10 @@ pub fn ftp_routes() -> Vec<Route> {
         change_ftp_password,
         toggle_ftp_status,
         delete_ftp_account,
-        get_ftp_server_info
+        get_ftp_server_info,
+        get_ftp_filezilla_config,
+        ftp_log_events,
+        sync_ftp_backend,
+        sync_sftp_backend
     ]
 }
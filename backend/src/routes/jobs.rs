@@ -0,0 +1,108 @@
+//! # Job Routes
+//!
+//! Route handlers untuk background job (status, progress dan pembatalan).
+
+use std::time::Duration;
+
+use rocket::response::stream::{Event, EventStream};
+use rocket::serde::json::Json;
+use rocket::{get, post, routes, Route, State};
+
+use crate::database::Database;
+use crate::errors::ApiResult;
+use crate::guards::AuthenticatedUser;
+use crate::models::{JobResponse, JobStatus};
+use crate::services::JobService;
+use crate::utils::response::{success, ApiResponse};
+
+/// Interval pengiriman progress lewat Server-Sent Events
+const EVENT_INTERVAL: Duration = Duration::from_secs(1);
+
+// ==========================================
+// JOB ENDPOINTS
+// ==========================================
+
+/// List job milik user (terbaru lebih dulu)
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Query Parameters
+/// - limit: Maximum job (default 50, max 100)
+#[get("/?<limit>")]
+pub async fn list_jobs(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    limit: Option<u32>,
+) -> ApiResult<Json<ApiResponse<Vec<JobResponse>>>> {
+    let jobs = JobService::list(db.get_pool(), &user.id, limit).await?;
+    Ok(success(jobs))
+}
+
+/// Status dan progress job
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+#[get("/<id>")]
+pub async fn get_job(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<Json<ApiResponse<JobResponse>>> {
+    let job = JobService::get(db.get_pool(), id, &user.id).await?;
+    Ok(success(job))
+}
+
+/// Batalkan job yang masih pending atau running
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+#[post("/<id>/cancel")]
+pub async fn cancel_job(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<Json<ApiResponse<JobResponse>>> {
+    let job = JobService::cancel(db.get_pool(), id, &user.id).await?;
+    Ok(success(job))
+}
+
+/// Stream progress job (Server-Sent Events)
+///
+/// Mengirim event `progress` berisi job setiap detik; stream ditutup setelah
+/// job selesai, gagal atau dibatalkan.
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+#[get("/<id>/events")]
+pub async fn job_events(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<EventStream![]> {
+    let pool = db.get_pool().clone();
+    // Pastikan job ada dan milik user sebelum stream dibuka
+    JobService::get(&pool, id, &user.id).await?;
+
+    let id = id.to_string();
+    Ok(EventStream! {
+        let mut interval = tokio::time::interval(EVENT_INTERVAL);
+        loop {
+            interval.tick().await;
+            let Ok(job) = JobService::get(&pool, &id, &user.id).await else {
+                break;
+            };
+
+            let finished = JobStatus::is_final(&job.job.status);
+            yield Event::json(&job).event("progress");
+            if finished {
+                break;
+            }
+        }
+    })
+}
+
+/// Mendapatkan routes untuk jobs
+pub fn job_routes() -> Vec<Route> {
+    routes![list_jobs, get_job, cancel_job, job_events]
+}
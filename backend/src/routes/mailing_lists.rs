@@ -0,0 +1,249 @@
+//! # Mailing List Routes
+//!
+//! Route handlers untuk mailing list management.
+
+use rocket::http::ContentType;
+use rocket::serde::json::Json;
+use rocket::{delete, get, post, put, routes, Route, State};
+
+use crate::database::Database;
+use crate::errors::ApiResult;
+use crate::guards::AuthenticatedUser;
+use crate::models::{
+    AddSubscriberRequest, CreateMailingListRequest, ImportSubscribersRequest,
+    ImportSubscribersResult, MailingListResponse, MailingListSubscriber, UpdateMailingListRequest,
+};
+use crate::services::MailingListService;
+use crate::utils::response::{success, success_message, ApiResponse};
+
+// ==========================================
+// MAILING LIST ENDPOINTS
+// ==========================================
+
+/// List user's mailing lists
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+#[get("/")]
+pub async fn list_mailing_lists(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+) -> ApiResult<Json<ApiResponse<Vec<MailingListResponse>>>> {
+    let lists = MailingListService::get_user_lists(db.get_pool(), &user.id).await?;
+    Ok(success(lists))
+}
+
+/// Get mailing list by ID
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: Mailing List ID
+#[get("/<id>")]
+pub async fn get_mailing_list(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<Json<ApiResponse<MailingListResponse>>> {
+    let list = MailingListService::get_list_by_id(db.get_pool(), id, &user.id).await?;
+    Ok(success(list))
+}
+
+/// Create mailing list
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Request Body
+/// ```json
+/// {
+///   "domain_id": "domain-uuid",
+///   "list_name": "news",
+///   "description": "Product announcements",  // optional
+///   "list_type": "announcement",  // announcement, discussion
+///   "moderation": "non_members",  // none, non_members, all
+///   "reply_to_list": false  // optional
+/// }
+/// ```
+#[post("/", format = "json", data = "<request>")]
+pub async fn create_mailing_list(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    request: Json<CreateMailingListRequest>,
+) -> ApiResult<Json<ApiResponse<MailingListResponse>>> {
+    let list =
+        MailingListService::create_list(db.get_pool(), &user.id, request.into_inner()).await?;
+    Ok(success(list))
+}
+
+/// Update mailing list
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Request Body
+/// ```json
+/// {
+///   "description": "Updated description",
+///   "moderation": "all",
+///   "is_active": true
+/// }
+/// ```
+#[put("/<id>", format = "json", data = "<request>")]
+pub async fn update_mailing_list(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+    request: Json<UpdateMailingListRequest>,
+) -> ApiResult<Json<ApiResponse<MailingListResponse>>> {
+    let list =
+        MailingListService::update_list(db.get_pool(), id, &user.id, request.into_inner()).await?;
+    Ok(success(list))
+}
+
+/// Delete mailing list
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+#[delete("/<id>")]
+pub async fn delete_mailing_list(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<Json<ApiResponse<()>>> {
+    MailingListService::delete_list(db.get_pool(), id, &user.id).await?;
+    Ok(success_message("Mailing list berhasil dihapus"))
+}
+
+// ==========================================
+// SUBSCRIBER ENDPOINTS
+// ==========================================
+
+/// List subscribers
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+#[get("/<id>/subscribers")]
+pub async fn list_subscribers(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<Json<ApiResponse<Vec<MailingListSubscriber>>>> {
+    let subscribers = MailingListService::get_subscribers(db.get_pool(), id, &user.id).await?;
+    Ok(success(subscribers))
+}
+
+/// Add subscriber
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Request Body
+/// ```json
+/// {
+///   "email": "john@gmail.com",
+///   "name": "John",  // optional
+///   "require_confirmation": true  // optional, default true (double opt-in)
+/// }
+/// ```
+#[post("/<id>/subscribers", format = "json", data = "<request>")]
+pub async fn add_subscriber(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+    request: Json<AddSubscriberRequest>,
+) -> ApiResult<Json<ApiResponse<MailingListSubscriber>>> {
+    let subscriber =
+        MailingListService::add_subscriber(db.get_pool(), id, &user.id, request.into_inner())
+            .await?;
+    Ok(success(subscriber))
+}
+
+/// Remove subscriber
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+#[delete("/<id>/subscribers/<subscriber_id>")]
+pub async fn remove_subscriber(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+    subscriber_id: &str,
+) -> ApiResult<Json<ApiResponse<()>>> {
+    MailingListService::remove_subscriber(db.get_pool(), id, subscriber_id, &user.id).await?;
+    Ok(success_message("Subscriber berhasil dihapus"))
+}
+
+/// Import subscribers dari CSV
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Request Body
+/// ```json
+/// {
+///   "csv": "email,name\njohn@gmail.com,John\njane@gmail.com,Jane",
+///   "require_confirmation": true
+/// }
+/// ```
+#[post("/<id>/subscribers/import", format = "json", data = "<request>")]
+pub async fn import_subscribers(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+    request: Json<ImportSubscribersRequest>,
+) -> ApiResult<Json<ApiResponse<ImportSubscribersResult>>> {
+    let result =
+        MailingListService::import_subscribers(db.get_pool(), id, &user.id, request.into_inner())
+            .await?;
+    Ok(success(result))
+}
+
+/// Export subscribers sebagai CSV
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+#[get("/<id>/subscribers/export")]
+pub async fn export_subscribers(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    id: &str,
+) -> ApiResult<(ContentType, String)> {
+    let csv = MailingListService::export_subscribers(db.get_pool(), id, &user.id).await?;
+    Ok((ContentType::CSV, csv))
+}
+
+/// Konfirmasi subscription (double opt-in)
+///
+/// Endpoint publik, dibuka dari link di email konfirmasi.
+#[get("/subscriptions/confirm/<token>")]
+pub async fn confirm_subscription(
+    db: &State<Database>,
+    token: &str,
+) -> ApiResult<Json<ApiResponse<()>>> {
+    let subscriber = MailingListService::confirm_subscription(db.get_pool(), token).await?;
+    Ok(success_message(format!(
+        "Langganan {} berhasil dikonfirmasi",
+        subscriber.email
+    )))
+}
+
+/// Mendapatkan routes untuk mailing lists
+pub fn mailing_list_routes() -> Vec<Route> {
+    routes![
+        // Mailing Lists
+        list_mailing_lists,
+        get_mailing_list,
+        create_mailing_list,
+        update_mailing_list,
+        delete_mailing_list,
+        // Subscribers
+        list_subscribers,
+        add_subscriber,
+        remove_subscriber,
+        import_subscribers,
+        export_subscribers,
+        confirm_subscription
+    ]
+}
@@ -10,6 +10,8 @@ pub mod emails;
 pub mod files;
 pub mod ftp;
 pub mod health;
+pub mod jobs;
+pub mod mailing_lists;
 pub mod nodejs;
 pub mod phpmyadmin;
 pub mod redis;
@@ -17,6 +19,7 @@ pub mod security;
 pub mod system;
 pub mod users;
 pub mod web_server;
+pub mod webmail;
 
 pub use apps::*;
 pub use auth::*;
@@ -26,6 +29,8 @@ pub use emails::*;
 pub use files::*;
 pub use ftp::*;
 pub use health::*;
+pub use jobs::*;
+pub use mailing_lists::*;
 pub use nodejs::*;
 pub use phpmyadmin::*;
 pub use redis::*;
@@ -33,3 +38,4 @@ pub use security::*;
 pub use system::*;
 pub use users::*;
 pub use web_server::*;
+pub use webmail::*;
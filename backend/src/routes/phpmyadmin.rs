@@ -9,7 +9,7 @@ use rocket::{get, post, routes, Route, State};
 
 use crate::database::Database;
 use crate::errors::ApiResult;
-use crate::guards::AuthenticatedUser;
+use crate::guards::{AdminUser, AuthenticatedUser};
 use crate::models::{SignonCredentials, SignonResponse, ValidateTokenRequest};
 use crate::services::PhpMyAdminSignonService;
 use crate::utils::response::{success, ApiResponse};
@@ -137,7 +137,42 @@ pub async fn signon_status(
     })))
 }
 
+/// Enkripsi ulang kredensial SSO dengan master key aktif (admin only)
+///
+/// Dipanggil setelah rotasi `SECRETS_MASTER_KEY`.
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Returns
+/// ```json
+/// {
+///   "success": true,
+///   "data": {
+///     "rotated": 12,
+///     "failed": 0
+///   }
+/// }
+/// ```
+#[post("/rotate-credentials")]
+pub async fn rotate_credentials(
+    db: &State<Database>,
+    _admin: AdminUser,
+) -> ApiResult<Json<ApiResponse<serde_json::Value>>> {
+    let (rotated, failed) = PhpMyAdminSignonService::rotate_credentials(db.get_pool()).await?;
+
+    Ok(success(serde_json::json!({
+        "rotated": rotated,
+        "failed": failed
+    })))
+}
+
 /// Mendapatkan routes untuk phpMyAdmin signon
 pub fn phpmyadmin_routes() -> Vec<Route> {
-    routes![generate_signon_token, validate_signon_token, signon_status]
+    routes![
+        generate_signon_token,
+        validate_signon_token,
+        signon_status,
+        rotate_credentials
+    ]
 }
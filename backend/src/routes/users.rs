@@ -9,8 +9,8 @@ use serde::Deserialize;
 use crate::database::Database;
 use crate::errors::ApiResult;
 use crate::guards::{AdminUser, AuthenticatedUser};
-use crate::models::{UpdateUserRequest, UserResponse};
-use crate::services::UserService;
+use crate::models::{DiskUsageResponse, UpdateDiskQuotaRequest, UpdateUserRequest, UserResponse};
+use crate::services::{QuotaService, UserService};
 use crate::utils::response::{paginated, success, success_message, ApiResponse, PaginatedResponse};
 
 /// Query parameters untuk pagination
@@ -187,13 +187,38 @@ pub async fn update_user_role(
     Ok(success(updated))
 }
 
+/// Update disk quota user (Admin only)
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - id: User ID
+///
+/// # Request Body
+/// ```json
+/// {
+///   "disk_quota_mb": 10240  // null = default server, 0 = unlimited
+/// }
+/// ```
+#[put("/<id>/quota", format = "json", data = "<request>")]
+pub async fn update_user_quota(
+    db: &State<Database>,
+    _admin: AdminUser,
+    id: &str,
+    request: Json<UpdateDiskQuotaRequest>,
+) -> ApiResult<Json<ApiResponse<DiskUsageResponse>>> {
+    let usage = QuotaService::set_limit(db.get_pool(), id, request.into_inner()).await?;
+    Ok(success(usage))
+}
+
 /// Get current user resource usage
 ///
 /// Menampilkan penggunaan resource user (disk, bandwidth, dll).
-/// Untuk saat ini mengembalikan placeholder data.
+/// Disk diambil dari kuota user; resource lain masih placeholder data.
 #[get("/<id>/usage")]
 pub async fn get_user_usage(
-    _db: &State<Database>,
+    db: &State<Database>,
     user: AuthenticatedUser,
     id: &str,
 ) -> ApiResult<Json<ApiResponse<UserResourceUsage>>> {
@@ -202,10 +227,15 @@ pub async fn get_user_usage(
         return Err(crate::errors::ApiError::Forbidden);
     }
 
+    let quota = QuotaService::get(db.get_pool(), id).await?;
+
     // TODO: Implement actual resource tracking
     let usage = UserResourceUsage {
-        disk_used_mb: 0,
-        disk_limit_mb: 10240, // 10GB default
+        disk_used_mb: (quota.used_bytes / (1024 * 1024)) as i64,
+        disk_limit_mb: quota
+            .limit_bytes
+            .map(|limit| (limit / (1024 * 1024)) as i64)
+            .unwrap_or(0), // 0 = unlimited
         bandwidth_used_mb: 0,
         bandwidth_limit_mb: 102400, // 100GB default
         domains_count: 0,
@@ -243,6 +273,7 @@ pub fn user_routes() -> Vec<Route> {
         delete_user,
         update_user_status,
         update_user_role,
+        update_user_quota,
         get_user_usage
     ]
 }
@@ -0,0 +1,119 @@
+//! # Webmail Signon Routes
+//!
+//! Route handlers untuk Webmail SSO (Roundcube / SnappyMail).
+
+use rocket::serde::json::Json;
+use rocket::{get, post, routes, Route, State};
+
+use crate::database::Database;
+use crate::errors::ApiResult;
+use crate::guards::AuthenticatedUser;
+use crate::models::{ValidateTokenRequest, WebmailCredentials, WebmailSignonResponse};
+use crate::routes::phpmyadmin::InternalKeyGuard;
+use crate::services::WebmailSignonService;
+use crate::utils::response::{success, ApiResponse};
+
+// ==========================================
+// SIGNON ENDPOINTS
+// ==========================================
+
+/// Generate signon token untuk webmail
+///
+/// Endpoint ini dipanggil oleh frontend untuk mendapatkan URL SSO webmail.
+/// Token hanya valid selama 30 detik dan hanya bisa digunakan sekali.
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+///
+/// # Path Parameters
+/// - email_account_id: ID email account yang akan di-login
+///
+/// # Returns
+/// ```json
+/// {
+///   "success": true,
+///   "data": {
+///     "signon_url": "https://webmail.example.com/?_action=nusa_signon&token=xxx",
+///     "expires_in": 30
+///   }
+/// }
+/// ```
+#[post("/signon/<email_account_id>")]
+pub async fn generate_webmail_signon_token(
+    db: &State<Database>,
+    user: AuthenticatedUser,
+    email_account_id: &str,
+) -> ApiResult<Json<ApiResponse<WebmailSignonResponse>>> {
+    let response =
+        WebmailSignonService::generate_token(db.get_pool(), email_account_id, &user.id).await?;
+
+    Ok(success(response))
+}
+
+/// Validate signon token (dipanggil oleh plugin Roundcube/SnappyMail)
+///
+/// Endpoint internal yang hanya boleh diakses dari localhost.
+/// Memerlukan X-Internal-Key header untuk autentikasi.
+/// Token akan dihapus setelah validasi (one-time use).
+///
+/// # Headers
+/// - X-Internal-Key: <internal_key>
+///
+/// # Request Body
+/// ```json
+/// {
+///   "token": "signon-token-uuid"
+/// }
+/// ```
+///
+/// # Returns
+/// ```json
+/// {
+///   "email": "info@example.com",
+///   "login": "info@example.com*nusapanel",
+///   "password": "master_password",
+///   "imap_host": "mail.example.com",
+///   "imap_port": 993,
+///   "smtp_host": "mail.example.com",
+///   "smtp_port": 587
+/// }
+/// ```
+#[post("/validate", format = "json", data = "<request>")]
+pub async fn validate_webmail_signon_token(
+    db: &State<Database>,
+    internal_key: InternalKeyGuard,
+    request: Json<ValidateTokenRequest>,
+) -> ApiResult<Json<WebmailCredentials>> {
+    let credentials = WebmailSignonService::validate_token(
+        db.get_pool(),
+        &request.into_inner(),
+        &internal_key.0,
+    )
+    .await?;
+
+    Ok(Json(credentials))
+}
+
+/// Get webmail signon service status (untuk monitoring)
+///
+/// # Headers
+/// - Authorization: Bearer <access_token>
+#[get("/status")]
+pub async fn webmail_signon_status(
+    _user: AuthenticatedUser,
+) -> ApiResult<Json<ApiResponse<serde_json::Value>>> {
+    let count = WebmailSignonService::get_active_token_count();
+
+    Ok(success(serde_json::json!({
+        "active_tokens": count
+    })))
+}
+
+/// Mendapatkan routes untuk webmail signon
+pub fn webmail_routes() -> Vec<Route> {
+    routes![
+        generate_webmail_signon_token,
+        validate_webmail_signon_token,
+        webmail_signon_status
+    ]
+}
@@ -57,6 +57,7 @@ impl AppInstallerService {
         // Note: DatabaseService::create returns "user_dbname", so we use that
         let db_req = CreateDatabaseRequest {
             name: db_name_req,
+            engine: None,
             charset: None,
             collation: None,
             description: Some(format!("Auto-created for {:?}", request.app_type)),
@@ -0,0 +1,1079 @@
+//! # Archive Service
+//!
+//! Compress dan extract archive untuk file manager: zip serta keluarga tar
+//! (tar, tar.gz, tar.bz2, tar.xz, tar.zst).
+//!
+//! Policy ekstraksi:
+//! - Entry dengan path absolut atau `..` dilewati (zip-slip/tar-slip)
+//! - Tidak pernah menulis melewati symlink yang sudah ada di direktori tujuan
+//! - Symlink hanya dibuat jika targetnya relatif dan tetap di dalam tujuan
+//! - Hardlink dibuat sebagai salinan file yang sudah diekstrak
+//! - Bit setuid, setgid dan world-writable dibuang dari mode entry
+//! - Total ukuran hasil ekstraksi dan jumlah entry dibatasi ([`ArchiveConfig`]);
+//!   jika gagal atau job dibatalkan, file dan direktori yang baru dibuat
+//!   dihapus kembali dan file yang ditimpa dikembalikan
+//!
+//! Saat compress, symlink tidak pernah diikuti: tar menyimpannya sebagai
+//! symlink, zip melewatinya.
+
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use uuid::Uuid;
+use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{ZipArchive, ZipWriter};
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{ArchiveConfig, ArchiveFormat, FORBIDDEN_MODE_BITS};
+use crate::services::job_service::{JobProgress, ProgressReader};
+
+/// Ukuran buffer saat menyalin isi entry
+const COPY_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Panjang maksimum target symlink di dalam zip
+const MAX_SYMLINK_TARGET: u64 = 4096;
+
+/// Jenis entry yang akan dimasukkan ke archive
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EntryKind {
+    Directory,
+    File,
+    Symlink,
+}
+
+/// Entry sumber compress beserta nama di dalam archive
+#[derive(Debug)]
+struct SourceEntry {
+    path: PathBuf,
+    name: String,
+    kind: EntryKind,
+    size: u64,
+}
+
+/// Service compress/extract archive
+pub struct ArchiveService;
+
+impl ArchiveService {
+    // ==========================================
+    // COMPRESS
+    // ==========================================
+
+    /// Buat archive dari daftar path sumber (blocking)
+    ///
+    /// Archive yang gagal dibuat dihapus kembali.
+    pub fn compress(
+        sources: &[PathBuf],
+        archive_path: &Path,
+        format: ArchiveFormat,
+        progress: &JobProgress,
+    ) -> ApiResult<()> {
+        let entries = Self::collect_entries(sources)?;
+        progress.set_total(
+            entries
+                .iter()
+                .filter(|entry| entry.kind == EntryKind::File)
+                .map(|entry| entry.size)
+                .sum(),
+        );
+
+        let file = File::create_new(archive_path).map_err(|e| {
+            tracing::error!("Failed to create archive file: {}", e);
+            ApiError::InternalError("Gagal membuat file archive".to_string())
+        })?;
+
+        let result = match format {
+            ArchiveFormat::Zip => Self::write_zip(file, &entries, progress),
+            _ => Self::write_tar(file, format, &entries, progress),
+        };
+
+        if result.is_err() {
+            fs::remove_file(archive_path).ok();
+        }
+
+        result
+    }
+
+    /// Kumpulkan entry dari path sumber tanpa mengikuti symlink
+    fn collect_entries(sources: &[PathBuf]) -> ApiResult<Vec<SourceEntry>> {
+        let mut entries = Vec::new();
+
+        for source in sources {
+            let base_name = source
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("folder");
+
+            for entry in WalkDir::new(source).follow_links(false) {
+                let entry = entry.map_err(|e| {
+                    tracing::error!("Failed to walk directory: {}", e);
+                    ApiError::FilePermissionDenied
+                })?;
+
+                let relative = entry
+                    .path()
+                    .strip_prefix(source)
+                    .map_err(|_| ApiError::FilePermissionDenied)?;
+                let name = std::iter::once(base_name.to_string())
+                    .chain(
+                        relative
+                            .iter()
+                            .map(|part| part.to_string_lossy().to_string()),
+                    )
+                    .collect::<Vec<_>>()
+                    .join("/");
+
+                let file_type = entry.file_type();
+                let (kind, size) = if file_type.is_symlink() {
+                    (EntryKind::Symlink, 0)
+                } else if file_type.is_dir() {
+                    (EntryKind::Directory, 0)
+                } else if file_type.is_file() {
+                    let size = entry.metadata().map(|m| m.len()).unwrap_or(0);
+                    (EntryKind::File, size)
+                } else {
+                    // Socket, FIFO dan device tidak dimasukkan
+                    continue;
+                };
+
+                entries.push(SourceEntry {
+                    path: entry.into_path(),
+                    name,
+                    kind,
+                    size,
+                });
+            }
+        }
+
+        Ok(entries)
+    }
+
+    fn write_zip(file: File, entries: &[SourceEntry], progress: &JobProgress) -> ApiResult<()> {
+        let mut zip = ZipWriter::new(file);
+        let options = SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .unix_permissions(0o644);
+
+        for entry in entries {
+            progress.check_cancelled()?;
+            match entry.kind {
+                EntryKind::Directory => {
+                    zip.add_directory(&entry.name, options.unix_permissions(0o755))
+                        .map_err(write_error)?;
+                }
+                EntryKind::File => {
+                    zip.start_file(
+                        &entry.name,
+                        options.large_file(entry.size >= u32::MAX as u64),
+                    )
+                    .map_err(write_error)?;
+                    let source =
+                        File::open(&entry.path).map_err(|_| ApiError::FilePermissionDenied)?;
+                    std::io::copy(&mut ProgressReader::new(source, progress), &mut zip)
+                        .map_err(write_error)?;
+                }
+                EntryKind::Symlink => {
+                    tracing::debug!("Skipping symlink in zip archive: {}", entry.name);
+                }
+            }
+        }
+
+        zip.finish().map_err(write_error)?;
+        Ok(())
+    }
+
+    fn write_tar(
+        file: File,
+        format: ArchiveFormat,
+        entries: &[SourceEntry],
+        progress: &JobProgress,
+    ) -> ApiResult<()> {
+        match format {
+            ArchiveFormat::Tar => {
+                Self::append_tar(file, entries, progress)?;
+            }
+            ArchiveFormat::TarGz => {
+                let encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+                Self::append_tar(encoder, entries, progress)?
+                    .finish()
+                    .map_err(write_error)?;
+            }
+            ArchiveFormat::TarBz2 => {
+                let encoder = bzip2::write::BzEncoder::new(file, bzip2::Compression::default());
+                Self::append_tar(encoder, entries, progress)?
+                    .finish()
+                    .map_err(write_error)?;
+            }
+            ArchiveFormat::TarXz => {
+                let encoder = xz2::write::XzEncoder::new(file, 6);
+                Self::append_tar(encoder, entries, progress)?
+                    .finish()
+                    .map_err(write_error)?;
+            }
+            ArchiveFormat::TarZst => {
+                let encoder = zstd::Encoder::new(file, 0).map_err(write_error)?;
+                Self::append_tar(encoder, entries, progress)?
+                    .finish()
+                    .map_err(write_error)?;
+            }
+            ArchiveFormat::Zip => {
+                return Err(ApiError::InternalError(
+                    "Zip is not a tar format".to_string(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn append_tar<W: Write>(
+        writer: W,
+        entries: &[SourceEntry],
+        progress: &JobProgress,
+    ) -> ApiResult<W> {
+        let mut builder = tar::Builder::new(writer);
+        builder.follow_symlinks(false);
+
+        for entry in entries {
+            progress.check_cancelled()?;
+            match entry.kind {
+                EntryKind::File => {
+                    let source =
+                        File::open(&entry.path).map_err(|_| ApiError::FilePermissionDenied)?;
+                    let metadata = source
+                        .metadata()
+                        .map_err(|_| ApiError::FilePermissionDenied)?;
+
+                    let mut header = tar::Header::new_gnu();
+                    header.set_metadata(&metadata);
+                    builder
+                        .append_data(
+                            &mut header,
+                            &entry.name,
+                            ProgressReader::new(source, progress),
+                        )
+                        .map_err(write_error)?;
+                }
+                EntryKind::Directory | EntryKind::Symlink => {
+                    builder
+                        .append_path_with_name(&entry.path, &entry.name)
+                        .map_err(write_error)?;
+                }
+            }
+        }
+
+        builder.into_inner().map_err(write_error)
+    }
+
+    // ==========================================
+    // EXTRACT
+    // ==========================================
+
+    /// Extract archive ke direktori tujuan (blocking)
+    ///
+    /// Entry yang sudah ada dilewati kecuali `overwrite`; direktori yang sudah
+    /// ada tidak pernah dihapus. File yang ditimpa dipindahkan ke directory
+    /// backup sementara di tujuan dan baru dihapus setelah ekstraksi berhasil.
+    pub fn extract(
+        archive_path: &Path,
+        destination: &Path,
+        format: ArchiveFormat,
+        overwrite: bool,
+        config: &ArchiveConfig,
+        progress: &JobProgress,
+    ) -> ApiResult<()> {
+        let destination = destination
+            .canonicalize()
+            .map_err(|_| ApiError::FilePermissionDenied)?;
+        let mut extractor = Extractor {
+            destination,
+            overwrite,
+            config,
+            progress,
+            count_output: format == ArchiveFormat::Zip,
+            entries: 0,
+            written: 0,
+            created: Vec::new(),
+            backup_dir: None,
+            replaced: Vec::new(),
+        };
+
+        let result = match format {
+            ArchiveFormat::Zip => Self::extract_zip(archive_path, &mut extractor),
+            _ => Self::extract_tar(archive_path, format, &mut extractor),
+        };
+
+        if result.is_err() {
+            extractor.rollback();
+        }
+        extractor.discard_backups();
+
+        result
+    }
+
+    fn extract_zip(archive_path: &Path, extractor: &mut Extractor) -> ApiResult<()> {
+        let file = File::open(archive_path).map_err(|_| ApiError::FilePermissionDenied)?;
+        let mut archive = ZipArchive::new(file).map_err(|e| {
+            tracing::error!("Failed to open zip archive: {}", e);
+            ApiError::ValidationError("File bukan archive zip yang valid".to_string())
+        })?;
+
+        // Ukuran yang dideklarasikan dicek sebelum menulis apa pun
+        let declared: u64 = (0..archive.len())
+            .filter_map(|i| archive.by_index_raw(i).ok().map(|entry| entry.size()))
+            .sum();
+        extractor.check_limits(archive.len() as u64, declared)?;
+        extractor.progress.set_total(declared);
+
+        for i in 0..archive.len() {
+            let mut entry = archive.by_index(i).map_err(invalid_archive)?;
+            extractor.begin_entry()?;
+
+            let name = PathBuf::from(entry.name());
+            let mode = entry.unix_mode();
+
+            if entry.is_dir() {
+                extractor.create_dir(&name, mode)?;
+            } else if entry.is_symlink() {
+                let mut target = String::new();
+                entry
+                    .by_ref()
+                    .take(MAX_SYMLINK_TARGET)
+                    .read_to_string(&mut target)
+                    .map_err(invalid_archive)?;
+                extractor.create_symlink(&name, Path::new(&target))?;
+            } else {
+                extractor.write_file(&name, &mut entry, mode)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn extract_tar(
+        archive_path: &Path,
+        format: ArchiveFormat,
+        extractor: &mut Extractor,
+    ) -> ApiResult<()> {
+        let progress = extractor.progress;
+        let file = File::open(archive_path).map_err(|_| ApiError::FilePermissionDenied)?;
+        progress.set_total(file.metadata().map(|m| m.len()).unwrap_or(0));
+
+        // Progress tar dihitung dari bytes archive (terkompresi) yang sudah dibaca
+        let reader = ProgressReader::new(file, progress);
+        match format {
+            ArchiveFormat::Tar => Self::unpack_tar(reader, extractor),
+            ArchiveFormat::TarGz => {
+                Self::unpack_tar(flate2::read::MultiGzDecoder::new(reader), extractor)
+            }
+            ArchiveFormat::TarBz2 => {
+                Self::unpack_tar(bzip2::read::MultiBzDecoder::new(reader), extractor)
+            }
+            ArchiveFormat::TarXz => {
+                Self::unpack_tar(xz2::read::XzDecoder::new_multi_decoder(reader), extractor)
+            }
+            ArchiveFormat::TarZst => {
+                let decoder = zstd::Decoder::new(reader).map_err(invalid_archive)?;
+                Self::unpack_tar(decoder, extractor)
+            }
+            ArchiveFormat::Zip => Err(ApiError::InternalError(
+                "Zip is not a tar format".to_string(),
+            )),
+        }
+    }
+
+    fn unpack_tar<R: Read>(reader: R, extractor: &mut Extractor) -> ApiResult<()> {
+        let mut archive = tar::Archive::new(reader);
+
+        for entry in archive.entries().map_err(invalid_archive)? {
+            let mut entry = entry.map_err(invalid_archive)?;
+            extractor.begin_entry()?;
+
+            let name = entry.path().map_err(invalid_archive)?.into_owned();
+            let mode = entry.header().mode().ok();
+            let entry_type = entry.header().entry_type();
+
+            if entry_type.is_dir() {
+                extractor.create_dir(&name, mode)?;
+            } else if entry_type.is_symlink() || entry_type.is_hard_link() {
+                let target = entry
+                    .link_name()
+                    .map_err(invalid_archive)?
+                    .map(|target| target.into_owned())
+                    .unwrap_or_default();
+
+                if entry_type.is_symlink() {
+                    extractor.create_symlink(&name, &target)?;
+                } else {
+                    extractor.create_hardlink(&name, &target)?;
+                }
+            } else if entry_type.is_file() || entry_type.is_gnu_sparse() {
+                extractor.write_file(&name, &mut entry, mode)?;
+            } else {
+                // Device, FIFO dan entry lain tidak diekstrak
+                tracing::debug!("Skipping tar entry {:?} of type {:?}", name, entry_type);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// State satu proses ekstraksi
+struct Extractor<'a> {
+    destination: PathBuf,
+    overwrite: bool,
+    config: &'a ArchiveConfig,
+    progress: &'a JobProgress,
+    /// Progress dihitung dari bytes yang ditulis (zip) alih-alih bytes archive (tar)
+    count_output: bool,
+    entries: u64,
+    written: u64,
+    /// File dan direktori yang dibuat, untuk rollback jika gagal
+    created: Vec<PathBuf>,
+    /// Directory backup file yang ditimpa, dibuat saat pertama dibutuhkan
+    backup_dir: Option<PathBuf>,
+    /// Pasangan (lokasi asli, lokasi backup) file yang ditimpa
+    replaced: Vec<(PathBuf, PathBuf)>,
+}
+
+impl Extractor<'_> {
+    fn check_limits(&self, entries: u64, bytes: u64) -> ApiResult<()> {
+        if entries > self.config.max_entries {
+            return Err(ApiError::ValidationError(format!(
+                "Archive berisi lebih dari {} entry",
+                self.config.max_entries
+            )));
+        }
+        if bytes > self.config.max_extract_size {
+            return Err(ApiError::ValidationError(format!(
+                "Hasil ekstraksi melebihi batas {} bytes",
+                self.config.max_extract_size
+            )));
+        }
+        if let Some(quota) = &self.config.quota {
+            quota.ensure_available(bytes)?;
+        }
+        Ok(())
+    }
+
+    fn begin_entry(&mut self) -> ApiResult<()> {
+        self.entries += 1;
+        self.check_limits(self.entries, self.written)?;
+        self.progress.check_cancelled()
+    }
+
+    /// Cek apakah salah satu direktori induk entry berupa symlink
+    fn crosses_symlink(&self, relative: &Path) -> bool {
+        let mut current = self.destination.clone();
+        relative
+            .parent()
+            .into_iter()
+            .flat_map(|parent| parent.components())
+            .any(|component| {
+                current.push(component);
+                fs::symlink_metadata(&current)
+                    .map(|m| m.file_type().is_symlink())
+                    .unwrap_or(false)
+            })
+    }
+
+    /// Siapkan lokasi output entry, `None` berarti entry dilewati
+    fn claim(&mut self, name: &Path, is_dir: bool) -> ApiResult<Option<PathBuf>> {
+        let Some(relative) = sanitize_entry_name(name) else {
+            tracing::warn!("Archive extraction attempted path traversal: {:?}", name);
+            return Ok(None);
+        };
+
+        if self.crosses_symlink(&relative) {
+            tracing::warn!(
+                "Archive entry {:?} would be written through a symlink",
+                name
+            );
+            return Ok(None);
+        }
+
+        let mut current = self.destination.clone();
+        for component in relative.parent().into_iter().flat_map(|p| p.components()) {
+            current.push(component);
+            match fs::symlink_metadata(&current) {
+                Ok(metadata) if metadata.is_dir() => {}
+                Ok(_) => return Ok(None),
+                Err(_) => {
+                    fs::create_dir(&current).map_err(|e| {
+                        tracing::error!("Failed to create directory: {}", e);
+                        ApiError::InternalError("Gagal membuat direktori".to_string())
+                    })?;
+                    self.created.push(current.clone());
+                }
+            }
+        }
+
+        let outpath = self.destination.join(&relative);
+        match fs::symlink_metadata(&outpath) {
+            Err(_) => Ok(Some(outpath)),
+            // Direktori yang sudah ada tidak pernah ditimpa file/symlink
+            Ok(metadata) if metadata.is_dir() => Ok(is_dir.then_some(outpath)),
+            Ok(_) if self.overwrite => {
+                self.move_aside(&outpath)?;
+                Ok(Some(outpath))
+            }
+            Ok(_) => Ok(None),
+        }
+    }
+
+    /// Pindahkan file yang akan ditimpa ke directory backup
+    fn move_aside(&mut self, path: &Path) -> ApiResult<()> {
+        let backup_dir = match &self.backup_dir {
+            Some(dir) => dir.clone(),
+            None => {
+                let dir = self
+                    .destination
+                    .join(format!(".nusa-extract-{}", Uuid::new_v4()));
+                fs::create_dir(&dir).map_err(|e| {
+                    tracing::error!("Failed to create extract backup directory: {}", e);
+                    ApiError::FilePermissionDenied
+                })?;
+                self.backup_dir = Some(dir.clone());
+                dir
+            }
+        };
+
+        let backup = backup_dir.join(self.replaced.len().to_string());
+        fs::rename(path, &backup).map_err(|e| {
+            tracing::error!("Failed to move {:?} aside: {}", path, e);
+            ApiError::FilePermissionDenied
+        })?;
+        self.replaced.push((path.to_path_buf(), backup));
+
+        Ok(())
+    }
+
+    fn create_dir(&mut self, name: &Path, mode: Option<u32>) -> ApiResult<()> {
+        let Some(outpath) = self.claim(name, true)? else {
+            return Ok(());
+        };
+
+        if outpath.is_dir() && !self.overwrite {
+            return Ok(());
+        }
+        if !outpath.is_dir() {
+            fs::create_dir(&outpath).map_err(|e| {
+                tracing::error!("Failed to create directory: {}", e);
+                ApiError::InternalError("Gagal membuat direktori".to_string())
+            })?;
+            self.created.push(outpath.clone());
+        }
+
+        set_mode(&outpath, sanitize_mode(mode, 0o755) | 0o700);
+        Ok(())
+    }
+
+    fn write_file(
+        &mut self,
+        name: &Path,
+        reader: &mut dyn Read,
+        mode: Option<u32>,
+    ) -> ApiResult<()> {
+        let Some(outpath) = self.claim(name, false)? else {
+            return Ok(());
+        };
+
+        let mut output = File::create_new(&outpath).map_err(|e| {
+            tracing::error!("Failed to create output file: {}", e);
+            ApiError::FilePermissionDenied
+        })?;
+        self.created.push(outpath.clone());
+
+        let mut buffer = vec![0u8; COPY_BUFFER_SIZE];
+        loop {
+            self.progress.check_cancelled()?;
+            let read = reader.read(&mut buffer).map_err(invalid_archive)?;
+            if read == 0 {
+                break;
+            }
+
+            self.written += read as u64;
+            self.check_limits(self.entries, self.written)?;
+
+            output.write_all(&buffer[..read]).map_err(|e| {
+                tracing::error!("Failed to write output file: {}", e);
+                ApiError::InternalError("Gagal menulis file".to_string())
+            })?;
+            if self.count_output {
+                self.progress.add_bytes(read as u64);
+            }
+        }
+
+        set_mode(&outpath, sanitize_mode(mode, 0o644) | 0o600);
+        Ok(())
+    }
+
+    fn create_symlink(&mut self, name: &Path, target: &Path) -> ApiResult<()> {
+        let allowed = sanitize_entry_name(name)
+            .is_some_and(|relative| symlink_target_allowed(&relative, target));
+        if !allowed {
+            tracing::warn!(
+                "Skipping symlink {:?} pointing outside destination: {:?}",
+                name,
+                target
+            );
+            return Ok(());
+        }
+
+        let Some(outpath) = self.claim(name, false)? else {
+            return Ok(());
+        };
+
+        #[cfg(unix)]
+        std::os::unix::fs::symlink(target, &outpath).map_err(|e| {
+            tracing::error!("Failed to create symlink: {}", e);
+            ApiError::InternalError("Gagal membuat symlink".to_string())
+        })?;
+        self.created.push(outpath);
+
+        Ok(())
+    }
+
+    /// Hardlink dibuat sebagai salinan file hasil ekstraksi yang ditunjuk
+    fn create_hardlink(&mut self, name: &Path, target: &Path) -> ApiResult<()> {
+        let source = sanitize_entry_name(target)
+            .filter(|relative| !self.crosses_symlink(relative))
+            .map(|relative| self.destination.join(relative))
+            .filter(|source| fs::symlink_metadata(source).is_ok_and(|m| m.is_file()));
+
+        let Some(source) = source else {
+            tracing::warn!(
+                "Skipping hardlink {:?} with invalid target {:?}",
+                name,
+                target
+            );
+            return Ok(());
+        };
+
+        let mut reader = File::open(&source).map_err(|_| ApiError::FilePermissionDenied)?;
+        #[cfg(unix)]
+        let mode = {
+            use std::os::unix::fs::PermissionsExt;
+            reader.metadata().ok().map(|m| m.permissions().mode())
+        };
+        #[cfg(not(unix))]
+        let mode = None;
+
+        self.write_file(name, &mut reader, mode)
+    }
+
+    /// Hapus file dan direktori yang dibuat oleh ekstraksi yang gagal dan
+    /// kembalikan file yang ditimpa
+    fn rollback(&mut self) {
+        for path in self.created.drain(..).rev() {
+            let Ok(metadata) = fs::symlink_metadata(&path) else {
+                // Entry duplikat di archive yang sudah dihapus sebelumnya
+                continue;
+            };
+            let removed = if metadata.is_dir() {
+                fs::remove_dir(&path)
+            } else {
+                fs::remove_file(&path)
+            };
+            if let Err(e) = removed {
+                tracing::warn!("Failed to roll back extracted entry {:?}: {}", path, e);
+            }
+        }
+
+        // Urutan terbalik agar file asli yang ditimpa berkali-kali menang
+        for (original, backup) in self.replaced.drain(..).rev() {
+            if let Err(e) = fs::rename(&backup, &original) {
+                tracing::warn!("Failed to restore replaced file {:?}: {}", original, e);
+            }
+        }
+    }
+
+    /// Hapus directory backup (berisi file yang ditimpa jika ekstraksi berhasil)
+    fn discard_backups(&mut self) {
+        if let Some(dir) = self.backup_dir.take() {
+            if let Err(e) = fs::remove_dir_all(&dir) {
+                tracing::warn!("Failed to remove extract backup {:?}: {}", dir, e);
+            }
+        }
+    }
+}
+
+/// Normalisasi nama entry, `None` jika absolut atau mengandung `..`
+fn sanitize_entry_name(name: &Path) -> Option<PathBuf> {
+    let mut clean = PathBuf::new();
+    for component in name.components() {
+        match component {
+            Component::Normal(part) => clean.push(part),
+            Component::CurDir => {}
+            _ => return None,
+        }
+    }
+    (!clean.as_os_str().is_empty()).then_some(clean)
+}
+
+/// Cek apakah target symlink tetap di dalam direktori tujuan
+///
+/// Target harus relatif dan `..` hanya boleh di awal path, sehingga naik ke
+/// atas hanya melewati direktori asli (bukan symlink) dan tidak melebihi
+/// kedalaman lokasi symlink.
+fn symlink_target_allowed(link: &Path, target: &Path) -> bool {
+    let depth = link.components().count().saturating_sub(1);
+    let mut ups = 0;
+    let mut descended = false;
+
+    for component in target.components() {
+        match component {
+            Component::ParentDir if !descended => ups += 1,
+            Component::Normal(_) => descended = true,
+            Component::CurDir => {}
+            _ => return false,
+        }
+    }
+
+    !target.as_os_str().is_empty() && ups <= depth
+}
+
+/// Mode entry tanpa setuid, setgid, sticky dan world-writable
+fn sanitize_mode(mode: Option<u32>, default: u32) -> u32 {
+    mode.unwrap_or(default) & 0o777 & !FORBIDDEN_MODE_BITS
+}
+
+#[cfg(unix)]
+fn set_mode(path: &Path, mode: u32) {
+    use std::os::unix::fs::PermissionsExt;
+    fs::set_permissions(path, fs::Permissions::from_mode(mode)).ok();
+}
+
+#[cfg(not(unix))]
+fn set_mode(_path: &Path, _mode: u32) {}
+
+fn write_error(e: impl std::fmt::Display) -> ApiError {
+    tracing::error!("Failed to write archive: {}", e);
+    ApiError::InternalError("Gagal menulis ke archive".to_string())
+}
+
+fn invalid_archive(e: impl std::fmt::Display) -> ApiError {
+    ApiError::ValidationError(format!("Archive tidak valid atau rusak: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{DiskQuota, UsageSource};
+
+    fn temp_dir(label: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("nusa-archive-{}-{}", label, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn config(max_extract_size: u64) -> ArchiveConfig {
+        ArchiveConfig {
+            max_extract_size,
+            max_entries: 100,
+            quota: None,
+        }
+    }
+
+    #[test]
+    fn test_sanitize_entry_name() {
+        assert_eq!(
+            sanitize_entry_name(Path::new("./site/index.php")),
+            Some(PathBuf::from("site/index.php"))
+        );
+        assert_eq!(sanitize_entry_name(Path::new("../etc/passwd")), None);
+        assert_eq!(sanitize_entry_name(Path::new("site/../../x")), None);
+        assert_eq!(sanitize_entry_name(Path::new("/etc/passwd")), None);
+        assert_eq!(sanitize_entry_name(Path::new(".")), None);
+    }
+
+    #[test]
+    fn test_symlink_target_allowed() {
+        assert!(symlink_target_allowed(
+            Path::new("site/current"),
+            Path::new("releases/v2")
+        ));
+        assert!(symlink_target_allowed(
+            Path::new("site/a/link"),
+            Path::new("../../shared/.env")
+        ));
+        assert!(!symlink_target_allowed(
+            Path::new("site/link"),
+            Path::new("../../etc")
+        ));
+        assert!(!symlink_target_allowed(
+            Path::new("link"),
+            Path::new("/etc/passwd")
+        ));
+        assert!(!symlink_target_allowed(
+            Path::new("site/a/link"),
+            Path::new("up/../..")
+        ));
+    }
+
+    #[test]
+    fn test_tar_roundtrip_all_formats() {
+        let root = temp_dir("roundtrip");
+        let source = root.join("site");
+        fs::create_dir_all(source.join("assets")).unwrap();
+        fs::write(source.join("index.php"), "<?php echo 'halo';").unwrap();
+        fs::write(source.join("assets/app.css"), "body {}").unwrap();
+
+        for format in [
+            ArchiveFormat::Zip,
+            ArchiveFormat::Tar,
+            ArchiveFormat::TarGz,
+            ArchiveFormat::TarBz2,
+            ArchiveFormat::TarXz,
+            ArchiveFormat::TarZst,
+        ] {
+            let archive = root.join(format!("site{}", format.extension()));
+            let progress = JobProgress::default();
+            ArchiveService::compress(std::slice::from_ref(&source), &archive, format, &progress)
+                .unwrap();
+            assert_eq!(progress.processed_bytes(), 25);
+
+            let output = root.join(format!("out{}", format.extension()));
+            fs::create_dir_all(&output).unwrap();
+            let progress = JobProgress::default();
+            ArchiveService::extract(&archive, &output, format, false, &config(1024), &progress)
+                .unwrap();
+
+            assert_eq!(
+                fs::read_to_string(output.join("site/index.php")).unwrap(),
+                "<?php echo 'halo';"
+            );
+            assert_eq!(
+                fs::read_to_string(output.join("site/assets/app.css")).unwrap(),
+                "body {}"
+            );
+            assert!(progress.processed_bytes() > 0);
+        }
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn test_extract_policy() {
+        let root = temp_dir("policy");
+        let archive = root.join("evil.tar");
+
+        let mut builder = tar::Builder::new(File::create(&archive).unwrap());
+        let mut append =
+            |name: &str, entry_type: tar::EntryType, link: Option<&str>, data: &[u8], mode: u32| {
+                let mut header = tar::Header::new_gnu();
+                header.set_entry_type(entry_type);
+                header.set_size(data.len() as u64);
+                header.set_mode(mode);
+                // Nama ditulis langsung agar `..` tidak ditolak oleh builder
+                header.as_gnu_mut().unwrap().name[..name.len()].copy_from_slice(name.as_bytes());
+                if let Some(link) = link {
+                    header.set_link_name(link).unwrap();
+                }
+                header.set_cksum();
+                builder.append(&header, data).unwrap();
+            };
+        append("../escape.txt", tar::EntryType::Regular, None, b"x", 0o644);
+        append(
+            "app/run.sh",
+            tar::EntryType::Regular,
+            None,
+            b"#!/bin/sh",
+            0o6777,
+        );
+        append("app/etc", tar::EntryType::Symlink, Some("/etc"), b"", 0o777);
+        append(
+            "app/config",
+            tar::EntryType::Symlink,
+            Some("../shared"),
+            b"",
+            0o777,
+        );
+        append(
+            "app/copy.sh",
+            tar::EntryType::Link,
+            Some("app/run.sh"),
+            b"",
+            0o644,
+        );
+        builder.finish().unwrap();
+        drop(builder);
+
+        let output = root.join("out");
+        fs::create_dir_all(&output).unwrap();
+        let progress = JobProgress::default();
+        ArchiveService::extract(
+            &archive,
+            &output,
+            ArchiveFormat::Tar,
+            false,
+            &config(1024),
+            &progress,
+        )
+        .unwrap();
+
+        assert!(!root.join("escape.txt").exists());
+        assert!(fs::symlink_metadata(output.join("app/etc")).is_err());
+        assert_eq!(
+            fs::read_link(output.join("app/config")).unwrap(),
+            PathBuf::from("../shared")
+        );
+        assert_eq!(
+            fs::read_to_string(output.join("app/copy.sh")).unwrap(),
+            "#!/bin/sh"
+        );
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(output.join("app/run.sh"))
+                .unwrap()
+                .permissions()
+                .mode();
+            assert_eq!(mode & 0o7777, 0o775);
+        }
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn test_extract_size_limit_rolls_back() {
+        let root = temp_dir("bomb");
+        let source = root.join("big");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("zeros.bin"), vec![0u8; 64 * 1024]).unwrap();
+
+        let archive = root.join("big.tar.gz");
+        let progress = JobProgress::default();
+        ArchiveService::compress(&[source], &archive, ArchiveFormat::TarGz, &progress).unwrap();
+
+        let output = root.join("out");
+        fs::create_dir_all(&output).unwrap();
+        let progress = JobProgress::default();
+        let result = ArchiveService::extract(
+            &archive,
+            &output,
+            ArchiveFormat::TarGz,
+            false,
+            &config(1024),
+            &progress,
+        );
+
+        assert!(matches!(result, Err(ApiError::ValidationError(_))));
+        assert!(!output.join("big").exists());
+
+        // Kuota disk user juga membatasi hasil ekstraksi
+        let quota = DiskQuota {
+            used_bytes: 1024,
+            limit_bytes: Some(2048),
+            source: UsageSource::Du,
+        };
+        let config = ArchiveConfig {
+            quota: Some(quota),
+            ..config(1024 * 1024)
+        };
+        let result = ArchiveService::extract(
+            &archive,
+            &output,
+            ArchiveFormat::TarGz,
+            false,
+            &config,
+            &JobProgress::default(),
+        );
+
+        assert!(matches!(result, Err(ApiError::QuotaExceeded(2048))));
+        assert!(!output.join("big").exists());
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn test_extract_overwrite_rolls_back() {
+        let root = temp_dir("overwrite");
+        let source = root.join("src");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("index.html"), "baru").unwrap();
+        fs::write(source.join("zeros.bin"), vec![0u8; 64 * 1024]).unwrap();
+
+        // index.html diekstrak (menimpa) sebelum zeros.bin melewati batas
+        let archive = root.join("site.tar");
+        ArchiveService::compress(
+            &[source.join("index.html"), source.join("zeros.bin")],
+            &archive,
+            ArchiveFormat::Tar,
+            &JobProgress::default(),
+        )
+        .unwrap();
+
+        let output = root.join("out");
+        fs::create_dir_all(&output).unwrap();
+        fs::write(output.join("index.html"), "lama").unwrap();
+
+        let result = ArchiveService::extract(
+            &archive,
+            &output,
+            ArchiveFormat::Tar,
+            true,
+            &config(1024),
+            &JobProgress::default(),
+        );
+
+        assert!(matches!(result, Err(ApiError::ValidationError(_))));
+        assert_eq!(
+            fs::read_to_string(output.join("index.html")).unwrap(),
+            "lama"
+        );
+        assert_eq!(fs::read_dir(&output).unwrap().count(), 1);
+
+        ArchiveService::extract(
+            &archive,
+            &output,
+            ArchiveFormat::Tar,
+            true,
+            &config(1024 * 1024),
+            &JobProgress::default(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(output.join("index.html")).unwrap(),
+            "baru"
+        );
+        assert_eq!(fs::read_dir(&output).unwrap().count(), 2);
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn test_extract_cancelled_rolls_back() {
+        let root = temp_dir("cancel");
+        let source = root.join("site");
+        fs::create_dir_all(&source).unwrap();
+        fs::write(source.join("index.html"), "<h1>halo</h1>").unwrap();
+
+        let archive = root.join("site.zip");
+        let progress = JobProgress::default();
+        ArchiveService::compress(&[source], &archive, ArchiveFormat::Zip, &progress).unwrap();
+
+        let output = root.join("out");
+        fs::create_dir_all(&output).unwrap();
+        let progress = JobProgress::default();
+        progress.cancel();
+        let result = ArchiveService::extract(
+            &archive,
+            &output,
+            ArchiveFormat::Zip,
+            false,
+            &config(1024),
+            &progress,
+        );
+
+        assert!(matches!(result, Err(ApiError::JobCancelled)));
+        assert_eq!(fs::read_dir(&output).unwrap().count(), 0);
+
+        fs::remove_dir_all(root).ok();
+    }
+}
@@ -0,0 +1,442 @@
+//! # Chunked Upload Service
+//!
+//! Resumable upload untuk file besar (mirip protokol tus): buat upload, kirim
+//! chunk dengan offset, cek progress, lalu finalize.
+//!
+//! Data disimpan di staging area `~/.nusa-uploads/<id>/` di dalam sandbox user
+//! (`upload.json` + `data`). Offset selalu diambil dari ukuran file `data`,
+//! sehingga chunk yang terputus di tengah bisa dilanjutkan dari byte terakhir
+//! yang tersimpan. Upload tanpa aktivitas melewati TTL dihapus oleh fairing GC.
+
+use std::collections::HashSet;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use rocket::fairing::AdHoc;
+use sha2::{Digest, Sha256};
+use tokio::io::{AsyncRead, AsyncReadExt};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{
+    ChunkedUpload, ChunkedUploadConfig, ChunkedUploadResponse, CreateChunkedUploadRequest,
+    DiskQuota, FileInfo, UPLOAD_STAGING_DIR,
+};
+use crate::services::{FileService, QuotaService, StagingArea};
+
+/// Staging area upload: `upload.json` berisi state upload
+const STAGING: StagingArea = StagingArea {
+    dir: UPLOAD_STAGING_DIR,
+    state_file: "upload.json",
+    item_name: "Upload",
+};
+
+/// File data upload di dalam staging directory
+const DATA_FILE: &str = "data";
+
+/// Upload yang sedang menerima chunk / finalize (satu request per upload)
+static ACTIVE_UPLOADS: Lazy<Mutex<HashSet<String>>> = Lazy::new(|| Mutex::new(HashSet::new()));
+
+/// Penanda upload sedang diproses, dilepas saat drop
+struct ActiveUpload(String);
+
+impl ActiveUpload {
+    fn acquire(id: &str) -> Option<Self> {
+        let mut active = ACTIVE_UPLOADS.lock().ok()?;
+        active.insert(id.to_string()).then(|| Self(id.to_string()))
+    }
+}
+
+impl Drop for ActiveUpload {
+    fn drop(&mut self) {
+        if let Ok(mut active) = ACTIVE_UPLOADS.lock() {
+            active.remove(&self.0);
+        }
+    }
+}
+
+/// Service untuk resumable upload
+pub struct ChunkedUploadService;
+
+impl ChunkedUploadService {
+    /// Fairing yang menjalankan garbage collection upload terbengkalai
+    pub fn fairing() -> AdHoc {
+        StagingArea::gc_fairing("Chunked Upload Cleanup", Self::cleanup_expired)
+    }
+
+    // ==========================================
+    // STAGING AREA
+    // ==========================================
+
+    /// Total bytes yang belum diterima dari semua upload di staging
+    fn reserved_bytes(staging: &Path) -> u64 {
+        let Ok(entries) = fs::read_dir(staging) else {
+            return 0;
+        };
+
+        entries
+            .flatten()
+            .filter_map(|entry| {
+                let dir = entry.path();
+                let upload: ChunkedUpload = STAGING.load(&dir).ok()?;
+                Some(upload.size.saturating_sub(Self::current_offset(&dir)))
+            })
+            .sum()
+    }
+
+    /// Jumlah bytes yang sudah diterima
+    fn current_offset(dir: &Path) -> u64 {
+        fs::metadata(dir.join(DATA_FILE))
+            .map(|m| m.len())
+            .unwrap_or(0)
+    }
+
+    fn response(dir: &Path, upload: &ChunkedUpload) -> ChunkedUploadResponse {
+        ChunkedUploadResponse::new(
+            upload,
+            Self::current_offset(dir),
+            ChunkedUploadConfig::default().ttl_secs,
+        )
+    }
+
+    /// Path file tujuan, ditolak jika sudah ada (kecuali overwrite)
+    fn resolve_target(username: &str, upload: &ChunkedUpload) -> ApiResult<PathBuf> {
+        let directory = FileService::resolve_path(username, &upload.directory)?;
+        if !directory.is_dir() {
+            return Err(ApiError::FileNotFound(upload.directory.clone()));
+        }
+
+        let target = directory.join(&upload.filename);
+        if target.is_dir() || (target.exists() && !upload.overwrite) {
+            return Err(ApiError::AlreadyExists(upload.filename.clone()));
+        }
+
+        Ok(target)
+    }
+
+    // ==========================================
+    // UPLOAD OPERATIONS
+    // ==========================================
+
+    /// Mulai resumable upload
+    ///
+    /// # Arguments
+    /// * `username` - Username pemilik sandbox
+    /// * `request` - Tujuan, ukuran dan checksum file
+    /// * `quota` - Kuota disk user (ukuran file dan upload lain yang belum selesai dicek di awal)
+    pub async fn create(
+        username: &str,
+        request: CreateChunkedUploadRequest,
+        quota: &DiskQuota,
+    ) -> ApiResult<ChunkedUploadResponse> {
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        let config = ChunkedUploadConfig::default();
+        if request.size > config.max_size {
+            return Err(ApiError::FileTooLarge(config.max_size));
+        }
+
+        let filename = FileService::sanitize_upload_name(&request.filename)?;
+        if !FileService::is_upload_allowed(&filename) {
+            return Err(ApiError::FileTypeNotAllowed(filename));
+        }
+
+        let now = Utc::now();
+        let upload = ChunkedUpload {
+            id: Uuid::new_v4().to_string(),
+            directory: request.path.trim().trim_matches('/').to_string(),
+            filename,
+            size: request.size,
+            checksum: request.checksum.map(|c| c.to_lowercase()),
+            overwrite: request.overwrite.unwrap_or(false),
+            created_at: now,
+            updated_at: now,
+        };
+        Self::resolve_target(username, &upload)?;
+
+        let staging = STAGING.ensure_root(username)?;
+        Self::cleanup_staging(&staging, config.ttl_secs);
+
+        // Sisa ukuran upload lain yang belum selesai ikut dipesan dari kuota
+        quota.ensure_available(Self::reserved_bytes(&staging).saturating_add(upload.size))?;
+
+        let dir = staging.join(&upload.id);
+        fs::create_dir(&dir).map_err(|e| {
+            tracing::error!("Failed to create upload staging directory: {}", e);
+            ApiError::FilePermissionDenied
+        })?;
+        fs::File::create(dir.join(DATA_FILE))?;
+        STAGING.save(&dir, &upload)?;
+
+        tracing::info!(
+            "Chunked upload {} started for {} ({} bytes) by user {}",
+            upload.id,
+            upload.filename,
+            upload.size,
+            username
+        );
+
+        Ok(Self::response(&dir, &upload))
+    }
+
+    /// Status dan offset upload
+    pub async fn status(username: &str, id: &str) -> ApiResult<ChunkedUploadResponse> {
+        let dir = STAGING.item_dir(username, id)?;
+        let upload: ChunkedUpload = STAGING.load(&dir)?;
+
+        Ok(Self::response(&dir, &upload))
+    }
+
+    /// Tambahkan chunk mulai dari `offset`
+    ///
+    /// `offset` harus sama dengan jumlah bytes yang sudah diterima. Jika koneksi
+    /// terputus di tengah chunk, bytes yang sudah masuk tetap tersimpan dan
+    /// client melanjutkan dari offset yang dikembalikan `status`.
+    ///
+    /// # Errors
+    /// - `UploadOffsetMismatch` jika offset tidak sesuai atau chunk lain sedang diproses
+    pub async fn write_chunk<R: AsyncRead + Unpin>(
+        username: &str,
+        id: &str,
+        offset: u64,
+        body: R,
+    ) -> ApiResult<ChunkedUploadResponse> {
+        let dir = STAGING.item_dir(username, id)?;
+        let mut upload: ChunkedUpload = STAGING.load(&dir)?;
+
+        let current = Self::current_offset(&dir);
+        let Some(_active) = ActiveUpload::acquire(&upload.id) else {
+            return Err(ApiError::UploadOffsetMismatch(current));
+        };
+        if offset != current {
+            return Err(ApiError::UploadOffsetMismatch(current));
+        }
+
+        // Data staging yang lebih besar dari ukuran deklarasi berarti state rusak
+        let Some(remaining) = upload.size.checked_sub(current) else {
+            return Err(ApiError::ValidationError(
+                "Data upload melebihi ukuran file yang dideklarasikan".to_string(),
+            ));
+        };
+        let data_path = dir.join(DATA_FILE);
+        let mut file = tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&data_path)
+            .await
+            .map_err(|e| {
+                tracing::error!("Failed to open upload data: {}", e);
+                ApiError::FilePermissionDenied
+            })?;
+
+        // Baca satu byte lebih untuk mendeteksi chunk yang melewati ukuran file
+        let copied = tokio::io::copy(&mut body.take(remaining + 1), &mut file).await;
+        let _ = file.sync_data().await;
+
+        upload.updated_at = Utc::now();
+        STAGING.save(&dir, &upload)?;
+
+        let written = copied.map_err(|e| {
+            tracing::warn!("Chunk for upload {} interrupted: {}", upload.id, e);
+            ApiError::ValidationError(format!(
+                "Chunk terputus, lanjutkan dari offset {}",
+                Self::current_offset(&dir)
+            ))
+        })?;
+
+        if written > remaining {
+            file.set_len(upload.size).await?;
+            return Err(ApiError::ValidationError(
+                "Chunk melebihi ukuran file yang dideklarasikan".to_string(),
+            ));
+        }
+
+        Ok(Self::response(&dir, &upload))
+    }
+
+    /// Finalize upload: verifikasi ukuran dan checksum lalu pindahkan ke tujuan
+    ///
+    /// Upload dengan checksum yang tidak cocok dihapus karena chunk yang rusak
+    /// tidak bisa diketahui.
+    pub async fn complete(username: &str, id: &str) -> ApiResult<FileInfo> {
+        let dir = STAGING.item_dir(username, id)?;
+        let upload: ChunkedUpload = STAGING.load(&dir)?;
+
+        let offset = Self::current_offset(&dir);
+        let Some(_active) = ActiveUpload::acquire(&upload.id) else {
+            return Err(ApiError::UploadOffsetMismatch(offset));
+        };
+        if offset != upload.size {
+            return Err(ApiError::ValidationError(format!(
+                "Upload belum lengkap: {} dari {} bytes",
+                offset, upload.size
+            )));
+        }
+
+        let data_path = dir.join(DATA_FILE);
+        if let Some(expected) = &upload.checksum {
+            let path = data_path.clone();
+            let actual = tokio::task::spawn_blocking(move || sha256_file(&path))
+                .await
+                .map_err(|e| ApiError::InternalError(format!("Checksum task failed: {}", e)))??;
+
+            if !actual.eq_ignore_ascii_case(expected) {
+                let _ = fs::remove_dir_all(&dir);
+                return Err(ApiError::ValidationError(
+                    "Checksum file tidak cocok, upload dibatalkan".to_string(),
+                ));
+            }
+        }
+
+        let target = Self::resolve_target(username, &upload)?;
+        fs::rename(&data_path, &target).map_err(|e| {
+            tracing::error!("Failed to move uploaded file: {}", e);
+            ApiError::FilePermissionDenied
+        })?;
+        let _ = fs::remove_dir_all(&dir);
+        QuotaService::invalidate(username);
+
+        let metadata = fs::metadata(&target)
+            .map_err(|_| ApiError::InternalError("Failed to get file metadata".to_string()))?;
+
+        tracing::info!(
+            "Chunked upload {} completed: {} by user {}",
+            upload.id,
+            target.display(),
+            username
+        );
+
+        Ok(FileService::build_file_info(
+            &target,
+            &metadata,
+            &FileService::get_user_base_path(username),
+        ))
+    }
+
+    /// Batalkan upload dan hapus data staging
+    pub async fn abort(username: &str, id: &str) -> ApiResult<()> {
+        let dir = STAGING.item_dir(username, id)?;
+        let upload: ChunkedUpload = STAGING.load(&dir)?;
+
+        let Some(_active) = ActiveUpload::acquire(&upload.id) else {
+            return Err(ApiError::InUse("Upload".to_string()));
+        };
+
+        fs::remove_dir_all(&dir).map_err(|e| {
+            tracing::error!("Failed to remove upload {}: {}", upload.id, e);
+            ApiError::FilePermissionDenied
+        })?;
+
+        tracing::info!("Chunked upload {} aborted by user {}", upload.id, username);
+
+        Ok(())
+    }
+
+    // ==========================================
+    // GARBAGE COLLECTION
+    // ==========================================
+
+    /// Hapus upload terbengkalai milik semua user
+    ///
+    /// # Returns
+    /// Jumlah upload yang dihapus
+    pub fn cleanup_expired() -> usize {
+        let ttl_secs = ChunkedUploadConfig::default().ttl_secs;
+        STAGING.sweep_homes(|staging| Self::cleanup_staging(staging, ttl_secs))
+    }
+
+    /// Hapus upload di satu staging area yang tidak aktif lebih dari `ttl_secs`
+    ///
+    /// Upload yang sedang menerima chunk / finalize dilewati.
+    fn cleanup_staging(staging: &Path, ttl_secs: u64) -> usize {
+        let cutoff = Utc::now() - chrono::Duration::seconds(ttl_secs as i64);
+        STAGING.remove_expired(
+            staging,
+            cutoff,
+            |upload: &ChunkedUpload| upload.updated_at,
+            ActiveUpload::acquire,
+        )
+    }
+}
+
+/// SHA-256 (hex) isi file
+fn sha256_file(path: &Path) -> ApiResult<String> {
+    let mut file = fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = vec![0u8; 1024 * 1024];
+
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.update(&buffer[..read]);
+    }
+
+    Ok(hex::encode(hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_file() {
+        let path = std::env::temp_dir().join(format!("nusa-upload-test-{}", Uuid::new_v4()));
+        fs::write(&path, b"hello world").unwrap();
+
+        assert_eq!(
+            sha256_file(&path).unwrap(),
+            "b94d27b9934d3e08a52e52d7da7dabfac484efe37a5380ee9088f7ace2efcde9"
+        );
+        let _ = fs::remove_file(&path);
+    }
+
+    #[test]
+    fn test_active_upload_is_exclusive() {
+        let first = ActiveUpload::acquire("upload-a").unwrap();
+        assert!(ActiveUpload::acquire("upload-a").is_none());
+        assert!(ActiveUpload::acquire("upload-b").is_some());
+
+        drop(first);
+        assert!(ActiveUpload::acquire("upload-a").is_some());
+    }
+
+    #[test]
+    fn test_cleanup_staging_removes_expired_uploads() {
+        let staging = std::env::temp_dir().join(format!("nusa-staging-test-{}", Uuid::new_v4()));
+        let now = Utc::now();
+
+        for (id, age) in [("fresh", 10), ("stale", 7200)] {
+            let dir = staging.join(id);
+            fs::create_dir_all(&dir).unwrap();
+            let upload = ChunkedUpload {
+                id: id.to_string(),
+                directory: String::new(),
+                filename: "site.tar.gz".to_string(),
+                size: 10,
+                checksum: None,
+                overwrite: false,
+                created_at: now,
+                updated_at: now - chrono::Duration::seconds(age),
+            };
+            STAGING.save(&dir, &upload).unwrap();
+        }
+
+        assert_eq!(ChunkedUploadService::cleanup_staging(&staging, 3600), 1);
+        assert!(staging.join("fresh").exists());
+        assert!(!staging.join("stale").exists());
+
+        // Upload yang tersisa masih memesan sisa ukurannya dari kuota
+        fs::write(staging.join("fresh").join(DATA_FILE), b"1234").unwrap();
+        assert_eq!(ChunkedUploadService::reserved_bytes(&staging), 6);
+
+        let _ = fs::remove_dir_all(&staging);
+    }
+}
@@ -7,8 +7,10 @@
 //! - Database system (nusa_panel) hanya diakses oleh API
 //! - Database customer (userid_dbname) diakses via phpMyAdmin
 //! - Setiap user hanya bisa melihat database miliknya sendiri
+//! - Engine dipilih per database (`mysql` atau `postgresql`); database user
+//!   mengikuti engine database yang di-grant
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use sqlx::MySqlPool;
 use uuid::Uuid;
 use validator::Validate;
@@ -16,11 +18,14 @@ use validator::Validate;
 use crate::config::CONFIG;
 use crate::errors::{ApiError, ApiResult};
 use crate::models::{
-    CreateDatabaseRequest, CreateDatabaseUserRequest, DatabaseUser, DatabaseUserResponse,
-    ManagedDatabase, ManagedDatabaseResponse, PhpMyAdminInfo, UpdateDatabaseRequest,
-    UpdateDatabaseUserRequest, SUPPORTED_CHARSETS, SUPPORTED_COLLATIONS,
+    CreateDatabaseGrantRequest, CreateDatabaseRequest, CreateDatabaseUserRequest,
+    DatabaseEngine, DatabaseGrantResponse, DatabasePrivilege, DatabaseUser, DatabaseUserResponse,
+    ManagedDatabase, ManagedDatabaseResponse, PhpMyAdminInfo, UpdateDatabaseGrantRequest,
+    UpdateDatabaseRequest, UpdateDatabaseUserRequest, PG_DEFAULT_COLLATION,
+    PG_SUPPORTED_ENCODINGS, SUPPORTED_CHARSETS, SUPPORTED_COLLATIONS,
 };
-use crate::utils::password;
+use crate::services::{PostgresService, RemoteMysqlService};
+use crate::utils::{mysql, password, postgres, secrets};
 
 /// Service untuk managed database operations
 pub struct DatabaseService;
@@ -44,16 +49,7 @@ impl DatabaseService {
 
         let mut responses = Vec::with_capacity(databases.len());
         for db in databases {
-            let users_count = sqlx::query_scalar::<_, i64>(
-                "SELECT COUNT(*) FROM database_users WHERE database_id = ?",
-            )
-            .bind(&db.id)
-            .fetch_one(pool)
-            .await? as i32;
-
-            let mut response = ManagedDatabaseResponse::from(db);
-            response.users_count = users_count;
-            responses.push(response);
+            responses.push(Self::to_database_response(pool, db).await?);
         }
 
         Ok(responses)
@@ -77,8 +73,16 @@ impl DatabaseService {
             return Err(ApiError::Forbidden);
         }
 
+        Self::to_database_response(pool, db).await
+    }
+
+    /// Build response database (dengan jumlah user yang punya grant)
+    async fn to_database_response(
+        pool: &MySqlPool,
+        db: ManagedDatabase,
+    ) -> ApiResult<ManagedDatabaseResponse> {
         let users_count = sqlx::query_scalar::<_, i64>(
-            "SELECT COUNT(*) FROM database_users WHERE database_id = ?",
+            "SELECT COUNT(DISTINCT database_user_id) FROM database_user_grants WHERE database_id = ?",
         )
         .bind(&db.id)
         .fetch_one(pool)
@@ -107,21 +111,41 @@ impl DatabaseService {
         // Use first 8 chars of user_id to keep name short
         let short_user_id = &user_id[..8.min(user_id.len())];
         let db_name = format!("{}_{}", short_user_id, request.name.to_lowercase());
+        let engine = request
+            .engine
+            .as_deref()
+            .and_then(DatabaseEngine::parse)
+            .unwrap_or_default();
 
         // Validate charset and collation
-        let charset = request.charset.unwrap_or_else(|| "utf8mb4".to_string());
-        let collation = request
-            .collation
-            .unwrap_or_else(|| "utf8mb4_unicode_ci".to_string());
+        let (charset, collation) = match engine {
+            DatabaseEngine::Mysql => (
+                request.charset.unwrap_or_else(|| "utf8mb4".to_string()),
+                request
+                    .collation
+                    .unwrap_or_else(|| "utf8mb4_unicode_ci".to_string()),
+            ),
+            DatabaseEngine::Postgresql => (
+                request.charset.unwrap_or_else(|| "UTF8".to_string()),
+                request
+                    .collation
+                    .unwrap_or_else(|| PG_DEFAULT_COLLATION.to_string()),
+            ),
+        };
+
+        let (charsets, collations) = match engine {
+            DatabaseEngine::Mysql => (SUPPORTED_CHARSETS, SUPPORTED_COLLATIONS),
+            DatabaseEngine::Postgresql => (PG_SUPPORTED_ENCODINGS, &[PG_DEFAULT_COLLATION][..]),
+        };
 
-        if !SUPPORTED_CHARSETS.contains(&charset.as_str()) {
+        if !charsets.contains(&charset.as_str()) {
             return Err(ApiError::ValidationError(format!(
                 "Charset '{}' tidak didukung",
                 charset
             )));
         }
 
-        if !SUPPORTED_COLLATIONS.contains(&collation.as_str()) {
+        if !collations.contains(&collation.as_str()) {
             return Err(ApiError::ValidationError(format!(
                 "Collation '{}' tidak didukung",
                 collation
@@ -140,17 +164,21 @@ impl DatabaseService {
             return Err(ApiError::AlreadyExists("Database".to_string()));
         }
 
-        // Create the actual MySQL database
-        // Note: This requires the panel's MySQL user to have CREATE privilege
-        let create_db_sql = format!(
-            "CREATE DATABASE IF NOT EXISTS `{}` CHARACTER SET {} COLLATE {}",
-            db_name, charset, collation
-        );
+        match engine {
+            DatabaseEngine::Mysql => {
+                // Create the actual MySQL database
+                // Note: This requires the panel's MySQL user to have CREATE privilege
+                let create_db_sql = mysql::create_database(&db_name, &charset, &collation)?;
 
-        sqlx::query(&create_db_sql).execute(pool).await.map_err(|e| {
-            tracing::error!("Failed to create MySQL database: {}", e);
-            ApiError::InternalError("Failed to create database".to_string())
-        })?;
+                sqlx::query(&create_db_sql).execute(pool).await.map_err(|e| {
+                    tracing::error!("Failed to create MySQL database: {}", e);
+                    ApiError::InternalError("Failed to create database".to_string())
+                })?;
+            }
+            DatabaseEngine::Postgresql => {
+                PostgresService::create_database(&db_name, &charset).await?;
+            }
+        }
 
         // Record in managed_databases table
         let database_id = Uuid::new_v4().to_string();
@@ -158,12 +186,13 @@ impl DatabaseService {
 
         sqlx::query(
             r#"
-            INSERT INTO managed_databases (id, user_id, db_name, description, size_bytes, charset, collation, created_at, updated_at)
-            VALUES (?, ?, ?, ?, 0, ?, ?, ?, ?)
+            INSERT INTO managed_databases (id, user_id, engine, db_name, description, size_bytes, charset, collation, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, 0, ?, ?, ?, ?)
             "#,
         )
         .bind(&database_id)
         .bind(user_id)
+        .bind(engine.as_str())
         .bind(&db_name)
         .bind(&request.description)
         .bind(&charset)
@@ -174,8 +203,9 @@ impl DatabaseService {
         .await?;
 
         tracing::info!(
-            "Database created: {} for user {}",
+            "Database created: {} ({}) for user {}",
             db_name,
+            engine.as_str(),
             user_id
         );
 
@@ -235,35 +265,64 @@ impl DatabaseService {
             return Err(ApiError::Forbidden);
         }
 
-        // Delete database users first
+        // Cabut grant semua user pada database ini
+        let granted_users = sqlx::query_as::<_, DatabaseUser>(
+            r#"
+            SELECT u.* FROM database_users u
+            JOIN database_user_grants g ON g.database_user_id = u.id
+            WHERE g.database_id = ?
+            "#,
+        )
+        .bind(database_id)
+        .fetch_all(pool)
+        .await?;
+
+        let engine = DatabaseEngine::from_column(&db.engine);
+
+        for db_user in &granted_users {
+            Self::revoke_grant(pool, db_user, &db.db_name).await?;
+        }
+
+        // User yang hanya punya akses ke database ini ikut dihapus
         let db_users = sqlx::query_as::<_, DatabaseUser>(
-            "SELECT * FROM database_users WHERE database_id = ?",
+            r#"
+            SELECT * FROM database_users u
+            WHERE u.database_id = ?
+              AND NOT EXISTS (
+                SELECT 1 FROM database_user_grants g
+                WHERE g.database_user_id = u.id AND g.database_id <> ?
+              )
+            "#,
         )
         .bind(database_id)
+        .bind(database_id)
         .fetch_all(pool)
         .await?;
 
         for db_user in db_users {
-            // Drop MySQL user
-            let drop_user_sql = format!(
-                "DROP USER IF EXISTS '{}'@'{}'",
-                db_user.db_username, db_user.host
-            );
-            let _ = sqlx::query(&drop_user_sql).execute(pool).await;
+            Self::drop_account(pool, &db_user, std::slice::from_ref(&db.db_name)).await?;
+
+            sqlx::query("DELETE FROM database_users WHERE id = ?")
+                .bind(&db_user.id)
+                .execute(pool)
+                .await?;
         }
 
-        // Delete from database_users table
-        sqlx::query("DELETE FROM database_users WHERE database_id = ?")
-            .bind(database_id)
-            .execute(pool)
-            .await?;
+        match engine {
+            DatabaseEngine::Mysql => {
+                let _ = sqlx::query("FLUSH PRIVILEGES").execute(pool).await;
 
-        // Drop the actual MySQL database
-        let drop_db_sql = format!("DROP DATABASE IF EXISTS `{}`", db.db_name);
-        sqlx::query(&drop_db_sql).execute(pool).await.map_err(|e| {
-            tracing::error!("Failed to drop MySQL database: {}", e);
-            ApiError::InternalError("Failed to delete database".to_string())
-        })?;
+                // Drop the actual MySQL database
+                let drop_db_sql = mysql::drop_database(&db.db_name)?;
+                sqlx::query(&drop_db_sql).execute(pool).await.map_err(|e| {
+                    tracing::error!("Failed to drop MySQL database: {}", e);
+                    ApiError::InternalError("Failed to delete database".to_string())
+                })?;
+            }
+            DatabaseEngine::Postgresql => {
+                PostgresService::drop_database(&db.db_name).await?;
+            }
+        }
 
         // Delete from managed_databases table
         sqlx::query("DELETE FROM managed_databases WHERE id = ?")
@@ -294,36 +353,7 @@ impl DatabaseService {
 
         let mut responses = Vec::with_capacity(db_users.len());
         for db_user in db_users {
-            // Get database info
-            let db = sqlx::query_as::<_, ManagedDatabase>(
-                "SELECT * FROM managed_databases WHERE id = ?",
-            )
-            .bind(&db_user.database_id)
-            .fetch_optional(pool)
-            .await?;
-
-            let db_name = db
-                .map(|d| d.db_name)
-                .unwrap_or_else(|| "unknown".to_string());
-
-            responses.push(DatabaseUserResponse {
-                id: db_user.id,
-                user_id: db_user.user_id,
-                database_id: db_user.database_id,
-                db_name: db_name.clone(),
-                db_username: db_user.db_username.clone(),
-                host: db_user.host.clone(),
-                privileges: db_user.privileges,
-                is_active: db_user.is_active,
-                created_at: db_user.created_at,
-                updated_at: db_user.updated_at,
-                phpmyadmin_info: PhpMyAdminInfo {
-                    url: CONFIG.phpmyadmin_url.clone(),
-                    username: db_user.db_username,
-                    database: db_name,
-                    mysql_host: "localhost".to_string(),
-                },
-            });
+            responses.push(Self::to_user_response(pool, db_user).await?);
         }
 
         Ok(responses)
@@ -347,35 +377,7 @@ impl DatabaseService {
             return Err(ApiError::Forbidden);
         }
 
-        let db = sqlx::query_as::<_, ManagedDatabase>(
-            "SELECT * FROM managed_databases WHERE id = ?",
-        )
-        .bind(&db_user.database_id)
-        .fetch_optional(pool)
-        .await?;
-
-        let db_name = db
-            .map(|d| d.db_name)
-            .unwrap_or_else(|| "unknown".to_string());
-
-        Ok(DatabaseUserResponse {
-            id: db_user.id,
-            user_id: db_user.user_id,
-            database_id: db_user.database_id,
-            db_name: db_name.clone(),
-            db_username: db_user.db_username.clone(),
-            host: db_user.host.clone(),
-            privileges: db_user.privileges,
-            is_active: db_user.is_active,
-            created_at: db_user.created_at,
-            updated_at: db_user.updated_at,
-            phpmyadmin_info: PhpMyAdminInfo {
-                url: CONFIG.phpmyadmin_url.clone(),
-                username: db_user.db_username,
-                database: db_name,
-                mysql_host: "localhost".to_string(),
-            },
-        })
+        Self::to_user_response(pool, db_user).await
     }
 
     /// Create database user
@@ -390,7 +392,6 @@ impl DatabaseService {
             .validate()
             .map_err(|e| ApiError::ValidationError(e.to_string()))?;
 
-        // Verify database ownership
         // Verify database ownership (if provided)
         let db = if let Some(db_id) = &request.database_id {
             if db_id.is_empty() {
@@ -413,20 +414,32 @@ impl DatabaseService {
             None
         };
 
-
-
         // Generate prefixed username
         let short_user_id = &user_id[..8.min(user_id.len())];
         let db_username = format!("{}_{}", short_user_id, request.username.to_lowercase());
-        let host = request.host.unwrap_or_else(|| "%".to_string());
-        let privileges = request.privileges.unwrap_or_else(|| "ALL".to_string());
+        let host = mysql::LOCAL_HOST.to_string();
+
+        // Engine mengikuti database yang dipilih
+        let requested_engine = request.engine.as_deref().and_then(DatabaseEngine::parse);
+        let engine = match (&db, requested_engine) {
+            (Some(db), Some(engine)) if DatabaseEngine::from_column(&db.engine) != engine => {
+                return Err(ApiError::ValidationError(
+                    "Engine database user harus sama dengan engine database".to_string(),
+                ));
+            }
+            (Some(db), _) => DatabaseEngine::from_column(&db.engine),
+            (None, engine) => engine.unwrap_or_default(),
+        };
+
+        let privilege_list =
+            Self::parse_privileges(engine, request.privileges.as_deref().unwrap_or("ALL"))?;
+        let privileges = DatabasePrivilege::join(&privilege_list);
 
         // Check if username already exists
         let existing = sqlx::query_scalar::<_, i64>(
-            "SELECT COUNT(*) FROM database_users WHERE db_username = ? AND host = ?",
+            "SELECT COUNT(*) FROM database_users WHERE db_username = ?",
         )
         .bind(&db_username)
-        .bind(&host)
         .fetch_one(pool)
         .await?;
 
@@ -434,38 +447,48 @@ impl DatabaseService {
             return Err(ApiError::AlreadyExists("Database Username".to_string()));
         }
 
-        // Create MySQL user
-        let create_user_sql = format!(
-            "CREATE USER IF NOT EXISTS '{}'@'{}' IDENTIFIED BY '{}'",
-            db_username, host, request.password
-        );
-        sqlx::query(&create_user_sql).execute(pool).await.map_err(|e| {
-            tracing::error!("Failed to create MySQL user: {}", e);
-            ApiError::InternalError("Failed to create database user".to_string())
-        })?;
+        match engine {
+            DatabaseEngine::Mysql => {
+                // user@localhost plus satu account per host di allow-list remote MySQL
+                let mut hosts = vec![host.clone()];
+                hosts.extend(RemoteMysqlService::mysql_hosts(pool, user_id).await?);
+
+                for account_host in &hosts {
+                    // Create MySQL user
+                    let create_user_sql =
+                        mysql::create_user(&db_username, account_host, &request.password)?;
+                    sqlx::query(&create_user_sql).execute(pool).await.map_err(|e| {
+                        tracing::error!("Failed to create MySQL user: {}", e);
+                        ApiError::InternalError("Failed to create database user".to_string())
+                    })?;
+
+                    // Grant privileges ONLY to this specific database (if database is selected)
+                    if let Some(db) = &db {
+                        let grant_sql = mysql::grant(
+                            &privilege_list,
+                            &db.db_name,
+                            &db_username,
+                            account_host,
+                        )?;
+                        sqlx::query(&grant_sql).execute(pool).await.map_err(|e| {
+                            tracing::error!("Failed to grant privileges: {}", e);
+                            ApiError::InternalError("Failed to grant privileges".to_string())
+                        })?;
+                    }
+                }
 
-        // Grant privileges ONLY to this specific database
-        // Grant privileges ONLY to this specific database (if database is selected)
-        if let Some(db) = &db {
-            let grant_sql = format!(
-                "GRANT {} ON `{}`.* TO '{}'@'{}'",
-                if privileges.to_uppercase() == "ALL" {
-                    "ALL PRIVILEGES"
-                } else {
-                    &privileges
-                },
-                db.db_name,
-                db_username,
-                host
-            );
-            sqlx::query(&grant_sql).execute(pool).await.map_err(|e| {
-                tracing::error!("Failed to grant privileges: {}", e);
-                ApiError::InternalError("Failed to grant privileges".to_string())
-            })?;
-        }
+                // Flush privileges
+                let _ = sqlx::query("FLUSH PRIVILEGES").execute(pool).await;
+            }
+            DatabaseEngine::Postgresql => {
+                PostgresService::create_role(&db_username, &request.password).await?;
 
-        // Flush privileges
-        let _ = sqlx::query("FLUSH PRIVILEGES").execute(pool).await;
+                if let Some(db) = &db {
+                    PostgresService::apply_grant(&db.db_name, &db_username, &privilege_list)
+                        .await?;
+                }
+            }
+        }
 
         // Hash password for storage (for reference only, MySQL has its own hash)
         let password_hash = password::hash_password(&request.password)?;
@@ -474,17 +497,22 @@ impl DatabaseService {
         let db_user_id = Uuid::new_v4().to_string();
         let now = Utc::now();
 
+        // Salinan terenkripsi untuk phpMyAdmin SSO (jika master key dikonfigurasi)
+        let password_encrypted = secrets::encrypt_if_enabled(&request.password, &db_user_id)?;
+
         sqlx::query(
             r#"
-            INSERT INTO database_users (id, user_id, database_id, db_username, password_hash, host, privileges, is_active, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, ?, ?, TRUE, ?, ?)
+            INSERT INTO database_users (id, user_id, database_id, engine, db_username, password_hash, password_encrypted, host, privileges, is_active, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, TRUE, ?, ?)
             "#,
         )
         .bind(&db_user_id)
         .bind(user_id)
         .bind(&request.database_id.clone().filter(|id| !id.is_empty()))
+        .bind(engine.as_str())
         .bind(&db_username)
         .bind(&password_hash)
+        .bind(&password_encrypted)
         .bind(&host)
         .bind(&privileges)
         .bind(now)
@@ -492,6 +520,10 @@ impl DatabaseService {
         .execute(pool)
         .await?;
 
+        if let Some(db) = &db {
+            Self::save_grant(pool, &db_user_id, &db.id, &privileges).await?;
+        }
+
         tracing::info!(
             "Database user created: {} for database {}",
             db_username,
@@ -524,8 +556,24 @@ impl DatabaseService {
             return Err(ApiError::Forbidden);
         }
 
+        // Validasi dan hash lebih dulu agar request invalid tidak mengubah akun
+        let privilege_list = Self::parse_privileges(
+            DatabaseEngine::from_column(&db_user.engine),
+            request.privileges.as_deref().unwrap_or(&db_user.privileges),
+        )?;
+        let privileges = DatabasePrivilege::join(&privilege_list);
+
+        let (password_hash, password_encrypted) = if let Some(ref new_password) = request.password {
+            (
+                password::hash_password(new_password)?,
+                secrets::encrypt_if_enabled(new_password, &db_user.id)?,
+            )
+        } else {
+            (db_user.password_hash.clone(), db_user.password_encrypted.clone())
+        };
+
         // 1. Handle Database Assignment Change
-        let mut final_db_name = None;
+        let mut final_db = None;
 
         if let Some(ref new_db_id) = request.database_id {
             // Verify new database exists and belongs to user
@@ -540,16 +588,10 @@ impl DatabaseService {
             if new_db.user_id != user_id {
                 return Err(ApiError::Forbidden);
             }
+            Self::ensure_same_engine(&db_user, &new_db)?;
 
-            final_db_name = Some(new_db.db_name.clone());
+            final_db = Some(new_db.clone());
 
-            // Update database_id in table
-            sqlx::query("UPDATE database_users SET database_id = ? WHERE id = ?")
-                .bind(new_db_id)
-                .bind(db_user_id)
-                .execute(pool)
-                .await?;
-            
             // Note: privileges will be granted below
         } else if let Some(ref current_db_id) = db_user.database_id {
              // If not changing DB, get current DB name for GRANT if needed
@@ -561,65 +603,52 @@ impl DatabaseService {
             .await?;
             
             if let Some(db) = current_db {
-                final_db_name = Some(db.db_name);
+                final_db = Some(db);
             }
         }
 
         // Update MySQL password if provided
         if let Some(ref new_password) = request.password {
-            let alter_user_sql = format!(
-                "ALTER USER '{}'@'{}' IDENTIFIED BY '{}'",
-                db_user.db_username, db_user.host, new_password
-            );
-            sqlx::query(&alter_user_sql).execute(pool).await.map_err(|e| {
-                tracing::error!("Failed to update MySQL password: {}", e);
-                ApiError::InternalError("Failed to update password".to_string())
-            })?;
+            match DatabaseEngine::from_column(&db_user.engine) {
+                DatabaseEngine::Mysql => {
+                    for host in Self::account_hosts(pool, &db_user).await? {
+                        let alter_user_sql =
+                            mysql::alter_user_password(&db_user.db_username, &host, new_password)?;
+                        sqlx::query(&alter_user_sql).execute(pool).await.map_err(|e| {
+                            tracing::error!("Failed to update MySQL password: {}", e);
+                            ApiError::InternalError("Failed to update password".to_string())
+                        })?;
+                    }
+                }
+                DatabaseEngine::Postgresql => {
+                    PostgresService::alter_role_password(&db_user.db_username, new_password)
+                        .await?;
+                }
+            }
         }
 
-        // Update record
-        let password_hash = if let Some(ref new_password) = request.password {
-            password::hash_password(new_password)?
-        } else {
-            db_user.password_hash.clone()
-        };
-
         let is_active = request.is_active.unwrap_or(db_user.is_active);
-        let privileges = request.privileges.clone().unwrap_or(db_user.privileges.clone()); // Use new privs or existing
 
         // Execute GRANT if we have a database assigned (either new or existing)
-        if let Some(db_name) = final_db_name {
+        if let Some(db) = final_db {
              // If privileges changed OR database changed, we need to GRANT again
              if request.privileges.is_some() || request.database_id.is_some() {
-                let grant_sql = format!(
-                    "GRANT {} ON `{}`.* TO '{}'@'{}'",
-                    if privileges.to_uppercase() == "ALL" {
-                        "ALL PRIVILEGES"
-                    } else {
-                        &privileges
-                    },
-                    db_name,
-                    db_user.db_username,
-                    db_user.host
-                );
-                
                 // Log the grant attempt
-                tracing::info!("Granting privileges to {} on {}: {}", db_user.db_username, db_name, privileges);
+                tracing::info!("Granting privileges to {} on {}: {}", db_user.db_username, db.db_name, privileges);
 
-                sqlx::query(&grant_sql).execute(pool).await.map_err(|e| {
-                    tracing::error!("Failed to grant privileges: {}", e);
-                    ApiError::InternalError("Failed to grant privileges".to_string())
-                })?;
-                
-                // Flush privileges
-                let _ = sqlx::query("FLUSH PRIVILEGES").execute(pool).await;
+                Self::apply_grant(pool, &db_user, &db.db_name, &privilege_list).await?;
+                Self::save_grant(pool, db_user_id, &db.id, &privileges).await?;
              }
         }
 
+        // Update record
+        let database_id = request.database_id.clone().or(db_user.database_id.clone());
         sqlx::query(
-            "UPDATE database_users SET password_hash = ?, privileges = ?, is_active = ?, updated_at = ? WHERE id = ?",
+            "UPDATE database_users SET database_id = ?, password_hash = ?, password_encrypted = ?, privileges = ?, is_active = ?, updated_at = ? WHERE id = ?",
         )
+        .bind(&database_id)
         .bind(&password_hash)
+        .bind(&password_encrypted)
         .bind(&privileges)
         .bind(is_active)
         .bind(Utc::now())
@@ -648,15 +677,12 @@ impl DatabaseService {
             return Err(ApiError::Forbidden);
         }
 
-        // Drop MySQL user
-        let drop_user_sql = format!(
-            "DROP USER IF EXISTS '{}'@'{}'",
-            db_user.db_username, db_user.host
-        );
-        let _ = sqlx::query(&drop_user_sql).execute(pool).await;
-
-        // Flush privileges
-        let _ = sqlx::query("FLUSH PRIVILEGES").execute(pool).await;
+        let granted_databases: Vec<String> = Self::load_grants(pool, &db_user.id)
+            .await?
+            .into_iter()
+            .map(|grant| grant.db_name)
+            .collect();
+        Self::drop_account(pool, &db_user, &granted_databases).await?;
 
         // Delete from database_users table
         sqlx::query("DELETE FROM database_users WHERE id = ?")
@@ -669,6 +695,481 @@ impl DatabaseService {
         Ok(())
     }
 
+    // ==========================================
+    // DATABASE USER GRANTS
+    // ==========================================
+
+    /// Get grants database user
+    pub async fn get_database_user_grants(
+        pool: &MySqlPool,
+        db_user_id: &str,
+        user_id: &str,
+    ) -> ApiResult<Vec<DatabaseGrantResponse>> {
+        let db_user = Self::find_owned_database_user(pool, db_user_id, user_id).await?;
+        Self::load_grants(pool, &db_user.id).await
+    }
+
+    /// Tambah grant database user pada database lain milik user
+    pub async fn add_database_user_grant(
+        pool: &MySqlPool,
+        db_user_id: &str,
+        user_id: &str,
+        request: CreateDatabaseGrantRequest,
+    ) -> ApiResult<DatabaseUserResponse> {
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        let db_user = Self::find_owned_database_user(pool, db_user_id, user_id).await?;
+        let db = Self::find_owned_database(pool, &request.database_id, user_id).await?;
+        Self::ensure_same_engine(&db_user, &db)?;
+
+        let existing = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM database_user_grants WHERE database_user_id = ? AND database_id = ?",
+        )
+        .bind(&db_user.id)
+        .bind(&db.id)
+        .fetch_one(pool)
+        .await?;
+
+        if existing > 0 {
+            return Err(ApiError::AlreadyExists("Database Grant".to_string()));
+        }
+
+        let privilege_list = Self::parse_privileges(
+            DatabaseEngine::from_column(&db_user.engine),
+            request.privileges.as_deref().unwrap_or("ALL"),
+        )?;
+        let privileges = DatabasePrivilege::join(&privilege_list);
+
+        Self::apply_grant(pool, &db_user, &db.db_name, &privilege_list).await?;
+        Self::save_grant(pool, &db_user.id, &db.id, &privileges).await?;
+
+        // User tanpa database utama memakai database ini sebagai default
+        if db_user.database_id.is_none() {
+            sqlx::query("UPDATE database_users SET database_id = ?, privileges = ? WHERE id = ?")
+                .bind(&db.id)
+                .bind(&privileges)
+                .bind(&db_user.id)
+                .execute(pool)
+                .await?;
+        }
+
+        tracing::info!(
+            "Database grant added: {} on {} ({})",
+            db_user.db_username,
+            db.db_name,
+            privileges
+        );
+
+        Self::get_database_user_by_id(pool, &db_user.id, user_id).await
+    }
+
+    /// Ubah privileges grant database user
+    pub async fn update_database_user_grant(
+        pool: &MySqlPool,
+        db_user_id: &str,
+        database_id: &str,
+        user_id: &str,
+        request: UpdateDatabaseGrantRequest,
+    ) -> ApiResult<DatabaseUserResponse> {
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        let db_user = Self::find_owned_database_user(pool, db_user_id, user_id).await?;
+        let db = Self::find_owned_database(pool, database_id, user_id).await?;
+        Self::ensure_grant_exists(pool, &db_user.id, &db.id).await?;
+
+        let privilege_list = Self::parse_privileges(
+            DatabaseEngine::from_column(&db_user.engine),
+            &request.privileges,
+        )?;
+        let privileges = DatabasePrivilege::join(&privilege_list);
+
+        Self::apply_grant(pool, &db_user, &db.db_name, &privilege_list).await?;
+        Self::save_grant(pool, &db_user.id, &db.id, &privileges).await?;
+
+        if db_user.database_id.as_deref() == Some(db.id.as_str()) {
+            sqlx::query("UPDATE database_users SET privileges = ? WHERE id = ?")
+                .bind(&privileges)
+                .bind(&db_user.id)
+                .execute(pool)
+                .await?;
+        }
+
+        tracing::info!(
+            "Database grant updated: {} on {} ({})",
+            db_user.db_username,
+            db.db_name,
+            privileges
+        );
+
+        Self::get_database_user_by_id(pool, &db_user.id, user_id).await
+    }
+
+    /// Cabut grant database user pada satu database
+    pub async fn remove_database_user_grant(
+        pool: &MySqlPool,
+        db_user_id: &str,
+        database_id: &str,
+        user_id: &str,
+    ) -> ApiResult<DatabaseUserResponse> {
+        let db_user = Self::find_owned_database_user(pool, db_user_id, user_id).await?;
+        let db = Self::find_owned_database(pool, database_id, user_id).await?;
+        Self::ensure_grant_exists(pool, &db_user.id, &db.id).await?;
+
+        match DatabaseEngine::from_column(&db_user.engine) {
+            DatabaseEngine::Mysql => {
+                for host in Self::account_hosts(pool, &db_user).await? {
+                    let revoke_sql = mysql::revoke_all(&db.db_name, &db_user.db_username, &host)?;
+                    sqlx::query(&revoke_sql).execute(pool).await.map_err(|e| {
+                        tracing::error!("Failed to revoke privileges: {}", e);
+                        ApiError::InternalError("Failed to revoke privileges".to_string())
+                    })?;
+                }
+                let _ = sqlx::query("FLUSH PRIVILEGES").execute(pool).await;
+            }
+            DatabaseEngine::Postgresql => {
+                PostgresService::revoke_all(&db.db_name, &db_user.db_username).await?;
+            }
+        }
+
+        sqlx::query("DELETE FROM database_user_grants WHERE database_user_id = ? AND database_id = ?")
+            .bind(&db_user.id)
+            .bind(&db.id)
+            .execute(pool)
+            .await?;
+
+        sqlx::query("UPDATE database_users SET database_id = NULL WHERE id = ? AND database_id = ?")
+            .bind(&db_user.id)
+            .bind(&db.id)
+            .execute(pool)
+            .await?;
+
+        tracing::info!("Database grant removed: {} on {}", db_user.db_username, db.db_name);
+
+        Self::get_database_user_by_id(pool, &db_user.id, user_id).await
+    }
+
+    /// Ambil database user dan pastikan milik user
+    async fn find_owned_database_user(
+        pool: &MySqlPool,
+        db_user_id: &str,
+        user_id: &str,
+    ) -> ApiResult<DatabaseUser> {
+        let db_user = sqlx::query_as::<_, DatabaseUser>(
+            "SELECT * FROM database_users WHERE id = ?",
+        )
+        .bind(db_user_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(ApiError::NotFound("Database User".to_string()))?;
+
+        if db_user.user_id != user_id {
+            return Err(ApiError::Forbidden);
+        }
+
+        Ok(db_user)
+    }
+
+    /// Ambil managed database dan pastikan milik user
+    async fn find_owned_database(
+        pool: &MySqlPool,
+        database_id: &str,
+        user_id: &str,
+    ) -> ApiResult<ManagedDatabase> {
+        let db = sqlx::query_as::<_, ManagedDatabase>(
+            "SELECT * FROM managed_databases WHERE id = ?",
+        )
+        .bind(database_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(ApiError::NotFound("Database".to_string()))?;
+
+        if db.user_id != user_id {
+            return Err(ApiError::Forbidden);
+        }
+
+        Ok(db)
+    }
+
+    /// Database user hanya bisa di-grant ke database dengan engine yang sama
+    fn ensure_same_engine(db_user: &DatabaseUser, db: &ManagedDatabase) -> ApiResult<()> {
+        if DatabaseEngine::from_column(&db_user.engine) != DatabaseEngine::from_column(&db.engine)
+        {
+            return Err(ApiError::ValidationError(format!(
+                "Database user {} tidak bisa diberi akses ke database {} (engine berbeda)",
+                db_user.engine, db.engine
+            )));
+        }
+        Ok(())
+    }
+
+    /// Parse privileges sesuai engine
+    fn parse_privileges(engine: DatabaseEngine, value: &str) -> ApiResult<Vec<DatabasePrivilege>> {
+        match engine {
+            DatabaseEngine::Mysql => mysql::parse_privileges(value),
+            DatabaseEngine::Postgresql => postgres::parse_privileges(value),
+        }
+    }
+
+    async fn ensure_grant_exists(
+        pool: &MySqlPool,
+        db_user_id: &str,
+        database_id: &str,
+    ) -> ApiResult<()> {
+        let existing = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM database_user_grants WHERE database_user_id = ? AND database_id = ?",
+        )
+        .bind(db_user_id)
+        .bind(database_id)
+        .fetch_one(pool)
+        .await?;
+
+        if existing == 0 {
+            return Err(ApiError::NotFound("Database Grant".to_string()));
+        }
+
+        Ok(())
+    }
+
+    /// Ganti privileges database user pada database (REVOKE lalu GRANT)
+    async fn apply_grant(
+        pool: &MySqlPool,
+        db_user: &DatabaseUser,
+        db_name: &str,
+        privileges: &[DatabasePrivilege],
+    ) -> ApiResult<()> {
+        if DatabaseEngine::from_column(&db_user.engine) == DatabaseEngine::Postgresql {
+            return PostgresService::apply_grant(db_name, &db_user.db_username, privileges).await;
+        }
+
+        let hosts = Self::account_hosts(pool, db_user).await?;
+        for (revoke_sql, grant_sql) in
+            mysql_grant_statements(privileges, db_name, &db_user.db_username, &hosts)?
+        {
+            // REVOKE gagal jika belum ada grant sebelumnya (error 1141), abaikan
+            let _ = sqlx::query(&revoke_sql).execute(pool).await;
+
+            sqlx::query(&grant_sql).execute(pool).await.map_err(|e| {
+                tracing::error!("Failed to grant privileges: {}", e);
+                ApiError::InternalError("Failed to grant privileges".to_string())
+            })?;
+        }
+
+        let _ = sqlx::query("FLUSH PRIVILEGES").execute(pool).await;
+
+        Ok(())
+    }
+
+    /// Cabut semua privileges database user pada database (error diabaikan)
+    async fn revoke_grant(pool: &MySqlPool, db_user: &DatabaseUser, db_name: &str) -> ApiResult<()> {
+        match DatabaseEngine::from_column(&db_user.engine) {
+            DatabaseEngine::Mysql => {
+                for host in Self::account_hosts(pool, db_user).await? {
+                    let revoke_sql = mysql::revoke_all(db_name, &db_user.db_username, &host)?;
+                    let _ = sqlx::query(&revoke_sql).execute(pool).await;
+                }
+            }
+            DatabaseEngine::Postgresql => {
+                if let Err(e) = PostgresService::revoke_all(db_name, &db_user.db_username).await {
+                    tracing::warn!("Revoke {} on {} failed: {}", db_user.db_username, db_name, e);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Hapus account database user (MySQL: semua host, PostgreSQL: role)
+    async fn drop_account(
+        pool: &MySqlPool,
+        db_user: &DatabaseUser,
+        granted_databases: &[String],
+    ) -> ApiResult<()> {
+        match DatabaseEngine::from_column(&db_user.engine) {
+            DatabaseEngine::Mysql => {
+                // Drop MySQL user
+                for host in Self::account_hosts(pool, db_user).await? {
+                    let drop_user_sql = mysql::drop_user(&db_user.db_username, &host)?;
+                    let _ = sqlx::query(&drop_user_sql).execute(pool).await;
+                }
+
+                // Flush privileges
+                let _ = sqlx::query("FLUSH PRIVILEGES").execute(pool).await;
+            }
+            DatabaseEngine::Postgresql => {
+                PostgresService::drop_role(&db_user.db_username, granted_databases).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Simpan (insert/update) record grant
+    async fn save_grant(
+        pool: &MySqlPool,
+        db_user_id: &str,
+        database_id: &str,
+        privileges: &str,
+    ) -> ApiResult<()> {
+        let now = Utc::now();
+
+        sqlx::query(
+            r#"
+            INSERT INTO database_user_grants (id, database_user_id, database_id, privileges, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE privileges = VALUES(privileges), updated_at = VALUES(updated_at)
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(db_user_id)
+        .bind(database_id)
+        .bind(privileges)
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Grants database user beserta nama database
+    async fn load_grants(
+        pool: &MySqlPool,
+        db_user_id: &str,
+    ) -> ApiResult<Vec<DatabaseGrantResponse>> {
+        let rows = sqlx::query_as::<_, (String, String, String, DateTime<Utc>, DateTime<Utc>)>(
+            r#"
+            SELECT g.database_id, d.db_name, g.privileges, g.created_at, g.updated_at
+            FROM database_user_grants g
+            JOIN managed_databases d ON d.id = g.database_id
+            WHERE g.database_user_id = ?
+            ORDER BY d.db_name
+            "#,
+        )
+        .bind(db_user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(database_id, db_name, privileges, created_at, updated_at)| DatabaseGrantResponse {
+                    database_id,
+                    db_name,
+                    privileges,
+                    created_at,
+                    updated_at,
+                },
+            )
+            .collect())
+    }
+
+    /// Host semua account MySQL database user (utama + allow-list remote)
+    ///
+    /// Role PostgreSQL tidak punya host, jadi hanya host utama.
+    async fn account_hosts(pool: &MySqlPool, db_user: &DatabaseUser) -> ApiResult<Vec<String>> {
+        let mut hosts = vec![db_user.host.clone()];
+        if DatabaseEngine::from_column(&db_user.engine) == DatabaseEngine::Postgresql {
+            return Ok(hosts);
+        }
+        for host in RemoteMysqlService::mysql_hosts(pool, &db_user.user_id).await? {
+            if !hosts.contains(&host) {
+                hosts.push(host);
+            }
+        }
+        Ok(hosts)
+    }
+
+    /// Grant aktual dari MySQL (`SHOW GRANTS`) untuk setiap account
+    ///
+    /// Account yang tidak ditemukan di MySQL dilewati.
+    async fn load_effective_grants(
+        pool: &MySqlPool,
+        db_user: &DatabaseUser,
+        hosts: &[String],
+    ) -> Vec<String> {
+        if DatabaseEngine::from_column(&db_user.engine) == DatabaseEngine::Postgresql {
+            return PostgresService::effective_grants(&db_user.db_username)
+                .await
+                .unwrap_or_else(|e| {
+                    tracing::warn!("PostgreSQL grants failed for {}: {}", db_user.db_username, e);
+                    Vec::new()
+                });
+        }
+
+        let mut effective = Vec::new();
+
+        for host in hosts {
+            let Ok(sql) = mysql::show_grants(&db_user.db_username, host) else {
+                continue;
+            };
+
+            match sqlx::query_scalar::<_, String>(&sql).fetch_all(pool).await {
+                Ok(grants) => effective.extend(grants),
+                Err(e) => {
+                    tracing::warn!(
+                        "SHOW GRANTS failed for {}@{}: {}",
+                        db_user.db_username,
+                        host,
+                        e
+                    );
+                }
+            }
+        }
+
+        effective
+    }
+
+    /// Build response database user
+    async fn to_user_response(
+        pool: &MySqlPool,
+        db_user: DatabaseUser,
+    ) -> ApiResult<DatabaseUserResponse> {
+        // Get database info
+        let db = sqlx::query_as::<_, ManagedDatabase>(
+            "SELECT * FROM managed_databases WHERE id = ?",
+        )
+        .bind(&db_user.database_id)
+        .fetch_optional(pool)
+        .await?;
+
+        let grants = Self::load_grants(pool, &db_user.id).await?;
+
+        // User tanpa database utama memakai database grant pertama
+        let db_name = db
+            .map(|d| d.db_name)
+            .or_else(|| grants.first().map(|grant| grant.db_name.clone()))
+            .unwrap_or_else(|| "unknown".to_string());
+
+        let hosts = Self::account_hosts(pool, &db_user).await?;
+        let effective_grants = Self::load_effective_grants(pool, &db_user, &hosts).await;
+        let remote_hosts = hosts.into_iter().skip(1).collect();
+
+        Ok(DatabaseUserResponse {
+            id: db_user.id,
+            user_id: db_user.user_id,
+            database_id: db_user.database_id,
+            engine: db_user.engine,
+            db_name: db_name.clone(),
+            db_username: db_user.db_username.clone(),
+            host: db_user.host.clone(),
+            privileges: db_user.privileges,
+            is_active: db_user.is_active,
+            created_at: db_user.created_at,
+            updated_at: db_user.updated_at,
+            phpmyadmin_info: PhpMyAdminInfo {
+                url: CONFIG.phpmyadmin_url.clone(),
+                username: db_user.db_username,
+                database: db_name,
+                mysql_host: "localhost".to_string(),
+            },
+            grants,
+            effective_grants,
+            remote_hosts,
+        })
+    }
+
     /// Get database count untuk user
     pub async fn get_database_count(pool: &MySqlPool, user_id: &str) -> ApiResult<i64> {
         let count = sqlx::query_scalar::<_, i64>(
@@ -693,3 +1194,104 @@ impl DatabaseService {
         Ok(count)
     }
 }
+
+/// Pasangan (REVOKE, GRANT) untuk setiap host account MySQL database user
+fn mysql_grant_statements(
+    privileges: &[DatabasePrivilege],
+    db_name: &str,
+    db_username: &str,
+    hosts: &[String],
+) -> ApiResult<Vec<(String, String)>> {
+    hosts
+        .iter()
+        .map(|host| {
+            Ok((
+                mysql::revoke_all(db_name, db_username, host)?,
+                mysql::grant(privileges, db_name, db_username, host)?,
+            ))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn database(engine: &str) -> ManagedDatabase {
+        ManagedDatabase {
+            id: "db-1".to_string(),
+            user_id: "user-1".to_string(),
+            engine: engine.to_string(),
+            db_name: "a1b2c3d4_shop".to_string(),
+            description: None,
+            size_bytes: 0,
+            size_updated_at: None,
+            charset: "utf8mb4".to_string(),
+            collation: "utf8mb4_unicode_ci".to_string(),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    fn database_user(engine: &str) -> DatabaseUser {
+        DatabaseUser {
+            id: "dbu-1".to_string(),
+            user_id: "user-1".to_string(),
+            database_id: None,
+            engine: engine.to_string(),
+            db_username: "a1b2c3d4_app".to_string(),
+            password_hash: "hash".to_string(),
+            password_encrypted: None,
+            host: "localhost".to_string(),
+            privileges: "ALL".to_string(),
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_ensure_same_engine() {
+        assert!(
+            DatabaseService::ensure_same_engine(&database_user("mysql"), &database("mysql"))
+                .is_ok()
+        );
+        assert!(DatabaseService::ensure_same_engine(
+            &database_user("postgresql"),
+            &database("postgresql")
+        )
+        .is_ok());
+        assert!(matches!(
+            DatabaseService::ensure_same_engine(&database_user("mysql"), &database("postgresql")),
+            Err(ApiError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn test_mysql_grant_statements() {
+        let hosts = vec!["localhost".to_string(), "10.0.0.%".to_string()];
+        let statements = mysql_grant_statements(
+            &[DatabasePrivilege::Select, DatabasePrivilege::Insert],
+            "a1b2c3d4_shop",
+            "a1b2c3d4_app",
+            &hosts,
+        )
+        .unwrap();
+
+        assert_eq!(statements.len(), 2);
+        for ((revoke, grant), host) in statements.iter().zip(&hosts) {
+            assert!(revoke.starts_with("REVOKE ALL PRIVILEGES ON `a1b2c3d4\\_shop`.*"));
+            assert!(grant.starts_with("GRANT SELECT, INSERT ON `a1b2c3d4\\_shop`.*"));
+            assert!(grant.contains(&format!("'{}'", host)));
+        }
+
+        // Nama database berbahaya ditolak sebelum SQL dijalankan
+        assert!(mysql_grant_statements(
+            &[DatabasePrivilege::All],
+            "shop`; DROP DATABASE x; --",
+            "a1b2c3d4_app",
+            &hosts,
+        )
+        .is_err());
+    }
+}
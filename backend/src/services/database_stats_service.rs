@@ -0,0 +1,205 @@
+//! # Database Stats Service
+//!
+//! Pengumpulan ukuran dan statistik tabel managed database.
+//!
+//! MySQL dibaca dari `information_schema.TABLES`, PostgreSQL dari katalog
+//! `pg_class`/`pg_stat_user_tables`. Collector periodik memperbarui kolom
+//! `size_bytes` dan `size_updated_at` di `managed_databases`.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use rocket::fairing::AdHoc;
+use sqlx::MySqlPool;
+
+use crate::database::Database;
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{
+    fragmentation_percent, DatabaseEngine, DatabaseStatsConfig, DatabaseStatsResponse,
+    DatabaseTableStats, ManagedDatabase,
+};
+use crate::services::PostgresService;
+
+/// Ukuran per schema MySQL (hanya schema yang punya tabel)
+const MYSQL_SCHEMA_SIZES_QUERY: &str = r#"
+    SELECT CAST(TABLE_SCHEMA AS CHAR),
+           CAST(COALESCE(SUM(DATA_LENGTH + INDEX_LENGTH), 0) AS SIGNED)
+    FROM information_schema.TABLES
+    GROUP BY TABLE_SCHEMA
+"#;
+
+/// Statistik tabel satu schema MySQL
+const MYSQL_TABLE_STATS_QUERY: &str = r#"
+    SELECT CAST(TABLE_NAME AS CHAR),
+           CAST(ENGINE AS CHAR),
+           CAST(COALESCE(TABLE_ROWS, 0) AS SIGNED),
+           CAST(COALESCE(DATA_LENGTH, 0) AS SIGNED),
+           CAST(COALESCE(INDEX_LENGTH, 0) AS SIGNED),
+           CAST(COALESCE(DATA_FREE, 0) AS SIGNED)
+    FROM information_schema.TABLES
+    WHERE TABLE_SCHEMA = ? AND TABLE_TYPE = 'BASE TABLE'
+"#;
+
+/// Service statistik managed database
+pub struct DatabaseStatsService;
+
+impl DatabaseStatsService {
+    /// Fairing yang menjalankan collector ukuran database setelah Rocket liftoff
+    ///
+    /// Collector dimatikan dengan `DB_STATS_ENABLED=false`.
+    pub fn fairing() -> AdHoc {
+        AdHoc::on_liftoff("Database Stats Collector", |rocket| {
+            Box::pin(async move {
+                let config = DatabaseStatsConfig::default();
+                if !config.enabled {
+                    return;
+                }
+
+                let Some(db) = rocket.state::<Database>() else {
+                    tracing::error!(
+                        "Database stats collector not started: database is not available"
+                    );
+                    return;
+                };
+
+                let pool = db.get_pool().clone();
+                tokio::spawn(async move {
+                    let mut interval =
+                        tokio::time::interval(Duration::from_secs(config.interval_secs));
+                    loop {
+                        interval.tick().await;
+                        match Self::collect_all(&pool).await {
+                            Ok(updated) => {
+                                tracing::debug!("Database sizes updated for {} databases", updated)
+                            }
+                            Err(e) => tracing::warn!("Database stats collection failed: {}", e),
+                        }
+                    }
+                });
+            })
+        })
+    }
+
+    /// Perbarui ukuran semua managed database, mengembalikan jumlah yang diperbarui
+    pub async fn collect_all(pool: &MySqlPool) -> ApiResult<usize> {
+        let databases = sqlx::query_as::<_, ManagedDatabase>("SELECT * FROM managed_databases")
+            .fetch_all(pool)
+            .await?;
+
+        if databases.is_empty() {
+            return Ok(0);
+        }
+
+        let mysql_sizes: HashMap<String, i64> =
+            sqlx::query_as::<_, (String, i64)>(MYSQL_SCHEMA_SIZES_QUERY)
+                .fetch_all(pool)
+                .await?
+                .into_iter()
+                .collect();
+
+        let mut updated = 0;
+        for db in &databases {
+            let size = match DatabaseEngine::from_column(&db.engine) {
+                // Database tanpa tabel tidak muncul di information_schema.TABLES
+                DatabaseEngine::Mysql => mysql_sizes.get(&db.db_name).copied().unwrap_or(0),
+                DatabaseEngine::Postgresql => {
+                    match PostgresService::database_size(&db.db_name).await {
+                        Ok(size) => size,
+                        Err(e) => {
+                            tracing::warn!(
+                                "PostgreSQL size query failed for {}: {}",
+                                db.db_name,
+                                e
+                            );
+                            continue;
+                        }
+                    }
+                }
+            };
+
+            Self::store_size(pool, &db.id, size).await?;
+            updated += 1;
+        }
+
+        Ok(updated)
+    }
+
+    /// Statistik per tabel satu database (sekaligus memperbarui ukuran tersimpan)
+    pub async fn get_stats(
+        pool: &MySqlPool,
+        database_id: &str,
+        user_id: &str,
+    ) -> ApiResult<DatabaseStatsResponse> {
+        let db =
+            sqlx::query_as::<_, ManagedDatabase>("SELECT * FROM managed_databases WHERE id = ?")
+                .bind(database_id)
+                .fetch_optional(pool)
+                .await?
+                .ok_or(ApiError::NotFound("Database".to_string()))?;
+
+        if db.user_id != user_id {
+            return Err(ApiError::Forbidden);
+        }
+
+        let (tables, size) = match DatabaseEngine::from_column(&db.engine) {
+            DatabaseEngine::Mysql => {
+                let tables = Self::mysql_table_stats(pool, &db.db_name).await?;
+                let size = tables.iter().map(|t| t.data_bytes + t.index_bytes).sum();
+                (tables, size)
+            }
+            DatabaseEngine::Postgresql => (
+                PostgresService::table_stats(&db.db_name).await?,
+                PostgresService::database_size(&db.db_name).await?,
+            ),
+        };
+
+        Self::store_size(pool, &db.id, size).await?;
+
+        Ok(DatabaseStatsResponse::new(&db, tables))
+    }
+
+    /// Statistik tabel MySQL dari information_schema
+    async fn mysql_table_stats(
+        pool: &MySqlPool,
+        db_name: &str,
+    ) -> ApiResult<Vec<DatabaseTableStats>> {
+        let rows = sqlx::query_as::<_, (String, Option<String>, i64, i64, i64, i64)>(
+            MYSQL_TABLE_STATS_QUERY,
+        )
+        .bind(db_name)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(name, engine, rows_estimate, data_bytes, index_bytes, free_bytes)| {
+                    DatabaseTableStats {
+                        name,
+                        engine,
+                        rows_estimate,
+                        data_bytes,
+                        index_bytes,
+                        free_bytes,
+                        fragmentation_percent: fragmentation_percent(
+                            free_bytes,
+                            data_bytes + index_bytes + free_bytes,
+                        ),
+                    }
+                },
+            )
+            .collect())
+    }
+
+    async fn store_size(pool: &MySqlPool, database_id: &str, size: i64) -> ApiResult<()> {
+        sqlx::query(
+            "UPDATE managed_databases SET size_bytes = ?, size_updated_at = NOW() WHERE id = ?",
+        )
+        .bind(size)
+        .bind(database_id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
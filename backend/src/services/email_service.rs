@@ -14,6 +14,7 @@ use crate::models::{
     CreateEmailForwarderRequest, Domain, EmailAccount, EmailAccountResponse, EmailForwarder,
     EmailForwarderResponse, UpdateAutoresponderRequest, UpdateEmailAccountRequest,
 };
+use crate::services::SpamFilterService;
 use crate::utils::password;
 
 /// Service untuk email operations
@@ -130,6 +131,23 @@ impl EmailService {
 
         tracing::info!("Email account created: {} for user {}", email_address, user_id);
 
+        // Render filter spam sesuai kebijakan domain
+        let account = sqlx::query_as::<_, EmailAccount>(
+            "SELECT * FROM email_accounts WHERE id = ?",
+        )
+        .bind(&account_id)
+        .fetch_one(pool)
+        .await?;
+
+        // Akun sudah tersimpan; script Sieve bisa di-render ulang nanti
+        if let Err(e) = SpamFilterService::sync_account(pool, &account).await {
+            tracing::warn!(
+                "Failed to sync spam filter for {}: {}",
+                account.email_address,
+                e
+            );
+        }
+
         Self::get_account_by_id(pool, &account_id, user_id).await
     }
 
@@ -160,7 +178,7 @@ impl EmailService {
         let password_hash = if let Some(ref new_password) = request.password {
             password::hash_password(new_password)?
         } else {
-            account.password_hash
+            account.password_hash.clone()
         };
 
         let quota_bytes = request
@@ -170,18 +188,42 @@ impl EmailService {
 
         let is_active = request.is_active.unwrap_or(account.is_active);
 
+        let send_limit_hourly = request.send_limit_hourly.or(account.send_limit_hourly);
+        let send_limit_daily = request.send_limit_daily.or(account.send_limit_daily);
+
         // Update account
         sqlx::query(
-            "UPDATE email_accounts SET password_hash = ?, quota_bytes = ?, is_active = ?, updated_at = ? WHERE id = ?",
+            "UPDATE email_accounts SET password_hash = ?, quota_bytes = ?, is_active = ?, send_limit_hourly = ?, send_limit_daily = ?, updated_at = ? WHERE id = ?",
         )
         .bind(&password_hash)
         .bind(quota_bytes)
         .bind(is_active)
+        .bind(send_limit_hourly)
+        .bind(send_limit_daily)
         .bind(Utc::now())
         .bind(account_id)
         .execute(pool)
         .await?;
 
+        // Update override spam dan render ulang script Sieve
+        if let Some(ref spam_policy) = request.spam_policy {
+            SpamFilterService::save_account_override(pool, &account, spam_policy).await?;
+
+            let updated = sqlx::query_as::<_, EmailAccount>(
+                "SELECT * FROM email_accounts WHERE id = ?",
+            )
+            .bind(account_id)
+            .fetch_one(pool)
+            .await?;
+            if let Err(e) = SpamFilterService::sync_account(pool, &updated).await {
+                tracing::warn!(
+                    "Failed to sync spam filter for {}: {}",
+                    updated.email_address,
+                    e
+                );
+            }
+        }
+
         tracing::info!("Email account updated: {}", account.email_address);
 
         Self::get_account_by_id(pool, account_id, user_id).await
@@ -217,6 +259,8 @@ impl EmailService {
             .execute(pool)
             .await?;
 
+        SpamFilterService::remove_account_script(&account.email_address);
+
         tracing::info!("Email account deleted: {}", account.email_address);
 
         Ok(())
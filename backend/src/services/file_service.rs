@@ -4,23 +4,60 @@
 //! WARNING: File operations harus selalu di-sandbox ke user home directory!
 
 use chrono::{TimeZone, Utc};
+use multer::{Field, Multipart};
 use std::fs::{self, Metadata};
 use std::io::{Read, Write, Seek};
 use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncSeekExt, AsyncWriteExt};
+use tokio::process::ChildStdout;
+use uuid::Uuid;
 use validator::Validate;
 use walkdir::WalkDir;
-use zip::write::SimpleFileOptions;
-use zip::{ZipArchive, ZipWriter};
 
 use crate::config::CONFIG;
 use crate::errors::{ApiError, ApiResult};
 use crate::models::{
-    permissions_to_octal, permissions_to_string,
-    CompressRequest, CopyRequest, CreateFileRequest, DeleteRequest, ExtractRequest,
-    FileContentResponse, FileInfo, FileListResponse, FileType, MoveRequest, RenameRequest,
-    WriteFileRequest, DANGEROUS_EXTENSIONS, TEXT_EXTENSIONS,
+    permissions_to_octal, permissions_to_string, ArchiveConfig, ArchiveFormat, ByteRange,
+    ChangeOwnerRequest, ChangePermissionsRequest, CompressRequest, CopyRequest,
+    CreateFileRequest, DeleteRequest, DiskQuota, ExtractRequest, FileContentResponse, FileInfo,
+    FileListResponse, FileMode, FileType, JobKind, MoveRequest, RenameRequest, SearchConfig,
+    SearchMode, SearchRequest, SearchResponse, SearchResult, TrashItem, WriteFileRequest,
+    DANGEROUS_EXTENSIONS, INTERNAL_DIRS, MAX_UPLOAD_FILES, THUMBNAIL_DEFAULT_SIZE,
+    THUMBNAIL_MAX_SIZE, THUMBNAIL_MAX_SOURCE_SIZE,
 };
-use crate::utils::system::ensure_directory;
+use crate::services::{
+    ArchiveService, JobProgress, JobTask, ProgressReader, QuotaService, TrashService,
+    VersionService,
+};
+use crate::utils::mime;
+use crate::utils::search::{self, GlobFilter};
+use crate::utils::system::{change_ownership, ensure_directory, group_name, user_name};
+
+/// File yang akan dikirim sebagai download
+pub struct FileDownload {
+    /// Nama file untuk Content-Disposition
+    pub filename: String,
+    /// Isi download
+    pub body: FileDownloadBody,
+}
+
+/// Isi download: file biasa (opsional sebagian) atau directory sebagai zip
+pub enum FileDownloadBody {
+    /// File yang sudah di-seek ke awal range
+    File {
+        file: tokio::fs::File,
+        /// Jumlah bytes yang dikirim
+        length: u64,
+        /// Ukuran file
+        total: u64,
+        /// Range yang diminta (response 206)
+        range: Option<ByteRange>,
+    },
+    /// Output `zip` untuk directory
+    Archive(ChildStdout),
+}
 
 /// Service untuk file operations
 pub struct FileService;
@@ -30,7 +67,7 @@ impl FileService {
     ///
     /// Path ini digunakan sebagai sandbox - user tidak bisa keluar dari direktori ini.
     /// Untuk development, menggunakan /tmp/nusa-panel-users untuk menghindari permission issues.
-    fn get_user_base_path(username: &str) -> PathBuf {
+    pub fn get_user_base_path(username: &str) -> PathBuf {
         // Development: gunakan /tmp yang pasti writable
         // Production: gunakan CONFIG.file.user_home_base
         // #[cfg(debug_assertions)]
@@ -55,7 +92,25 @@ impl FileService {
     ///
     /// # Returns
     /// Absolute path yang sudah divalidasi
-    fn resolve_path(username: &str, relative_path: &str) -> ApiResult<PathBuf> {
+    pub fn resolve_path(username: &str, relative_path: &str) -> ApiResult<PathBuf> {
+        let full_path = Self::resolve_internal_path(username, relative_path)?;
+
+        // Area internal hanya boleh diubah lewat service masing-masing
+        let base_path = Self::get_user_base_path(username);
+        if Self::is_internal_dir(&base_path, &full_path) {
+            tracing::warn!(
+                "Internal directory access attempt for user {}: {}",
+                username,
+                relative_path
+            );
+            return Err(ApiError::FilePermissionDenied);
+        }
+
+        Ok(full_path)
+    }
+
+    /// Resolve path seperti `resolve_path`, termasuk area internal (`INTERNAL_DIRS`)
+    pub fn resolve_internal_path(username: &str, relative_path: &str) -> ApiResult<PathBuf> {
         let base_path = Self::get_user_base_path(username);
 
         tracing::debug!("Resolving path for user {}: base_path={:?}, relative_path={}", username, base_path, relative_path);
@@ -139,6 +194,21 @@ impl FileService {
         }
     }
 
+    /// Check apakah path (setelah symlink di-resolve) berada di area internal
+    fn is_internal_dir(base_path: &Path, path: &Path) -> bool {
+        let resolved = path.canonicalize().unwrap_or_else(|_| {
+            match (path.parent().and_then(|p| p.canonicalize().ok()), path.file_name()) {
+                (Some(parent), Some(name)) => parent.join(name),
+                _ => path.to_path_buf(),
+            }
+        });
+        let canonical_base = base_path.canonicalize().unwrap_or(base_path.to_path_buf());
+
+        INTERNAL_DIRS.iter().any(|dir| {
+            resolved.starts_with(canonical_base.join(dir)) || resolved.starts_with(base_path.join(dir))
+        })
+    }
+
     /// Check apakah ekstensi file diizinkan
     fn is_extension_allowed(filename: &str) -> bool {
         let extension = Path::new(filename)
@@ -152,21 +222,18 @@ impl FileService {
         }
     }
 
-    /// Check apakah file adalah text file
-    fn is_text_file(filename: &str) -> bool {
-        let extension = Path::new(filename)
-            .extension()
-            .and_then(|e| e.to_str())
-            .map(|e| e.to_lowercase());
-
-        match extension {
-            Some(ext) => TEXT_EXTENSIONS.contains(&ext.as_str()),
-            None => true, // Files tanpa extension dianggap text
-        }
+    /// Dimensi gambar dari header file (tanpa decode seluruh gambar)
+    fn image_dimensions(path: &Path) -> Option<(u32, u32)> {
+        image::ImageReader::open(path)
+            .ok()?
+            .with_guessed_format()
+            .ok()?
+            .into_dimensions()
+            .ok()
     }
 
     /// Build FileInfo dari path dan metadata
-    fn build_file_info(path: &Path, metadata: &Metadata, base_path: &Path) -> FileInfo {
+    pub fn build_file_info(path: &Path, metadata: &Metadata, base_path: &Path) -> FileInfo {
         let name = path
             .file_name()
             .and_then(|n| n.to_str())
@@ -194,21 +261,27 @@ impl FileService {
             None
         };
 
-        // Get permissions (platform specific)
+        // Get permissions and ownership (platform specific)
         #[cfg(unix)]
-        let (permissions, permissions_octal_str) = {
-            use std::os::unix::fs::PermissionsExt;
+        let (permissions, permissions_octal_str, owner, group) = {
+            use std::os::unix::fs::{MetadataExt, PermissionsExt};
             let mode = metadata.permissions().mode();
-            (permissions_to_string(mode), permissions_to_octal(mode))
+            (
+                permissions_to_string(mode),
+                permissions_to_octal(mode),
+                user_name(metadata.uid()),
+                group_name(metadata.gid()),
+            )
         };
 
         #[cfg(windows)]
-        let (permissions, permissions_octal_str) = {
-            if metadata.permissions().readonly() {
+        let (permissions, permissions_octal_str, owner, group) = {
+            let (permissions, octal) = if metadata.permissions().readonly() {
                 ("r--r--r--".to_string(), "444".to_string())
             } else {
                 ("rw-rw-rw-".to_string(), "666".to_string())
-            }
+            };
+            (permissions, octal, "user".to_string(), "user".to_string())
         };
 
         // Get modification time
@@ -231,6 +304,17 @@ impl FileService {
             .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
             .map(|d| Utc.timestamp_opt(d.as_secs() as i64, 0).unwrap());
 
+        // Sniff konten untuk MIME type dan dimensi gambar
+        let sniffed = if file_type == FileType::File {
+            mime::detect_file(path)
+        } else {
+            None
+        };
+        let dimensions = sniffed
+            .as_ref()
+            .filter(|s| s.mime_type.starts_with("image/"))
+            .and_then(|_| Self::image_dimensions(path));
+
         FileInfo {
             name: name.clone(),
             path: relative_path,
@@ -238,13 +322,16 @@ impl FileService {
             size: metadata.len(),
             permissions,
             permissions_octal: permissions_octal_str,
-            owner: "user".to_string(), // Simplified
-            group: "user".to_string(), // Simplified
+            owner,
+            group,
             modified_at,
             accessed_at,
             created_at,
             extension,
-            mime_type: None, // TODO: Implement MIME type detection
+            is_text: sniffed.as_ref().is_some_and(|s| s.is_text),
+            mime_type: sniffed.map(|s| s.mime_type),
+            image_width: dimensions.map(|(width, _)| width),
+            image_height: dimensions.map(|(_, height)| height),
             is_hidden: name.starts_with('.'),
         }
     }
@@ -342,10 +429,17 @@ impl FileService {
     }
 
     /// Create file or directory
-    pub async fn create(user_id: &str, request: CreateFileRequest) -> ApiResult<FileInfo> {
+    ///
+    /// Ditolak jika pemakaian disk user sudah melewati kuota.
+    pub async fn create(
+        user_id: &str,
+        request: CreateFileRequest,
+        quota: &DiskQuota,
+    ) -> ApiResult<FileInfo> {
         request
             .validate()
             .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+        quota.ensure_available(0)?;
 
         // Check dangerous extensions
         if !Self::is_extension_allowed(&request.name) {
@@ -441,20 +535,16 @@ impl FileService {
             .unwrap_or("")
             .to_string();
 
-        // Check if text file or binary
-        let (content_string, encoding) = if Self::is_text_file(&name) {
-            match String::from_utf8(content.clone()) {
-                Ok(s) => (s, "utf-8".to_string()),
-                Err(_) => {
-                    // Fallback to base64 for binary content
-                    use base64::{engine::general_purpose::STANDARD, Engine};
-                    (STANDARD.encode(&content), "base64".to_string())
-                }
+        // Text/binary ditentukan dari konten, bukan hanya ekstensi
+        let sniffed = mime::detect(&name, &content[..content.len().min(mime::SNIFF_LENGTH)]);
+        let (content_string, encoding) = match String::from_utf8(content) {
+            Ok(s) if sniffed.is_text && !s.contains('\0') => (s, "utf-8".to_string()),
+            result => {
+                // Binary file - return as base64
+                use base64::{engine::general_purpose::STANDARD, Engine};
+                let bytes = result.map(String::into_bytes).unwrap_or_else(|e| e.into_bytes());
+                (STANDARD.encode(&bytes), "base64".to_string())
             }
-        } else {
-            // Binary file - return as base64
-            use base64::{engine::general_purpose::STANDARD, Engine};
-            (STANDARD.encode(&content), "base64".to_string())
         };
 
         Ok(FileContentResponse {
@@ -463,12 +553,16 @@ impl FileService {
             content: content_string,
             encoding,
             size: metadata.len(),
-            mime_type: None, // TODO: Detect MIME type
+            mime_type: Some(sniffed.mime_type),
         })
     }
 
     /// Write file content
-    pub async fn write_file(user_id: &str, request: WriteFileRequest) -> ApiResult<FileInfo> {
+    pub async fn write_file(
+        user_id: &str,
+        request: WriteFileRequest,
+        quota: &DiskQuota,
+    ) -> ApiResult<FileInfo> {
         request
             .validate()
             .map_err(|e| ApiError::ValidationError(e.to_string()))?;
@@ -506,6 +600,15 @@ impl FileService {
             return Err(ApiError::FileTooLarge(CONFIG.file.max_upload_size));
         }
 
+        // Hanya pertambahan ukuran yang dihitung ke kuota
+        let existing_size = fs::metadata(&full_path).map(|m| m.len()).unwrap_or(0);
+        quota.ensure_available((content_bytes.len() as u64).saturating_sub(existing_size))?;
+
+        // Simpan konten lama sebagai revisi; kegagalan tidak membatalkan save
+        if let Err(e) = VersionService::snapshot(user_id, &full_path) {
+            tracing::warn!("Failed to save revision of {}: {}", request.path, e);
+        }
+
         // Write file
         let mut file = fs::File::create(&full_path).map_err(|e| {
             tracing::error!("Failed to create file: {}", e);
@@ -516,6 +619,7 @@ impl FileService {
             tracing::error!("Failed to write file: {}", e);
             ApiError::InternalError("Failed to write file".to_string())
         })?;
+        QuotaService::invalidate(user_id);
 
         let metadata = fs::metadata(&full_path).map_err(|_| {
             ApiError::InternalError("Failed to get file metadata".to_string())
@@ -526,8 +630,304 @@ impl FileService {
         Ok(Self::build_file_info(&full_path, &metadata, &base_path))
     }
 
+    // ==========================================
+    // UPLOAD & DOWNLOAD
+    // ==========================================
+
+    /// Check apakah file boleh di-upload
+    ///
+    /// Selain `DANGEROUS_EXTENSIONS`, ekstensi di `CONFIG.file.forbidden_extensions` ditolak.
+    pub fn is_upload_allowed(filename: &str) -> bool {
+        let extension = Path::new(filename)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase());
+
+        Self::is_extension_allowed(filename)
+            && !extension.is_some_and(|ext| {
+                CONFIG
+                    .file
+                    .forbidden_extensions
+                    .iter()
+                    .any(|forbidden| forbidden.eq_ignore_ascii_case(&ext))
+            })
+    }
+
+    /// Ambil nama file dari filename multipart (browser bisa mengirim path lengkap)
+    pub fn sanitize_upload_name(filename: &str) -> ApiResult<String> {
+        let name = filename
+            .rsplit(['/', '\\'])
+            .next()
+            .unwrap_or("")
+            .trim();
+
+        if name.is_empty()
+            || name == "."
+            || name == ".."
+            || name.len() > 255
+            || name.chars().any(|c| c.is_control())
+        {
+            return Err(ApiError::ValidationError(format!(
+                "Nama file tidak valid: {}",
+                filename
+            )));
+        }
+
+        Ok(name.to_string())
+    }
+
+    /// Upload file via multipart (streaming)
+    ///
+    /// Setiap part yang memiliki filename ditulis ke file sementara di direktori
+    /// tujuan lalu di-rename setelah lengkap, sehingga file tujuan tidak pernah
+    /// terlihat setengah tertulis. Part tanpa filename diabaikan. Jika satu file
+    /// gagal, file sebelumnya yang sudah selesai tetap tersimpan.
+    ///
+    /// # Arguments
+    /// * `user_id` - ID user
+    /// * `directory` - Direktori tujuan (relatif dari user home)
+    /// * `overwrite` - Timpa file yang sudah ada
+    /// * `quota` - Kuota disk user (total semua file dicek)
+    /// * `multipart` - Body request multipart/form-data
+    ///
+    /// # Returns
+    /// FileInfo dari file yang berhasil di-upload
+    pub async fn upload(
+        user_id: &str,
+        directory: &str,
+        overwrite: bool,
+        quota: &DiskQuota,
+        mut multipart: Multipart<'_>,
+    ) -> ApiResult<Vec<FileInfo>> {
+        let base_path = Self::get_user_base_path(user_id);
+        let target_dir = Self::resolve_path(user_id, directory)?;
+
+        if !target_dir.is_dir() {
+            return Err(ApiError::FileNotFound(directory.to_string()));
+        }
+
+        let max_size = CONFIG.file.max_upload_size;
+        let mut uploaded = Vec::new();
+        let mut quota = quota.clone();
+
+        while let Some(field) = multipart.next_field().await.map_err(Self::multipart_error)? {
+            let Some(file_name) = field.file_name().map(|n| n.to_string()) else {
+                continue;
+            };
+
+            if uploaded.len() as u64 >= MAX_UPLOAD_FILES {
+                return Err(ApiError::ValidationError(format!(
+                    "Maksimal {} file per upload",
+                    MAX_UPLOAD_FILES
+                )));
+            }
+
+            let file_name = Self::sanitize_upload_name(&file_name)?;
+            if !Self::is_upload_allowed(&file_name) {
+                return Err(ApiError::FileTypeNotAllowed(file_name));
+            }
+
+            let target = target_dir.join(&file_name);
+            if target.is_dir() || (target.exists() && !overwrite) {
+                return Err(ApiError::AlreadyExists(file_name));
+            }
+
+            let temp_path = target_dir.join(format!(".{}.upload-{}", file_name, Uuid::new_v4()));
+            let result = match Self::write_upload(field, &temp_path, max_size, &quota).await {
+                Ok(written) => {
+                    quota.used_bytes += written;
+                    QuotaService::invalidate(user_id);
+                    tokio::fs::rename(&temp_path, &target).await.map_err(|e| {
+                        tracing::error!("Failed to move uploaded file: {}", e);
+                        ApiError::FilePermissionDenied
+                    })
+                }
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                let _ = tokio::fs::remove_file(&temp_path).await;
+                return Err(e);
+            }
+
+            let metadata = fs::metadata(&target).map_err(|_| {
+                ApiError::InternalError("Failed to get file metadata".to_string())
+            })?;
+            uploaded.push(Self::build_file_info(&target, &metadata, &base_path));
+        }
+
+        if uploaded.is_empty() {
+            return Err(ApiError::ValidationError(
+                "Tidak ada file yang di-upload".to_string(),
+            ));
+        }
+
+        tracing::info!(
+            "{} file(s) uploaded to {} by user {}",
+            uploaded.len(),
+            directory,
+            user_id
+        );
+
+        Ok(uploaded)
+    }
+
+    /// Tulis satu part multipart ke file (dibatasi `max_size` bytes dan kuota)
+    ///
+    /// Mengembalikan jumlah bytes yang ditulis.
+    async fn write_upload(
+        mut field: Field<'_>,
+        path: &Path,
+        max_size: u64,
+        quota: &DiskQuota,
+    ) -> ApiResult<u64> {
+        let mut file = tokio::fs::File::create(path).await.map_err(|e| {
+            tracing::error!("Failed to create upload file: {}", e);
+            ApiError::FilePermissionDenied
+        })?;
+
+        let mut written: u64 = 0;
+        while let Some(chunk) = field.chunk().await.map_err(Self::multipart_error)? {
+            written += chunk.len() as u64;
+            if written > max_size {
+                return Err(ApiError::FileTooLarge(max_size));
+            }
+            quota.ensure_available(written)?;
+
+            file.write_all(&chunk).await.map_err(|e| {
+                tracing::error!("Failed to write upload file: {}", e);
+                ApiError::InternalError("Failed to write file".to_string())
+            })?;
+        }
+
+        file.sync_all().await.map_err(|e| {
+            tracing::error!("Failed to flush upload file: {}", e);
+            ApiError::InternalError("Failed to write file".to_string())
+        })?;
+
+        Ok(written)
+    }
+
+    fn multipart_error(e: multer::Error) -> ApiError {
+        ApiError::ValidationError(format!("Upload multipart tidak valid: {}", e))
+    }
+
+    /// Download file atau directory (sebagai zip)
+    ///
+    /// # Arguments
+    /// * `user_id` - ID user
+    /// * `path` - Path file/directory
+    /// * `range` - Header HTTP `Range` (hanya untuk file)
+    pub async fn download(
+        user_id: &str,
+        path: &str,
+        range: Option<&str>,
+    ) -> ApiResult<FileDownload> {
+        let base_path = Self::get_user_base_path(user_id);
+        let full_path = Self::resolve_path(user_id, path)?;
+
+        if !full_path.exists() {
+            return Err(ApiError::FileNotFound(path.to_string()));
+        }
+
+        let name = full_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("download")
+            .to_string();
+
+        if full_path.is_dir() {
+            return Self::download_directory(&full_path, &base_path, name).await;
+        }
+
+        let mut file = tokio::fs::File::open(&full_path)
+            .await
+            .map_err(|_| ApiError::FilePermissionDenied)?;
+        let total = file
+            .metadata()
+            .await
+            .map_err(|_| ApiError::InternalError("Failed to get file metadata".to_string()))?
+            .len();
+
+        let range = match range {
+            Some(header) => ByteRange::parse(header, total)?,
+            None => None,
+        };
+
+        let length = match range {
+            Some(range) => {
+                file.seek(std::io::SeekFrom::Start(range.start))
+                    .await
+                    .map_err(|e| ApiError::InternalError(format!("Seek error: {}", e)))?;
+                range.length()
+            }
+            None => total,
+        };
+
+        tracing::info!("File downloaded: {} by user {}", path, user_id);
+
+        Ok(FileDownload {
+            filename: name,
+            body: FileDownloadBody::File {
+                file,
+                length,
+                total,
+                range,
+            },
+        })
+    }
+
+    /// Stream directory sebagai zip (`zip -y` menyimpan symlink apa adanya)
+    async fn download_directory(
+        full_path: &Path,
+        base_path: &Path,
+        name: String,
+    ) -> ApiResult<FileDownload> {
+        // Home directory di-zip tanpa folder induk
+        let (work_dir, entry) = match full_path.parent() {
+            Some(parent) if full_path != base_path => (parent.to_path_buf(), name.clone()),
+            _ => (full_path.to_path_buf(), ".".to_string()),
+        };
+
+        let mut zip = tokio::process::Command::new("zip")
+            .args(["-q", "-r", "-y", "-", "--", &entry])
+            .current_dir(&work_dir)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|e| ApiError::InternalError(format!("Failed to execute zip: {}", e)))?;
+
+        let stream = zip
+            .stdout
+            .take()
+            .ok_or_else(|| ApiError::InternalError("Zip stdout unavailable".to_string()))?;
+
+        // Response sudah terkirim saat zip gagal, jadi error hanya bisa di-log
+        let archive = name.clone();
+        tokio::spawn(async move {
+            match zip.wait_with_output().await {
+                Ok(output) if !output.status.success() => tracing::error!(
+                    "Zip failed for {}: {}",
+                    archive,
+                    String::from_utf8_lossy(&output.stderr).trim()
+                ),
+                Err(e) => tracing::error!("Zip wait error for {}: {}", archive, e),
+                _ => {}
+            }
+        });
+
+        Ok(FileDownload {
+            filename: format!("{}.zip", name),
+            body: FileDownloadBody::Archive(stream),
+        })
+    }
+
     /// Delete file or directory
-    pub async fn delete(user_id: &str, request: DeleteRequest) -> ApiResult<()> {
+    ///
+    /// Default-nya item dipindahkan ke trash dan dikembalikan sebagai
+    /// `TrashItem`; dengan `permanent=true` (atau untuk item di dalam trash)
+    /// item dihapus permanen dan hasilnya `None`.
+    pub async fn delete(user_id: &str, request: DeleteRequest) -> ApiResult<Option<TrashItem>> {
         request
             .validate()
             .map_err(|e| ApiError::ValidationError(e.to_string()))?;
@@ -546,6 +946,23 @@ impl FileService {
             ));
         }
 
+        let permanent = request.permanent.unwrap_or(false);
+
+        if !permanent {
+            // Directory tidak kosong tetap butuh recursive=true
+            if full_path.is_dir()
+                && !request.recursive.unwrap_or(false)
+                && fs::read_dir(&full_path).is_ok_and(|mut entries| entries.next().is_some())
+            {
+                return Err(ApiError::ValidationError(
+                    "Directory tidak kosong. Gunakan recursive=true".to_string(),
+                ));
+            }
+
+            let item = TrashService::move_to_trash(user_id, &full_path)?;
+            return Ok(Some(item));
+        }
+
         if full_path.is_dir() {
             if request.recursive.unwrap_or(false) {
                 fs::remove_dir_all(&full_path).map_err(|e| {
@@ -569,7 +986,7 @@ impl FileService {
 
         tracing::info!("File/directory deleted: {} by user {}", request.path, user_id);
 
-        Ok(())
+        Ok(None)
     }
 
     /// Rename file or directory
@@ -622,7 +1039,18 @@ impl FileService {
     }
 
     /// Copy file or directory
-    pub async fn copy(user_id: &str, request: CopyRequest) -> ApiResult<FileInfo> {
+    ///
+    /// Request divalidasi langsung; penyalinan dijalankan sebagai background
+    /// job. Symlink di dalam directory disalin sebagai symlink (tidak diikuti).
+    /// Jika job gagal atau dibatalkan, destination yang baru dibuat dihapus.
+    ///
+    /// # Returns
+    /// Task yang menghasilkan FileInfo dari destination
+    pub async fn copy(
+        user_id: &str,
+        request: CopyRequest,
+        quota: DiskQuota,
+    ) -> ApiResult<JobTask> {
         request
             .validate()
             .map_err(|e| ApiError::ValidationError(e.to_string()))?;
@@ -640,56 +1068,135 @@ impl FileService {
             return Err(ApiError::AlreadyExists("Destination".to_string()));
         }
 
-        if source_path.is_dir() {
-            // Copy directory recursively
-            Self::copy_dir_recursive(&source_path, &dest_path)?;
-        } else {
-            // Copy file
-            fs::copy(&source_path, &dest_path).map_err(|e| {
-                tracing::error!("Failed to copy file: {}", e);
-                ApiError::FilePermissionDenied
-            })?;
+        if source_path.is_dir() && dest_path.starts_with(&source_path) {
+            return Err(ApiError::ValidationError(
+                "Tidak bisa menyalin directory ke dalam dirinya sendiri".to_string(),
+            ));
         }
 
-        let metadata = fs::metadata(&dest_path).map_err(|_| {
-            ApiError::InternalError("Failed to get file metadata".to_string())
-        })?;
+        let user_id = user_id.to_string();
+        let description = format!("Copy {} ke {}", request.source, request.destination);
 
-        tracing::info!(
-            "File copied: {} -> {} by user {}",
-            request.source,
-            request.destination,
-            user_id
-        );
+        Ok(JobTask::blocking(JobKind::Copy, description, move |progress| {
+            let total = Self::tree_size(&source_path);
+            quota.ensure_available(total)?;
+            progress.set_total(total);
+            let dest_existed = dest_path.exists();
 
-        Ok(Self::build_file_info(&dest_path, &metadata, &base_path))
+            let result = if source_path.is_dir() {
+                Self::copy_dir_recursive(&source_path, &dest_path, progress)
+            } else {
+                Self::copy_file(&source_path, &dest_path, progress)
+            };
+            QuotaService::invalidate(&user_id);
+
+            if let Err(e) = result {
+                if !dest_existed {
+                    let _ = if dest_path.is_dir() {
+                        fs::remove_dir_all(&dest_path)
+                    } else {
+                        fs::remove_file(&dest_path)
+                    };
+                }
+                return Err(e);
+            }
+
+            let metadata = fs::metadata(&dest_path).map_err(|_| {
+                ApiError::InternalError("Failed to get file metadata".to_string())
+            })?;
+
+            tracing::info!(
+                "File copied: {} -> {} by user {}",
+                request.source,
+                request.destination,
+                user_id
+            );
+
+            Ok(Self::build_file_info(&dest_path, &metadata, &base_path))
+        }))
+    }
+
+    /// Total ukuran file di dalam path (symlink tidak diikuti)
+    pub fn tree_size(path: &Path) -> u64 {
+        WalkDir::new(path)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|e| e.file_type().is_file())
+            .filter_map(|e| e.metadata().ok())
+            .map(|m| m.len())
+            .sum()
     }
 
     /// Helper untuk copy directory recursively
-    fn copy_dir_recursive(src: &Path, dst: &Path) -> ApiResult<()> {
+    fn copy_dir_recursive(src: &Path, dst: &Path, progress: &JobProgress) -> ApiResult<()> {
         fs::create_dir_all(dst).map_err(|e| {
             tracing::error!("Failed to create directory: {}", e);
             ApiError::FilePermissionDenied
         })?;
 
         for entry in fs::read_dir(src).map_err(|_| ApiError::FilePermissionDenied)? {
+            progress.check_cancelled()?;
+
             let entry = entry.map_err(|_| ApiError::FilePermissionDenied)?;
+            let file_type = entry.file_type().map_err(|_| ApiError::FilePermissionDenied)?;
             let src_path = entry.path();
             let dst_path = dst.join(entry.file_name());
 
-            if src_path.is_dir() {
-                Self::copy_dir_recursive(&src_path, &dst_path)?;
+            if file_type.is_symlink() {
+                Self::copy_symlink(&src_path, &dst_path)?;
+            } else if file_type.is_dir() {
+                Self::copy_dir_recursive(&src_path, &dst_path, progress)?;
             } else {
-                fs::copy(&src_path, &dst_path).map_err(|e| {
-                    tracing::error!("Failed to copy file: {}", e);
-                    ApiError::FilePermissionDenied
-                })?;
+                Self::copy_file(&src_path, &dst_path, progress)?;
             }
         }
 
         Ok(())
     }
 
+    /// Copy satu file beserta permission-nya, melaporkan progress
+    ///
+    /// Symlink yang sudah ada di destination diganti, tidak ditulis melewatinya.
+    fn copy_file(src: &Path, dst: &Path, progress: &JobProgress) -> ApiResult<()> {
+        let copy_error = |e: std::io::Error| {
+            tracing::error!("Failed to copy file: {}", e);
+            ApiError::FilePermissionDenied
+        };
+
+        if fs::symlink_metadata(dst).is_ok_and(|m| m.file_type().is_symlink()) {
+            fs::remove_file(dst).map_err(copy_error)?;
+        }
+
+        let source = fs::File::open(src).map_err(copy_error)?;
+        let permissions = source.metadata().map_err(copy_error)?.permissions();
+        let mut output = fs::File::create(dst).map_err(copy_error)?;
+
+        std::io::copy(&mut ProgressReader::new(source, progress), &mut output).map_err(|e| {
+            progress.check_cancelled().err().unwrap_or_else(|| copy_error(e))
+        })?;
+        fs::set_permissions(dst, permissions).map_err(copy_error)
+    }
+
+    /// Salin symlink sebagai symlink dengan target yang sama
+    #[cfg(unix)]
+    fn copy_symlink(src: &Path, dst: &Path) -> ApiResult<()> {
+        let target = fs::read_link(src).map_err(|_| ApiError::FilePermissionDenied)?;
+
+        if fs::symlink_metadata(dst).is_ok_and(|m| !m.is_dir()) {
+            let _ = fs::remove_file(dst);
+        }
+
+        std::os::unix::fs::symlink(&target, dst).map_err(|e| {
+            tracing::error!("Failed to copy symlink: {}", e);
+            ApiError::FilePermissionDenied
+        })
+    }
+
+    #[cfg(windows)]
+    fn copy_symlink(_src: &Path, _dst: &Path) -> ApiResult<()> {
+        Ok(())
+    }
+
     /// Move file or directory
     pub async fn move_file(user_id: &str, request: MoveRequest) -> ApiResult<FileInfo> {
         request
@@ -729,19 +1236,244 @@ impl FileService {
         Ok(Self::build_file_info(&dest_path, &metadata, &base_path))
     }
 
+    /// Buat thumbnail PNG untuk file gambar
+    ///
+    /// # Arguments
+    /// * `user_id` - ID user
+    /// * `path` - Path file gambar
+    /// * `size` - Sisi maksimum thumbnail dalam pixel (aspect ratio dipertahankan)
+    pub async fn thumbnail(user_id: &str, path: &str, size: Option<u32>) -> ApiResult<Vec<u8>> {
+        let full_path = Self::resolve_path(user_id, path)?;
+
+        if !full_path.is_file() {
+            return Err(ApiError::FileNotFound(path.to_string()));
+        }
+
+        let metadata = fs::metadata(&full_path).map_err(|_| {
+            ApiError::FileNotFound(path.to_string())
+        })?;
+
+        if metadata.len() > THUMBNAIL_MAX_SOURCE_SIZE {
+            return Err(ApiError::ValidationError(format!(
+                "Gambar terlalu besar untuk thumbnail (max {}MB)",
+                THUMBNAIL_MAX_SOURCE_SIZE / 1024 / 1024
+            )));
+        }
+
+        let size = size.unwrap_or(THUMBNAIL_DEFAULT_SIZE).clamp(16, THUMBNAIL_MAX_SIZE);
+
+        tokio::task::spawn_blocking(move || {
+            let not_image = || ApiError::ValidationError("File bukan gambar yang didukung".to_string());
+
+            let image = image::ImageReader::open(&full_path)
+                .map_err(|_| ApiError::FilePermissionDenied)?
+                .with_guessed_format()
+                .map_err(|_| not_image())?
+                .decode()
+                .map_err(|_| not_image())?;
+
+            let mut output = std::io::Cursor::new(Vec::new());
+            image
+                .thumbnail(size, size)
+                .write_to(&mut output, image::ImageFormat::Png)
+                .map_err(|e| ApiError::InternalError(format!("Failed to encode thumbnail: {}", e)))?;
+
+            Ok(output.into_inner())
+        })
+        .await
+        .map_err(|e| ApiError::InternalError(format!("Thumbnail task failed: {}", e)))?
+    }
+
+    // ==========================================
+    // PERMISSIONS & OWNERSHIP
+    // ==========================================
+
+    /// Parse mode dari request dan tolak bit yang dilarang policy
+    fn parse_mode(value: Option<&str>) -> ApiResult<Option<FileMode>> {
+        let Some(value) = value else {
+            return Ok(None);
+        };
+
+        let mode = FileMode::parse(value).ok_or_else(|| {
+            ApiError::ValidationError(format!("Format permissions tidak valid: {}", value))
+        })?;
+
+        if mode.grants_forbidden_bits() {
+            return Err(ApiError::ValidationError(
+                "Permissions setuid, setgid dan world-writable tidak diizinkan".to_string(),
+            ));
+        }
+
+        Ok(Some(mode))
+    }
+
+    /// Terapkan mode ke satu path (symlink tidak disentuh)
+    #[cfg(unix)]
+    fn apply_mode(path: &Path, mode: &FileMode) -> ApiResult<()> {
+        use std::os::unix::fs::PermissionsExt;
+
+        let metadata = fs::symlink_metadata(path).map_err(|_| ApiError::FilePermissionDenied)?;
+        if metadata.file_type().is_symlink() {
+            return Ok(());
+        }
+
+        let new_mode = mode.apply(metadata.permissions().mode(), metadata.is_dir());
+        fs::set_permissions(path, fs::Permissions::from_mode(new_mode)).map_err(|e| {
+            tracing::error!("Failed to change permissions of {:?}: {}", path, e);
+            ApiError::FilePermissionDenied
+        })
+    }
+
+    #[cfg(windows)]
+    fn apply_mode(_path: &Path, _mode: &FileMode) -> ApiResult<()> {
+        Err(ApiError::ValidationError(
+            "Permissions tidak didukung di platform ini".to_string(),
+        ))
+    }
+
+    /// Change permissions file atau directory
+    ///
+    /// Mendukung mode octal (`755`) dan simbolik (`u+x,go-w`). Untuk recursive,
+    /// `file_permissions` dan `dir_permissions` bisa dibedakan; jika kosong
+    /// keduanya memakai `permissions`. Setuid, setgid dan world-writable ditolak.
+    pub async fn change_permissions(
+        user_id: &str,
+        request: ChangePermissionsRequest,
+    ) -> ApiResult<FileInfo> {
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        let mode = Self::parse_mode(request.permissions.as_deref())?;
+        let file_mode = Self::parse_mode(request.file_permissions.as_deref())?.or(mode.clone());
+        let dir_mode = Self::parse_mode(request.dir_permissions.as_deref())?.or(mode);
+
+        if file_mode.is_none() && dir_mode.is_none() {
+            return Err(ApiError::ValidationError(
+                "permissions, file_permissions atau dir_permissions wajib diisi".to_string(),
+            ));
+        }
+
+        let base_path = Self::get_user_base_path(user_id);
+        let full_path = Self::resolve_path(user_id, &request.path)?;
+
+        if !full_path.exists() {
+            return Err(ApiError::FileNotFound(request.path.clone()));
+        }
+
+        if full_path == base_path.canonicalize().unwrap_or(base_path.clone()) {
+            return Err(ApiError::ValidationError(
+                "Tidak dapat mengubah permissions home directory".to_string(),
+            ));
+        }
+
+        let recursive = request.recursive.unwrap_or(false) && full_path.is_dir();
+        let entries: Vec<PathBuf> = if recursive {
+            WalkDir::new(&full_path)
+                .follow_links(false)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .map(|e| e.into_path())
+                .collect()
+        } else {
+            vec![full_path.clone()]
+        };
+
+        for entry in &entries {
+            let mode = if entry.is_dir() { &dir_mode } else { &file_mode };
+            if let Some(mode) = mode {
+                Self::apply_mode(entry, mode)?;
+            }
+        }
+
+        let metadata = fs::metadata(&full_path).map_err(|_| {
+            ApiError::InternalError("Failed to get file metadata".to_string())
+        })?;
+
+        tracing::info!(
+            "Permissions changed: {} ({} entries) by user {}",
+            request.path,
+            entries.len(),
+            user_id
+        );
+
+        Ok(Self::build_file_info(&full_path, &metadata, &base_path))
+    }
+
+    /// Change owner/group file atau directory
+    ///
+    /// Owner hanya boleh system user milik akun, group hanya group milik akun
+    /// atau group web server (`CONFIG.file.web_group`).
+    pub async fn change_owner(user_id: &str, request: ChangeOwnerRequest) -> ApiResult<FileInfo> {
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        let system_user = format!("user_{}", user_id);
+        let owner = request.owner.clone().unwrap_or_else(|| system_user.clone());
+        let group = request.group.clone().unwrap_or_else(|| user_id.to_string());
+
+        if owner != system_user {
+            return Err(ApiError::ValidationError(format!(
+                "Owner hanya boleh {}",
+                system_user
+            )));
+        }
+
+        let allowed_groups = [system_user.as_str(), user_id, CONFIG.file.web_group.as_str()];
+        if !allowed_groups.contains(&group.as_str()) {
+            return Err(ApiError::ValidationError(format!(
+                "Group hanya boleh salah satu dari: {}",
+                allowed_groups.join(", ")
+            )));
+        }
+
+        let base_path = Self::get_user_base_path(user_id);
+        let full_path = Self::resolve_path(user_id, &request.path)?;
+
+        if !full_path.exists() {
+            return Err(ApiError::FileNotFound(request.path.clone()));
+        }
+
+        change_ownership(
+            &full_path.to_string_lossy(),
+            &owner,
+            &group,
+            request.recursive.unwrap_or(false),
+        )?;
+
+        let metadata = fs::metadata(&full_path).map_err(|_| {
+            ApiError::InternalError("Failed to get file metadata".to_string())
+        })?;
+
+        tracing::info!(
+            "Ownership changed: {} -> {}:{} by user {}",
+            request.path,
+            owner,
+            group,
+            user_id
+        );
+
+        Ok(Self::build_file_info(&full_path, &metadata, &base_path))
+    }
+
     // ==========================================
     // COMPRESS & EXTRACT OPERATIONS
     // ==========================================
 
-    /// Compress files/directories ke zip archive
+    /// Compress files/directories ke archive
+    ///
+    /// Format diambil dari `format` atau ekstensi `archive_name` (default zip).
+    /// Request divalidasi langsung; pembuatan archive dijalankan sebagai
+    /// background job.
     ///
     /// # Arguments
     /// * `user_id` - ID user
     /// * `request` - Data files yang akan di-compress
     ///
     /// # Returns
-    /// FileInfo dari archive yang dibuat
-    pub async fn compress(user_id: &str, request: CompressRequest) -> ApiResult<FileInfo> {
+    /// Task yang menghasilkan FileInfo dari archive yang dibuat
+    pub async fn compress(user_id: &str, request: CompressRequest) -> ApiResult<JobTask> {
         request
             .validate()
             .map_err(|e| ApiError::ValidationError(e.to_string()))?;
@@ -752,18 +1484,37 @@ impl FileService {
             ));
         }
 
+        let format = match request.format.as_deref() {
+            Some(format) => ArchiveFormat::parse(format).ok_or_else(|| {
+                ApiError::ValidationError(format!("Format archive tidak didukung: {}", format))
+            })?,
+            None => ArchiveFormat::from_filename(&request.archive_name).unwrap_or(ArchiveFormat::Zip),
+        };
+
         let base_path = Self::get_user_base_path(user_id);
 
         // Determine output path
-        let archive_name = if request.archive_name.ends_with(".zip") {
-            request.archive_name.clone()
+        let archive_name = Self::sanitize_upload_name(&request.archive_name)?;
+        let archive_name = if ArchiveFormat::from_filename(&archive_name) == Some(format) {
+            archive_name
         } else {
-            format!("{}.zip", request.archive_name)
+            format!("{}{}", archive_name, format.extension())
         };
 
+        let sources = request
+            .paths
+            .iter()
+            .map(|path_str| {
+                let source_path = Self::resolve_path(user_id, path_str)?;
+                if !source_path.exists() {
+                    return Err(ApiError::FileNotFound(path_str.clone()));
+                }
+                Ok(source_path)
+            })
+            .collect::<ApiResult<Vec<_>>>()?;
+
         // Use parent of first file as output location
-        let first_path = Self::resolve_path(user_id, &request.paths[0])?;
-        let output_dir = first_path
+        let output_dir = sources[0]
             .parent()
             .unwrap_or(&base_path)
             .to_path_buf();
@@ -774,128 +1525,43 @@ impl FileService {
             return Err(ApiError::AlreadyExists(archive_name));
         }
 
-        // Create zip file
-        let zip_file = fs::File::create(&archive_path).map_err(|e| {
-            tracing::error!("Failed to create zip file: {}", e);
-            ApiError::InternalError("Gagal membuat file archive".to_string())
-        })?;
-
-        let mut zip = ZipWriter::new(zip_file);
-        let options = SimpleFileOptions::default()
-            .compression_method(zip::CompressionMethod::Deflated)
-            .unix_permissions(0o644);
+        let user_id = user_id.to_string();
+        let description = format!("Compress ke {}", archive_name);
 
-        // Add each path to archive
-        for path_str in &request.paths {
-            let source_path = Self::resolve_path(user_id, path_str)?;
+        Ok(JobTask::blocking(JobKind::Compress, description, move |progress| {
+            ArchiveService::compress(&sources, &archive_path, format, progress)?;
 
-            if !source_path.exists() {
-                return Err(ApiError::FileNotFound(path_str.clone()));
-            }
-
-            if source_path.is_file() {
-                // Add single file
-                let file_name = source_path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("file");
-
-                zip.start_file(file_name, options).map_err(|e| {
-                    tracing::error!("Failed to add file to zip: {}", e);
-                    ApiError::InternalError("Gagal menambahkan file ke archive".to_string())
-                })?;
-
-                let mut file = fs::File::open(&source_path).map_err(|_| {
-                    ApiError::FilePermissionDenied
-                })?;
-                let mut buffer = Vec::new();
-                file.read_to_end(&mut buffer).map_err(|_| {
-                    ApiError::FilePermissionDenied
-                })?;
-
-                zip.write_all(&buffer).map_err(|e| {
-                    tracing::error!("Failed to write to zip: {}", e);
-                    ApiError::InternalError("Gagal menulis ke archive".to_string())
-                })?;
-            } else if source_path.is_dir() {
-                // Add directory recursively
-                let base_name = source_path
-                    .file_name()
-                    .and_then(|n| n.to_str())
-                    .unwrap_or("folder");
-
-                for entry in WalkDir::new(&source_path) {
-                    let entry = entry.map_err(|e| {
-                        tracing::error!("Failed to walk directory: {}", e);
-                        ApiError::FilePermissionDenied
-                    })?;
-
-                    let entry_path = entry.path();
-                    let relative_path = entry_path
-                        .strip_prefix(&source_path)
-                        .map_err(|_| ApiError::FilePermissionDenied)?;
-
-                    let archive_path_name = if relative_path.as_os_str().is_empty() {
-                        base_name.to_string()
-                    } else {
-                        format!("{}/{}", base_name, relative_path.to_string_lossy())
-                    };
-
-                    if entry_path.is_dir() {
-                        zip.add_directory(&archive_path_name, options).map_err(|e| {
-                            tracing::error!("Failed to add directory to zip: {}", e);
-                            ApiError::InternalError("Gagal menambahkan folder ke archive".to_string())
-                        })?;
-                    } else {
-                        zip.start_file(&archive_path_name, options).map_err(|e| {
-                            tracing::error!("Failed to start file in zip: {}", e);
-                            ApiError::InternalError("Gagal menambahkan file ke archive".to_string())
-                        })?;
-
-                        let mut file = fs::File::open(entry_path).map_err(|_| {
-                            ApiError::FilePermissionDenied
-                        })?;
-                        let mut buffer = Vec::new();
-                        file.read_to_end(&mut buffer).map_err(|_| {
-                            ApiError::FilePermissionDenied
-                        })?;
-
-                        zip.write_all(&buffer).map_err(|e| {
-                            tracing::error!("Failed to write to zip: {}", e);
-                            ApiError::InternalError("Gagal menulis ke archive".to_string())
-                        })?;
-                    }
-                }
-            }
-        }
-
-        zip.finish().map_err(|e| {
-            tracing::error!("Failed to finish zip: {}", e);
-            ApiError::InternalError("Gagal menyelesaikan archive".to_string())
-        })?;
-
-        let metadata = fs::metadata(&archive_path).map_err(|_| {
-            ApiError::InternalError("Gagal membaca metadata archive".to_string())
-        })?;
+            let metadata = fs::metadata(&archive_path).map_err(|_| {
+                ApiError::InternalError("Gagal membaca metadata archive".to_string())
+            })?;
 
-        tracing::info!(
-            "Files compressed to {} by user {}",
-            archive_name,
-            user_id
-        );
+            tracing::info!(
+                "Files compressed to {} by user {}",
+                archive_name,
+                user_id
+            );
 
-        Ok(Self::build_file_info(&archive_path, &metadata, &base_path))
+            Ok(Self::build_file_info(&archive_path, &metadata, &base_path))
+        }))
     }
 
-    /// Extract zip archive
+    /// Extract archive (zip, tar, tar.gz, tar.bz2, tar.xz, tar.zst)
+    ///
+    /// Format dideteksi dari magic bytes dengan fallback ke ekstensi file.
+    /// Policy keamanan ekstraksi ada di [`ArchiveService`]. Ekstraksi dijalankan
+    /// sebagai background job.
     ///
     /// # Arguments
     /// * `user_id` - ID user
     /// * `request` - Data archive dan destination
     ///
     /// # Returns
-    /// FileInfo dari destination directory
-    pub async fn extract(user_id: &str, request: ExtractRequest) -> ApiResult<FileInfo> {
+    /// Task yang menghasilkan FileInfo dari destination directory
+    pub async fn extract(
+        user_id: &str,
+        request: ExtractRequest,
+        quota: DiskQuota,
+    ) -> ApiResult<JobTask> {
         request
             .validate()
             .map_err(|e| ApiError::ValidationError(e.to_string()))?;
@@ -916,162 +1582,224 @@ impl FileService {
             ));
         }
 
-        // Create destination directory if needed
-        if !dest_path.exists() {
-            fs::create_dir_all(&dest_path).map_err(|e| {
-                tracing::error!("Failed to create destination directory: {}", e);
-                ApiError::InternalError("Gagal membuat direktori tujuan".to_string())
-            })?;
-        }
-
-        // Open and extract zip
-        let zip_file = fs::File::open(&archive_path).map_err(|_| {
-            ApiError::FilePermissionDenied
-        })?;
-
-        let mut archive = ZipArchive::new(zip_file).map_err(|e| {
-            tracing::error!("Failed to open zip archive: {}", e);
-            ApiError::ValidationError("File bukan archive zip yang valid".to_string())
-        })?;
-
-        for i in 0..archive.len() {
-            let mut file = archive.by_index(i).map_err(|e| {
-                tracing::error!("Failed to read archive entry: {}", e);
-                ApiError::InternalError("Gagal membaca isi archive".to_string())
+        let format = mime::read_head(&archive_path)
+            .ok()
+            .and_then(|head| ArchiveFormat::from_magic(&head))
+            .or_else(|| ArchiveFormat::from_filename(&request.archive_path))
+            .ok_or_else(|| {
+                ApiError::ValidationError("Format archive tidak dikenali".to_string())
             })?;
 
-            let outpath = match file.enclosed_name() {
-                Some(path) => dest_path.join(path),
-                None => continue,
-            };
-
-            // Check if extracted path is within destination (security)
-            if !outpath.starts_with(&dest_path) {
-                tracing::warn!(
-                    "Zip extraction attempted path traversal: {:?}",
-                    outpath
-                );
-                continue;
-            }
-
-            // Check if should overwrite
-            if outpath.exists() && !request.overwrite.unwrap_or(false) {
-                continue;
-            }
-
-            if file.is_dir() {
-                fs::create_dir_all(&outpath).map_err(|e| {
-                    tracing::error!("Failed to create directory: {}", e);
-                    ApiError::InternalError("Gagal membuat direktori".to_string())
-                })?;
-            } else {
-                // Ensure parent directory exists
-                if let Some(parent) = outpath.parent() {
-                    if !parent.exists() {
-                        fs::create_dir_all(parent).map_err(|e| {
-                            tracing::error!("Failed to create parent directory: {}", e);
-                            ApiError::InternalError("Gagal membuat direktori".to_string())
-                        })?;
-                    }
-                }
-
-                let mut outfile = fs::File::create(&outpath).map_err(|e| {
-                    tracing::error!("Failed to create output file: {}", e);
-                    ApiError::FilePermissionDenied
-                })?;
-
-                std::io::copy(&mut file, &mut outfile).map_err(|e| {
-                    tracing::error!("Failed to write output file: {}", e);
-                    ApiError::InternalError("Gagal menulis file".to_string())
+        let user_id = user_id.to_string();
+        let overwrite = request.overwrite.unwrap_or(false);
+        let description = format!("Extract {} ke {}", request.archive_path, request.destination);
+
+        Ok(JobTask::blocking(JobKind::Extract, description, move |progress| {
+            // Create destination directory if needed
+            let created_dest = !dest_path.exists();
+            if created_dest {
+                fs::create_dir_all(&dest_path).map_err(|e| {
+                    tracing::error!("Failed to create destination directory: {}", e);
+                    ApiError::InternalError("Gagal membuat direktori tujuan".to_string())
                 })?;
+            }
 
-                // Set permissions on Unix
-                #[cfg(unix)]
-                {
-                    use std::os::unix::fs::PermissionsExt;
-                    if let Some(mode) = file.unix_mode() {
-                        fs::set_permissions(&outpath, fs::Permissions::from_mode(mode)).ok();
-                    }
-                }
+            let config = ArchiveConfig {
+                quota: Some(quota),
+                ..ArchiveConfig::default()
+            };
+            let result = ArchiveService::extract(
+                &archive_path,
+                &dest_path,
+                format,
+                overwrite,
+                &config,
+                progress,
+            );
+            QuotaService::invalidate(&user_id);
+            if result.is_err() && created_dest {
+                let _ = fs::remove_dir(&dest_path);
             }
-        }
+            result?;
 
-        let metadata = fs::metadata(&dest_path).map_err(|_| {
-            ApiError::InternalError("Gagal membaca metadata".to_string())
-        })?;
+            let metadata = fs::metadata(&dest_path).map_err(|_| {
+                ApiError::InternalError("Gagal membaca metadata".to_string())
+            })?;
 
-        tracing::info!(
-            "Archive extracted: {} -> {} by user {}",
-            request.archive_path,
-            request.destination,
-            user_id
-        );
+            tracing::info!(
+                "Archive extracted: {} -> {} by user {}",
+                request.archive_path,
+                request.destination,
+                user_id
+            );
 
-        Ok(Self::build_file_info(&dest_path, &metadata, &base_path))
+            Ok(Self::build_file_info(&dest_path, &metadata, &base_path))
+        }))
     }
 
     // ==========================================
     // SEARCH OPERATIONS
     // ==========================================
 
-    /// Search files by name
+    /// Search files by name atau isi file
+    ///
+    /// Mode `name` mencocokkan nama file; mode `literal` dan `regex` mencari di
+    /// dalam isi file text (binary dan file di atas `SEARCH_MAX_FILE_SIZE`
+    /// dilewati) dan mengembalikan nomor baris beserta konteksnya. Symlink
+    /// tidak diikuti sehingga pencarian tetap di dalam sandbox user.
+    /// Pencarian dijalankan sebagai background job dan dihentikan setelah
+    /// `SEARCH_TIMEOUT_SECS` dengan hasil parsial.
     ///
     /// # Arguments
     /// * `user_id` - ID user
-    /// * `query` - Search query string
-    /// * `path` - Optional path to start search from
-    /// * `max_results` - Maximum results to return (default 50)
+    /// * `request` - Query, mode, filter glob dan limit
     ///
     /// # Returns
-    /// Vector of matching FileInfo
-    pub async fn search(
-        user_id: &str,
-        query: &str,
-        path: Option<&str>,
-        max_results: Option<usize>,
-    ) -> ApiResult<Vec<FileInfo>> {
-        if query.trim().is_empty() {
+    /// Task yang menghasilkan SearchResponse
+    pub async fn search(user_id: &str, request: SearchRequest) -> ApiResult<JobTask> {
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        if request.query.trim().is_empty() {
             return Err(ApiError::ValidationError(
                 "Query pencarian tidak boleh kosong".to_string(),
             ));
         }
 
+        let mode = match request.mode.as_deref() {
+            Some(mode) => SearchMode::parse(mode).ok_or_else(|| {
+                ApiError::ValidationError(format!("Mode pencarian tidak didukung: {}", mode))
+            })?,
+            None => SearchMode::Name,
+        };
+        let matcher = search::build_matcher(&request.query, mode, request.case_sensitive)?;
+        let include = GlobFilter::parse(request.include.as_deref())?;
+        let exclude = GlobFilter::parse(request.exclude.as_deref())?;
+
         let base_path = Self::get_user_base_path(user_id);
-        let search_path = Self::resolve_path(user_id, path.unwrap_or(""))?;
-        let max = max_results.unwrap_or(50).min(100); // Cap at 100
+        let search_path = Self::resolve_path(user_id, request.path.as_deref().unwrap_or(""))?;
+        let max = request.limit.unwrap_or(50).clamp(1, 100); // Cap at 100
+        let context = request.context;
+        let config = SearchConfig::default();
+        // Area internal (trash, riwayat versi, staging upload) tidak ikut dicari
+        let canonical_base = base_path
+            .canonicalize()
+            .unwrap_or_else(|_| base_path.clone());
+        let hidden_paths: Vec<PathBuf> =
+            INTERNAL_DIRS.iter().map(|dir| canonical_base.join(dir)).collect();
+
+        let user_id = user_id.to_string();
+        let description = format!("Cari \"{}\"", request.query);
+
+        Ok(JobTask::blocking(JobKind::Search, description, move |progress| {
+            let started = Instant::now();
+            let timeout = Duration::from_secs(config.timeout_secs);
+            let mut response = SearchResponse {
+                results: Vec::new(),
+                files_scanned: 0,
+                files_skipped: 0,
+                truncated: false,
+                timed_out: false,
+            };
 
-        let query_lower = query.to_lowercase();
-        let mut results = Vec::new();
+            let walker = WalkDir::new(&search_path)
+                .min_depth(1)
+                .into_iter()
+                .filter_entry(|entry| {
+                    !hidden_paths.iter().any(|path| entry.path() == path)
+                        && !exclude
+                            .as_ref()
+                            .is_some_and(|exclude| exclude.matches(entry.path(), &search_path))
+                });
+
+            for entry in walker.filter_map(|e| e.ok()) {
+                progress.check_cancelled()?;
+
+                if started.elapsed() > timeout {
+                    response.timed_out = true;
+                    break;
+                }
+                if response.results.len() >= max {
+                    response.truncated = true;
+                    break;
+                }
 
-        for entry in WalkDir::new(&search_path)
-            .into_iter()
-            .filter_map(|e| e.ok())
-        {
-            if results.len() >= max {
-                break;
-            }
+                let entry_path = entry.path();
+                let is_file = entry.file_type().is_file();
+                if include.is_some() && !is_file {
+                    continue;
+                }
+                if include
+                    .as_ref()
+                    .is_some_and(|include| !include.matches(entry_path, &search_path))
+                {
+                    continue;
+                }
+
+                let matches = if mode == SearchMode::Name {
+                    let file_name = entry_path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("");
+                    if !matcher.is_match(file_name) {
+                        continue;
+                    }
+                    Vec::new()
+                } else {
+                    if !is_file {
+                        continue;
+                    }
+                    match Self::read_searchable(entry_path, config.max_file_size, progress) {
+                        Some(text) => {
+                            response.files_scanned += 1;
+                            search::find_matches(&text, &matcher, context)
+                        }
+                        None => {
+                            response.files_skipped += 1;
+                            continue;
+                        }
+                    }
+                };
 
-            let entry_path = entry.path();
-            let file_name = entry_path
-                .file_name()
-                .and_then(|n| n.to_str())
-                .unwrap_or("");
+                if mode != SearchMode::Name && matches.is_empty() {
+                    continue;
+                }
 
-            // Match filename (case-insensitive)
-            if file_name.to_lowercase().contains(&query_lower) {
                 if let Ok(metadata) = entry.metadata() {
-                    results.push(Self::build_file_info(entry_path, &metadata, &base_path));
+                    response.results.push(SearchResult {
+                        file: Self::build_file_info(entry_path, &metadata, &base_path),
+                        matches,
+                    });
                 }
             }
+
+            tracing::info!(
+                "Search '{}' ({:?}) found {} results for user {}",
+                request.query,
+                mode,
+                response.results.len(),
+                user_id
+            );
+
+            Ok(response)
+        }))
+    }
+
+    /// Baca isi file untuk pencarian, `None` jika terlalu besar atau binary
+    fn read_searchable(path: &Path, max_size: u64, progress: &JobProgress) -> Option<String> {
+        let size = fs::symlink_metadata(path).ok()?.len();
+        if size > max_size {
+            return None;
         }
 
-        tracing::info!(
-            "Search '{}' found {} results for user {}",
-            query,
-            results.len(),
-            user_id
-        );
+        let data = fs::read(path).ok()?;
+        progress.add_bytes(data.len() as u64);
+
+        if !mime::looks_like_text(&data[..data.len().min(mime::SNIFF_LENGTH)]) {
+            return None;
+        }
 
-        Ok(results)
+        Some(String::from_utf8_lossy(&data).into_owned())
     }
 }
@@ -1,20 +1,43 @@
 //! # FTP Service
 //!
 //! Business logic untuk FTP account management.
+//! FTP daemon (Pure-FTPd) mengautentikasi langsung dari tabel `ftp_accounts`;
+//! service ini menyiapkan home directory, uid/gid, dan konfigurasi daemon.
 
-use chrono::Utc;
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use once_cell::sync::Lazy;
+use regex::Regex;
 use sqlx::MySqlPool;
 use uuid::Uuid;
 use validator::Validate;
 
-use crate::config::CONFIG;
+use crate::config::{FtpTlsMode, CONFIG};
 use crate::errors::{ApiError, ApiResult};
 use crate::models::{
     ChangeFtpPasswordRequest, CreateFtpAccountRequest, FtpAccount, FtpAccountResponse,
-    FtpServerInfo, UpdateFtpAccountRequest,
+    FtpBackendConfig, FtpLogEventsRequest, FtpLogEventsResult, FtpServerInfo,
+    UpdateFtpAccountRequest,
 };
+use crate::services::{SftpService, MAX_SFTP_USERNAME_LENGTH};
 use crate::utils::password;
 
+/// Regex baris log login Pure-FTPd: `(?@1.2.3.4) [INFO] alice is now logged in`
+static PUREFTPD_LOGIN_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"\(\?@([^)]+)\) \[INFO\] (\S+) is now logged in").unwrap()
+});
+
+/// Regex baris log sesi Pure-FTPd: `(alice@1.2.3.4) [NOTICE] ... uploaded`
+static PUREFTPD_SESSION_REGEX: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"\(([^?@()\s]+)@[^)]+\) \[").unwrap());
+
+/// Service systemd FTP daemon yang dikenali (urutan prioritas deteksi)
+const FTP_DAEMON_SERVICES: &[&str] = &["pure-ftpd-mysql", "pure-ftpd", "proftpd", "vsftpd"];
+
 /// Service untuk FTP account operations
 pub struct FtpService;
 
@@ -89,6 +112,18 @@ impl FtpService {
 
         // Generate FTP username (format: username_userid)
         let ftp_username = format!("{}_{}", request.username.to_lowercase(), &user_id[..8]);
+        let sftp_only = request.sftp_only.unwrap_or(false);
+
+        // Akun SFTP-only memakai system user, batas panjang username Linux berlaku
+        if sftp_only && ftp_username.len() > MAX_SFTP_USERNAME_LENGTH {
+            return Err(ApiError::ValidationError(format!(
+                "Username terlalu panjang untuk akun SFTP (maksimal {} karakter)",
+                MAX_SFTP_USERNAME_LENGTH - 9
+            )));
+        }
+        if sftp_only && request.quota_mb.unwrap_or(0) > 0 {
+            return Err(sftp_quota_error());
+        }
 
         // Check if username already exists
         let existing = sqlx::query_scalar::<_, i64>(
@@ -103,12 +138,17 @@ impl FtpService {
         }
 
         // Hash password
-        let password_hash = password::hash_password(&request.password)?;
+        let password_hash = password::hash_crypt_password(&request.password)?;
 
-        // Generate home directory
-        let home_directory = request.home_directory.unwrap_or_else(|| {
-            format!("{}/{}", CONFIG.file.user_home_base, user_id)
-        });
+        // Resolve home directory di dalam home user (chroot FTP)
+        let username = Self::get_panel_username(pool, user_id).await?;
+        let user_home = Self::user_home(&username);
+        let home_directory =
+            resolve_ftp_home(&user_home, request.home_directory.as_deref())?;
+
+        // Siapkan direktori dengan uid/gid system user pemilik
+        let (system_uid, system_gid) =
+            Self::provision_home(&format!("user_{}", username), &home_directory)?;
 
         // Convert quota from MB to bytes
         let quota_bytes = request.quota_mb.unwrap_or(0) * 1024 * 1024;
@@ -120,8 +160,8 @@ impl FtpService {
         // Insert FTP account
         sqlx::query(
             r#"
-            INSERT INTO ftp_accounts (id, user_id, ftp_username, password_hash, home_directory, is_active, quota_bytes, used_bytes, created_at, updated_at)
-            VALUES (?, ?, ?, ?, ?, TRUE, ?, 0, ?, ?)
+            INSERT INTO ftp_accounts (id, user_id, ftp_username, password_hash, home_directory, is_active, quota_bytes, used_bytes, system_uid, system_gid, sftp_only, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, TRUE, ?, 0, ?, ?, ?, ?, ?)
             "#,
         )
         .bind(&account_id)
@@ -130,11 +170,47 @@ impl FtpService {
         .bind(&password_hash)
         .bind(&home_directory)
         .bind(quota_bytes)
+        .bind(system_uid)
+        .bind(system_gid)
+        .bind(sftp_only)
         .bind(now)
         .bind(now)
         .execute(pool)
         .await?;
 
+        if sftp_only {
+            let account = sqlx::query_as::<_, FtpAccount>(
+                "SELECT * FROM ftp_accounts WHERE id = ?",
+            )
+            .bind(&account_id)
+            .fetch_one(pool)
+            .await?;
+
+            // Rollback record jika system user gagal dibuat
+            if let Err(e) = SftpService::provision(&account, &request.password) {
+                sqlx::query("DELETE FROM ftp_accounts WHERE id = ?")
+                    .bind(&account_id)
+                    .execute(pool)
+                    .await?;
+                return Err(e);
+            }
+
+            // Rollback system user, mount, dan record jika sshd gagal dikonfigurasi
+            if let Err(e) = SftpService::sync_sshd_config(pool).await {
+                if let Err(remove_err) = SftpService::remove(&ftp_username) {
+                    tracing::warn!("SFTP rollback for {} failed: {}", ftp_username, remove_err);
+                }
+                sqlx::query("DELETE FROM ftp_accounts WHERE id = ?")
+                    .bind(&account_id)
+                    .execute(pool)
+                    .await?;
+                if let Err(resync_err) = SftpService::sync_sshd_config(pool).await {
+                    tracing::warn!("SSHD resync after rollback failed: {}", resync_err);
+                }
+                return Err(e);
+            }
+        }
+
         tracing::info!(
             "FTP account created: {} for user {}",
             ftp_username,
@@ -173,23 +249,55 @@ impl FtpService {
 
         // Build update values
         let password_hash = if let Some(ref new_password) = request.password {
-            password::hash_password(new_password)?
+            password::hash_crypt_password(new_password)?
         } else {
-            account.password_hash
+            account.password_hash.clone()
         };
 
-        let home_directory = request.home_directory.unwrap_or(account.home_directory);
+        let (home_directory, system_uid, system_gid) = match request.home_directory {
+            Some(ref requested) => {
+                let username = Self::get_panel_username(pool, user_id).await?;
+                let home = resolve_ftp_home(&Self::user_home(&username), Some(requested))?;
+                let (uid, gid) = Self::provision_home(&format!("user_{}", username), &home)?;
+                (home, Some(uid), Some(gid))
+            }
+            None => (
+                account.home_directory.clone(),
+                account.system_uid,
+                account.system_gid,
+            ),
+        };
         let is_active = request.is_active.unwrap_or(account.is_active);
         let quota_bytes = request
             .quota_mb
             .map(|mb| mb * 1024 * 1024)
             .unwrap_or(account.quota_bytes);
 
+        if account.sftp_only {
+            if quota_bytes > 0 {
+                return Err(sftp_quota_error());
+            }
+
+            // System user diubah lebih dulu, record hanya disimpan jika berhasil
+            if let Some(ref new_password) = request.password {
+                SftpService::set_password(&account.ftp_username, new_password)?;
+            }
+            if home_directory != account.home_directory {
+                SftpService::remount(&FtpAccount {
+                    home_directory: home_directory.clone(),
+                    ..account.clone()
+                })?;
+            }
+            if is_active != account.is_active {
+                SftpService::set_locked(&account.ftp_username, !is_active)?;
+            }
+        }
+
         // Update account
         sqlx::query(
             r#"
             UPDATE ftp_accounts 
-            SET password_hash = ?, home_directory = ?, is_active = ?, quota_bytes = ?, updated_at = ?
+            SET password_hash = ?, home_directory = ?, is_active = ?, quota_bytes = ?, system_uid = ?, system_gid = ?, updated_at = ?
             WHERE id = ?
             "#,
         )
@@ -197,6 +305,8 @@ impl FtpService {
         .bind(&home_directory)
         .bind(is_active)
         .bind(quota_bytes)
+        .bind(system_uid)
+        .bind(system_gid)
         .bind(Utc::now())
         .bind(account_id)
         .execute(pool)
@@ -234,7 +344,11 @@ impl FtpService {
         }
 
         // Hash new password
-        let password_hash = password::hash_password(&request.new_password)?;
+        let password_hash = password::hash_crypt_password(&request.new_password)?;
+
+        if account.sftp_only {
+            SftpService::set_password(&account.ftp_username, &request.new_password)?;
+        }
 
         // Update password
         sqlx::query(
@@ -267,12 +381,20 @@ impl FtpService {
             return Err(ApiError::Forbidden);
         }
 
+        if account.sftp_only {
+            SftpService::remove(&account.ftp_username)?;
+        }
+
         // Delete account
         sqlx::query("DELETE FROM ftp_accounts WHERE id = ?")
             .bind(account_id)
             .execute(pool)
             .await?;
 
+        if account.sftp_only {
+            SftpService::sync_sshd_config(pool).await?;
+        }
+
         tracing::info!("FTP account deleted: {}", account.ftp_username);
 
         Ok(())
@@ -301,6 +423,10 @@ impl FtpService {
         // Toggle status
         let new_status = !account.is_active;
 
+        if account.sftp_only {
+            SftpService::set_locked(&account.ftp_username, !new_status)?;
+        }
+
         sqlx::query(
             "UPDATE ftp_accounts SET is_active = ?, updated_at = ? WHERE id = ?",
         )
@@ -320,9 +446,39 @@ impl FtpService {
     }
 
     /// Get FTP server info
+    ///
+    /// Host, port, dan mode TLS dibaca dari `AppConfig`; daemon yang berjalan
+    /// dan masa berlaku sertifikat dideteksi dari sistem.
     pub fn get_server_info() -> FtpServerInfo {
-        // TODO: Load from config
-        FtpServerInfo::default()
+        let config = &CONFIG.ftp;
+        let mut info = FtpServerInfo::from(config);
+        info.daemon = Self::detect_daemon();
+
+        if config.tls_mode != FtpTlsMode::None {
+            if let Some((expires_at, fingerprint)) = Self::read_certificate(&config.tls_cert_path)
+            {
+                info.cert_expires_at = expires_at;
+                info.cert_days_remaining =
+                    expires_at.map(|at| at.signed_duration_since(Utc::now()).num_days());
+                if info.cert_fingerprint.is_none() {
+                    info.cert_fingerprint = fingerprint;
+                }
+            }
+        }
+
+        info
+    }
+
+    /// Site Manager XML FileZilla untuk satu FTP account
+    pub async fn get_filezilla_xml(
+        pool: &MySqlPool,
+        account_id: &str,
+        user_id: &str,
+    ) -> ApiResult<String> {
+        let account = Self::get_by_id(pool, account_id, user_id).await?;
+        let info = FtpServerInfo::from(&CONFIG.ftp);
+
+        Ok(render_filezilla_xml(&info, &account))
     }
 
     /// Get FTP account count untuk user
@@ -367,4 +523,452 @@ impl FtpService {
 
         Ok(())
     }
+
+    // ==========================================
+    // FTP DAEMON INTEGRATION
+    // ==========================================
+
+    /// Proses baris log Pure-FTPd (dikirim via syslog/omprog ke endpoint internal)
+    ///
+    /// Login dicatat lewat `record_login`; akun yang aktif di log diperbarui
+    /// pemakaian disk-nya dari file `.ftpquota` lewat `update_used_bytes`.
+    pub async fn process_log_events(
+        pool: &MySqlPool,
+        request: FtpLogEventsRequest,
+        internal_key: &str,
+    ) -> ApiResult<FtpLogEventsResult> {
+        let config = FtpBackendConfig::default();
+        if internal_key != config.internal_key {
+            return Err(ApiError::Unauthorized);
+        }
+
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        let mut result = FtpLogEventsResult::default();
+        let mut active_users: HashSet<String> = HashSet::new();
+
+        for line in &request.lines {
+            if let Some(ftp_username) = parse_pureftpd_login(line) {
+                Self::record_login(pool, &ftp_username).await?;
+                result.logins += 1;
+                active_users.insert(ftp_username);
+            } else if let Some(ftp_username) = parse_pureftpd_session_user(line) {
+                active_users.insert(ftp_username);
+            }
+        }
+
+        for ftp_username in active_users {
+            let account = sqlx::query_as::<_, FtpAccount>(
+                "SELECT * FROM ftp_accounts WHERE ftp_username = ?",
+            )
+            .bind(&ftp_username)
+            .fetch_optional(pool)
+            .await?;
+
+            let Some(account) = account else {
+                continue;
+            };
+
+            let quota_file = Path::new(&account.home_directory).join(".ftpquota");
+            if let Some(used_bytes) = fs::read_to_string(&quota_file)
+                .ok()
+                .and_then(|content| parse_ftpquota(&content))
+            {
+                Self::update_used_bytes(pool, &account.ftp_username, used_bytes).await?;
+                result.usage_updated += 1;
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Tulis konfigurasi MySQL Pure-FTPd dan restart daemon
+    pub async fn sync_backend_config() -> ApiResult<()> {
+        let config = FtpBackendConfig::default();
+
+        if let Some(parent) = Path::new(&config.mysql_conf_path).parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| ApiError::InternalError(format!("FTP config dir error: {}", e)))?;
+        }
+
+        fs::write(&config.mysql_conf_path, render_pureftpd_mysql_conf(&config))
+            .map_err(|e| ApiError::InternalError(format!("FTP config write error: {}", e)))?;
+
+        let output = Command::new("sudo")
+            .arg("systemctl")
+            .arg("restart")
+            .arg(&config.service_name)
+            .output()
+            .map_err(|e| ApiError::InternalError(format!("Failed to execute systemctl: {}", e)))?;
+
+        if !output.status.success() {
+            let err = String::from_utf8_lossy(&output.stderr);
+            return Err(ApiError::InternalError(format!("FTP daemon restart error: {}", err)));
+        }
+
+        tracing::info!("FTP backend config synced: {}", config.mysql_conf_path);
+
+        Ok(())
+    }
+
+    /// Deteksi FTP daemon yang aktif via systemd
+    fn detect_daemon() -> Option<String> {
+        let configured = FtpBackendConfig::default().service_name;
+        let mut candidates = vec![configured.as_str()];
+        candidates.extend(FTP_DAEMON_SERVICES.iter().filter(|name| **name != configured));
+
+        candidates.into_iter().find_map(|name| {
+            Command::new("systemctl")
+                .args(["is-active", "--quiet", name])
+                .status()
+                .ok()
+                .filter(|status| status.success())
+                .map(|_| name.to_string())
+        })
+    }
+
+    /// Baca tanggal kedaluwarsa dan fingerprint SHA-256 sertifikat TLS
+    fn read_certificate(cert_path: &str) -> Option<(Option<DateTime<Utc>>, Option<String>)> {
+        let output = Command::new("sudo")
+            .args(["openssl", "x509", "-noout", "-enddate", "-fingerprint", "-sha256", "-in"])
+            .arg(cert_path)
+            .output()
+            .ok()?;
+
+        if !output.status.success() {
+            tracing::warn!("Failed to read FTP TLS certificate: {}", cert_path);
+            return None;
+        }
+
+        Some(parse_openssl_x509_output(&String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Username panel milik user
+    async fn get_panel_username(pool: &MySqlPool, user_id: &str) -> ApiResult<String> {
+        sqlx::query_scalar::<_, String>("SELECT username FROM users WHERE id = ?")
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or(ApiError::NotFound("User".to_string()))
+    }
+
+    /// Home directory user (sama dengan sandbox file manager)
+    fn user_home(username: &str) -> PathBuf {
+        PathBuf::from(&CONFIG.file.user_home_base).join(format!("user_{}", username))
+    }
+
+    /// Buat home directory FTP dan set owner ke system user
+    ///
+    /// # Returns
+    /// (uid, gid) system user
+    fn provision_home(system_username: &str, home_directory: &str) -> ApiResult<(i32, i32)> {
+        let uid = Self::lookup_id(system_username, "-u")?;
+        let gid = Self::lookup_id(system_username, "-g")?;
+
+        fs::create_dir_all(home_directory)
+            .map_err(|e| ApiError::InternalError(format!("Failed to create FTP home: {}", e)))?;
+
+        let output = Command::new("sudo")
+            .arg("chown")
+            .arg(format!("{}:{}", uid, gid))
+            .arg(home_directory)
+            .output()
+            .map_err(|e| ApiError::InternalError(format!("Failed to execute chown: {}", e)))?;
+
+        if !output.status.success() {
+            let err = String::from_utf8_lossy(&output.stderr);
+            return Err(ApiError::InternalError(format!("Chown error: {}", err)));
+        }
+
+        Ok((uid, gid))
+    }
+
+    /// Ambil uid (`-u`) atau gid (`-g`) system user via `id`
+    fn lookup_id(system_username: &str, flag: &str) -> ApiResult<i32> {
+        let output = Command::new("id")
+            .arg(flag)
+            .arg(system_username)
+            .output()
+            .map_err(|e| ApiError::InternalError(format!("Failed to execute id: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(ApiError::InternalError(format!(
+                "System user {} tidak ditemukan",
+                system_username
+            )));
+        }
+
+        String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .map_err(|_| ApiError::InternalError("Invalid id output".to_string()))
+    }
+}
+
+/// Quota per akun hanya ditegakkan Pure-FTPd; akun SFTP-only memakai uid
+/// pemilik sehingga hanya disk quota user panel yang berlaku
+fn sftp_quota_error() -> ApiError {
+    ApiError::ValidationError(
+        "Quota per akun tidak didukung untuk akun SFTP (disk quota user tetap berlaku)"
+            .to_string(),
+    )
+}
+
+/// Resolve home directory FTP di dalam home user
+///
+/// Path relatif di-join ke home user; path absolut harus berada di dalamnya.
+/// Komponen `..` ditolak agar akun tidak bisa keluar dari chroot.
+pub fn resolve_ftp_home(user_home: &Path, requested: Option<&str>) -> ApiResult<String> {
+    let requested = requested.map(str::trim).unwrap_or("");
+
+    let relative = match Path::new(requested).strip_prefix(user_home) {
+        Ok(stripped) => stripped.to_path_buf(),
+        Err(_) if requested.starts_with('/') => {
+            return Err(ApiError::ValidationError(
+                "Home directory harus berada di dalam home user".to_string(),
+            ))
+        }
+        Err(_) => PathBuf::from(requested),
+    };
+
+    let mut home = user_home.to_path_buf();
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) => home.push(part),
+            Component::CurDir => {}
+            _ => {
+                return Err(ApiError::ValidationError(
+                    "Home directory tidak valid".to_string(),
+                ))
+            }
+        }
+    }
+
+    Ok(home.to_string_lossy().to_string())
+}
+
+/// Render konfigurasi MySQL Pure-FTPd yang membaca `ftp_accounts`
+pub fn render_pureftpd_mysql_conf(config: &FtpBackendConfig) -> String {
+    let filter =
+        "FROM ftp_accounts WHERE ftp_username = \"\\L\" AND is_active = 1 AND sftp_only = 0";
+
+    format!(
+        "# Generated by NusaPanel - jangan edit manual\n\
+         MYSQLServer     {host}\n\
+         MYSQLPort       {port}\n\
+         MYSQLUser       {user}\n\
+         MYSQLPassword   {password}\n\
+         MYSQLDatabase   {database}\n\
+         MYSQLCrypt      crypt\n\
+         MYSQLGetPW      SELECT password_hash {filter}\n\
+         MYSQLGetUID     SELECT system_uid {filter}\n\
+         MYSQLGetGID     SELECT system_gid {filter}\n\
+         MYSQLGetDir     SELECT home_directory {filter}\n\
+         MYSQLGetQTASZ   SELECT NULLIF(quota_bytes, 0) DIV 1048576 {filter}\n",
+        host = config.db_host,
+        port = config.db_port,
+        user = config.db_user,
+        password = config.db_password,
+        database = config.db_name,
+        filter = filter,
+    )
+}
+
+/// Parse output `openssl x509 -noout -enddate -fingerprint -sha256`
+///
+/// # Returns
+/// (waktu kedaluwarsa, fingerprint)
+pub fn parse_openssl_x509_output(output: &str) -> (Option<DateTime<Utc>>, Option<String>) {
+    let mut expires_at = None;
+    let mut fingerprint = None;
+
+    for line in output.lines() {
+        if let Some(date) = line.trim().strip_prefix("notAfter=") {
+            // Format: "Jan  1 00:00:00 2027 GMT"
+            let date = date.split_whitespace().collect::<Vec<_>>().join(" ");
+            expires_at = NaiveDateTime::parse_from_str(&date, "%b %d %H:%M:%S %Y GMT")
+                .ok()
+                .map(|naive| naive.and_utc());
+        } else if let Some((_, value)) = line.split_once("Fingerprint=") {
+            fingerprint = Some(value.trim().to_uppercase());
+        }
+    }
+
+    (expires_at, fingerprint)
+}
+
+/// Render Site Manager XML FileZilla (Import via File > Import)
+///
+/// Protocol FileZilla: 0 = FTP, 1 = SFTP, 3 = FTPS implicit, 4 = FTPES explicit.
+/// Password tidak disertakan; FileZilla akan menanyakannya saat connect.
+pub fn render_filezilla_xml(info: &FtpServerInfo, account: &FtpAccountResponse) -> String {
+    let (protocol, port) = if account.sftp_only {
+        (1, info.sftp_port)
+    } else {
+        match info.tls_mode {
+            FtpTlsMode::None => (0, info.port),
+            FtpTlsMode::Explicit => (4, info.port),
+            FtpTlsMode::Implicit => (3, info.ftps_port),
+        }
+    };
+
+    format!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <FileZilla3>\n\
+         \x20 <Servers>\n\
+         \x20   <Server>\n\
+         \x20     <Host>{host}</Host>\n\
+         \x20     <Port>{port}</Port>\n\
+         \x20     <Protocol>{protocol}</Protocol>\n\
+         \x20     <Type>0</Type>\n\
+         \x20     <User>{user}</User>\n\
+         \x20     <Logontype>2</Logontype>\n\
+         \x20     <PasvMode>MODE_DEFAULT</PasvMode>\n\
+         \x20     <EncodingType>Auto</EncodingType>\n\
+         \x20     <BypassProxy>0</BypassProxy>\n\
+         \x20     <Name>{user}@{host}</Name>\n\
+         \x20     <SyncBrowsing>0</SyncBrowsing>\n\
+         \x20     <DirectoryComparison>0</DirectoryComparison>\n\
+         \x20   </Server>\n\
+         \x20 </Servers>\n\
+         </FileZilla3>\n",
+        host = xml_escape(&info.hostname),
+        port = port,
+        protocol = protocol,
+        user = xml_escape(&account.ftp_username),
+    )
+}
+
+/// Escape karakter khusus XML
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Ambil username dari baris log login Pure-FTPd
+pub fn parse_pureftpd_login(line: &str) -> Option<String> {
+    PUREFTPD_LOGIN_REGEX
+        .captures(line)
+        .map(|caps| caps[2].to_string())
+}
+
+/// Ambil username dari baris log sesi Pure-FTPd (`(user@ip) [...]`)
+fn parse_pureftpd_session_user(line: &str) -> Option<String> {
+    PUREFTPD_SESSION_REGEX
+        .captures(line)
+        .map(|caps| caps[1].to_string())
+}
+
+/// Parse file `.ftpquota` Pure-FTPd (`<jumlah file> <bytes>`)
+pub fn parse_ftpquota(content: &str) -> Option<i64> {
+    content.split_whitespace().nth(1)?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_ftp_home() {
+        let user_home = Path::new("/home/nusa/user_john");
+
+        assert_eq!(resolve_ftp_home(user_home, None).unwrap(), "/home/nusa/user_john");
+        assert_eq!(
+            resolve_ftp_home(user_home, Some("public_html/uploads")).unwrap(),
+            "/home/nusa/user_john/public_html/uploads"
+        );
+        assert_eq!(
+            resolve_ftp_home(user_home, Some("/home/nusa/user_john/site")).unwrap(),
+            "/home/nusa/user_john/site"
+        );
+        assert!(resolve_ftp_home(user_home, Some("../user_jane")).is_err());
+        assert!(resolve_ftp_home(user_home, Some("/etc")).is_err());
+    }
+
+    #[test]
+    fn test_parse_pureftpd_log_lines() {
+        let login = "Jan 10 10:00:01 host pure-ftpd: (?@203.0.113.5) [INFO] alice_1234abcd is now logged in";
+        let upload = "Jan 10 10:01:00 host pure-ftpd: (alice_1234abcd@203.0.113.5) [NOTICE] /home/x/a.txt uploaded (12 bytes, 1.2KB/sec)";
+
+        assert_eq!(parse_pureftpd_login(login).as_deref(), Some("alice_1234abcd"));
+        assert_eq!(parse_pureftpd_login(upload), None);
+        assert_eq!(parse_pureftpd_session_user(upload).as_deref(), Some("alice_1234abcd"));
+        assert_eq!(parse_pureftpd_session_user(login), None);
+        assert_eq!(parse_ftpquota("42 1048576\n"), Some(1048576));
+        assert_eq!(parse_ftpquota(""), None);
+    }
+
+    #[test]
+    fn test_render_pureftpd_mysql_conf() {
+        let config = FtpBackendConfig {
+            db_user: "nusa_ftp".to_string(),
+            db_password: "secret".to_string(),
+            ..FtpBackendConfig::default()
+        };
+
+        let conf = render_pureftpd_mysql_conf(&config);
+        assert!(conf.contains("MYSQLUser       nusa_ftp\n"));
+        assert!(conf.contains("MYSQLCrypt      crypt\n"));
+        assert!(conf.contains(
+            "MYSQLGetPW      SELECT password_hash FROM ftp_accounts WHERE ftp_username = \"\\L\" AND is_active = 1 AND sftp_only = 0\n"
+        ));
+    }
+
+    #[test]
+    fn test_parse_openssl_x509_output() {
+        let output = "notAfter=Jan  5 12:30:00 2027 GMT\n\
+                      sha256 Fingerprint=ab:cd:ef:01\n";
+        let (expires_at, fingerprint) = parse_openssl_x509_output(output);
+
+        assert_eq!(
+            expires_at.unwrap().to_rfc3339(),
+            "2027-01-05T12:30:00+00:00"
+        );
+        assert_eq!(fingerprint.as_deref(), Some("AB:CD:EF:01"));
+        assert_eq!(parse_openssl_x509_output(""), (None, None));
+    }
+
+    #[test]
+    fn test_render_filezilla_xml() {
+        let mut info = FtpServerInfo::from(&CONFIG.ftp);
+        info.hostname = "ftp.example.com".to_string();
+        info.port = 21;
+        info.ftps_port = 990;
+        info.sftp_port = 2222;
+        info.tls_mode = FtpTlsMode::Implicit;
+
+        let mut account = FtpAccountResponse {
+            id: "1".to_string(),
+            user_id: "1".to_string(),
+            ftp_username: "alice_1234abcd".to_string(),
+            home_directory: "/home/nusa/user_alice".to_string(),
+            is_active: true,
+            quota_bytes: 0,
+            used_bytes: 0,
+            quota_percentage: 0.0,
+            sftp_only: false,
+            password_reset_required: false,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            last_login: None,
+        };
+
+        let xml = render_filezilla_xml(&info, &account);
+        assert!(xml.contains("<Host>ftp.example.com</Host>"));
+        assert!(xml.contains("<Port>990</Port>"));
+        assert!(xml.contains("<Protocol>3</Protocol>"));
+        assert!(xml.contains("<User>alice_1234abcd</User>"));
+        assert!(!xml.contains("<Pass"));
+
+        account.sftp_only = true;
+        let xml = render_filezilla_xml(&info, &account);
+        assert!(xml.contains("<Port>2222</Port>"));
+        assert!(xml.contains("<Protocol>1</Protocol>"));
+    }
 }
@@ -0,0 +1,454 @@
+//! # Job Service
+//!
+//! Background job runner untuk operasi file yang berjalan lama (compress,
+//! extract, copy, search) agar tidak menahan worker Rocket.
+//!
+//! - Job dicatat di tabel `jobs`, lalu dijalankan oleh pool worker terbatas
+//!   ([`JobConfig::workers`]); job lain menunggu dengan status `pending`
+//! - Progress (bytes) disimpan ke database secara berkala
+//! - Task memeriksa flag pembatalan di sela proses ([`JobProgress::check_cancelled`])
+//! - Saat startup, job `pending`/`running` sisa proses sebelumnya ditandai gagal
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::io::Read;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use rocket::fairing::AdHoc;
+use serde::Serialize;
+use sqlx::MySqlPool;
+use tokio::sync::Semaphore;
+use uuid::Uuid;
+
+use crate::database::Database;
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{Job, JobConfig, JobKind, JobResponse, JobStatus};
+
+/// Interval penyimpanan progress job ke database
+const PROGRESS_FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Jumlah maksimum job per halaman list
+const MAX_LIST_LIMIT: u32 = 100;
+
+/// Panjang maksimum deskripsi job (kolom `description`)
+const MAX_DESCRIPTION_LENGTH: usize = 500;
+
+/// Pool worker: jumlah permit = job yang boleh berjalan bersamaan
+static WORKERS: Lazy<Semaphore> = Lazy::new(|| Semaphore::new(JobConfig::default().workers));
+
+/// Progress job yang sedang antri atau berjalan di proses ini
+static ACTIVE_JOBS: Lazy<Mutex<HashMap<String, Arc<JobProgress>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+type JobFuture = Pin<Box<dyn Future<Output = ApiResult<serde_json::Value>> + Send>>;
+
+/// Progress dan flag pembatalan satu job
+#[derive(Debug, Default)]
+pub struct JobProgress {
+    total_bytes: AtomicU64,
+    processed_bytes: AtomicU64,
+    cancelled: AtomicBool,
+}
+
+impl JobProgress {
+    /// Set total bytes yang akan diproses
+    pub fn set_total(&self, total: u64) {
+        self.total_bytes.store(total, Ordering::Relaxed);
+    }
+
+    /// Tambah bytes yang sudah diproses
+    pub fn add_bytes(&self, bytes: u64) {
+        self.processed_bytes.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Total bytes (0 jika belum diketahui)
+    pub fn total_bytes(&self) -> u64 {
+        self.total_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Bytes yang sudah diproses
+    pub fn processed_bytes(&self) -> u64 {
+        self.processed_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Minta job berhenti
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    /// Apakah job diminta berhenti
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Error `JobCancelled` jika job diminta berhenti
+    pub fn check_cancelled(&self) -> ApiResult<()> {
+        if self.is_cancelled() {
+            return Err(ApiError::JobCancelled);
+        }
+        Ok(())
+    }
+}
+
+/// Reader yang melaporkan bytes terbaca ke progress dan berhenti saat job dibatalkan
+pub struct ProgressReader<'a, R> {
+    inner: R,
+    progress: &'a JobProgress,
+}
+
+impl<'a, R: Read> ProgressReader<'a, R> {
+    pub fn new(inner: R, progress: &'a JobProgress) -> Self {
+        Self { inner, progress }
+    }
+}
+
+impl<R: Read> Read for ProgressReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        if self.progress.is_cancelled() {
+            return Err(std::io::Error::other(ApiError::JobCancelled.to_string()));
+        }
+        let read = self.inner.read(buf)?;
+        self.progress.add_bytes(read as u64);
+        Ok(read)
+    }
+}
+
+/// Pekerjaan yang akan dijalankan sebagai background job
+pub struct JobTask {
+    kind: JobKind,
+    description: String,
+    run: Box<dyn FnOnce(Arc<JobProgress>) -> JobFuture + Send>,
+}
+
+impl JobTask {
+    /// Buat task async; hasilnya disimpan sebagai JSON di kolom `result`
+    pub fn new<F, Fut, T>(kind: JobKind, description: impl Into<String>, run: F) -> Self
+    where
+        F: FnOnce(Arc<JobProgress>) -> Fut + Send + 'static,
+        Fut: Future<Output = ApiResult<T>> + Send + 'static,
+        T: Serialize,
+    {
+        let mut description: String = description.into();
+        if let Some((index, _)) = description.char_indices().nth(MAX_DESCRIPTION_LENGTH) {
+            description.truncate(index);
+        }
+
+        Self {
+            kind,
+            description,
+            run: Box::new(move |progress| {
+                Box::pin(async move {
+                    let value = run(progress).await?;
+                    serde_json::to_value(value).map_err(|e| {
+                        ApiError::InternalError(format!("Failed to serialize job result: {}", e))
+                    })
+                })
+            }),
+        }
+    }
+
+    /// Buat task untuk pekerjaan blocking (filesystem), dijalankan di `spawn_blocking`
+    pub fn blocking<F, T>(kind: JobKind, description: impl Into<String>, run: F) -> Self
+    where
+        F: FnOnce(&JobProgress) -> ApiResult<T> + Send + 'static,
+        T: Serialize + Send + 'static,
+    {
+        Self::new(kind, description, move |progress| async move {
+            tokio::task::spawn_blocking(move || run(&progress))
+                .await
+                .unwrap_or_else(|e| Err(ApiError::InternalError(format!("Job task failed: {}", e))))
+        })
+    }
+
+    /// Jenis job
+    pub fn kind(&self) -> JobKind {
+        self.kind
+    }
+
+    /// Jalankan task langsung (tanpa antrian dan tanpa pencatatan)
+    pub async fn execute(self, progress: Arc<JobProgress>) -> ApiResult<serde_json::Value> {
+        (self.run)(progress).await
+    }
+}
+
+/// Service background job
+pub struct JobService;
+
+impl JobService {
+    /// Fairing: tandai job yang terhenti saat restart dan hapus riwayat lama secara berkala
+    pub fn fairing() -> AdHoc {
+        AdHoc::on_liftoff("Job Runner", |rocket| {
+            Box::pin(async move {
+                let Some(db) = rocket.state::<Database>() else {
+                    tracing::error!("Job runner not started: database is not available");
+                    return;
+                };
+
+                let pool = db.get_pool().clone();
+                match Self::fail_interrupted(&pool).await {
+                    Ok(0) => {}
+                    Ok(failed) => tracing::warn!("Marked {} interrupted jobs as failed", failed),
+                    Err(e) => tracing::warn!("Failed to recover interrupted jobs: {}", e),
+                }
+
+                let config = JobConfig::default();
+                tokio::spawn(async move {
+                    let mut interval = tokio::time::interval(Duration::from_secs(3600));
+                    loop {
+                        interval.tick().await;
+                        match Self::cleanup_finished(&pool, config.retention_days).await {
+                            Ok(0) => {}
+                            Ok(removed) => tracing::info!("Removed {} old jobs", removed),
+                            Err(e) => tracing::warn!("Job cleanup failed: {}", e),
+                        }
+                    }
+                });
+            })
+        })
+    }
+
+    /// Catat job baru dan jalankan saat worker tersedia
+    pub async fn enqueue(pool: &MySqlPool, user_id: &str, task: JobTask) -> ApiResult<JobResponse> {
+        let job_id = Uuid::new_v4().to_string();
+
+        sqlx::query(
+            r#"
+            INSERT INTO jobs (id, user_id, kind, description, status, total_bytes, processed_bytes, created_at)
+            VALUES (?, ?, ?, ?, 'pending', 0, 0, ?)
+            "#,
+        )
+        .bind(&job_id)
+        .bind(user_id)
+        .bind(task.kind.as_str())
+        .bind(&task.description)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+        let progress = Arc::new(JobProgress::default());
+        if let Ok(mut jobs) = ACTIVE_JOBS.lock() {
+            jobs.insert(job_id.clone(), progress.clone());
+        }
+
+        tracing::info!(
+            "Job {} queued: {} ({}) by user {}",
+            job_id,
+            task.description,
+            task.kind.as_str(),
+            user_id
+        );
+
+        tokio::spawn(Self::run(pool.clone(), job_id.clone(), task, progress));
+
+        Self::get(pool, &job_id, user_id).await
+    }
+
+    /// Tunggu worker, jalankan task dan catat hasilnya
+    async fn run(pool: MySqlPool, job_id: String, task: JobTask, progress: Arc<JobProgress>) {
+        // Semaphore tidak pernah di-close, acquire hanya gagal jika di-close
+        let _permit = WORKERS.acquire().await;
+
+        if !progress.is_cancelled() {
+            let _ = sqlx::query(
+                "UPDATE jobs SET status = 'running', started_at = ? WHERE id = ? AND status = 'pending'",
+            )
+            .bind(Utc::now())
+            .bind(&job_id)
+            .execute(&pool)
+            .await;
+
+            let reporter = tokio::spawn(Self::report_progress(
+                pool.clone(),
+                job_id.clone(),
+                progress.clone(),
+            ));
+            let result = task.execute(progress.clone()).await;
+            reporter.abort();
+
+            let (status, result, error_message) = match result {
+                Ok(value) => (JobStatus::Completed, Some(value.to_string()), None),
+                Err(_) if progress.is_cancelled() => (JobStatus::Cancelled, None, None),
+                Err(e) => {
+                    tracing::error!("Job {} failed: {}", job_id, e);
+                    (JobStatus::Failed, None, Some(e.to_string()))
+                }
+            };
+
+            let _ = sqlx::query(
+                r#"
+                UPDATE jobs
+                SET status = ?, total_bytes = ?, processed_bytes = ?, result = ?, error_message = ?, completed_at = ?
+                WHERE id = ?
+                "#,
+            )
+            .bind(status.as_str())
+            .bind(progress.total_bytes() as i64)
+            .bind(progress.processed_bytes() as i64)
+            .bind(result)
+            .bind(error_message)
+            .bind(Utc::now())
+            .bind(&job_id)
+            .execute(&pool)
+            .await;
+        }
+
+        if let Ok(mut jobs) = ACTIVE_JOBS.lock() {
+            jobs.remove(&job_id);
+        }
+    }
+
+    /// Simpan progress job yang sedang berjalan secara berkala
+    async fn report_progress(pool: MySqlPool, job_id: String, progress: Arc<JobProgress>) {
+        let mut interval = tokio::time::interval(PROGRESS_FLUSH_INTERVAL);
+        loop {
+            interval.tick().await;
+            let _ = sqlx::query(
+                "UPDATE jobs SET total_bytes = ?, processed_bytes = ? WHERE id = ? AND status = 'running'",
+            )
+            .bind(progress.total_bytes() as i64)
+            .bind(progress.processed_bytes() as i64)
+            .bind(&job_id)
+            .execute(&pool)
+            .await;
+        }
+    }
+
+    /// Detail job milik user
+    pub async fn get(pool: &MySqlPool, job_id: &str, user_id: &str) -> ApiResult<JobResponse> {
+        let job = sqlx::query_as::<_, Job>("SELECT * FROM jobs WHERE id = ? AND user_id = ?")
+            .bind(job_id)
+            .bind(user_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or_else(|| ApiError::NotFound("Job".to_string()))?;
+
+        Ok(job.into())
+    }
+
+    /// Daftar job milik user, terbaru lebih dulu
+    pub async fn list(
+        pool: &MySqlPool,
+        user_id: &str,
+        limit: Option<u32>,
+    ) -> ApiResult<Vec<JobResponse>> {
+        let jobs = sqlx::query_as::<_, Job>(
+            "SELECT * FROM jobs WHERE user_id = ? ORDER BY created_at DESC LIMIT ?",
+        )
+        .bind(user_id)
+        .bind(limit.unwrap_or(50).clamp(1, MAX_LIST_LIMIT))
+        .fetch_all(pool)
+        .await?;
+
+        Ok(jobs.into_iter().map(Into::into).collect())
+    }
+
+    /// Batalkan job milik user
+    ///
+    /// Job `pending` langsung ditandai `cancelled`; job `running` berhenti di
+    /// titik pemeriksaan berikutnya dan statusnya diperbarui oleh worker.
+    pub async fn cancel(pool: &MySqlPool, job_id: &str, user_id: &str) -> ApiResult<JobResponse> {
+        let job = Self::get(pool, job_id, user_id).await?;
+        if JobStatus::is_final(&job.job.status) {
+            return Err(ApiError::ValidationError(
+                "Job sudah selesai dan tidak bisa dibatalkan".to_string(),
+            ));
+        }
+
+        if let Some(progress) = ACTIVE_JOBS
+            .lock()
+            .ok()
+            .and_then(|jobs| jobs.get(job_id).cloned())
+        {
+            progress.cancel();
+        }
+
+        sqlx::query(
+            "UPDATE jobs SET status = 'cancelled', completed_at = ? WHERE id = ? AND status = 'pending'",
+        )
+        .bind(Utc::now())
+        .bind(job_id)
+        .execute(pool)
+        .await?;
+
+        tracing::info!("Job {} cancelled by user {}", job_id, user_id);
+
+        Self::get(pool, job_id, user_id).await
+    }
+
+    /// Tandai job yang tertinggal dari proses sebelumnya sebagai gagal
+    async fn fail_interrupted(pool: &MySqlPool) -> ApiResult<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE jobs
+            SET status = 'failed', error_message = 'Job terhenti karena server restart', completed_at = ?
+            WHERE status IN ('pending', 'running')
+            "#,
+        )
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Hapus riwayat job yang sudah selesai lebih lama dari `retention_days`
+    async fn cleanup_finished(pool: &MySqlPool, retention_days: u32) -> ApiResult<u64> {
+        let cutoff = Utc::now() - chrono::Duration::days(retention_days as i64);
+        let result =
+            sqlx::query("DELETE FROM jobs WHERE completed_at IS NOT NULL AND completed_at < ?")
+                .bind(cutoff)
+                .execute(pool)
+                .await?;
+
+        Ok(result.rows_affected())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_reader_stops_on_cancel() {
+        let progress = JobProgress::default();
+        let mut reader = ProgressReader::new(&b"halo dunia"[..], &progress);
+
+        let mut buf = [0u8; 4];
+        assert_eq!(reader.read(&mut buf).unwrap(), 4);
+        assert_eq!(progress.processed_bytes(), 4);
+
+        progress.cancel();
+        assert!(reader.read(&mut buf).is_err());
+        assert!(matches!(
+            progress.check_cancelled(),
+            Err(ApiError::JobCancelled)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_blocking_task_result() {
+        let task = JobTask::blocking(JobKind::Search, "cari", |progress| {
+            progress.set_total(10);
+            progress.add_bytes(10);
+            Ok(vec!["index.php"])
+        });
+        assert_eq!(task.kind(), JobKind::Search);
+        assert_eq!(task.description, "cari");
+
+        let progress = Arc::new(JobProgress::default());
+        let value = task.execute(progress.clone()).await.unwrap();
+        assert_eq!(value, serde_json::json!(["index.php"]));
+        assert_eq!(progress.processed_bytes(), 10);
+    }
+
+    #[test]
+    fn test_description_truncated() {
+        let task = JobTask::blocking(JobKind::Copy, "é".repeat(600), |_| Ok(()));
+        assert_eq!(task.description.chars().count(), MAX_DESCRIPTION_LENGTH);
+    }
+}
@@ -0,0 +1,227 @@
+//! # Mail Policy Server
+//!
+//! Implementasi Postfix policy delegation protocol untuk menegakkan limit
+//! pengiriman email keluar. Postfix dikonfigurasi dengan:
+//!
+//! ```text
+//! smtpd_recipient_restrictions = ..., check_policy_service inet:127.0.0.1:10031, ...
+//! ```
+//!
+//! Setiap request berisi atribut `name=value` per baris dan diakhiri baris kosong.
+//! Server membalas `action=<aksi>` diikuti baris kosong.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use rocket::fairing::AdHoc;
+use sqlx::MySqlPool;
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, UnixListener};
+
+use crate::database::Database;
+use crate::models::SendLimitConfig;
+use crate::services::SendLimitService;
+
+/// Ukuran maksimum satu request policy (bytes)
+const MAX_REQUEST_SIZE: usize = 16 * 1024;
+
+/// Policy server untuk Postfix
+pub struct MailPolicyServer;
+
+impl MailPolicyServer {
+    /// Fairing yang menjalankan policy server setelah Rocket liftoff
+    ///
+    /// Server hanya dijalankan jika `MAIL_POLICY_ENABLED=true`.
+    pub fn fairing() -> AdHoc {
+        AdHoc::on_liftoff("Mail Policy Server", |rocket| {
+            Box::pin(async move {
+                let config = SendLimitConfig::default();
+                if !config.enabled {
+                    return;
+                }
+
+                let Some(db) = rocket.state::<Database>() else {
+                    tracing::error!("Mail policy server not started: database is not available");
+                    return;
+                };
+
+                let pool = db.get_pool().clone();
+                tokio::spawn(async move {
+                    if let Err(e) = Self::run(pool, config).await {
+                        tracing::error!("Mail policy server stopped: {}", e);
+                    }
+                });
+            })
+        })
+    }
+
+    /// Listen dan layani koneksi dari Postfix
+    pub async fn run(pool: MySqlPool, config: SendLimitConfig) -> std::io::Result<()> {
+        // Bersihkan bucket pemakaian lama secara berkala
+        let prune_pool = pool.clone();
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(Duration::from_secs(3600));
+            loop {
+                interval.tick().await;
+                if let Err(e) = SendLimitService::prune_usage(&prune_pool).await {
+                    tracing::warn!("Failed to prune send usage: {}", e);
+                }
+            }
+        });
+
+        if let Some(path) = config.listen.strip_prefix("unix:") {
+            let _ = std::fs::remove_file(path);
+            let listener = UnixListener::bind(path)?;
+            set_socket_permissions(path, &config.socket_group)?;
+            tracing::info!("📮 Mail policy server listening on unix:{}", path);
+
+            loop {
+                let (stream, _) = listener.accept().await?;
+                tokio::spawn(Self::handle_connection(stream, pool.clone()));
+            }
+        } else {
+            let listener = TcpListener::bind(&config.listen).await?;
+            tracing::info!("📮 Mail policy server listening on {}", config.listen);
+
+            loop {
+                let (stream, _) = listener.accept().await?;
+                tokio::spawn(Self::handle_connection(stream, pool.clone()));
+            }
+        }
+    }
+
+    /// Layani satu koneksi (Postfix bisa mengirim banyak request per koneksi)
+    async fn handle_connection<S>(stream: S, pool: MySqlPool)
+    where
+        S: AsyncRead + AsyncWrite + Unpin,
+    {
+        let (reader, mut writer) = tokio::io::split(stream);
+        let mut reader = BufReader::new(reader);
+        let mut request = String::new();
+        let mut line = String::new();
+
+        loop {
+            line.clear();
+            match reader.read_line(&mut line).await {
+                Ok(0) => return,
+                Ok(_) => {}
+                Err(e) => {
+                    tracing::debug!("Policy connection read error: {}", e);
+                    return;
+                }
+            }
+
+            if line.trim_end().is_empty() {
+                let attributes = parse_policy_request(&request);
+                request.clear();
+
+                let action = Self::evaluate(&pool, &attributes).await;
+                if let Err(e) = writer
+                    .write_all(format_policy_response(&action).as_bytes())
+                    .await
+                {
+                    tracing::debug!("Policy connection write error: {}", e);
+                    return;
+                }
+                continue;
+            }
+
+            if request.len() + line.len() > MAX_REQUEST_SIZE {
+                tracing::warn!("Policy request too large, closing connection");
+                return;
+            }
+            request.push_str(&line);
+        }
+    }
+
+    /// Tentukan aksi untuk satu request
+    ///
+    /// Hanya pengirim terautentikasi (SASL) pada tahap RCPT yang dihitung.
+    /// Jika database bermasalah, request diloloskan (`DUNNO`) agar mail flow tidak terhenti.
+    async fn evaluate(pool: &MySqlPool, attributes: &HashMap<String, String>) -> String {
+        let attribute = |name: &str| attributes.get(name).map(String::as_str).unwrap_or("");
+
+        if attribute("request") != "smtpd_access_policy" || attribute("protocol_state") != "RCPT" {
+            return "DUNNO".to_string();
+        }
+
+        let sasl_username = attribute("sasl_username");
+        if sasl_username.is_empty() {
+            return "DUNNO".to_string();
+        }
+
+        let client_address = attributes.get("client_address").map(String::as_str);
+
+        match SendLimitService::check_recipient(pool, sasl_username, client_address).await {
+            Ok(Some(exceeded)) => format!(
+                "DEFER 4.7.1 Sending limit exceeded ({} {} limit: {} recipients)",
+                exceeded.scope, exceeded.period, exceeded.limit
+            ),
+            Ok(None) => "DUNNO".to_string(),
+            Err(e) => {
+                tracing::error!("Send limit check failed for {}: {}", sasl_username, e);
+                "DUNNO".to_string()
+            }
+        }
+    }
+}
+
+/// Parse atribut request policy (`name=value` per baris)
+pub fn parse_policy_request(request: &str) -> HashMap<String, String> {
+    request
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+/// Format response policy
+pub fn format_policy_response(action: &str) -> String {
+    format!("action={}\n\n", action.replace(['\r', '\n'], " "))
+}
+
+/// Izinkan hanya Postfix (lewat group) terhubung ke unix socket
+fn set_socket_permissions(path: &str, group: &str) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o660))?;
+
+    let output = std::process::Command::new("sudo")
+        .args(["chgrp", group, "--", path])
+        .output()?;
+    if !output.status.success() {
+        return Err(std::io::Error::other(format!(
+            "chgrp {} failed: {}",
+            group,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_policy_request() {
+        let request = "request=smtpd_access_policy\nprotocol_state=RCPT\n\
+                       sasl_username=info@example.com\nrecipient=john@gmail.com\n\
+                       client_address=10.0.0.5\nccert_subject=\n";
+        let attributes = parse_policy_request(request);
+
+        assert_eq!(attributes["request"], "smtpd_access_policy");
+        assert_eq!(attributes["sasl_username"], "info@example.com");
+        assert_eq!(attributes["client_address"], "10.0.0.5");
+        assert_eq!(attributes["ccert_subject"], "");
+    }
+
+    #[test]
+    fn test_format_policy_response() {
+        assert_eq!(format_policy_response("DUNNO"), "action=DUNNO\n\n");
+        assert_eq!(
+            format_policy_response("DEFER 4.7.1 limit\nexceeded"),
+            "action=DEFER 4.7.1 limit exceeded\n\n"
+        );
+    }
+}
@@ -0,0 +1,280 @@
+//! # Mail Queue Service
+//!
+//! Business logic untuk inspeksi dan manajemen antrian Postfix.
+//! Membaca `postqueue -j` dan menjalankan aksi via `postsuper`.
+
+use std::collections::HashMap;
+use std::process::Command;
+
+use chrono::{TimeZone, Utc};
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{
+    MailQueueActionRequest, MailQueueActionResult, MailQueueMessage, MailQueueRecipient,
+    MailQueueResponse, MailQueueSenderCount, MailQueueSummary,
+};
+
+/// Satu baris output `postqueue -j`
+#[derive(Debug, Deserialize)]
+struct PostqueueEntry {
+    queue_name: String,
+    queue_id: String,
+    arrival_time: i64,
+    message_size: u64,
+    #[serde(default)]
+    sender: String,
+    #[serde(default)]
+    recipients: Vec<MailQueueRecipient>,
+}
+
+/// Service untuk mail queue operations
+pub struct MailQueueService;
+
+impl MailQueueService {
+    /// List pesan antrian dengan filter opsional
+    ///
+    /// # Arguments
+    /// * `sender` - Filter alamat pengirim (case-insensitive)
+    /// * `queue_name` - Filter nama antrian (active, deferred, hold, ...)
+    pub async fn list_messages(
+        sender: Option<&str>,
+        queue_name: Option<&str>,
+    ) -> ApiResult<MailQueueResponse> {
+        let messages: Vec<MailQueueMessage> = Self::read_queue()?
+            .into_iter()
+            .filter(|m| sender.is_none_or(|s| m.sender.eq_ignore_ascii_case(s)))
+            .filter(|m| queue_name.is_none_or(|q| m.queue_name == q))
+            .collect();
+
+        Ok(MailQueueResponse {
+            summary: summarize(&messages),
+            messages,
+        })
+    }
+
+    /// Jumlah pesan antrian per pengirim (untuk deteksi akun yang disalahgunakan)
+    pub async fn get_sender_counts() -> ApiResult<Vec<MailQueueSenderCount>> {
+        Ok(count_by_sender(&Self::read_queue()?))
+    }
+
+    /// Flush antrian (coba kirim ulang semua pesan deferred)
+    pub async fn flush() -> ApiResult<()> {
+        let output = Command::new("sudo")
+            .arg("postqueue")
+            .arg("-f")
+            .output()
+            .map_err(|e| ApiError::InternalError(format!("Failed to execute postqueue: {}", e)))?;
+
+        if !output.status.success() {
+            let err = String::from_utf8_lossy(&output.stderr);
+            return Err(ApiError::InternalError(format!("Postqueue error: {}", err)));
+        }
+
+        tracing::info!("Mail queue flushed");
+
+        Ok(())
+    }
+
+    /// Jalankan aksi hold/release/requeue/delete terhadap pesan antrian
+    pub async fn apply_action(request: MailQueueActionRequest) -> ApiResult<MailQueueActionResult> {
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        let flag = match request.action.as_str() {
+            "hold" => "-h",
+            "release" => "-H",
+            "requeue" => "-r",
+            "delete" => "-d",
+            _ => return Err(ApiError::ValidationError("Aksi tidak valid".to_string())),
+        };
+
+        // Tentukan target: queue ID eksplisit atau semua pesan dari sender
+        let queue_ids = match (request.queue_ids, request.sender.as_deref()) {
+            (Some(ids), _) if !ids.is_empty() => ids,
+            (_, Some(sender)) => Self::read_queue()?
+                .into_iter()
+                .filter(|m| m.sender.eq_ignore_ascii_case(sender))
+                .map(|m| m.queue_id)
+                .collect(),
+            _ => {
+                return Err(ApiError::MissingParameter(
+                    "queue_ids atau sender".to_string(),
+                ))
+            }
+        };
+
+        if queue_ids.is_empty() {
+            return Ok(MailQueueActionResult {
+                action: request.action,
+                affected: 0,
+            });
+        }
+
+        let mut command = Command::new("sudo");
+        command.arg("postsuper");
+        for queue_id in &queue_ids {
+            command.arg(flag).arg(queue_id);
+        }
+
+        let output = command
+            .output()
+            .map_err(|e| ApiError::InternalError(format!("Failed to execute postsuper: {}", e)))?;
+
+        if !output.status.success() {
+            let err = String::from_utf8_lossy(&output.stderr);
+            return Err(ApiError::InternalError(format!("Postsuper error: {}", err)));
+        }
+
+        tracing::info!(
+            "Mail queue action '{}' applied to {} messages",
+            request.action,
+            queue_ids.len()
+        );
+
+        Ok(MailQueueActionResult {
+            action: request.action,
+            affected: queue_ids.len(),
+        })
+    }
+
+    /// Baca seluruh antrian dari `postqueue -j`
+    fn read_queue() -> ApiResult<Vec<MailQueueMessage>> {
+        let output = Command::new("sudo")
+            .arg("postqueue")
+            .arg("-j")
+            .output()
+            .map_err(|e| ApiError::InternalError(format!("Failed to execute postqueue: {}", e)))?;
+
+        if !output.status.success() {
+            let err = String::from_utf8_lossy(&output.stderr);
+            return Err(ApiError::InternalError(format!("Postqueue error: {}", err)));
+        }
+
+        Ok(parse_postqueue_json(
+            &String::from_utf8_lossy(&output.stdout),
+            Utc::now().timestamp(),
+        ))
+    }
+}
+
+/// Parse output `postqueue -j` (satu objek JSON per baris)
+///
+/// Baris yang tidak bisa di-parse dilewati.
+pub fn parse_postqueue_json(output: &str, now: i64) -> Vec<MailQueueMessage> {
+    output
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match serde_json::from_str::<PostqueueEntry>(line) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                tracing::warn!("Skipping unparsable postqueue line: {}", e);
+                None
+            }
+        })
+        .map(|entry| {
+            let deferral_reason = entry
+                .recipients
+                .iter()
+                .find_map(|r| r.delay_reason.clone().filter(|reason| !reason.is_empty()));
+            let sender = if entry.sender.is_empty() {
+                "MAILER-DAEMON".to_string()
+            } else {
+                entry.sender
+            };
+
+            MailQueueMessage {
+                queue_id: entry.queue_id,
+                queue_name: entry.queue_name,
+                sender,
+                recipients: entry.recipients,
+                size: entry.message_size,
+                arrival_time: Utc
+                    .timestamp_opt(entry.arrival_time, 0)
+                    .single()
+                    .unwrap_or_else(Utc::now),
+                age_seconds: (now - entry.arrival_time).max(0),
+                deferral_reason,
+            }
+        })
+        .collect()
+}
+
+/// Hitung pesan per pengirim, diurutkan dari yang terbanyak
+pub fn count_by_sender(messages: &[MailQueueMessage]) -> Vec<MailQueueSenderCount> {
+    let mut counts: HashMap<String, MailQueueSenderCount> = HashMap::new();
+
+    for message in messages {
+        let key = message.sender.to_lowercase();
+        let entry = counts.entry(key.clone()).or_insert(MailQueueSenderCount {
+            sender: key,
+            count: 0,
+            recipient_count: 0,
+            total_size: 0,
+        });
+        entry.count += 1;
+        entry.recipient_count += message.recipients.len();
+        entry.total_size += message.size;
+    }
+
+    let mut result: Vec<MailQueueSenderCount> = counts.into_values().collect();
+    result.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.sender.cmp(&b.sender)));
+    result
+}
+
+/// Ringkasan jumlah dan ukuran antrian
+fn summarize(messages: &[MailQueueMessage]) -> MailQueueSummary {
+    let in_queue = |name: &str| messages.iter().filter(|m| m.queue_name == name).count();
+
+    MailQueueSummary {
+        total: messages.len(),
+        total_size: messages.iter().map(|m| m.size).sum(),
+        active: in_queue("active"),
+        deferred: in_queue("deferred"),
+        hold: in_queue("hold"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = r#"{"queue_name": "deferred", "queue_id": "4XyZ1k2Lm3", "arrival_time": 1700000000, "message_size": 2048, "forced_expire": false, "sender": "spam@example.com", "recipients": [{"address": "a@gmail.com", "delay_reason": "connect to gmail-smtp-in.l.google.com: Connection timed out"}, {"address": "b@gmail.com"}]}
+{"queue_name": "active", "queue_id": "5AbC2d3Ef4", "arrival_time": 1700000500, "message_size": 1024, "forced_expire": false, "sender": "", "recipients": [{"address": "c@yahoo.com"}]}
+not json
+{"queue_name": "hold", "queue_id": "6GhI3j4Kl5", "arrival_time": 1700000900, "message_size": 512, "forced_expire": false, "sender": "Spam@Example.com", "recipients": [{"address": "d@gmail.com"}]}
+"#;
+
+    #[test]
+    fn test_parse_postqueue_json() {
+        let messages = parse_postqueue_json(SAMPLE, 1700001000);
+        assert_eq!(messages.len(), 3);
+
+        assert_eq!(messages[0].queue_id, "4XyZ1k2Lm3");
+        assert_eq!(messages[0].recipients.len(), 2);
+        assert_eq!(messages[0].age_seconds, 1000);
+        assert_eq!(
+            messages[0].deferral_reason.as_deref(),
+            Some("connect to gmail-smtp-in.l.google.com: Connection timed out")
+        );
+        assert_eq!(messages[1].sender, "MAILER-DAEMON");
+        assert!(messages[1].deferral_reason.is_none());
+
+        let summary = summarize(&messages);
+        assert_eq!(summary.total, 3);
+        assert_eq!(summary.total_size, 3584);
+        assert_eq!((summary.active, summary.deferred, summary.hold), (1, 1, 1));
+    }
+
+    #[test]
+    fn test_count_by_sender() {
+        let counts = count_by_sender(&parse_postqueue_json(SAMPLE, 1700001000));
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts[0].sender, "spam@example.com");
+        assert_eq!(counts[0].count, 2);
+        assert_eq!(counts[0].recipient_count, 3);
+        assert_eq!(counts[0].total_size, 2560);
+    }
+}
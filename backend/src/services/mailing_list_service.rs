@@ -0,0 +1,1092 @@
+//! # Mailing List Service
+//!
+//! Business logic untuk mailing list per domain.
+//! Includes subscriber management (double opt-in, CSV import/export),
+//! provisioning list, subscriber aktif dan moderasi ke Mailman 3 lewat REST API,
+//! dan generate transport/virtual map Postfix untuk list manager.
+
+use chrono::Utc;
+use rand::distributions::Alphanumeric;
+use rand::{thread_rng, Rng};
+use sqlx::MySqlPool;
+use std::collections::HashSet;
+use std::fs;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{
+    AddSubscriberRequest, CreateMailingListRequest, Domain, ImportSubscribersRequest,
+    ImportSubscribersResult, MailingList, MailingListConfig, MailingListResponse,
+    MailingListSubscriber, UpdateMailingListRequest, LIST_ADDRESS_SUFFIXES,
+};
+
+/// Service untuk mailing list operations
+pub struct MailingListService;
+
+impl MailingListService {
+    // ==========================================
+    // MAILING LIST OPERATIONS
+    // ==========================================
+
+    /// Get all mailing lists untuk user
+    pub async fn get_user_lists(
+        pool: &MySqlPool,
+        user_id: &str,
+    ) -> ApiResult<Vec<MailingListResponse>> {
+        let lists = sqlx::query_as::<_, MailingList>(
+            "SELECT * FROM mailing_lists WHERE user_id = ? ORDER BY list_address",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        let mut responses = Vec::with_capacity(lists.len());
+        for list in lists {
+            responses.push(Self::to_response(pool, list).await?);
+        }
+
+        Ok(responses)
+    }
+
+    /// Get mailing list by ID
+    pub async fn get_list_by_id(
+        pool: &MySqlPool,
+        list_id: &str,
+        user_id: &str,
+    ) -> ApiResult<MailingListResponse> {
+        let list = Self::get_owned_list(pool, list_id, user_id).await?;
+        Self::to_response(pool, list).await
+    }
+
+    /// Create mailing list
+    pub async fn create_list(
+        pool: &MySqlPool,
+        user_id: &str,
+        request: CreateMailingListRequest,
+    ) -> ApiResult<MailingListResponse> {
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        // Verify domain ownership
+        let domain = sqlx::query_as::<_, Domain>("SELECT * FROM domains WHERE id = ?")
+            .bind(&request.domain_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or(ApiError::NotFound("Domain".to_string()))?;
+
+        if domain.user_id != user_id {
+            return Err(ApiError::Forbidden);
+        }
+
+        let list_name = request.list_name.to_lowercase();
+        let list_address = format!("{}@{}", list_name, domain.domain_name);
+
+        // Alamat list dan semua suffix-nya tidak boleh bentrok dengan mailbox/forwarder/list lain
+        for address in list_addresses(&list_address) {
+            let existing = sqlx::query_scalar::<_, i64>(
+                r#"
+                SELECT
+                    (SELECT COUNT(*) FROM email_accounts WHERE email_address = ?) +
+                    (SELECT COUNT(*) FROM email_forwarders WHERE source_email = ?) +
+                    (SELECT COUNT(*) FROM mailing_lists WHERE list_address = ?)
+                "#,
+            )
+            .bind(&address)
+            .bind(&address)
+            .bind(&address)
+            .fetch_one(pool)
+            .await?;
+
+            if existing > 0 {
+                return Err(ApiError::AlreadyExists(format!("Alamat {}", address)));
+            }
+        }
+
+        let list_id = Uuid::new_v4().to_string();
+        let now = Utc::now();
+
+        sqlx::query(
+            r#"
+            INSERT INTO mailing_lists (id, user_id, domain_id, list_name, list_address, description, list_type, moderation, reply_to_list, is_active, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, TRUE, ?, ?)
+            "#,
+        )
+        .bind(&list_id)
+        .bind(user_id)
+        .bind(&request.domain_id)
+        .bind(&list_name)
+        .bind(&list_address)
+        .bind(&request.description)
+        .bind(request.list_type.as_deref().unwrap_or("discussion"))
+        .bind(request.moderation.as_deref().unwrap_or("non_members"))
+        .bind(request.reply_to_list.unwrap_or(true))
+        .bind(now)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        // List yang gagal di-provision ke Mailman tidak disimpan
+        let list = Self::get_owned_list(pool, &list_id, user_id).await?;
+        if let Err(e) = Self::sync_mailman_list(pool, &list).await {
+            sqlx::query("DELETE FROM mailing_lists WHERE id = ?")
+                .bind(&list_id)
+                .execute(pool)
+                .await?;
+            return Err(e);
+        }
+
+        tracing::info!("Mailing list created: {} for user {}", list_address, user_id);
+
+        Self::sync_mta_maps(pool).await?;
+
+        Self::get_list_by_id(pool, &list_id, user_id).await
+    }
+
+    /// Update mailing list
+    pub async fn update_list(
+        pool: &MySqlPool,
+        list_id: &str,
+        user_id: &str,
+        request: UpdateMailingListRequest,
+    ) -> ApiResult<MailingListResponse> {
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        let list = Self::get_owned_list(pool, list_id, user_id).await?;
+
+        let description = request.description.or(list.description.clone());
+        let list_type = request.list_type.unwrap_or(list.list_type.clone());
+        let moderation = request.moderation.unwrap_or(list.moderation.clone());
+        let reply_to_list = request.reply_to_list.unwrap_or(list.reply_to_list);
+        let is_active = request.is_active.unwrap_or(list.is_active);
+
+        // Setting diterapkan ke Mailman dulu agar database tidak mendahului list manager
+        let updated = MailingList {
+            description: description.clone(),
+            list_type: list_type.clone(),
+            moderation: moderation.clone(),
+            reply_to_list,
+            is_active,
+            ..list.clone()
+        };
+        Self::sync_mailman_list(pool, &updated).await?;
+
+        sqlx::query(
+            "UPDATE mailing_lists SET description = ?, list_type = ?, moderation = ?, reply_to_list = ?, is_active = ?, updated_at = ? WHERE id = ?",
+        )
+        .bind(&description)
+        .bind(&list_type)
+        .bind(&moderation)
+        .bind(reply_to_list)
+        .bind(is_active)
+        .bind(Utc::now())
+        .bind(list_id)
+        .execute(pool)
+        .await?;
+
+        tracing::info!("Mailing list updated: {}", list.list_address);
+
+        if is_active != list.is_active {
+            Self::sync_mta_maps(pool).await?;
+        }
+
+        Self::get_list_by_id(pool, list_id, user_id).await
+    }
+
+    /// Delete mailing list beserta semua subscriber
+    pub async fn delete_list(pool: &MySqlPool, list_id: &str, user_id: &str) -> ApiResult<()> {
+        let list = Self::get_owned_list(pool, list_id, user_id).await?;
+
+        Self::mailman_call(
+            "DELETE",
+            &mailman_list_path(&list.list_address),
+            &[],
+            &[204, 404],
+        )?;
+
+        sqlx::query("DELETE FROM mailing_list_subscribers WHERE list_id = ?")
+            .bind(list_id)
+            .execute(pool)
+            .await?;
+
+        sqlx::query("DELETE FROM mailing_lists WHERE id = ?")
+            .bind(list_id)
+            .execute(pool)
+            .await?;
+
+        tracing::info!("Mailing list deleted: {}", list.list_address);
+
+        Self::sync_mta_maps(pool).await
+    }
+
+    // ==========================================
+    // SUBSCRIBER OPERATIONS
+    // ==========================================
+
+    /// Get all subscribers untuk mailing list
+    pub async fn get_subscribers(
+        pool: &MySqlPool,
+        list_id: &str,
+        user_id: &str,
+    ) -> ApiResult<Vec<MailingListSubscriber>> {
+        Self::get_owned_list(pool, list_id, user_id).await?;
+
+        let subscribers = sqlx::query_as::<_, MailingListSubscriber>(
+            "SELECT * FROM mailing_list_subscribers WHERE list_id = ? ORDER BY email",
+        )
+        .bind(list_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(subscribers)
+    }
+
+    /// Tambah subscriber ke mailing list
+    ///
+    /// Jika `require_confirmation` aktif (default), subscriber berstatus `pending`
+    /// dan email konfirmasi double opt-in dikirim.
+    pub async fn add_subscriber(
+        pool: &MySqlPool,
+        list_id: &str,
+        user_id: &str,
+        request: AddSubscriberRequest,
+    ) -> ApiResult<MailingListSubscriber> {
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        let list = Self::get_owned_list(pool, list_id, user_id).await?;
+        let email = request.email.trim().to_lowercase();
+
+        let existing = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM mailing_list_subscribers WHERE list_id = ? AND email = ?",
+        )
+        .bind(list_id)
+        .bind(&email)
+        .fetch_one(pool)
+        .await?;
+
+        if existing > 0 {
+            return Err(ApiError::AlreadyExists("Subscriber".to_string()));
+        }
+
+        let subscriber = Self::insert_subscriber(
+            pool,
+            &list,
+            &email,
+            request.name,
+            request.require_confirmation.unwrap_or(true),
+        )
+        .await?;
+
+        tracing::info!("Subscriber {} added to {}", email, list.list_address);
+
+        Ok(subscriber)
+    }
+
+    /// Hapus subscriber dari mailing list
+    pub async fn remove_subscriber(
+        pool: &MySqlPool,
+        list_id: &str,
+        subscriber_id: &str,
+        user_id: &str,
+    ) -> ApiResult<()> {
+        let list = Self::get_owned_list(pool, list_id, user_id).await?;
+
+        let subscriber = sqlx::query_as::<_, MailingListSubscriber>(
+            "SELECT * FROM mailing_list_subscribers WHERE id = ? AND list_id = ?",
+        )
+        .bind(subscriber_id)
+        .bind(list_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(ApiError::NotFound("Subscriber".to_string()))?;
+
+        if subscriber.status == "active" {
+            Self::mailman_unsubscribe(&list, &subscriber.email)?;
+        }
+
+        sqlx::query("DELETE FROM mailing_list_subscribers WHERE id = ?")
+            .bind(subscriber_id)
+            .execute(pool)
+            .await?;
+
+        tracing::info!("Subscriber removed from {}", list.list_address);
+
+        Ok(())
+    }
+
+    /// Import subscribers dari CSV (`email[,name]` per baris)
+    pub async fn import_subscribers(
+        pool: &MySqlPool,
+        list_id: &str,
+        user_id: &str,
+        request: ImportSubscribersRequest,
+    ) -> ApiResult<ImportSubscribersResult> {
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        let list = Self::get_owned_list(pool, list_id, user_id).await?;
+        let require_confirmation = request.require_confirmation.unwrap_or(true);
+
+        let (rows, invalid) = parse_subscriber_csv(&request.csv);
+        let mut result = ImportSubscribersResult {
+            invalid,
+            ..Default::default()
+        };
+
+        for (email, name) in rows {
+            let existing = sqlx::query_scalar::<_, i64>(
+                "SELECT COUNT(*) FROM mailing_list_subscribers WHERE list_id = ? AND email = ?",
+            )
+            .bind(list_id)
+            .bind(&email)
+            .fetch_one(pool)
+            .await?;
+
+            if existing > 0 {
+                result.skipped += 1;
+                continue;
+            }
+
+            Self::insert_subscriber(pool, &list, &email, name, require_confirmation).await?;
+            result.imported += 1;
+        }
+
+        tracing::info!(
+            "Imported {} subscribers to {} ({} skipped, {} invalid)",
+            result.imported,
+            list.list_address,
+            result.skipped,
+            result.invalid.len()
+        );
+
+        Ok(result)
+    }
+
+    /// Export subscribers sebagai CSV
+    pub async fn export_subscribers(
+        pool: &MySqlPool,
+        list_id: &str,
+        user_id: &str,
+    ) -> ApiResult<String> {
+        let subscribers = Self::get_subscribers(pool, list_id, user_id).await?;
+        Ok(build_subscriber_csv(&subscribers))
+    }
+
+    /// Konfirmasi subscription (double opt-in) menggunakan token
+    ///
+    /// Endpoint publik - dipanggil dari link di email konfirmasi.
+    pub async fn confirm_subscription(
+        pool: &MySqlPool,
+        token: &str,
+    ) -> ApiResult<MailingListSubscriber> {
+        let subscriber = sqlx::query_as::<_, MailingListSubscriber>(
+            "SELECT * FROM mailing_list_subscribers WHERE confirm_token = ? AND status = 'pending'",
+        )
+        .bind(token)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(ApiError::InvalidToken)?;
+
+        if subscriber.is_token_expired() {
+            return Err(ApiError::InvalidToken);
+        }
+
+        let list = sqlx::query_as::<_, MailingList>("SELECT * FROM mailing_lists WHERE id = ?")
+            .bind(&subscriber.list_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or(ApiError::NotFound("Mailing List".to_string()))?;
+        Self::mailman_subscribe(&list, &subscriber.email, subscriber.name.as_deref())?;
+
+        let now = Utc::now();
+        sqlx::query(
+            "UPDATE mailing_list_subscribers SET status = 'active', confirm_token = NULL, token_expires_at = NULL, confirmed_at = ? WHERE id = ?",
+        )
+        .bind(now)
+        .bind(&subscriber.id)
+        .execute(pool)
+        .await?;
+
+        tracing::info!("Subscription confirmed: {}", subscriber.email);
+
+        Ok(MailingListSubscriber {
+            status: "active".to_string(),
+            confirm_token: None,
+            token_expires_at: None,
+            confirmed_at: Some(now),
+            ..subscriber
+        })
+    }
+
+    // ==========================================
+    // MAILMAN INTEGRATION
+    // ==========================================
+
+    /// Provision list ke Mailman
+    ///
+    /// List dibuat jika belum ada, setting (deskripsi, tipe, moderasi,
+    /// Reply-To) diterapkan, lalu member disamakan dengan subscriber aktif.
+    async fn sync_mailman_list(pool: &MySqlPool, list: &MailingList) -> ApiResult<()> {
+        let list_path = mailman_list_path(&list.list_address);
+
+        if Self::mailman_request("GET", &list_path, &[])?.0 == 404 {
+            let domain = list.list_address.split_once('@').map_or("", |(_, d)| d);
+            // Domain yang sudah terdaftar ditolak dengan 400
+            Self::mailman_call(
+                "POST",
+                "/domains",
+                &[("mail_host", domain.to_string())],
+                &[201, 400],
+            )?;
+            Self::mailman_call(
+                "POST",
+                "/lists",
+                &[("fqdn_listname", list.list_address.clone())],
+                &[201],
+            )?;
+        }
+
+        Self::mailman_call(
+            "PATCH",
+            &format!("{}/config", list_path),
+            &mailman_list_settings(list),
+            &[204],
+        )?;
+
+        let subscribers = sqlx::query_as::<_, (String, Option<String>)>(
+            "SELECT email, name FROM mailing_list_subscribers WHERE list_id = ? AND status = 'active'",
+        )
+        .bind(&list.id)
+        .fetch_all(pool)
+        .await?;
+
+        let roster =
+            Self::mailman_call("GET", &format!("{}/roster/member", list_path), &[], &[200])?;
+        let members = parse_mailman_roster(&roster)?;
+
+        for (email, name) in &subscribers {
+            if !members.contains(email) {
+                Self::mailman_subscribe(list, email, name.as_deref())?;
+            }
+        }
+        for member in &members {
+            if !subscribers.iter().any(|(email, _)| email == member) {
+                Self::mailman_unsubscribe(list, member)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Daftarkan subscriber aktif sebagai member list (sudah dikonfirmasi panel)
+    fn mailman_subscribe(list: &MailingList, email: &str, name: Option<&str>) -> ApiResult<()> {
+        let mut form = vec![
+            ("list_id", mailman_list_id(&list.list_address)),
+            ("subscriber", email.to_string()),
+            ("pre_verified", "true".to_string()),
+            ("pre_confirmed", "true".to_string()),
+            ("pre_approved", "true".to_string()),
+            ("send_welcome_message", "false".to_string()),
+        ];
+        if let Some(name) = name {
+            form.push(("display_name", name.to_string()));
+        }
+
+        // 409 = sudah menjadi member
+        Self::mailman_call("POST", "/members", &form, &[201, 409]).map(|_| ())
+    }
+
+    /// Hapus member dari list (member yang sudah tidak ada diabaikan)
+    fn mailman_unsubscribe(list: &MailingList, email: &str) -> ApiResult<()> {
+        let path = format!(
+            "{}/member/{}",
+            mailman_list_path(&list.list_address),
+            encode_path_segment(email)
+        );
+        Self::mailman_call("DELETE", &path, &[], &[204, 404]).map(|_| ())
+    }
+
+    /// Request ke Mailman REST API, error jika status bukan salah satu `expected`
+    fn mailman_call(
+        method: &str,
+        path: &str,
+        form: &[(&str, String)],
+        expected: &[u16],
+    ) -> ApiResult<String> {
+        let (status, body) = Self::mailman_request(method, path, form)?;
+        if !expected.contains(&status) {
+            return Err(ApiError::InternalError(format!(
+                "Mailman API {} {} failed ({}): {}",
+                method,
+                path,
+                status,
+                body.trim()
+            )));
+        }
+        Ok(body)
+    }
+
+    /// Jalankan request ke Mailman REST API lewat `curl`
+    ///
+    /// Kredensial dikirim lewat config di stdin agar tidak terlihat di process list.
+    ///
+    /// # Returns
+    /// Tuple (HTTP status, body)
+    fn mailman_request(
+        method: &str,
+        path: &str,
+        form: &[(&str, String)],
+    ) -> ApiResult<(u16, String)> {
+        let config = MailingListConfig::default();
+
+        let mut command = Command::new("curl");
+        command
+            .args(["-sS", "-K", "-", "-X", method, "-w", "\n%{http_code}"])
+            .arg(format!(
+                "{}{}",
+                config.mailman_api_url.trim_end_matches('/'),
+                path
+            ));
+        for (key, value) in form {
+            command
+                .arg("--data-urlencode")
+                .arg(format!("{}={}", key, value));
+        }
+
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| ApiError::InternalError(format!("Failed to execute curl: {}", e)))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            let credentials = format!(
+                "{}:{}",
+                config.mailman_api_user, config.mailman_api_password
+            );
+            stdin
+                .write_all(format!("user = \"{}\"\n", escape_curl_config(&credentials)).as_bytes())
+                .map_err(|e| ApiError::InternalError(format!("Failed to write to curl: {}", e)))?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|e| ApiError::InternalError(format!("Failed to wait for curl: {}", e)))?;
+
+        if !output.status.success() {
+            let err = String::from_utf8_lossy(&output.stderr);
+            return Err(ApiError::InternalError(format!(
+                "Mailman API error: {}",
+                err
+            )));
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        let (body, status) = stdout.rsplit_once('\n').unwrap_or(("", &stdout));
+        let status = status
+            .trim()
+            .parse()
+            .map_err(|_| ApiError::InternalError("Invalid Mailman API response".to_string()))?;
+
+        Ok((status, body.to_string()))
+    }
+
+    // ==========================================
+    // MTA INTEGRATION
+    // ==========================================
+
+    /// Regenerate transport & virtual map Postfix untuk semua list aktif
+    ///
+    /// Setiap alamat list (dan suffix Mailman-nya) diarahkan ke transport
+    /// list manager, lalu `postmap` dijalankan untuk kedua map.
+    pub async fn sync_mta_maps(pool: &MySqlPool) -> ApiResult<()> {
+        let addresses = sqlx::query_scalar::<_, String>(
+            "SELECT list_address FROM mailing_lists WHERE is_active = TRUE ORDER BY list_address",
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let config = MailingListConfig::default();
+
+        fs::write(
+            &config.transport_map_path,
+            build_transport_map(&addresses, &config.transport),
+        )
+        .map_err(|e| ApiError::InternalError(format!("Transport map write error: {}", e)))?;
+
+        fs::write(&config.virtual_map_path, build_virtual_map(&addresses))
+            .map_err(|e| ApiError::InternalError(format!("Virtual map write error: {}", e)))?;
+
+        for map_path in [&config.transport_map_path, &config.virtual_map_path] {
+            let output = Command::new("postmap")
+                .arg(format!("hash:{}", map_path))
+                .output()
+                .map_err(|e| ApiError::InternalError(format!("Failed to execute postmap: {}", e)))?;
+
+            if !output.status.success() {
+                let err = String::from_utf8_lossy(&output.stderr);
+                return Err(ApiError::InternalError(format!("Postmap error: {}", err)));
+            }
+        }
+
+        Ok(())
+    }
+
+    // ==========================================
+    // HELPERS
+    // ==========================================
+
+    /// Ambil mailing list dan pastikan dimiliki user
+    async fn get_owned_list(
+        pool: &MySqlPool,
+        list_id: &str,
+        user_id: &str,
+    ) -> ApiResult<MailingList> {
+        let list = sqlx::query_as::<_, MailingList>("SELECT * FROM mailing_lists WHERE id = ?")
+            .bind(list_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or(ApiError::NotFound("Mailing List".to_string()))?;
+
+        if list.user_id != user_id {
+            return Err(ApiError::Forbidden);
+        }
+
+        Ok(list)
+    }
+
+    /// Build response dengan jumlah subscriber
+    async fn to_response(pool: &MySqlPool, list: MailingList) -> ApiResult<MailingListResponse> {
+        let (active, pending): (i64, i64) = sqlx::query_as(
+            r#"
+            SELECT
+                CAST(COALESCE(SUM(status = 'active'), 0) AS SIGNED),
+                CAST(COALESCE(SUM(status = 'pending'), 0) AS SIGNED)
+            FROM mailing_list_subscribers WHERE list_id = ?
+            "#,
+        )
+        .bind(&list.id)
+        .fetch_one(pool)
+        .await?;
+
+        Ok(MailingListResponse::from_list(list, active, pending))
+    }
+
+    /// Insert subscriber baru, kirim email konfirmasi jika perlu
+    async fn insert_subscriber(
+        pool: &MySqlPool,
+        list: &MailingList,
+        email: &str,
+        name: Option<String>,
+        require_confirmation: bool,
+    ) -> ApiResult<MailingListSubscriber> {
+        let now = Utc::now();
+        let (status, confirm_token, token_expires_at, confirmed_at) = if require_confirmation {
+            let token: String = thread_rng()
+                .sample_iter(&Alphanumeric)
+                .take(48)
+                .map(char::from)
+                .collect();
+            (
+                "pending",
+                Some(token),
+                Some(now + MailingListSubscriber::confirm_token_ttl()),
+                None,
+            )
+        } else {
+            ("active", None, None, Some(now))
+        };
+
+        let subscriber = MailingListSubscriber {
+            id: Uuid::new_v4().to_string(),
+            list_id: list.id.clone(),
+            email: email.to_string(),
+            name,
+            status: status.to_string(),
+            confirm_token,
+            token_expires_at,
+            confirmed_at,
+            created_at: now,
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO mailing_list_subscribers (id, list_id, email, name, status, confirm_token, token_expires_at, confirmed_at, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&subscriber.id)
+        .bind(&subscriber.list_id)
+        .bind(&subscriber.email)
+        .bind(&subscriber.name)
+        .bind(&subscriber.status)
+        .bind(&subscriber.confirm_token)
+        .bind(subscriber.token_expires_at)
+        .bind(subscriber.confirmed_at)
+        .bind(subscriber.created_at)
+        .execute(pool)
+        .await?;
+
+        // Subscriber tanpa konfirmasi langsung didaftarkan ke Mailman
+        if subscriber.confirm_token.is_none() {
+            if let Err(e) =
+                Self::mailman_subscribe(list, &subscriber.email, subscriber.name.as_deref())
+            {
+                sqlx::query("DELETE FROM mailing_list_subscribers WHERE id = ?")
+                    .bind(&subscriber.id)
+                    .execute(pool)
+                    .await?;
+                return Err(e);
+            }
+        }
+
+        if let Some(ref token) = subscriber.confirm_token {
+            if let Err(e) = Self::send_confirmation_email(list, &subscriber.email, token) {
+                tracing::warn!(
+                    "Failed to send confirmation email to {}: {}",
+                    subscriber.email,
+                    e
+                );
+            }
+        }
+
+        Ok(subscriber)
+    }
+
+    /// Kirim email konfirmasi double opt-in melalui `sendmail`
+    fn send_confirmation_email(list: &MailingList, email: &str, token: &str) -> ApiResult<()> {
+        let config = MailingListConfig::default();
+        let confirm_url = format!(
+            "{}/api/mailing-lists/subscriptions/confirm/{}",
+            config.confirm_base_url.trim_end_matches('/'),
+            token
+        );
+
+        let message = format!(
+            "From: {from}\r\nTo: {to}\r\nSubject: Konfirmasi langganan {list}\r\n\
+             Content-Type: text/plain; charset=utf-8\r\n\r\n\
+             Seseorang (semoga Anda) meminta untuk berlangganan mailing list {list}.\r\n\r\n\
+             Klik link berikut untuk konfirmasi:\r\n{url}\r\n\r\n\
+             Abaikan email ini jika Anda tidak merasa mendaftar.\r\n",
+            from = config.sender_address,
+            to = email,
+            list = list.list_address,
+            url = confirm_url,
+        );
+
+        let mut child = Command::new("sendmail")
+            .arg("-t")
+            .arg("-i")
+            .stdin(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| ApiError::InternalError(format!("Failed to spawn sendmail: {}", e)))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(message.as_bytes())
+                .map_err(|e| ApiError::InternalError(format!("Failed to write to sendmail: {}", e)))?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|e| ApiError::InternalError(format!("Failed to wait for sendmail: {}", e)))?;
+
+        if !output.status.success() {
+            let err = String::from_utf8_lossy(&output.stderr);
+            return Err(ApiError::InternalError(format!("Sendmail error: {}", err)));
+        }
+
+        Ok(())
+    }
+}
+
+/// Semua alamat yang dilayani list manager untuk satu list
+fn list_addresses(list_address: &str) -> Vec<String> {
+    let (local, domain) = list_address.split_once('@').unwrap_or((list_address, ""));
+    LIST_ADDRESS_SUFFIXES
+        .iter()
+        .map(|suffix| format!("{}{}@{}", local, suffix, domain))
+        .collect()
+}
+
+/// Generate isi transport map Postfix (`alamat transport`)
+fn build_transport_map(list_addresses: &[String], transport: &str) -> String {
+    let mut content = String::from("# Generated by NusaPanel - jangan edit manual\n");
+    for list_address in list_addresses {
+        for address in self::list_addresses(list_address) {
+            content.push_str(&format!("{} {}\n", address, transport));
+        }
+    }
+    content
+}
+
+/// Generate isi virtual alias map Postfix agar alamat list diterima
+fn build_virtual_map(list_addresses: &[String]) -> String {
+    let mut content = String::from("# Generated by NusaPanel - jangan edit manual\n");
+    for list_address in list_addresses {
+        for address in self::list_addresses(list_address) {
+            content.push_str(&format!("{} {}\n", address, address));
+        }
+    }
+    content
+}
+
+/// Path resource list di Mailman REST API
+fn mailman_list_path(list_address: &str) -> String {
+    format!("/lists/{}", encode_path_segment(list_address))
+}
+
+/// List-ID Mailman (`news@example.com` -> `news.example.com`)
+fn mailman_list_id(list_address: &str) -> String {
+    list_address.replacen('@', ".", 1)
+}
+
+/// Setting list Mailman dari tipe list, moderasi dan Reply-To
+///
+/// List announcement dan moderasi `all` menahan post member untuk moderator;
+/// moderasi `none` menerima post dari non-member.
+fn mailman_list_settings(list: &MailingList) -> Vec<(&'static str, String)> {
+    let member_action = if list.list_type == "announcement" || list.moderation == "all" {
+        "hold"
+    } else {
+        "accept"
+    };
+    let nonmember_action = if list.moderation == "none" && list.list_type != "announcement" {
+        "accept"
+    } else {
+        "hold"
+    };
+    let reply_goes_to_list = if list.reply_to_list {
+        "point_to_list"
+    } else {
+        "no_munging"
+    };
+
+    vec![
+        ("description", list.description.clone().unwrap_or_default()),
+        ("default_member_action", member_action.to_string()),
+        ("default_nonmember_action", nonmember_action.to_string()),
+        ("reply_goes_to_list", reply_goes_to_list.to_string()),
+    ]
+}
+
+/// Alamat member dari response roster Mailman (`entries` tidak ada jika kosong)
+fn parse_mailman_roster(body: &str) -> ApiResult<HashSet<String>> {
+    let roster: serde_json::Value = serde_json::from_str(body)
+        .map_err(|e| ApiError::InternalError(format!("Invalid Mailman roster: {}", e)))?;
+
+    Ok(roster["entries"]
+        .as_array()
+        .map(|entries| {
+            entries
+                .iter()
+                .filter_map(|entry| entry["email"].as_str())
+                .map(str::to_lowercase)
+                .collect()
+        })
+        .unwrap_or_default())
+}
+
+/// Percent-encode satu segment path URL
+fn encode_path_segment(segment: &str) -> String {
+    segment
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'.' | b'-' | b'_' | b'@' | b'+' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Escape nilai string di config curl (`-K`)
+fn escape_curl_config(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Parse CSV subscriber (`email[,name]`), header `email` opsional
+///
+/// # Returns
+/// Tuple (baris valid, baris tidak valid)
+fn parse_subscriber_csv(csv: &str) -> (Vec<(String, Option<String>)>, Vec<String>) {
+    let mut rows: Vec<(String, Option<String>)> = Vec::new();
+    let mut invalid = Vec::new();
+
+    for (index, line) in csv.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (email, name) = match line.split_once(',') {
+            Some((email, name)) => (email, Some(name)),
+            None => (line, None),
+        };
+        let email = unquote_csv_field(email).to_lowercase();
+        let name = name
+            .map(unquote_csv_field)
+            .filter(|n| !n.is_empty());
+
+        if index == 0 && email == "email" {
+            continue;
+        }
+
+        if !validator::validate_email(&email) {
+            invalid.push(line.to_string());
+            continue;
+        }
+
+        if rows.iter().any(|(existing, _)| *existing == email) {
+            continue;
+        }
+
+        rows.push((email, name));
+    }
+
+    (rows, invalid)
+}
+
+/// Hapus whitespace dan tanda kutip CSV dari field
+fn unquote_csv_field(field: &str) -> String {
+    let field = field.trim();
+    field
+        .strip_prefix('"')
+        .and_then(|f| f.strip_suffix('"'))
+        .map(|f| f.replace("\"\"", "\""))
+        .unwrap_or_else(|| field.to_string())
+}
+
+/// Escape field CSV jika mengandung koma, kutip, atau newline
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// Build CSV export subscriber
+fn build_subscriber_csv(subscribers: &[MailingListSubscriber]) -> String {
+    let mut csv = String::from("email,name,status,confirmed_at\n");
+    for subscriber in subscribers {
+        csv.push_str(&format!(
+            "{},{},{},{}\n",
+            escape_csv_field(&subscriber.email),
+            escape_csv_field(subscriber.name.as_deref().unwrap_or("")),
+            subscriber.status,
+            subscriber
+                .confirmed_at
+                .map(|d| d.to_rfc3339())
+                .unwrap_or_default()
+        ));
+    }
+    csv
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_list_addresses_include_manager_suffixes() {
+        let addresses = list_addresses("news@example.com");
+        assert_eq!(addresses[0], "news@example.com");
+        assert!(addresses.contains(&"news-bounces@example.com".to_string()));
+        assert!(addresses.contains(&"news-request@example.com".to_string()));
+        assert_eq!(addresses.len(), LIST_ADDRESS_SUFFIXES.len());
+    }
+
+    #[test]
+    fn test_build_transport_map() {
+        let map = build_transport_map(
+            &["news@example.com".to_string()],
+            "lmtp:[127.0.0.1]:8024",
+        );
+        assert!(map.contains("news@example.com lmtp:[127.0.0.1]:8024\n"));
+        assert!(map.contains("news-owner@example.com lmtp:[127.0.0.1]:8024\n"));
+    }
+
+    #[test]
+    fn test_mailman_list_settings() {
+        let list = MailingList {
+            id: "1".to_string(),
+            user_id: "1".to_string(),
+            domain_id: "1".to_string(),
+            list_name: "news".to_string(),
+            list_address: "news@example.com".to_string(),
+            description: None,
+            list_type: "discussion".to_string(),
+            moderation: "non_members".to_string(),
+            reply_to_list: true,
+            is_active: true,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        };
+        let setting = |list: &MailingList, key: &str| {
+            mailman_list_settings(list)
+                .into_iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v)
+                .unwrap()
+        };
+
+        assert_eq!(setting(&list, "default_member_action"), "accept");
+        assert_eq!(setting(&list, "default_nonmember_action"), "hold");
+        assert_eq!(setting(&list, "reply_goes_to_list"), "point_to_list");
+
+        let announcement = MailingList {
+            list_type: "announcement".to_string(),
+            moderation: "none".to_string(),
+            ..list
+        };
+        assert_eq!(setting(&announcement, "default_member_action"), "hold");
+        assert_eq!(setting(&announcement, "default_nonmember_action"), "hold");
+
+        assert_eq!(mailman_list_id("news@example.com"), "news.example.com");
+        assert_eq!(
+            encode_path_segment("a b#c@example.com"),
+            "a%20b%23c@example.com"
+        );
+    }
+
+    #[test]
+    fn test_parse_mailman_roster() {
+        let members = parse_mailman_roster(
+            r#"{"entries": [{"email": "John@example.com"}], "total_size": 1}"#,
+        )
+        .unwrap();
+        assert!(members.contains("john@example.com"));
+        assert!(parse_mailman_roster(r#"{"total_size": 0}"#)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parse_subscriber_csv() {
+        let csv = "email,name\njohn@example.com,John Doe\n\"Jane@Example.com\",\"Doe, Jane\"\nnot-an-email\njohn@example.com\n";
+        let (rows, invalid) = parse_subscriber_csv(csv);
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0], ("john@example.com".to_string(), Some("John Doe".to_string())));
+        assert_eq!(rows[1].0, "jane@example.com");
+        assert_eq!(invalid, vec!["not-an-email".to_string()]);
+    }
+
+    #[test]
+    fn test_escape_csv_field() {
+        assert_eq!(escape_csv_field("John"), "John");
+        assert_eq!(escape_csv_field("Doe, Jane"), "\"Doe, Jane\"");
+        assert_eq!(escape_csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+    }
+}
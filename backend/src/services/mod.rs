@@ -3,36 +3,72 @@
 //! Business logic layer untuk NusaPanel.
 
 pub mod app_installer_service;
+pub mod archive_service;
 pub mod auth_service;
+pub mod chunked_upload_service;
+pub mod database_backup_service;
 pub mod database_service;
+pub mod database_stats_service;
 pub mod domain_service;
 pub mod email_service;
 pub mod file_service;
 pub mod ftp_service;
+pub mod job_service;
+pub mod mail_policy_server;
+pub mod mail_queue_service;
+pub mod mailing_list_service;
 pub mod phpmyadmin_signon_service;
+pub mod postgres_service;
+pub mod quota_service;
 pub mod redis_service;
 pub mod redis_service_real;
+pub mod remote_mysql_service;
 pub mod security_service;
 pub mod security_service_real;
+pub mod send_limit_service;
+pub mod sftp_service;
+pub mod spam_filter_service;
+pub mod staging_area;
 pub mod system_service;
 pub mod system_service_real;
+pub mod trash_service;
 pub mod nodejs_service;
 pub mod user_service;
 pub mod user_service_real;
+pub mod version_service;
 pub mod web_server_service;
 pub mod web_server_service_real;
+pub mod webmail_signon_service;
 
 pub use app_installer_service::*;
+pub use archive_service::*;
 pub use auth_service::*;
+pub use chunked_upload_service::*;
+pub use database_backup_service::*;
 pub use database_service::*;
+pub use database_stats_service::*;
 pub use domain_service::*;
 pub use email_service::*;
 pub use file_service::*;
 pub use ftp_service::*;
+pub use job_service::*;
+pub use mail_policy_server::*;
+pub use mail_queue_service::*;
+pub use mailing_list_service::*;
 pub use nodejs_service::*;
 pub use phpmyadmin_signon_service::*;
+pub use postgres_service::*;
+pub use quota_service::*;
 pub use redis_service_real::RedisServiceReal as RedisService;
+pub use remote_mysql_service::*;
 pub use security_service_real::SecurityServiceReal as SecurityService;
+pub use send_limit_service::*;
+pub use sftp_service::*;
+pub use spam_filter_service::*;
+pub use staging_area::*;
 pub use system_service_real::SystemServiceReal as SystemService;
+pub use trash_service::*;
 pub use user_service_real::UserServiceReal as UserService;
+pub use version_service::*;
 pub use web_server_service_real::WebServerServiceReal as WebServerService;
+pub use webmail_signon_service::*;
@@ -12,9 +12,11 @@ use sqlx::MySqlPool;
 
 use crate::errors::{ApiError, ApiResult};
 use crate::models::{
-    PhpMyAdminConfig, SignonCredentials, SignonResponse, SignonToken, ValidateTokenRequest,
+    DatabaseEngine, PhpMyAdminConfig, SignonCredentials, SignonResponse, SignonToken,
+    ValidateTokenRequest,
 };
 use crate::services::DatabaseService;
+use crate::utils::secrets;
 
 /// In-memory storage untuk signon tokens
 /// Menggunakan RwLock untuk thread-safe access
@@ -58,6 +60,13 @@ impl PhpMyAdminSignonService {
             return Err(ApiError::Forbidden);
         }
 
+        // phpMyAdmin hanya untuk database user MySQL
+        if DatabaseEngine::from_column(&db_user.engine) != DatabaseEngine::Mysql {
+            return Err(ApiError::ValidationError(
+                "phpMyAdmin hanya tersedia untuk database MySQL".to_string(),
+            ));
+        }
+
         // 3. Buat token baru
         let token = SignonToken::new(
             db_user_id,
@@ -141,10 +150,7 @@ impl PhpMyAdminSignonService {
             return Err(ApiError::Unauthorized);
         }
 
-        // 4. Ambil password dari database
-        // Karena kita menyimpan password sebagai hash, kita perlu pendekatan berbeda
-        // Untuk SSO, kita perlu menyimpan password terenkripsi (bukan hash)
-        // Sementara ini, kita return error dan perlu implementasi enkripsi password
+        // 4. Dekripsi password database user
         let password = Self::get_db_user_password(pool, &token.db_user_id).await?;
 
         Ok(SignonCredentials {
@@ -157,16 +163,16 @@ impl PhpMyAdminSignonService {
     }
 
     /// Get password database user
-    /// 
-    /// # Note
-    /// Saat ini menggunakan fallback karena password disimpan sebagai hash.
-    /// Untuk production, implementasikan encrypted password storage.
+    ///
+    /// Password didekripsi dari `password_encrypted` dengan master key panel.
+    /// Plaintext hanya ada di memori selama validasi token.
+    ///
+    /// # Errors
+    /// - `ValidationError` jika belum ada password terenkripsi (user dibuat
+    ///   sebelum master key dikonfigurasi; password harus diganti dulu)
     async fn get_db_user_password(pool: &MySqlPool, db_user_id: &str) -> ApiResult<String> {
-        // Query untuk mendapatkan password
-        // Karena SSO membutuhkan password plaintext, coba ambil dari password_encrypted
-        // Fallback ke password_hash (tapi ini tidak akan work karena hash tidak bisa di-decrypt)
-        let result: Option<(Option<String>, String)> = sqlx::query_as(
-            "SELECT password_encrypted, password_hash FROM database_users WHERE id = ?"
+        let result: Option<Option<String>> = sqlx::query_scalar(
+            "SELECT password_encrypted FROM database_users WHERE id = ?",
         )
         .bind(db_user_id)
         .fetch_optional(pool)
@@ -174,24 +180,63 @@ impl PhpMyAdminSignonService {
         .map_err(|e| ApiError::InternalError(format!("Database query failed: {}", e)))?;
 
         match result {
-            Some((Some(encrypted_password), _)) if !encrypted_password.is_empty() => {
-                // Password terenkripsi tersedia
-                // TODO: Decrypt password menggunakan master key
-                Ok(encrypted_password)
+            Some(Some(encrypted_password)) if !encrypted_password.is_empty() => {
+                secrets::decrypt(&encrypted_password, db_user_id)
             }
-            Some((_, password_hash)) => {
-                // Fallback: untuk development, gunakan password_hash sebagai placeholder
-                // WARNING: Ini TIDAK akan work di production karena hash tidak bisa di-decrypt
-                // User harus mengupdate password untuk enable SSO
-                Err(ApiError::InternalError(
-                    "SSO not available: password stored as hash (cannot decrypt). \
-                    Add 'password_encrypted' column and update user password to enable SSO.".to_string()
-                ))
-            }
-            None => {
-                Err(ApiError::NotFound("Database user not found".to_string()))
+            Some(_) => Err(ApiError::ValidationError(
+                "SSO not available: update the database user password to enable SSO".to_string(),
+            )),
+            None => Err(ApiError::NotFound("Database user not found".to_string())),
+        }
+    }
+
+    /// Enkripsi ulang password database user dengan master key aktif
+    ///
+    /// Dijalankan setelah rotasi master key; key lama di `SECRETS_PREVIOUS_KEYS`
+    /// boleh dihapus setelah hasilnya `failed == 0`.
+    ///
+    /// # Returns
+    /// Tuple (jumlah yang dienkripsi ulang, jumlah yang gagal didekripsi)
+    pub async fn rotate_credentials(pool: &MySqlPool) -> ApiResult<(usize, usize)> {
+        let keyring = secrets::keyring()?;
+
+        let rows = sqlx::query_as::<_, (String, String)>(
+            "SELECT id, password_encrypted FROM database_users WHERE password_encrypted IS NOT NULL",
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let mut rotated = 0;
+        let mut failed = 0;
+        for (id, stored) in rows {
+            if !keyring.needs_rotation(&stored) {
+                continue;
             }
+
+            let resealed = match keyring.decrypt(&stored, &id) {
+                Ok(password) => keyring.encrypt(&password, &id)?,
+                Err(e) => {
+                    tracing::warn!("Cannot decrypt credentials of database user {}: {}", id, e);
+                    failed += 1;
+                    continue;
+                }
+            };
+
+            sqlx::query("UPDATE database_users SET password_encrypted = ? WHERE id = ?")
+                .bind(&resealed)
+                .bind(&id)
+                .execute(pool)
+                .await?;
+            rotated += 1;
         }
+
+        tracing::info!(
+            "Database user credentials re-encrypted: {} rotated, {} failed",
+            rotated,
+            failed
+        );
+
+        Ok((rotated, failed))
     }
 
     /// Cleanup expired tokens secara manual
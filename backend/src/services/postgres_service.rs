@@ -0,0 +1,263 @@
+//! # PostgreSQL Service
+//!
+//! Eksekusi statement administrasi PostgreSQL untuk managed database customer.
+//!
+//! Koneksi admin diambil dari `POSTGRES_ADMIN_URL` dan dibuat saat pertama kali
+//! dipakai; tanpa konfigurasi tersebut semua operasi PostgreSQL ditolak.
+//! Statement yang harus berjalan di dalam database customer (schema, default
+//! privileges) memakai koneksi terpisah ke database tersebut.
+
+use std::str::FromStr;
+
+use sqlx::postgres::{PgConnectOptions, PgConnection, PgPool, PgPoolOptions};
+use sqlx::Connection;
+use tokio::sync::OnceCell;
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{fragmentation_percent, DatabasePrivilege, DatabaseTableStats, PostgresConfig};
+use crate::utils::postgres::{self, GrantStatements};
+
+/// Pool koneksi admin PostgreSQL
+static ADMIN_POOL: OnceCell<PgPool> = OnceCell::const_new();
+
+/// Service untuk operasi PostgreSQL
+pub struct PostgresService;
+
+impl PostgresService {
+    /// URL admin PostgreSQL dari konfigurasi
+    pub fn admin_url() -> ApiResult<String> {
+        PostgresConfig::default().admin_url.ok_or_else(|| {
+            ApiError::ValidationError("PostgreSQL belum dikonfigurasi di server ini".to_string())
+        })
+    }
+
+    /// Pool admin (dibuat saat pertama kali dipakai)
+    async fn pool() -> ApiResult<&'static PgPool> {
+        let url = Self::admin_url()?;
+
+        ADMIN_POOL
+            .get_or_try_init(|| async {
+                PgPoolOptions::new()
+                    .max_connections(5)
+                    .connect(&url)
+                    .await
+                    .map_err(|e| {
+                        tracing::error!("Failed to connect to PostgreSQL: {}", e);
+                        ApiError::InternalError("Failed to connect to PostgreSQL".to_string())
+                    })
+            })
+            .await
+    }
+
+    /// Koneksi admin ke database tertentu
+    async fn connect_to(database: &str) -> ApiResult<PgConnection> {
+        let options = PgConnectOptions::from_str(&Self::admin_url()?)
+            .map_err(|e| ApiError::InternalError(format!("Invalid POSTGRES_ADMIN_URL: {}", e)))?
+            .database(database);
+
+        PgConnection::connect_with(&options).await.map_err(|e| {
+            tracing::error!(
+                "Failed to connect to PostgreSQL database {}: {}",
+                database,
+                e
+            );
+            ApiError::InternalError("Failed to connect to PostgreSQL".to_string())
+        })
+    }
+
+    /// Jalankan statement berurutan di koneksi admin
+    async fn execute(statements: &[String], error_message: &str) -> ApiResult<()> {
+        let pool = Self::pool().await?;
+
+        for statement in statements {
+            sqlx::query(statement).execute(pool).await.map_err(|e| {
+                tracing::error!("{}: {}", error_message, e);
+                ApiError::InternalError(error_message.to_string())
+            })?;
+        }
+
+        Ok(())
+    }
+
+    /// Jalankan statement berurutan di dalam database tertentu
+    async fn execute_in(
+        database: &str,
+        statements: &[String],
+        error_message: &str,
+    ) -> ApiResult<()> {
+        if statements.is_empty() {
+            return Ok(());
+        }
+
+        let mut conn = Self::connect_to(database).await?;
+        for statement in statements {
+            sqlx::query(statement)
+                .execute(&mut conn)
+                .await
+                .map_err(|e| {
+                    tracing::error!("{}: {}", error_message, e);
+                    ApiError::InternalError(error_message.to_string())
+                })?;
+        }
+        let _ = conn.close().await;
+
+        Ok(())
+    }
+
+    // ==========================================
+    // DATABASE
+    // ==========================================
+
+    /// Buat database beserta role owner-nya
+    pub async fn create_database(name: &str, encoding: &str) -> ApiResult<()> {
+        Self::execute(
+            &postgres::create_database(name, encoding)?,
+            "Failed to create database",
+        )
+        .await?;
+
+        Self::execute_in(
+            name,
+            &postgres::setup_database_schema(name)?,
+            "Failed to set up database schema",
+        )
+        .await
+    }
+
+    /// Hapus database beserta role owner-nya
+    pub async fn drop_database(name: &str) -> ApiResult<()> {
+        Self::execute(&postgres::drop_database(name)?, "Failed to delete database").await
+    }
+
+    /// Ukuran database dalam bytes
+    pub async fn database_size(name: &str) -> ApiResult<i64> {
+        let size = sqlx::query_scalar::<_, i64>(postgres::DATABASE_SIZE_QUERY)
+            .bind(name)
+            .fetch_one(Self::pool().await?)
+            .await?;
+
+        Ok(size)
+    }
+
+    /// Statistik tabel database (fragmentasi dari dead tuples)
+    pub async fn table_stats(database: &str) -> ApiResult<Vec<DatabaseTableStats>> {
+        let mut conn = Self::connect_to(database).await?;
+        let rows = sqlx::query_as::<_, (String, String, i64, i64, i64, i64, i64)>(
+            postgres::TABLE_STATS_QUERY,
+        )
+        .fetch_all(&mut conn)
+        .await?;
+        let _ = conn.close().await;
+
+        Ok(rows
+            .into_iter()
+            .map(
+                |(name, engine, rows_estimate, data_bytes, index_bytes, live, dead)| {
+                    DatabaseTableStats {
+                        name,
+                        engine: Some(engine),
+                        rows_estimate,
+                        data_bytes,
+                        index_bytes,
+                        free_bytes: 0,
+                        fragmentation_percent: fragmentation_percent(dead, live + dead),
+                    }
+                },
+            )
+            .collect())
+    }
+
+    // ==========================================
+    // ROLE
+    // ==========================================
+
+    /// Buat role login
+    pub async fn create_role(username: &str, password: &str) -> ApiResult<()> {
+        Self::execute(
+            &[postgres::create_role(username, password)?],
+            "Failed to create database user",
+        )
+        .await
+    }
+
+    /// Ganti password role
+    pub async fn alter_role_password(username: &str, password: &str) -> ApiResult<()> {
+        Self::execute(
+            &[postgres::alter_role_password(username, password)?],
+            "Failed to update password",
+        )
+        .await
+    }
+
+    /// Hapus role setelah semua grant-nya dicabut
+    pub async fn drop_role(username: &str, databases: &[String]) -> ApiResult<()> {
+        for database in databases {
+            // Database mungkin sudah dihapus di luar panel
+            if let Err(e) = Self::revoke_all(database, username).await {
+                tracing::warn!("Revoke {} on {} failed: {}", username, database, e);
+            }
+        }
+
+        Self::execute(
+            &[postgres::drop_role(username)?],
+            "Failed to delete database user",
+        )
+        .await
+    }
+
+    // ==========================================
+    // GRANT
+    // ==========================================
+
+    /// Ganti privileges role pada database (REVOKE lalu GRANT)
+    pub async fn apply_grant(
+        database: &str,
+        username: &str,
+        privileges: &[DatabasePrivilege],
+    ) -> ApiResult<()> {
+        let statements = postgres::grant(privileges, database, username)?;
+
+        Self::revoke_all(database, username).await?;
+        Self::run_grant_statements(database, &statements, "Failed to grant privileges").await
+    }
+
+    /// Cabut semua privileges role pada database
+    pub async fn revoke_all(database: &str, username: &str) -> ApiResult<()> {
+        let statements = postgres::revoke_all(database, username)?;
+        Self::run_grant_statements(database, &statements, "Failed to revoke privileges").await
+    }
+
+    /// Privilege level database dan role owner yang dimiliki role
+    pub async fn effective_grants(username: &str) -> ApiResult<Vec<String>> {
+        let pool = Self::pool().await?;
+
+        let privileges = sqlx::query_as::<_, (String, String)>(postgres::DATABASE_PRIVILEGES_QUERY)
+            .bind(username)
+            .fetch_all(pool)
+            .await?;
+
+        let memberships = sqlx::query_scalar::<_, String>(postgres::ROLE_MEMBERSHIPS_QUERY)
+            .bind(username)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(privileges
+            .into_iter()
+            .map(|(database, privilege)| format!("{} ON DATABASE {}", privilege, database))
+            .chain(
+                memberships
+                    .into_iter()
+                    .map(|role| format!("MEMBER OF {}", role)),
+            )
+            .collect())
+    }
+
+    async fn run_grant_statements(
+        database: &str,
+        statements: &GrantStatements,
+        error_message: &str,
+    ) -> ApiResult<()> {
+        Self::execute(&statements.cluster, error_message).await?;
+        Self::execute_in(database, &statements.database, error_message).await
+    }
+}
@@ -0,0 +1,307 @@
+//! # Quota Service
+//!
+//! Kuota disk per user. Batas disimpan di `users.disk_quota_mb` dan, jika
+//! filesystem mendukung, diterapkan ke Linux quota lewat `setquota`.
+//! Pemakaian dibaca dari `repquota` bila quota filesystem aktif, dengan
+//! fallback `du` pada home user; hasilnya di-cache `DISK_QUOTA_CACHE_SECS`.
+
+use std::collections::HashMap;
+#[cfg(unix)]
+use std::os::unix::fs::MetadataExt;
+use std::path::Path;
+use std::process::Command;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use once_cell::sync::Lazy;
+use sqlx::MySqlPool;
+use validator::Validate;
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{
+    DiskQuota, DiskUsageResponse, QuotaConfig, UpdateDiskQuotaRequest, UsageSource,
+};
+use crate::services::FileService;
+use crate::utils::system::user_name;
+
+/// Pemakaian disk yang di-cache
+#[derive(Debug, Clone, Copy)]
+struct CachedUsage {
+    measured_at: Instant,
+    used_bytes: u64,
+    source: UsageSource,
+}
+
+/// Cache pemakaian disk per username
+static USAGE_CACHE: Lazy<Mutex<HashMap<String, CachedUsage>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Service untuk kuota disk user
+pub struct QuotaService;
+
+impl QuotaService {
+    // ==========================================
+    // QUOTA
+    // ==========================================
+
+    /// Kuota dan pemakaian disk user
+    pub async fn get(pool: &MySqlPool, user_id: &str) -> ApiResult<DiskQuota> {
+        let (username, disk_quota_mb): (String, Option<i64>) =
+            sqlx::query_as("SELECT username, disk_quota_mb FROM users WHERE id = ?")
+                .bind(user_id)
+                .fetch_optional(pool)
+                .await?
+                .ok_or_else(|| ApiError::NotFound("User".to_string()))?;
+
+        let config = QuotaConfig::default();
+        let limit_bytes = resolve_limit(disk_quota_mb, &config);
+        let (used_bytes, source) =
+            tokio::task::spawn_blocking(move || Self::usage(&username, &config))
+                .await
+                .map_err(|e| ApiError::InternalError(format!("Disk usage task failed: {}", e)))?;
+
+        Ok(DiskQuota {
+            used_bytes,
+            limit_bytes,
+            source,
+        })
+    }
+
+    /// Ubah kuota disk user (admin)
+    ///
+    /// Batas disimpan di database lalu diterapkan ke filesystem quota jika
+    /// tersedia; kegagalan `setquota` hanya dicatat karena pengecekan di file
+    /// manager tetap memakai batas dari database.
+    pub async fn set_limit(
+        pool: &MySqlPool,
+        user_id: &str,
+        request: UpdateDiskQuotaRequest,
+    ) -> ApiResult<DiskUsageResponse> {
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        let result = sqlx::query("UPDATE users SET disk_quota_mb = ? WHERE id = ?")
+            .bind(request.disk_quota_mb)
+            .bind(user_id)
+            .execute(pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(ApiError::NotFound("User".to_string()));
+        }
+
+        let (username,): (String,) = sqlx::query_as("SELECT username FROM users WHERE id = ?")
+            .bind(user_id)
+            .fetch_one(pool)
+            .await?;
+        let limit_bytes = resolve_limit(request.disk_quota_mb, &QuotaConfig::default());
+
+        let owner = username.clone();
+        tokio::task::spawn_blocking(move || Self::apply_filesystem_quota(&owner, limit_bytes))
+            .await
+            .map_err(|e| ApiError::InternalError(format!("setquota task failed: {}", e)))?;
+        Self::invalidate(&username);
+
+        tracing::info!(
+            "Disk quota for user {} set to {:?} MB",
+            username,
+            request.disk_quota_mb
+        );
+
+        Self::get(pool, user_id).await.map(DiskUsageResponse::from)
+    }
+
+    /// Hapus cache pemakaian disk user
+    pub fn invalidate(username: &str) {
+        if let Ok(mut cache) = USAGE_CACHE.lock() {
+            cache.remove(username);
+        }
+    }
+
+    // ==========================================
+    // USAGE
+    // ==========================================
+
+    /// System user pemilik home (uid) beserta path home
+    #[cfg(unix)]
+    fn home_owner(username: &str) -> Option<(u32, String)> {
+        let home = FileService::get_user_base_path(username);
+        let uid = std::fs::metadata(&home).ok()?.uid();
+        Some((uid, home.to_string_lossy().to_string()))
+    }
+
+    /// Tanpa uid di luar unix, pemakaian tidak bisa diukur
+    #[cfg(not(unix))]
+    fn home_owner(_username: &str) -> Option<(u32, String)> {
+        None
+    }
+
+    /// Pemakaian disk user dalam bytes (cache, `repquota`, lalu `du`)
+    fn usage(username: &str, config: &QuotaConfig) -> (u64, UsageSource) {
+        let ttl = Duration::from_secs(config.usage_cache_secs);
+        if let Some(cached) = USAGE_CACHE
+            .lock()
+            .ok()
+            .and_then(|cache| cache.get(username).copied())
+        {
+            if cached.measured_at.elapsed() < ttl {
+                return (cached.used_bytes, cached.source);
+            }
+        }
+
+        let Some((uid, home)) = Self::home_owner(username) else {
+            return (0, UsageSource::Du);
+        };
+        let (used, source) = match Self::repquota_usage(uid) {
+            Some(used) => (used, UsageSource::Quota),
+            None => (Self::du_usage(Path::new(&home)), UsageSource::Du),
+        };
+
+        if let Ok(mut cache) = USAGE_CACHE.lock() {
+            cache.insert(
+                username.to_string(),
+                CachedUsage {
+                    measured_at: Instant::now(),
+                    used_bytes: used,
+                    source,
+                },
+            );
+        }
+        (used, source)
+    }
+
+    /// Pemakaian dari Linux filesystem quota, `None` jika quota tidak aktif
+    ///
+    /// `sudo -n` agar pengecekan di jalur write tidak menunggu prompt password.
+    fn repquota_usage(uid: u32) -> Option<u64> {
+        let output = Command::new("sudo")
+            .args(["-n", "repquota", "-a", "-u", "-n"])
+            .output()
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+
+        parse_repquota(&String::from_utf8_lossy(&output.stdout), uid)
+    }
+
+    /// Pemakaian dari `du -sb`, fallback menjumlah ukuran file
+    fn du_usage(home: &Path) -> u64 {
+        Command::new("du")
+            .arg("-sb")
+            .arg(home)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| parse_du(&String::from_utf8_lossy(&output.stdout)))
+            .unwrap_or_else(|| FileService::tree_size(home))
+    }
+
+    /// Terapkan batas ke Linux filesystem quota (best effort)
+    fn apply_filesystem_quota(username: &str, limit_bytes: Option<u64>) {
+        let Some((uid, _)) = Self::home_owner(username) else {
+            return;
+        };
+        // setquota memakai blok 1KB, 0 = tanpa batas
+        let blocks = limit_bytes.map(|limit| limit.div_ceil(1024)).unwrap_or(0);
+
+        let result = Command::new("sudo")
+            .arg("setquota")
+            .arg("-u")
+            .arg(user_name(uid))
+            .arg(blocks.to_string())
+            .arg(blocks.to_string())
+            .args(["0", "0", "-a"])
+            .output();
+
+        match result {
+            Ok(output) if output.status.success() => {}
+            Ok(output) => tracing::warn!(
+                "setquota for {} failed (filesystem quota unavailable?): {}",
+                username,
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+            Err(e) => tracing::warn!("Failed to execute setquota: {}", e),
+        }
+    }
+}
+
+/// Batas dalam bytes dari kolom `disk_quota_mb` (`None` = tanpa batas)
+fn resolve_limit(disk_quota_mb: Option<i64>, config: &QuotaConfig) -> Option<u64> {
+    let limit_mb = disk_quota_mb
+        .map(|mb| mb.max(0) as u64)
+        .unwrap_or(config.default_limit_mb);
+
+    (limit_mb > 0).then(|| limit_mb * 1024 * 1024)
+}
+
+/// Parse output `repquota -a -u -n` untuk uid (jumlah semua filesystem)
+///
+/// Baris user berbentuk `#<uid> <flags> <blocks> <soft> <hard> ...` dengan
+/// blocks dalam satuan 1KB.
+fn parse_repquota(output: &str, uid: u32) -> Option<u64> {
+    let id = format!("#{}", uid);
+    let blocks: Vec<u64> = output
+        .lines()
+        .filter_map(|line| {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.first() != Some(&id.as_str()) {
+                return None;
+            }
+            fields.get(2)?.trim_end_matches('*').parse().ok()
+        })
+        .collect();
+
+    (!blocks.is_empty()).then(|| blocks.iter().sum::<u64>() * 1024)
+}
+
+/// Parse output `du -sb` (`<bytes>\t<path>`)
+fn parse_du(output: &str) -> Option<u64> {
+    output.split_whitespace().next()?.parse().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resolve_limit() {
+        let config = QuotaConfig {
+            default_limit_mb: 100,
+            usage_cache_secs: 30,
+        };
+        assert_eq!(resolve_limit(None, &config), Some(100 * 1024 * 1024));
+        assert_eq!(resolve_limit(Some(5), &config), Some(5 * 1024 * 1024));
+        assert_eq!(resolve_limit(Some(0), &config), None);
+
+        let unlimited = QuotaConfig {
+            default_limit_mb: 0,
+            ..config
+        };
+        assert_eq!(resolve_limit(None, &unlimited), None);
+    }
+
+    #[test]
+    fn test_parse_repquota() {
+        let output = "\
+*** Report for user quotas on device /dev/sda1
+Block grace time: 7days; Inode grace time: 7days
+                        Block limits                File limits
+User            used    soft    hard  grace    used  soft  hard  grace
+----------------------------------------------------------------------
+#0        --  812345       0       0          45678     0     0
+#1001     +-   10240    8192   10240  6days      12     0     0
+
+*** Report for user quotas on device /dev/sdb1
+#1001     --     512       0       0              3     0     0
+";
+        assert_eq!(parse_repquota(output, 1001), Some((10240 + 512) * 1024));
+        assert_eq!(parse_repquota(output, 1002), None);
+    }
+
+    #[test]
+    fn test_parse_du() {
+        assert_eq!(parse_du("1048576\t/home/user_budi\n"), Some(1048576));
+        assert_eq!(parse_du(""), None);
+    }
+}
@@ -0,0 +1,540 @@
+//! # Remote MySQL Service
+//!
+//! Allow-list IP/CIDR per akun untuk akses MySQL dari luar server.
+//!
+//! Database user selalu dibuat sebagai `user@localhost`. Untuk setiap host di
+//! allow-list akun, dibuat account tambahan `user@<host>` dengan password dan
+//! grant yang sama. Port MySQL di firewall hanya dibuka untuk host tersebut.
+
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use chrono::Utc;
+use rocket::fairing::AdHoc;
+use sqlx::MySqlPool;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::database::Database;
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{
+    CreateRemoteMysqlHostRequest, DatabaseUser, RemoteMysqlConfig, RemoteMysqlHost,
+};
+use crate::utils::mysql::{self, RemoteHost, LOCAL_HOST};
+
+/// Service untuk remote MySQL access
+pub struct RemoteMysqlService;
+
+impl RemoteMysqlService {
+    /// Fairing yang memulihkan rule firewall remote MySQL setelah Rocket liftoff
+    ///
+    /// Rule iptables tidak bertahan setelah reboot, sehingga chain panel
+    /// dibangun ulang dari allow-list di database.
+    pub fn fairing() -> AdHoc {
+        AdHoc::on_liftoff("Remote MySQL Firewall", |rocket| {
+            Box::pin(async move {
+                let Some(db) = rocket.state::<Database>() else {
+                    tracing::error!(
+                        "Remote MySQL firewall not restored: database is not available"
+                    );
+                    return;
+                };
+
+                let pool = db.get_pool().clone();
+                tokio::spawn(async move {
+                    if let Err(e) = Self::sync_firewall(&pool).await {
+                        tracing::warn!("Failed to restore remote MySQL firewall: {}", e);
+                    }
+                });
+            })
+        })
+    }
+
+    /// List allow-list host milik user
+    pub async fn list_hosts(pool: &MySqlPool, user_id: &str) -> ApiResult<Vec<RemoteMysqlHost>> {
+        let hosts = sqlx::query_as::<_, RemoteMysqlHost>(
+            "SELECT * FROM remote_mysql_hosts WHERE user_id = ? ORDER BY host",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(hosts)
+    }
+
+    /// Tambah host ke allow-list lalu sinkronkan account MySQL dan firewall
+    ///
+    /// Jika sinkronisasi gagal, host dihapus lagi dari allow-list.
+    pub async fn add_host(
+        pool: &MySqlPool,
+        user_id: &str,
+        request: CreateRemoteMysqlHostRequest,
+    ) -> ApiResult<RemoteMysqlHost> {
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        let remote = mysql::parse_remote_host(&request.host)
+            .ok_or_else(|| ApiError::ValidationError("IP atau CIDR tidak valid".to_string()))?;
+
+        let existing = sqlx::query_scalar::<_, i64>(
+            "SELECT COUNT(*) FROM remote_mysql_hosts WHERE user_id = ? AND host = ?",
+        )
+        .bind(user_id)
+        .bind(&remote.source)
+        .fetch_one(pool)
+        .await?;
+
+        if existing > 0 {
+            return Err(ApiError::AlreadyExists("Remote MySQL Host".to_string()));
+        }
+
+        let host = RemoteMysqlHost {
+            id: Uuid::new_v4().to_string(),
+            user_id: user_id.to_string(),
+            host: remote.source,
+            description: request.description,
+            created_at: Utc::now(),
+        };
+
+        Self::insert_host(pool, &host).await?;
+
+        if let Err(e) = Self::sync(pool, user_id).await {
+            sqlx::query("DELETE FROM remote_mysql_hosts WHERE id = ?")
+                .bind(&host.id)
+                .execute(pool)
+                .await?;
+            Self::resync_after_rollback(pool, user_id).await;
+            return Err(e);
+        }
+
+        tracing::info!(
+            "Remote MySQL host added: {} for user {}",
+            host.host,
+            user_id
+        );
+
+        Ok(host)
+    }
+
+    /// Hapus host dari allow-list lalu sinkronkan account MySQL dan firewall
+    ///
+    /// Jika sinkronisasi gagal, host dikembalikan ke allow-list.
+    pub async fn remove_host(pool: &MySqlPool, host_id: &str, user_id: &str) -> ApiResult<()> {
+        let host =
+            sqlx::query_as::<_, RemoteMysqlHost>("SELECT * FROM remote_mysql_hosts WHERE id = ?")
+                .bind(host_id)
+                .fetch_optional(pool)
+                .await?
+                .ok_or(ApiError::NotFound("Remote MySQL Host".to_string()))?;
+
+        if host.user_id != user_id {
+            return Err(ApiError::Forbidden);
+        }
+
+        sqlx::query("DELETE FROM remote_mysql_hosts WHERE id = ?")
+            .bind(host_id)
+            .execute(pool)
+            .await?;
+
+        if let Err(e) = Self::sync(pool, user_id).await {
+            Self::insert_host(pool, &host).await?;
+            Self::resync_after_rollback(pool, user_id).await;
+            return Err(e);
+        }
+
+        tracing::info!(
+            "Remote MySQL host removed: {} for user {}",
+            host.host,
+            user_id
+        );
+
+        Ok(())
+    }
+
+    async fn insert_host(pool: &MySqlPool, host: &RemoteMysqlHost) -> ApiResult<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO remote_mysql_hosts (id, user_id, host, description, created_at)
+            VALUES (?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&host.id)
+        .bind(&host.user_id)
+        .bind(&host.host)
+        .bind(&host.description)
+        .bind(host.created_at)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Samakan lagi account MySQL dan firewall dengan allow-list yang dikembalikan
+    ///
+    /// Sinkronisasi yang gagal bisa berhenti di tengah; error di sini hanya dicatat
+    /// karena error aslinya yang dikembalikan ke user.
+    async fn resync_after_rollback(pool: &MySqlPool, user_id: &str) {
+        if let Err(e) = Self::sync(pool, user_id).await {
+            tracing::warn!(
+                "Remote MySQL resync after rollback failed for user {}: {}",
+                user_id,
+                e
+            );
+        }
+    }
+
+    /// Sinkronkan account MySQL milik user dan firewall
+    pub async fn sync(pool: &MySqlPool, user_id: &str) -> ApiResult<()> {
+        Self::sync_user_accounts(pool, user_id).await?;
+        Self::sync_firewall(pool).await?;
+        Ok(())
+    }
+
+    /// Host account MySQL remote (format MySQL) dari allow-list user
+    pub async fn mysql_hosts(pool: &MySqlPool, user_id: &str) -> ApiResult<Vec<String>> {
+        let hosts = sqlx::query_scalar::<_, String>(
+            "SELECT host FROM remote_mysql_hosts WHERE user_id = ? ORDER BY host",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(hosts
+            .iter()
+            .filter_map(|host| mysql::parse_remote_host(host))
+            .map(|remote| remote.mysql_host)
+            .collect())
+    }
+
+    /// Samakan account `user@<host>` setiap database user dengan allow-list
+    ///
+    /// Account lama dengan host selain `localhost` (mis. `%`) dipindah ke
+    /// `localhost`. Account remote baru menyalin password dari account utama
+    /// dan mendapat grant yang sama.
+    pub async fn sync_user_accounts(pool: &MySqlPool, user_id: &str) -> ApiResult<()> {
+        let desired = Self::mysql_hosts(pool, user_id).await?;
+
+        let db_users = sqlx::query_as::<_, DatabaseUser>(
+            "SELECT * FROM database_users WHERE user_id = ? AND engine = 'mysql'",
+        )
+        .bind(user_id)
+        .fetch_all(pool)
+        .await?;
+
+        for db_user in db_users {
+            if db_user.host != LOCAL_HOST {
+                Self::move_to_localhost(pool, &db_user).await?;
+            }
+
+            let existing = Self::existing_hosts(pool, &db_user.db_username).await?;
+
+            for host in existing
+                .iter()
+                .filter(|host| host.as_str() != LOCAL_HOST && !desired.contains(host))
+            {
+                let drop_sql = mysql::drop_user(&db_user.db_username, host)?;
+                sqlx::query(&drop_sql).execute(pool).await?;
+            }
+
+            let missing: Vec<&String> = desired
+                .iter()
+                .filter(|host| !existing.contains(host))
+                .collect();
+            if missing.is_empty() {
+                continue;
+            }
+
+            let auth = sqlx::query_as::<_, (String, String)>(
+                r#"
+                SELECT CONVERT(plugin USING utf8mb4), HEX(authentication_string)
+                FROM mysql.user WHERE User = ? AND Host = ?
+                "#,
+            )
+            .bind(&db_user.db_username)
+            .bind(LOCAL_HOST)
+            .fetch_optional(pool)
+            .await?;
+
+            let Some((plugin, auth_hex)) = auth else {
+                tracing::warn!(
+                    "MySQL account {}@{} not found, skipping remote hosts",
+                    db_user.db_username,
+                    LOCAL_HOST
+                );
+                continue;
+            };
+
+            let grants = sqlx::query_as::<_, (String, String)>(
+                r#"
+                SELECT d.db_name, g.privileges
+                FROM database_user_grants g
+                JOIN managed_databases d ON d.id = g.database_id
+                WHERE g.database_user_id = ?
+                "#,
+            )
+            .bind(&db_user.id)
+            .fetch_all(pool)
+            .await?;
+
+            for host in missing {
+                let create_sql =
+                    mysql::create_user_with_auth(&db_user.db_username, host, &plugin, &auth_hex)?;
+                sqlx::query(&create_sql).execute(pool).await.map_err(|e| {
+                    tracing::error!("Failed to create remote MySQL account: {}", e);
+                    ApiError::InternalError("Failed to create remote MySQL account".to_string())
+                })?;
+
+                for (db_name, privileges) in &grants {
+                    let privilege_list = mysql::parse_privileges(privileges)?;
+                    let grant_sql =
+                        mysql::grant(&privilege_list, db_name, &db_user.db_username, host)?;
+                    sqlx::query(&grant_sql).execute(pool).await?;
+                }
+            }
+        }
+
+        let _ = sqlx::query("FLUSH PRIVILEGES").execute(pool).await;
+
+        Ok(())
+    }
+
+    /// Buka port MySQL hanya untuk host di allow-list semua akun
+    pub async fn sync_firewall(pool: &MySqlPool) -> ApiResult<usize> {
+        let config = RemoteMysqlConfig::default();
+
+        let hosts = sqlx::query_scalar::<_, String>(
+            "SELECT DISTINCT host FROM remote_mysql_hosts ORDER BY host",
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let remotes: Vec<RemoteHost> = hosts
+            .iter()
+            .filter_map(|host| mysql::parse_remote_host(host))
+            .collect();
+
+        let ipv4: Vec<&str> = remotes
+            .iter()
+            .filter(|r| !r.is_ipv6)
+            .map(|r| r.source.as_str())
+            .collect();
+        let ipv6: Vec<&str> = remotes
+            .iter()
+            .filter(|r| r.is_ipv6)
+            .map(|r| r.source.as_str())
+            .collect();
+
+        Self::apply_firewall("iptables", &config, &ipv4)?;
+        Self::apply_firewall("ip6tables", &config, &ipv6)?;
+
+        tracing::info!("Remote MySQL firewall synced: {} sources", remotes.len());
+
+        Ok(remotes.len())
+    }
+
+    /// Pindahkan account lama (mis. `user@%`) ke `user@localhost`
+    async fn move_to_localhost(pool: &MySqlPool, db_user: &DatabaseUser) -> ApiResult<()> {
+        let existing = Self::existing_hosts(pool, &db_user.db_username).await?;
+
+        let sql = if existing.iter().any(|host| host == LOCAL_HOST) {
+            mysql::drop_user(&db_user.db_username, &db_user.host)?
+        } else {
+            mysql::rename_user(&db_user.db_username, &db_user.host, LOCAL_HOST)?
+        };
+        sqlx::query(&sql).execute(pool).await.map_err(|e| {
+            tracing::error!("Failed to move MySQL account to localhost: {}", e);
+            ApiError::InternalError("Failed to update database user host".to_string())
+        })?;
+
+        sqlx::query("UPDATE database_users SET host = ? WHERE id = ?")
+            .bind(LOCAL_HOST)
+            .bind(&db_user.id)
+            .execute(pool)
+            .await?;
+
+        tracing::info!(
+            "Database user {}@{} moved to localhost",
+            db_user.db_username,
+            db_user.host
+        );
+
+        Ok(())
+    }
+
+    /// Host account MySQL yang sudah ada untuk username
+    async fn existing_hosts(pool: &MySqlPool, db_username: &str) -> ApiResult<Vec<String>> {
+        let hosts = sqlx::query_scalar::<_, String>(
+            "SELECT CONVERT(Host USING utf8mb4) FROM mysql.user WHERE User = ?",
+        )
+        .bind(db_username)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(hosts)
+    }
+
+    /// Bangun ulang chain firewall panel dan pastikan INPUT melompat ke chain tersebut
+    ///
+    /// Isi chain diganti lewat `<binary>-restore --noflush` dalam satu commit
+    /// tabel, sehingga tidak ada jeda di mana chain kosong atau setengah terisi.
+    fn apply_firewall(binary: &str, config: &RemoteMysqlConfig, sources: &[&str]) -> ApiResult<()> {
+        let restore = format!("{}-restore", binary);
+        let mut child = Command::new("sudo")
+            .args([restore.as_str(), "--noflush"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| {
+                ApiError::InternalError(format!("Failed to execute {}: {}", restore, e))
+            })?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin
+                .write_all(render_chain_restore(config, sources).as_bytes())
+                .map_err(|e| {
+                    ApiError::InternalError(format!("Failed to write to {}: {}", restore, e))
+                })?;
+        }
+
+        let output = child.wait_with_output().map_err(|e| {
+            ApiError::InternalError(format!("Failed to execute {}: {}", restore, e))
+        })?;
+        if !output.status.success() {
+            let err = String::from_utf8_lossy(&output.stderr);
+            return Err(ApiError::InternalError(format!(
+                "{} error: {}",
+                restore, err
+            )));
+        }
+
+        let jump = jump_rule(config);
+        let exists = Command::new("sudo")
+            .arg(binary)
+            .arg("-C")
+            .args(&jump)
+            .output()
+            .map(|output| output.status.success())
+            .unwrap_or(false);
+
+        if !exists {
+            run(
+                binary,
+                Command::new("sudo").arg(binary).arg("-I").args(&jump),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Jalankan command dan petakan kegagalan ke `ApiError`
+fn run(name: &str, command: &mut Command) -> ApiResult<()> {
+    let output = command
+        .output()
+        .map_err(|e| ApiError::InternalError(format!("Failed to execute {}: {}", name, e)))?;
+
+    if !output.status.success() {
+        let err = String::from_utf8_lossy(&output.stderr);
+        return Err(ApiError::InternalError(format!("{} error: {}", name, err)));
+    }
+
+    Ok(())
+}
+
+/// Rule `INPUT` yang mengarahkan trafik port MySQL ke chain panel
+fn jump_rule(config: &RemoteMysqlConfig) -> Vec<String> {
+    [
+        "INPUT",
+        "-p",
+        "tcp",
+        "--dport",
+        &config.port.to_string(),
+        "-j",
+        &config.chain,
+    ]
+    .iter()
+    .map(|arg| arg.to_string())
+    .collect()
+}
+
+/// Render rule chain: loopback dan host allow-list diterima, sisanya di-drop
+pub fn render_chain_rules(config: &RemoteMysqlConfig, sources: &[&str]) -> Vec<Vec<String>> {
+    let port = config.port.to_string();
+    let rule = |args: &[&str]| -> Vec<String> {
+        ["-A", config.chain.as_str()]
+            .iter()
+            .chain(args)
+            .map(|arg| arg.to_string())
+            .collect()
+    };
+
+    let mut rules = vec![rule(&["-i", "lo", "-j", "ACCEPT"])];
+    for source in sources {
+        rules.push(rule(&[
+            "-p", "tcp", "--dport", &port, "-s", source, "-j", "ACCEPT",
+        ]));
+    }
+    rules.push(rule(&["-p", "tcp", "--dport", &port, "-j", "DROP"]));
+
+    rules
+}
+
+/// Render input `iptables-restore --noflush` yang mengganti isi chain panel
+///
+/// Deklarasi `:<chain> - [0:0]` membuat chain jika belum ada dan mengosongkannya,
+/// lalu rule baru diterapkan bersamaan saat `COMMIT`.
+pub fn render_chain_restore(config: &RemoteMysqlConfig, sources: &[&str]) -> String {
+    let mut lines = vec!["*filter".to_string(), format!(":{} - [0:0]", config.chain)];
+    lines.extend(
+        render_chain_rules(config, sources)
+            .iter()
+            .map(|rule| rule.join(" ")),
+    );
+    lines.push("COMMIT".to_string());
+
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_chain_rules() {
+        let config = RemoteMysqlConfig {
+            port: 3306,
+            chain: "NUSA_MYSQL".to_string(),
+        };
+
+        let rules = render_chain_rules(&config, &["203.0.113.0/24", "198.51.100.7"]);
+        let rules: Vec<String> = rules.iter().map(|rule| rule.join(" ")).collect();
+
+        assert_eq!(
+            rules,
+            vec![
+                "-A NUSA_MYSQL -i lo -j ACCEPT",
+                "-A NUSA_MYSQL -p tcp --dport 3306 -s 203.0.113.0/24 -j ACCEPT",
+                "-A NUSA_MYSQL -p tcp --dport 3306 -s 198.51.100.7 -j ACCEPT",
+                "-A NUSA_MYSQL -p tcp --dport 3306 -j DROP",
+            ]
+        );
+
+        assert_eq!(
+            render_chain_rules(&config, &[]).len(),
+            2,
+            "tanpa allow-list port tetap tertutup"
+        );
+        assert_eq!(
+            render_chain_restore(&config, &["198.51.100.7"]),
+            "*filter\n:NUSA_MYSQL - [0:0]\n-A NUSA_MYSQL -i lo -j ACCEPT\n\
+             -A NUSA_MYSQL -p tcp --dport 3306 -s 198.51.100.7 -j ACCEPT\n\
+             -A NUSA_MYSQL -p tcp --dport 3306 -j DROP\nCOMMIT\n"
+        );
+        assert_eq!(
+            jump_rule(&config).join(" "),
+            "INPUT -p tcp --dport 3306 -j NUSA_MYSQL"
+        );
+    }
+}
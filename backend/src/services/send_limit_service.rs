@@ -0,0 +1,517 @@
+//! # Send Limit Service
+//!
+//! Business logic untuk limit pengiriman email keluar per mailbox dan per domain.
+//! Pemakaian dihitung per penerima dalam bucket per jam; limit harian dihitung
+//! dari 24 bucket terakhir.
+
+use std::collections::HashMap;
+use std::io::Write;
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+
+use chrono::{DateTime, Duration, DurationRound, Utc};
+use once_cell::sync::Lazy;
+use sqlx::MySqlPool;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{
+    check_send_limits, Domain, DomainSendLimit, EmailAccount, SendLimitAlert, SendLimitConfig,
+    SendLimitExceeded, SendLimitUsage, UpdateDomainSendLimitRequest,
+};
+
+/// Waktu alert terakhir per mailbox/limit, agar admin tidak dibanjiri email
+static LAST_ALERTS: Lazy<Mutex<HashMap<String, DateTime<Utc>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Jeda minimum antar alert untuk limit yang sama
+const ALERT_COOLDOWN_MINUTES: i64 = 60;
+
+/// Service untuk send limit operations
+pub struct SendLimitService;
+
+impl SendLimitService {
+    // ==========================================
+    // LIMIT MANAGEMENT
+    // ==========================================
+
+    /// Get limit dan pemakaian mailbox
+    pub async fn get_account_usage(
+        pool: &MySqlPool,
+        account_id: &str,
+        user_id: &str,
+    ) -> ApiResult<SendLimitUsage> {
+        let account =
+            sqlx::query_as::<_, EmailAccount>("SELECT * FROM email_accounts WHERE id = ?")
+                .bind(account_id)
+                .fetch_optional(pool)
+                .await?
+                .ok_or(ApiError::NotFound("Email Account".to_string()))?;
+
+        if account.user_id != user_id {
+            return Err(ApiError::Forbidden);
+        }
+
+        Self::account_usage(pool, &account, &SendLimitConfig::default()).await
+    }
+
+    /// Get limit dan pemakaian domain
+    pub async fn get_domain_usage(
+        pool: &MySqlPool,
+        domain_id: &str,
+        user_id: &str,
+    ) -> ApiResult<SendLimitUsage> {
+        let domain = Self::get_owned_domain(pool, domain_id, user_id).await?;
+        Self::domain_usage(pool, &domain.id, &domain.domain_name).await
+    }
+
+    /// Update limit domain (admin, field kosong = tanpa limit)
+    pub async fn update_domain_limit(
+        pool: &MySqlPool,
+        domain_id: &str,
+        request: UpdateDomainSendLimitRequest,
+    ) -> ApiResult<SendLimitUsage> {
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        let domain = sqlx::query_as::<_, Domain>("SELECT * FROM domains WHERE id = ?")
+            .bind(domain_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or(ApiError::NotFound("Domain".to_string()))?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO email_domain_send_limits (domain_id, user_id, hourly_limit, daily_limit, updated_at)
+            VALUES (?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE hourly_limit = VALUES(hourly_limit), daily_limit = VALUES(daily_limit),
+                updated_at = VALUES(updated_at)
+            "#,
+        )
+        .bind(domain_id)
+        .bind(&domain.user_id)
+        .bind(request.hourly_limit)
+        .bind(request.daily_limit)
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+        tracing::info!("Send limit updated for domain {}", domain.domain_name);
+
+        Self::domain_usage(pool, &domain.id, &domain.domain_name).await
+    }
+
+    /// List alert limit terbaru (admin)
+    pub async fn get_recent_alerts(pool: &MySqlPool, limit: i64) -> ApiResult<Vec<SendLimitAlert>> {
+        let alerts = sqlx::query_as::<_, SendLimitAlert>(
+            "SELECT * FROM email_send_limit_alerts ORDER BY created_at DESC LIMIT ?",
+        )
+        .bind(limit)
+        .fetch_all(pool)
+        .await?;
+
+        Ok(alerts)
+    }
+
+    // ==========================================
+    // POLICY ENFORCEMENT
+    // ==========================================
+
+    /// Cek dan catat satu penerima dari mailbox yang terautentikasi (SASL)
+    ///
+    /// # Returns
+    /// `Some(SendLimitExceeded)` jika limit terlampaui (pesan harus di-DEFER),
+    /// `None` jika diizinkan atau pengirim bukan mailbox lokal.
+    pub async fn check_recipient(
+        pool: &MySqlPool,
+        sasl_username: &str,
+        client_address: Option<&str>,
+    ) -> ApiResult<Option<SendLimitExceeded>> {
+        let account = sqlx::query_as::<_, EmailAccount>(
+            "SELECT * FROM email_accounts WHERE email_address = ?",
+        )
+        .bind(sasl_username.to_lowercase())
+        .fetch_optional(pool)
+        .await?;
+
+        let Some(account) = account else {
+            return Ok(None);
+        };
+
+        let domain_name =
+            sqlx::query_scalar::<_, String>("SELECT domain_name FROM domains WHERE id = ?")
+                .bind(&account.domain_id)
+                .fetch_optional(pool)
+                .await?
+                .unwrap_or_else(|| account.domain_id.clone());
+
+        // Penerima dicatat lebih dulu (upsert atomik), lalu limit dicek terhadap
+        // pemakaian yang sudah termasuk penerima ini. Request paralel tidak bisa
+        // lolos bersamaan hanya karena membaca pemakaian sebelum increment.
+        let bucket = current_bucket();
+        let scope_keys = [
+            account_scope_key(&account.email_address),
+            domain_scope_key(&account.domain_id),
+        ];
+        Self::record_recipient(pool, &scope_keys, bucket).await?;
+
+        let config = SendLimitConfig::default();
+        let account_usage = Self::account_usage(pool, &account, &config).await?;
+        let domain_usage = Self::domain_usage(pool, &account.domain_id, &domain_name).await?;
+
+        let exceeded = check_send_limits(
+            &excluding_current(account_usage),
+            &excluding_current(domain_usage),
+        );
+        if let Some(exceeded) = exceeded {
+            // Penerima yang di-DEFER tidak dihitung sebagai terkirim
+            Self::unrecord_recipient(pool, &scope_keys, bucket).await?;
+            Self::raise_alert(pool, &account, &exceeded, client_address, &config).await?;
+            return Ok(Some(exceeded));
+        }
+
+        Ok(None)
+    }
+
+    /// Hapus bucket pemakaian yang sudah lebih dari 48 jam
+    pub async fn prune_usage(pool: &MySqlPool) -> ApiResult<u64> {
+        let result = sqlx::query("DELETE FROM email_send_usage WHERE bucket_start < ?")
+            .bind(current_bucket() - Duration::hours(48))
+            .execute(pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    // ==========================================
+    // HELPERS
+    // ==========================================
+
+    /// Tambah satu penerima ke bucket setiap scope
+    async fn record_recipient(
+        pool: &MySqlPool,
+        scope_keys: &[String],
+        bucket: DateTime<Utc>,
+    ) -> ApiResult<()> {
+        for scope_key in scope_keys {
+            sqlx::query(
+                r#"
+                INSERT INTO email_send_usage (scope_key, bucket_start, recipients)
+                VALUES (?, ?, 1)
+                ON DUPLICATE KEY UPDATE recipients = recipients + 1
+                "#,
+            )
+            .bind(scope_key)
+            .bind(bucket)
+            .execute(pool)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Batalkan penerima yang sudah dicatat `record_recipient`
+    async fn unrecord_recipient(
+        pool: &MySqlPool,
+        scope_keys: &[String],
+        bucket: DateTime<Utc>,
+    ) -> ApiResult<()> {
+        for scope_key in scope_keys {
+            let result = sqlx::query(
+                r#"
+                UPDATE email_send_usage SET recipients = recipients - 1
+                WHERE scope_key = ? AND bucket_start = ? AND recipients > 0
+                "#,
+            )
+            .bind(scope_key)
+            .bind(bucket)
+            .execute(pool)
+            .await?;
+
+            if result.rows_affected() == 0 {
+                tracing::warn!("Send usage bucket missing for {}", scope_key);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Limit efektif dan pemakaian mailbox
+    async fn account_usage(
+        pool: &MySqlPool,
+        account: &EmailAccount,
+        config: &SendLimitConfig,
+    ) -> ApiResult<SendLimitUsage> {
+        let (sent_last_hour, sent_last_day) =
+            Self::sent_counts(pool, &account_scope_key(&account.email_address)).await?;
+
+        Ok(SendLimitUsage {
+            scope: account.email_address.clone(),
+            hourly_limit: Some(
+                account
+                    .send_limit_hourly
+                    .map(i64::from)
+                    .unwrap_or(config.default_hourly),
+            ),
+            daily_limit: Some(
+                account
+                    .send_limit_daily
+                    .map(i64::from)
+                    .unwrap_or(config.default_daily),
+            ),
+            sent_last_hour,
+            sent_last_day,
+        })
+    }
+
+    /// Limit dan pemakaian domain
+    async fn domain_usage(
+        pool: &MySqlPool,
+        domain_id: &str,
+        scope: &str,
+    ) -> ApiResult<SendLimitUsage> {
+        let limit = sqlx::query_as::<_, DomainSendLimit>(
+            "SELECT * FROM email_domain_send_limits WHERE domain_id = ?",
+        )
+        .bind(domain_id)
+        .fetch_optional(pool)
+        .await?;
+
+        let (sent_last_hour, sent_last_day) =
+            Self::sent_counts(pool, &domain_scope_key(domain_id)).await?;
+
+        Ok(SendLimitUsage {
+            scope: scope.to_string(),
+            hourly_limit: limit.as_ref().and_then(|l| l.hourly_limit).map(i64::from),
+            daily_limit: limit.as_ref().and_then(|l| l.daily_limit).map(i64::from),
+            sent_last_hour,
+            sent_last_day,
+        })
+    }
+
+    /// Jumlah penerima jam berjalan dan 24 jam terakhir
+    async fn sent_counts(pool: &MySqlPool, scope_key: &str) -> ApiResult<(i64, i64)> {
+        let bucket = current_bucket();
+
+        let counts = sqlx::query_as::<_, (i64, i64)>(
+            r#"
+            SELECT
+                CAST(COALESCE(SUM(CASE WHEN bucket_start >= ? THEN recipients ELSE 0 END), 0) AS SIGNED),
+                CAST(COALESCE(SUM(recipients), 0) AS SIGNED)
+            FROM email_send_usage
+            WHERE scope_key = ? AND bucket_start > ?
+            "#,
+        )
+        .bind(bucket)
+        .bind(scope_key)
+        .bind(bucket - Duration::hours(24))
+        .fetch_one(pool)
+        .await?;
+
+        Ok(counts)
+    }
+
+    /// Catat alert dan kirim email ke admin (maksimal sekali per jam per limit)
+    async fn raise_alert(
+        pool: &MySqlPool,
+        account: &EmailAccount,
+        exceeded: &SendLimitExceeded,
+        client_address: Option<&str>,
+        config: &SendLimitConfig,
+    ) -> ApiResult<()> {
+        let alert_key = format!(
+            "{}:{}:{}",
+            exceeded.scope, exceeded.period, account.email_address
+        );
+        let now = Utc::now();
+
+        {
+            let mut last_alerts = LAST_ALERTS
+                .lock()
+                .map_err(|_| ApiError::InternalError("Failed to acquire alert lock".into()))?;
+
+            if let Some(last) = last_alerts.get(&alert_key) {
+                if now - *last < Duration::minutes(ALERT_COOLDOWN_MINUTES) {
+                    return Ok(());
+                }
+            }
+            last_alerts.insert(alert_key, now);
+        }
+
+        tracing::warn!(
+            "Send limit exceeded: {} ({} {} limit {}) from {}",
+            account.email_address,
+            exceeded.scope,
+            exceeded.period,
+            exceeded.limit,
+            client_address.unwrap_or("unknown")
+        );
+
+        sqlx::query(
+            r#"
+            INSERT INTO email_send_limit_alerts (id, email_address, domain_id, scope, period, limit_value, client_address, created_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(Uuid::new_v4().to_string())
+        .bind(&account.email_address)
+        .bind(&account.domain_id)
+        .bind(exceeded.scope)
+        .bind(exceeded.period)
+        .bind(exceeded.limit)
+        .bind(client_address)
+        .bind(now)
+        .execute(pool)
+        .await?;
+
+        let admin_emails = sqlx::query_scalar::<_, String>(
+            "SELECT email FROM users WHERE role = 'admin' AND status = 'active'",
+        )
+        .fetch_all(pool)
+        .await?;
+
+        for admin_email in admin_emails {
+            if let Err(e) =
+                Self::send_alert_email(config, &admin_email, account, exceeded, client_address)
+            {
+                tracing::warn!("Failed to send limit alert to {}: {}", admin_email, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Kirim email alert ke admin melalui `sendmail`
+    fn send_alert_email(
+        config: &SendLimitConfig,
+        admin_email: &str,
+        account: &EmailAccount,
+        exceeded: &SendLimitExceeded,
+        client_address: Option<&str>,
+    ) -> ApiResult<()> {
+        let message = format!(
+            "From: {from}\r\nTo: {to}\r\nSubject: [NusaPanel] Limit pengiriman terlampaui: {mailbox}\r\n\
+             Content-Type: text/plain; charset=utf-8\r\n\r\n\
+             Mailbox {mailbox} melampaui limit {scope} {period} ({limit} penerima).\r\n\
+             IP client: {client}\r\n\r\n\
+             Pesan berikutnya akan di-DEFER sampai pemakaian turun di bawah limit.\r\n\
+             Periksa kemungkinan akun disalahgunakan dan antrian email di panel admin.\r\n",
+            from = config.alert_sender,
+            to = admin_email,
+            mailbox = account.email_address,
+            scope = exceeded.scope,
+            period = exceeded.period,
+            limit = exceeded.limit,
+            client = client_address.unwrap_or("unknown"),
+        );
+
+        let mut child = Command::new("sendmail")
+            .arg("-t")
+            .arg("-i")
+            .stdin(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| ApiError::InternalError(format!("Failed to spawn sendmail: {}", e)))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(message.as_bytes()).map_err(|e| {
+                ApiError::InternalError(format!("Failed to write to sendmail: {}", e))
+            })?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|e| ApiError::InternalError(format!("Failed to wait for sendmail: {}", e)))?;
+
+        if !output.status.success() {
+            let err = String::from_utf8_lossy(&output.stderr);
+            return Err(ApiError::InternalError(format!("Sendmail error: {}", err)));
+        }
+
+        Ok(())
+    }
+
+    /// Ambil domain dan pastikan dimiliki user
+    async fn get_owned_domain(
+        pool: &MySqlPool,
+        domain_id: &str,
+        user_id: &str,
+    ) -> ApiResult<Domain> {
+        let domain = sqlx::query_as::<_, Domain>("SELECT * FROM domains WHERE id = ?")
+            .bind(domain_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or(ApiError::NotFound("Domain".to_string()))?;
+
+        if domain.user_id != user_id {
+            return Err(ApiError::Forbidden);
+        }
+
+        Ok(domain)
+    }
+}
+
+/// Awal bucket jam berjalan
+fn current_bucket() -> DateTime<Utc> {
+    let now = Utc::now();
+    now.duration_trunc(Duration::hours(1)).unwrap_or(now)
+}
+
+/// Pemakaian sebelum penerima yang sedang dicek (sudah tercatat di bucket)
+fn excluding_current(usage: SendLimitUsage) -> SendLimitUsage {
+    SendLimitUsage {
+        sent_last_hour: (usage.sent_last_hour - 1).max(0),
+        sent_last_day: (usage.sent_last_day - 1).max(0),
+        ..usage
+    }
+}
+
+/// Scope key pemakaian mailbox
+fn account_scope_key(email_address: &str) -> String {
+    format!("account:{}", email_address.to_lowercase())
+}
+
+/// Scope key pemakaian domain
+fn domain_scope_key(domain_id: &str) -> String {
+    format!("domain:{}", domain_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Timelike;
+
+    #[test]
+    fn test_current_bucket_is_hour_aligned() {
+        let bucket = current_bucket();
+        assert_eq!(bucket.minute(), 0);
+        assert_eq!(bucket.second(), 0);
+        assert!(Utc::now() - bucket < Duration::hours(1));
+    }
+
+    #[test]
+    fn test_scope_keys() {
+        assert_eq!(
+            account_scope_key("Info@Example.com"),
+            "account:info@example.com"
+        );
+        assert_eq!(domain_scope_key("abc"), "domain:abc");
+    }
+
+    #[test]
+    fn test_limit_reached_by_current_recipient() {
+        let usage = |sent| SendLimitUsage {
+            scope: "example.com".to_string(),
+            hourly_limit: Some(2),
+            daily_limit: None,
+            sent_last_hour: sent,
+            sent_last_day: sent,
+        };
+
+        // Bucket sudah termasuk penerima yang sedang dicek
+        assert!(check_send_limits(&excluding_current(usage(2)), &usage(0)).is_none());
+        assert!(check_send_limits(&excluding_current(usage(3)), &usage(0)).is_some());
+    }
+}
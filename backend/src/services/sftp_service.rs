@@ -0,0 +1,370 @@
+//! # SFTP Service
+//!
+//! Akses SFTP-only untuk FTP account lewat OpenSSH `internal-sftp`.
+//! Setiap akun SFTP-only mendapat system user sendiri (uid sama dengan
+//! pemilik, sehingga file dan disk quota tetap milik user panel) dan blok
+//! `Match User` dengan `ChrootDirectory` di file konfigurasi sshd milik panel.
+
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+use rocket::fairing::AdHoc;
+use sqlx::MySqlPool;
+
+use crate::database::Database;
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{validate_ftp_password, FtpAccount, SftpConfig};
+
+/// Panjang maksimum username Linux
+pub const MAX_SFTP_USERNAME_LENGTH: usize = 32;
+
+/// Service untuk akun SFTP-only
+pub struct SftpService;
+
+impl SftpService {
+    /// Fairing yang memasang ulang bind mount akun SFTP-only setelah liftoff
+    ///
+    /// Bind mount tidak bertahan setelah reboot, sedangkan konfigurasi sshd
+    /// tetap ada di disk.
+    pub fn fairing() -> AdHoc {
+        AdHoc::on_liftoff("SFTP Mounts", |rocket| {
+            Box::pin(async move {
+                let Some(db) = rocket.state::<Database>() else {
+                    tracing::error!("SFTP mounts not restored: database is not available");
+                    return;
+                };
+
+                let pool = db.get_pool().clone();
+                tokio::spawn(async move {
+                    match Self::mount_missing(&pool).await {
+                        Ok(0) => {}
+                        Ok(mounted) => tracing::info!("Restored {} SFTP bind mounts", mounted),
+                        Err(e) => tracing::warn!("Restoring SFTP bind mounts failed: {}", e),
+                    }
+                });
+            })
+        })
+    }
+
+    /// Buat system user dan chroot untuk akun SFTP-only
+    pub fn provision(account: &FtpAccount, password: &str) -> ApiResult<()> {
+        let config = SftpConfig::default();
+        let (uid, gid) = match (account.system_uid, account.system_gid) {
+            (Some(uid), Some(gid)) => (uid, gid),
+            _ => {
+                return Err(ApiError::InternalError(
+                    "FTP account belum memiliki uid/gid".to_string(),
+                ))
+            }
+        };
+
+        run(
+            "groupadd",
+            Command::new("sudo").args(["groupadd", "-f", &config.group]),
+        )?;
+
+        // `-o` mengizinkan uid duplikat: file yang di-upload tetap milik user panel
+        run(
+            "useradd",
+            Command::new("sudo")
+                .arg("useradd")
+                .args(["-M", "-o", "-d", "/files", "-s", "/usr/sbin/nologin"])
+                .args(["-u", &uid.to_string(), "-g", &gid.to_string()])
+                .args(["-G", &config.group])
+                .arg(&account.ftp_username),
+        )?;
+
+        Self::set_password(&account.ftp_username, password)?;
+        Self::mount_home(&config, account)?;
+
+        if !account.is_active {
+            Self::set_locked(&account.ftp_username, true)?;
+        }
+
+        tracing::info!("SFTP user provisioned: {}", account.ftp_username);
+
+        Ok(())
+    }
+
+    /// Set password system user (di-hash oleh `chpasswd`)
+    pub fn set_password(ftp_username: &str, password: &str) -> ApiResult<()> {
+        // Satu baris `user:password` per akun, newline/`:` akan menyisipkan baris lain
+        if validate_ftp_password(password).is_err() {
+            return Err(ApiError::ValidationError(
+                "Password tidak boleh mengandung karakter kontrol atau ':'".to_string(),
+            ));
+        }
+
+        let mut child = Command::new("sudo")
+            .arg("chpasswd")
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| ApiError::InternalError(format!("Failed to execute chpasswd: {}", e)))?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            writeln!(stdin, "{}:{}", ftp_username, password)
+                .map_err(|e| ApiError::InternalError(format!("Chpasswd write error: {}", e)))?;
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|e| ApiError::InternalError(format!("Failed to execute chpasswd: {}", e)))?;
+
+        if !output.status.success() {
+            let err = String::from_utf8_lossy(&output.stderr);
+            return Err(ApiError::InternalError(format!("Chpasswd error: {}", err)));
+        }
+
+        Ok(())
+    }
+
+    /// Kunci (`usermod -L`) atau buka kunci (`usermod -U`) login SFTP
+    pub fn set_locked(ftp_username: &str, locked: bool) -> ApiResult<()> {
+        run(
+            "usermod",
+            Command::new("sudo")
+                .arg("usermod")
+                .arg(if locked { "-L" } else { "-U" })
+                .arg(ftp_username),
+        )
+    }
+
+    /// Pindahkan bind mount ke home directory baru
+    pub fn remount(account: &FtpAccount) -> ApiResult<()> {
+        let config = SftpConfig::default();
+        Self::unmount_home(&config, &account.ftp_username)?;
+        Self::mount_home(&config, account)
+    }
+
+    /// Hapus system user, bind mount, dan chroot akun SFTP-only
+    pub fn remove(ftp_username: &str) -> ApiResult<()> {
+        let config = SftpConfig::default();
+        Self::unmount_home(&config, ftp_username)?;
+
+        run(
+            "userdel",
+            Command::new("sudo").arg("userdel").arg(ftp_username),
+        )?;
+
+        let chroot = config.chroot_dir(ftp_username);
+        if Path::new(&chroot).exists() {
+            run(
+                "rm",
+                Command::new("sudo").args(["rm", "-rf", "--one-file-system", &chroot]),
+            )?;
+        }
+
+        tracing::info!("SFTP user removed: {}", ftp_username);
+
+        Ok(())
+    }
+
+    /// Pasang ulang bind mount akun SFTP-only yang hilang (mis. setelah reboot)
+    ///
+    /// # Returns
+    /// Jumlah bind mount yang dipasang
+    pub async fn mount_missing(pool: &MySqlPool) -> ApiResult<usize> {
+        let config = SftpConfig::default();
+        let accounts = Self::sftp_accounts(pool).await?;
+
+        let mut mounted = 0;
+        for account in &accounts {
+            if !Self::is_mounted(&config.files_dir(&account.ftp_username)) {
+                Self::mount_home(&config, account)?;
+                mounted += 1;
+            }
+        }
+
+        Ok(mounted)
+    }
+
+    /// Tulis blok `Match User` untuk semua akun SFTP-only lalu reload sshd
+    ///
+    /// Bind mount yang hilang (mis. setelah reboot) dipasang ulang.
+    pub async fn sync_sshd_config(pool: &MySqlPool) -> ApiResult<usize> {
+        let config = SftpConfig::default();
+
+        Self::mount_missing(pool).await?;
+        let accounts = Self::sftp_accounts(pool).await?;
+
+        let usernames: Vec<&str> = accounts.iter().map(|a| a.ftp_username.as_str()).collect();
+        let content = render_sshd_match_blocks(&config, &usernames);
+
+        if let Some(parent) = Path::new(&config.sshd_config_path).parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| ApiError::InternalError(format!("SSHD config dir error: {}", e)))?;
+        }
+
+        let previous = fs::read_to_string(&config.sshd_config_path).ok();
+        fs::write(&config.sshd_config_path, &content)
+            .map_err(|e| ApiError::InternalError(format!("SSHD config write error: {}", e)))?;
+
+        // Validasi konfigurasi sebelum reload; kembalikan versi lama jika gagal
+        let test = Command::new("sudo")
+            .args(["sshd", "-t"])
+            .output()
+            .map_err(|e| ApiError::InternalError(format!("Failed to execute sshd: {}", e)))?;
+
+        if !test.status.success() {
+            match previous {
+                Some(previous) => {
+                    let _ = fs::write(&config.sshd_config_path, previous);
+                }
+                None => {
+                    let _ = fs::remove_file(&config.sshd_config_path);
+                }
+            }
+            let err = String::from_utf8_lossy(&test.stderr);
+            return Err(ApiError::InternalError(format!(
+                "SSHD config error: {}",
+                err
+            )));
+        }
+
+        run(
+            "systemctl",
+            Command::new("sudo").args(["systemctl", "reload", &config.service_name]),
+        )?;
+
+        tracing::info!("SFTP sshd config synced: {} accounts", accounts.len());
+
+        Ok(accounts.len())
+    }
+
+    async fn sftp_accounts(pool: &MySqlPool) -> ApiResult<Vec<FtpAccount>> {
+        let accounts = sqlx::query_as::<_, FtpAccount>(
+            "SELECT * FROM ftp_accounts WHERE sftp_only = TRUE ORDER BY ftp_username",
+        )
+        .fetch_all(pool)
+        .await?;
+
+        Ok(accounts)
+    }
+
+    /// Siapkan chroot milik root dan bind mount home FTP ke `<chroot>/files`
+    fn mount_home(config: &SftpConfig, account: &FtpAccount) -> ApiResult<()> {
+        let chroot = config.chroot_dir(&account.ftp_username);
+        let files = config.files_dir(&account.ftp_username);
+
+        run("mkdir", Command::new("sudo").args(["mkdir", "-p", &files]))?;
+
+        // OpenSSH menolak chroot yang bisa ditulis selain root
+        run(
+            "chown",
+            Command::new("sudo").args(["chown", "root:root", &chroot]),
+        )?;
+        run(
+            "chmod",
+            Command::new("sudo").args(["chmod", "755", &chroot]),
+        )?;
+
+        run(
+            "mount",
+            Command::new("sudo")
+                .args(["mount", "--bind"])
+                .arg(&account.home_directory)
+                .arg(&files),
+        )
+    }
+
+    /// Lepas bind mount jika terpasang
+    fn unmount_home(config: &SftpConfig, ftp_username: &str) -> ApiResult<()> {
+        let files = config.files_dir(ftp_username);
+        if Self::is_mounted(&files) {
+            run("umount", Command::new("sudo").args(["umount", &files]))?;
+        }
+        Ok(())
+    }
+
+    /// Cek apakah path adalah mount point
+    fn is_mounted(path: &str) -> bool {
+        Command::new("mountpoint")
+            .args(["-q", path])
+            .status()
+            .map(|status| status.success())
+            .unwrap_or(false)
+    }
+}
+
+/// Jalankan command dan petakan kegagalan ke `ApiError`
+fn run(name: &str, command: &mut Command) -> ApiResult<()> {
+    let output = command
+        .output()
+        .map_err(|e| ApiError::InternalError(format!("Failed to execute {}: {}", name, e)))?;
+
+    if !output.status.success() {
+        let err = String::from_utf8_lossy(&output.stderr);
+        return Err(ApiError::InternalError(format!("{} error: {}", name, err)));
+    }
+
+    Ok(())
+}
+
+/// Render blok `Match User` sshd untuk akun SFTP-only
+pub fn render_sshd_match_blocks(config: &SftpConfig, usernames: &[&str]) -> String {
+    let mut content = String::from("# Generated by NusaPanel - jangan edit manual\n");
+
+    for username in usernames {
+        content.push_str(&format!(
+            "\nMatch User {username}\n\
+             \x20   ChrootDirectory {chroot}\n\
+             \x20   ForceCommand internal-sftp -d /files\n\
+             \x20   PasswordAuthentication yes\n\
+             \x20   AllowTcpForwarding no\n\
+             \x20   AllowAgentForwarding no\n\
+             \x20   PermitTunnel no\n\
+             \x20   X11Forwarding no\n",
+            username = username,
+            chroot = config.chroot_dir(username),
+        ));
+    }
+
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> SftpConfig {
+        SftpConfig {
+            sshd_config_path: "/etc/ssh/sshd_config.d/nusa-sftp.conf".to_string(),
+            chroot_base: "/var/sftp/".to_string(),
+            group: "sftponly".to_string(),
+            service_name: "ssh".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_sftp_config_paths() {
+        let config = config();
+        assert_eq!(
+            config.chroot_dir("alice_1234abcd"),
+            "/var/sftp/alice_1234abcd"
+        );
+        assert_eq!(
+            config.files_dir("alice_1234abcd"),
+            "/var/sftp/alice_1234abcd/files"
+        );
+    }
+
+    #[test]
+    fn test_render_sshd_match_blocks() {
+        let content = render_sshd_match_blocks(&config(), &["alice_1234abcd", "bob_1234abcd"]);
+
+        assert!(content.starts_with("# Generated by NusaPanel"));
+        assert_eq!(content.matches("Match User ").count(), 2);
+        assert!(content.contains(
+            "\nMatch User alice_1234abcd\n    ChrootDirectory /var/sftp/alice_1234abcd\n    ForceCommand internal-sftp -d /files\n"
+        ));
+        assert!(content.contains("    AllowTcpForwarding no\n"));
+        assert_eq!(
+            render_sshd_match_blocks(&config(), &[]),
+            "# Generated by NusaPanel - jangan edit manual\n"
+        );
+    }
+}
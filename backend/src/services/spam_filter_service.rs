@@ -0,0 +1,390 @@
+//! # Spam Filter Service
+//!
+//! Business logic untuk kebijakan spam per domain dan per mailbox.
+//! Kebijakan efektif di-render menjadi script Sieve yang dijalankan Dovecot
+//! (`sieve_before`) berdasarkan header skor dari rspamd.
+
+use std::fs;
+use std::process::Command;
+
+use chrono::Utc;
+use sqlx::MySqlPool;
+use validator::Validate;
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{
+    join_patterns, Domain, DomainSpamPolicy, EmailAccount, EmailSpamPolicyResponse,
+    SpamFilterConfig, SpamPolicy, UpdateSpamPolicyRequest,
+};
+
+/// Service untuk spam filter operations
+pub struct SpamFilterService;
+
+impl SpamFilterService {
+    // ==========================================
+    // DOMAIN POLICY
+    // ==========================================
+
+    /// Get kebijakan spam domain milik user
+    pub async fn get_domain_policy(
+        pool: &MySqlPool,
+        domain_id: &str,
+        user_id: &str,
+    ) -> ApiResult<SpamPolicy> {
+        Self::get_owned_domain(pool, domain_id, user_id).await?;
+        Self::load_domain_policy(pool, domain_id).await
+    }
+
+    /// Update kebijakan spam domain lalu sync script semua mailbox di domain
+    pub async fn update_domain_policy(
+        pool: &MySqlPool,
+        domain_id: &str,
+        user_id: &str,
+        request: UpdateSpamPolicyRequest,
+    ) -> ApiResult<SpamPolicy> {
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        let domain = Self::get_owned_domain(pool, domain_id, user_id).await?;
+        let current = Self::load_domain_policy(pool, domain_id).await?;
+
+        let policy = SpamPolicy {
+            enabled: request.enabled.unwrap_or(current.enabled),
+            threshold: request.threshold.unwrap_or(current.threshold),
+            action: request.action.unwrap_or(current.action),
+            whitelist: request.whitelist.unwrap_or(current.whitelist),
+            blacklist: request.blacklist.unwrap_or(current.blacklist),
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO email_domain_spam_policies (domain_id, user_id, enabled, threshold, action, whitelist, blacklist, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?)
+            ON DUPLICATE KEY UPDATE enabled = VALUES(enabled), threshold = VALUES(threshold), action = VALUES(action),
+                whitelist = VALUES(whitelist), blacklist = VALUES(blacklist), updated_at = VALUES(updated_at)
+            "#,
+        )
+        .bind(domain_id)
+        .bind(user_id)
+        .bind(policy.enabled)
+        .bind(policy.threshold)
+        .bind(&policy.action)
+        .bind(join_patterns(&policy.whitelist))
+        .bind(join_patterns(&policy.blacklist))
+        .bind(Utc::now())
+        .execute(pool)
+        .await?;
+
+        let accounts =
+            sqlx::query_as::<_, EmailAccount>("SELECT * FROM email_accounts WHERE domain_id = ?")
+                .bind(domain_id)
+                .fetch_all(pool)
+                .await?;
+
+        // Kebijakan sudah tersimpan; script Sieve yang gagal bisa di-render ulang nanti
+        for account in &accounts {
+            if let Err(e) = Self::sync_account(pool, account).await {
+                tracing::warn!(
+                    "Failed to sync spam filter for {}: {}",
+                    account.email_address,
+                    e
+                );
+            }
+        }
+
+        tracing::info!(
+            "Spam policy updated for domain {} ({} mailboxes synced)",
+            domain.domain_name,
+            accounts.len()
+        );
+
+        Self::load_domain_policy(pool, domain_id).await
+    }
+
+    // ==========================================
+    // MAILBOX POLICY
+    // ==========================================
+
+    /// Get kebijakan spam efektif untuk mailbox
+    pub async fn get_account_policy(
+        pool: &MySqlPool,
+        account_id: &str,
+        user_id: &str,
+    ) -> ApiResult<EmailSpamPolicyResponse> {
+        let account = Self::get_owned_account(pool, account_id, user_id).await?;
+        let domain_policy = Self::load_domain_policy(pool, &account.domain_id).await?;
+        let account_override = account.spam_override();
+
+        Ok(EmailSpamPolicyResponse {
+            email_address: account.email_address,
+            effective: domain_policy.with_override(&account_override),
+            domain_policy,
+            account_override,
+        })
+    }
+
+    /// Simpan override spam mailbox (dipanggil dari update email account)
+    pub async fn save_account_override(
+        pool: &MySqlPool,
+        account: &EmailAccount,
+        request: &UpdateSpamPolicyRequest,
+    ) -> ApiResult<()> {
+        let current = account.spam_override();
+
+        let whitelist = request.whitelist.as_ref().unwrap_or(&current.whitelist);
+        let blacklist = request.blacklist.as_ref().unwrap_or(&current.blacklist);
+
+        sqlx::query(
+            r#"
+            UPDATE email_accounts
+            SET spam_enabled = ?, spam_threshold = ?, spam_action = ?, spam_whitelist = ?, spam_blacklist = ?, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(request.enabled.or(current.enabled))
+        .bind(request.threshold.or(current.threshold))
+        .bind(request.action.clone().or(current.action))
+        .bind(join_patterns(whitelist))
+        .bind(join_patterns(blacklist))
+        .bind(Utc::now())
+        .bind(&account.id)
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Reset override mailbox sehingga kembali ikut kebijakan domain
+    pub async fn reset_account_policy(
+        pool: &MySqlPool,
+        account_id: &str,
+        user_id: &str,
+    ) -> ApiResult<EmailSpamPolicyResponse> {
+        Self::get_owned_account(pool, account_id, user_id).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE email_accounts
+            SET spam_enabled = NULL, spam_threshold = NULL, spam_action = NULL, spam_whitelist = NULL, spam_blacklist = NULL, updated_at = ?
+            WHERE id = ?
+            "#,
+        )
+        .bind(Utc::now())
+        .bind(account_id)
+        .execute(pool)
+        .await?;
+
+        let account = Self::get_owned_account(pool, account_id, user_id).await?;
+        if let Err(e) = Self::sync_account(pool, &account).await {
+            tracing::warn!(
+                "Failed to sync spam filter for {}: {}",
+                account.email_address,
+                e
+            );
+        }
+
+        tracing::info!("Spam policy override reset: {}", account.email_address);
+
+        Self::get_account_policy(pool, account_id, user_id).await
+    }
+
+    // ==========================================
+    // SIEVE SYNC
+    // ==========================================
+
+    /// Render kebijakan efektif mailbox ke script Sieve dan compile
+    pub async fn sync_account(pool: &MySqlPool, account: &EmailAccount) -> ApiResult<()> {
+        let domain_policy = Self::load_domain_policy(pool, &account.domain_id).await?;
+        let policy = domain_policy.with_override(&account.spam_override());
+
+        let config = SpamFilterConfig::default();
+        let script_path = config.script_path(&account.email_address);
+
+        fs::create_dir_all(&config.sieve_dir)
+            .map_err(|e| ApiError::InternalError(format!("Sieve dir error: {}", e)))?;
+        fs::write(&script_path, render_spam_sieve(&policy))
+            .map_err(|e| ApiError::InternalError(format!("Sieve script write error: {}", e)))?;
+
+        let output = Command::new(&config.sievec_bin)
+            .arg(&script_path)
+            .output()
+            .map_err(|e| ApiError::InternalError(format!("Failed to execute sievec: {}", e)))?;
+
+        if !output.status.success() {
+            let err = String::from_utf8_lossy(&output.stderr);
+            return Err(ApiError::InternalError(format!("Sievec error: {}", err)));
+        }
+
+        Ok(())
+    }
+
+    /// Hapus script Sieve mailbox (saat mailbox dihapus)
+    pub fn remove_account_script(email_address: &str) {
+        let config = SpamFilterConfig::default();
+        let script_path = config.script_path(email_address);
+
+        let binary_path = format!("{}.svbin", script_path.trim_end_matches(".sieve"));
+
+        for path in [script_path, binary_path] {
+            if let Err(e) = fs::remove_file(&path) {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    tracing::warn!("Failed to remove sieve file {}: {}", path, e);
+                }
+            }
+        }
+    }
+
+    // ==========================================
+    // HELPERS
+    // ==========================================
+
+    /// Load kebijakan domain (default jika belum pernah diatur)
+    async fn load_domain_policy(pool: &MySqlPool, domain_id: &str) -> ApiResult<SpamPolicy> {
+        let policy = sqlx::query_as::<_, DomainSpamPolicy>(
+            "SELECT * FROM email_domain_spam_policies WHERE domain_id = ?",
+        )
+        .bind(domain_id)
+        .fetch_optional(pool)
+        .await?;
+
+        Ok(policy.map(SpamPolicy::from).unwrap_or_default())
+    }
+
+    /// Ambil domain dan pastikan dimiliki user
+    async fn get_owned_domain(
+        pool: &MySqlPool,
+        domain_id: &str,
+        user_id: &str,
+    ) -> ApiResult<Domain> {
+        let domain = sqlx::query_as::<_, Domain>("SELECT * FROM domains WHERE id = ?")
+            .bind(domain_id)
+            .fetch_optional(pool)
+            .await?
+            .ok_or(ApiError::NotFound("Domain".to_string()))?;
+
+        if domain.user_id != user_id {
+            return Err(ApiError::Forbidden);
+        }
+
+        Ok(domain)
+    }
+
+    /// Ambil email account dan pastikan dimiliki user
+    async fn get_owned_account(
+        pool: &MySqlPool,
+        account_id: &str,
+        user_id: &str,
+    ) -> ApiResult<EmailAccount> {
+        let account =
+            sqlx::query_as::<_, EmailAccount>("SELECT * FROM email_accounts WHERE id = ?")
+                .bind(account_id)
+                .fetch_optional(pool)
+                .await?
+                .ok_or(ApiError::NotFound("Email Account".to_string()))?;
+
+        if account.user_id != user_id {
+            return Err(ApiError::Forbidden);
+        }
+
+        Ok(account)
+    }
+}
+
+/// Render kebijakan spam menjadi script Sieve
+///
+/// Urutan: whitelist (lewati filter), blacklist (buang), lalu skor rspamd
+/// dari header `X-Spam-Level` (satu `*` per poin).
+pub fn render_spam_sieve(policy: &SpamPolicy) -> String {
+    let mut script = String::from(
+        "# Generated by NusaPanel - jangan edit manual\nrequire [\"fileinto\", \"mailbox\"];\n\n",
+    );
+    let mut rules: Vec<String> = Vec::new();
+
+    if !policy.whitelist.is_empty() {
+        rules.push(format!(
+            "if address :all :matches \"from\" {} {{\n    keep;\n}}",
+            sieve_string_list(&policy.whitelist)
+        ));
+    }
+
+    if !policy.blacklist.is_empty() {
+        rules.push(format!(
+            "if address :all :matches \"from\" {} {{\n    discard;\n    stop;\n}}",
+            sieve_string_list(&policy.blacklist)
+        ));
+    }
+
+    if policy.enabled {
+        let stars = "*".repeat(policy.threshold.ceil().max(1.0) as usize);
+        let action = match policy.action.as_str() {
+            "discard" => "discard;\n    stop;",
+            _ => "fileinto :create \"Junk\";\n    stop;",
+        };
+        rules.push(format!(
+            "if header :contains \"X-Spam-Level\" \"{}\" {{\n    {}\n}}",
+            stars, action
+        ));
+    }
+
+    // Aturan digabung dengan elsif agar whitelist melewati aturan berikutnya
+    script.push_str(&rules.join(" els"));
+    if !rules.is_empty() {
+        script.push('\n');
+    }
+
+    script
+}
+
+/// Format list string Sieve (`["a", "b"]`) dengan escaping
+fn sieve_string_list(items: &[String]) -> String {
+    let quoted: Vec<String> = items
+        .iter()
+        .map(|item| format!("\"{}\"", item.replace('\\', "\\\\").replace('"', "\\\"")))
+        .collect();
+    format!("[{}]", quoted.join(", "))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_spam_sieve_default_policy() {
+        let script = render_spam_sieve(&SpamPolicy::default());
+        assert!(script.contains("require [\"fileinto\", \"mailbox\"];"));
+        assert!(script.contains("if header :contains \"X-Spam-Level\" \"*****\""));
+        assert!(script.contains("fileinto :create \"Junk\";"));
+        assert!(!script.contains("address"));
+    }
+
+    #[test]
+    fn test_render_spam_sieve_lists_and_discard() {
+        let policy = SpamPolicy {
+            threshold: 7.5,
+            action: "discard".to_string(),
+            whitelist: vec!["*@partner.com".to_string()],
+            blacklist: vec!["spam@bad.com".to_string(), "*@evil.org".to_string()],
+            ..SpamPolicy::default()
+        };
+
+        let script = render_spam_sieve(&policy);
+        assert!(script.contains(
+            "if address :all :matches \"from\" [\"*@partner.com\"] {\n    keep;\n} elsif"
+        ));
+        assert!(script.contains("[\"spam@bad.com\", \"*@evil.org\"]"));
+        assert!(script.contains("} elsif header :contains \"X-Spam-Level\" \"********\""));
+        assert!(!script.contains("Junk"));
+    }
+
+    #[test]
+    fn test_render_spam_sieve_disabled_without_lists() {
+        let policy = SpamPolicy {
+            enabled: false,
+            ..SpamPolicy::default()
+        };
+
+        let script = render_spam_sieve(&policy);
+        assert!(!script.contains("if "));
+    }
+}
@@ -0,0 +1,265 @@
+//! # Staging Area
+//!
+//! Area internal per user di dalam home (trash, staging upload): satu
+//! directory per item berisi file state JSON dan data. Item yang kedaluwarsa
+//! dibersihkan berkala oleh fairing GC di semua user home.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use rocket::fairing::AdHoc;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::config::CONFIG;
+use crate::errors::{ApiError, ApiResult};
+use crate::services::FileService;
+
+/// Interval fairing GC
+const GC_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Definisi satu area internal di dalam home user
+pub struct StagingArea {
+    /// Directory area relatif terhadap home user
+    pub dir: &'static str,
+    /// File state JSON di setiap directory item
+    pub state_file: &'static str,
+    /// Nama item untuk error `NotFound` dan log
+    pub item_name: &'static str,
+}
+
+impl StagingArea {
+    /// Fairing yang menjalankan `task` setiap jam di blocking thread
+    ///
+    /// # Arguments
+    /// * `name` - Nama fairing
+    /// * `task` - GC, mengembalikan jumlah item yang dihapus
+    pub fn gc_fairing(name: &'static str, task: fn() -> usize) -> AdHoc {
+        AdHoc::on_liftoff(name, move |_| {
+            Box::pin(async move {
+                tokio::spawn(async move {
+                    let mut interval = tokio::time::interval(GC_INTERVAL);
+                    loop {
+                        interval.tick().await;
+                        match tokio::task::spawn_blocking(task).await {
+                            Ok(0) => {}
+                            Ok(removed) => tracing::info!("{}: removed {} items", name, removed),
+                            Err(e) => tracing::warn!("{} failed: {}", name, e),
+                        }
+                    }
+                });
+            })
+        })
+    }
+
+    // ==========================================
+    // DIRECTORIES
+    // ==========================================
+
+    /// Directory area user (divalidasi tetap di dalam sandbox)
+    pub fn root(&self, username: &str) -> ApiResult<PathBuf> {
+        FileService::resolve_internal_path(username, self.dir)
+    }
+
+    /// Directory area user, dibuat jika belum ada
+    pub fn ensure_root(&self, username: &str) -> ApiResult<PathBuf> {
+        let dir = self.root(username)?;
+
+        match fs::symlink_metadata(&dir) {
+            Ok(metadata) if metadata.is_dir() => Ok(dir),
+            Ok(_) => Err(ApiError::ValidationError(format!(
+                "{} bukan directory",
+                self.dir
+            ))),
+            Err(_) => {
+                fs::create_dir(&dir).map_err(|e| {
+                    tracing::error!("Failed to create {} directory: {}", self.dir, e);
+                    ApiError::FilePermissionDenied
+                })?;
+                Ok(dir)
+            }
+        }
+    }
+
+    /// Directory satu item yang sudah memiliki file state
+    pub fn item_dir(&self, username: &str, id: &str) -> ApiResult<PathBuf> {
+        let id = Uuid::parse_str(id).map_err(|_| self.not_found())?;
+        let dir = FileService::resolve_internal_path(username, &format!("{}/{}", self.dir, id))?;
+
+        if !dir.join(self.state_file).is_file() {
+            return Err(self.not_found());
+        }
+
+        Ok(dir)
+    }
+
+    fn not_found(&self) -> ApiError {
+        ApiError::NotFound(self.item_name.to_string())
+    }
+
+    // ==========================================
+    // STATE
+    // ==========================================
+
+    /// Baca file state item
+    pub fn load<T: DeserializeOwned>(&self, dir: &Path) -> ApiResult<T> {
+        let content = fs::read(dir.join(self.state_file)).map_err(|_| self.not_found())?;
+
+        serde_json::from_slice(&content)
+            .map_err(|e| ApiError::InternalError(format!("Invalid {}: {}", self.state_file, e)))
+    }
+
+    /// Tulis file state item
+    pub fn save<T: Serialize>(&self, dir: &Path, value: &T) -> ApiResult<()> {
+        let content = serde_json::to_vec(value).map_err(|e| {
+            ApiError::InternalError(format!("Failed to encode {}: {}", self.state_file, e))
+        })?;
+
+        // Tulis ke file sementara lalu rename agar state tidak pernah setengah tertulis
+        let temp = dir.join(format!("{}.tmp", self.state_file));
+        fs::write(&temp, content)
+            .and_then(|_| fs::rename(&temp, dir.join(self.state_file)))
+            .map_err(|e| {
+                tracing::error!("Failed to write {}: {}", self.state_file, e);
+                ApiError::FilePermissionDenied
+            })
+    }
+
+    // ==========================================
+    // GARBAGE COLLECTION
+    // ==========================================
+
+    /// Jalankan `sweep` untuk area di setiap user home
+    ///
+    /// # Returns
+    /// Total hasil `sweep` (jumlah item yang dihapus)
+    pub fn sweep_homes(&self, sweep: impl Fn(&Path) -> usize) -> usize {
+        let Ok(homes) = fs::read_dir(&CONFIG.file.user_home_base) else {
+            return 0;
+        };
+
+        homes
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().starts_with("user_"))
+            .map(|entry| sweep(&entry.path().join(self.dir)))
+            .sum()
+    }
+
+    /// Hapus item di satu area yang aktivitas terakhirnya sebelum `cutoff`
+    ///
+    /// # Arguments
+    /// * `root` - Directory area
+    /// * `cutoff` - Batas waktu aktivitas terakhir
+    /// * `last_activity` - Waktu aktivitas dari state (fallback mtime entry)
+    /// * `lock` - Guard item selama dihapus, `None` untuk melewati item
+    pub fn remove_expired<T, G>(
+        &self,
+        root: &Path,
+        cutoff: DateTime<Utc>,
+        last_activity: impl Fn(&T) -> DateTime<Utc>,
+        lock: impl Fn(&str) -> Option<G>,
+    ) -> usize
+    where
+        T: DeserializeOwned,
+    {
+        // Area yang diganti symlink oleh user tidak disentuh
+        if !fs::symlink_metadata(root).is_ok_and(|m| m.is_dir()) {
+            return 0;
+        }
+        let Ok(entries) = fs::read_dir(root) else {
+            return 0;
+        };
+
+        let mut removed = 0;
+        for entry in entries.filter_map(|e| e.ok()) {
+            let path = entry.path();
+
+            let activity = self
+                .load::<T>(&path)
+                .map(|state| last_activity(&state))
+                .ok()
+                .or_else(|| {
+                    entry
+                        .metadata()
+                        .and_then(|m| m.modified())
+                        .ok()
+                        .map(DateTime::<Utc>::from)
+                });
+            if activity.is_some_and(|t| t > cutoff) {
+                continue;
+            }
+
+            let Some(_guard) = lock(&entry.file_name().to_string_lossy()) else {
+                continue;
+            };
+
+            let result = if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                fs::remove_dir_all(&path)
+            } else {
+                fs::remove_file(&path)
+            };
+            match result {
+                Ok(()) => removed += 1,
+                Err(e) => tracing::warn!(
+                    "Failed to remove expired {} {:?}: {}",
+                    self.item_name,
+                    path,
+                    e
+                ),
+            }
+        }
+
+        removed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use serde::Deserialize;
+
+    #[derive(Serialize, Deserialize)]
+    struct State {
+        updated_at: DateTime<Utc>,
+    }
+
+    const AREA: StagingArea = StagingArea {
+        dir: ".test-area",
+        state_file: "state.json",
+        item_name: "Item",
+    };
+
+    #[test]
+    fn test_remove_expired() {
+        let root = std::env::temp_dir().join(format!("nusa-area-test-{}", Uuid::new_v4()));
+
+        for (id, age) in [("fresh", 10), ("stale", 7200), ("locked", 7200)] {
+            let dir = root.join(id);
+            fs::create_dir_all(&dir).unwrap();
+            let state = State {
+                updated_at: Utc::now() - chrono::Duration::seconds(age),
+            };
+            AREA.save(&dir, &state).unwrap();
+        }
+
+        let cutoff = Utc::now() - chrono::Duration::seconds(3600);
+        let removed = AREA.remove_expired(
+            &root,
+            cutoff,
+            |s: &State| s.updated_at,
+            |id| (id != "locked").then_some(()),
+        );
+
+        assert_eq!(removed, 1);
+        assert!(root.join("fresh").exists());
+        assert!(root.join("locked").exists());
+        assert!(!root.join("stale").exists());
+        assert!(AREA.load::<State>(&root.join("fresh")).is_ok());
+
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -7,6 +7,7 @@ use chrono::Utc;
 use sqlx::MySqlPool;
 use std::fs;
 use std::io::Write;
+use std::path::PathBuf;
 use std::process::{Command, Stdio};
 use uuid::Uuid;
 use validator::Validate;
@@ -16,6 +17,7 @@ use crate::models::{
     CreateBackupRequest, CreateCronJobRequest, CronJob, ServiceStatus, SystemBackup,
     UpdateCronJobRequest,
 };
+use crate::services::DatabaseBackupService;
 
 pub struct SystemServiceReal;
 
@@ -183,8 +185,8 @@ impl SystemServiceReal {
         let now = Utc::now();
         let system_username = format!("user_{}", username);
         let backup_filename = format!("backup_{}_{}_{}.tar.gz", username, request.backup_type, now.format("%Y%m%d%H%M%S"));
-        let backup_path = format!("/home/{}/backups/{}", system_username, backup_filename);
-        let backup_dir = format!("/home/{}/backups", system_username);
+        let backup_dir = Self::backup_dir(username);
+        let backup_path = format!("{}/{}", backup_dir, backup_filename);
 
         // Ensure backup dir exists
         fs::create_dir_all(&backup_dir).map_err(|e| ApiError::InternalError(format!("Backup dir error: {}", e)))?;
@@ -202,24 +204,33 @@ impl SystemServiceReal {
                     .output()?
             },
             "database" => {
-                // mysqldump -u root user_db | gzip > /path/backup.sql.gz
-                // NOTE: Ini simple implementation, idealnya loop semua DB user
-                // Untuk sekarang kita simulasi command success agar tidak error di dev environment
-                // Di production, gunakan `mysqldump` dengan credentials yang benar
-                Command::new("echo").arg("Mysqldump implementation needed").output()?
-                // Command::new("mysqldump")
-                // .arg("-u")
-                // .arg("-p")
+                // tar -czf /path/backup.tar.gz -C staging databases (databases/<db_name>.sql.gz)
+                let staging = Self::stage_database_dumps(pool, user_id, &backup_dir, &backup_id).await?;
+                let output = Command::new("tar")
+                    .arg("-czf")
+                    .arg(&backup_path)
+                    .arg("-C")
+                    .arg(&staging)
+                    .arg("databases")
+                    .output();
+                let _ = fs::remove_dir_all(&staging);
+                output?
             },
             "full" => {
-                // tar + mysqldump wrapper
-                 Command::new("tar")
+                // public_html + dump semua database user
+                let staging = Self::stage_database_dumps(pool, user_id, &backup_dir, &backup_id).await?;
+                let output = Command::new("tar")
                     .arg("-czf")
                     .arg(&backup_path)
                     .arg("-C")
                     .arg(format!("/home/{}", system_username))
                     .arg("public_html")
-                    .output()?
+                    .arg("-C")
+                    .arg(&staging)
+                    .arg("databases")
+                    .output();
+                let _ = fs::remove_dir_all(&staging);
+                output?
             },
             _ => return Err(ApiError::ValidationError("Invalid backup type".to_string())),
         };
@@ -261,6 +272,33 @@ impl SystemServiceReal {
         })
     }
 
+    /// Direktori backup milik user
+    pub fn backup_dir(username: &str) -> String {
+        format!("/home/user_{}/backups", username)
+    }
+
+    /// Dump semua database user ke `<backup_dir>/.staging-<id>/databases`
+    ///
+    /// # Returns
+    /// Path staging (parent dari direktori `databases`)
+    async fn stage_database_dumps(
+        pool: &MySqlPool,
+        user_id: &str,
+        backup_dir: &str,
+        backup_id: &str,
+    ) -> ApiResult<PathBuf> {
+        let staging = PathBuf::from(backup_dir).join(format!(".staging-{}", backup_id));
+
+        if let Err(e) =
+            DatabaseBackupService::dump_user_databases(pool, user_id, &staging.join("databases")).await
+        {
+            let _ = fs::remove_dir_all(&staging);
+            return Err(e);
+        }
+
+        Ok(staging)
+    }
+
     /// Get all cron jobs for user
     pub async fn get_user_cron_jobs(pool: &MySqlPool, user_id: &str) -> ApiResult<Vec<CronJob>> {
         let jobs = sqlx::query_as::<_, CronJob>(
@@ -0,0 +1,291 @@
+//! # Trash Service
+//!
+//! Trash bin per user: file/directory yang dihapus dipindahkan ke
+//! `~/.trash/<id>/` di dalam sandbox user (`trash.json` + `data`) sehingga bisa
+//! dikembalikan ke path asli. Trash hanya bisa diakses lewat service ini
+//! (ditolak oleh `FileService::resolve_path`). Karena trash berada di dalam
+//! home, ukurannya tetap dihitung dalam disk usage user. Item yang melewati
+//! retention dihapus permanen oleh fairing.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use chrono::Utc;
+use rocket::fairing::AdHoc;
+use uuid::Uuid;
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{FileInfo, FileType, TrashConfig, TrashItem, TrashListResponse, TRASH_DIR};
+use crate::services::{FileService, StagingArea};
+
+/// Area trash: `trash.json` berisi metadata item
+const TRASH: StagingArea = StagingArea {
+    dir: TRASH_DIR,
+    state_file: "trash.json",
+    item_name: "Item trash",
+};
+
+/// Nama file/directory yang dihapus di dalam directory item trash
+const DATA_FILE: &str = "data";
+
+/// Service untuk trash bin
+pub struct TrashService;
+
+impl TrashService {
+    /// Fairing yang menghapus item trash melewati retention
+    pub fn fairing() -> AdHoc {
+        StagingArea::gc_fairing("Trash Cleanup", Self::purge_expired)
+    }
+
+    // ==========================================
+    // TRASH AREA
+    // ==========================================
+
+    /// Home user yang sudah di-canonicalize (sama dengan hasil `resolve_path`)
+    fn canonical_base(username: &str) -> PathBuf {
+        let base = FileService::get_user_base_path(username);
+        base.canonicalize().unwrap_or(base)
+    }
+
+    // ==========================================
+    // OPERATIONS
+    // ==========================================
+
+    /// Pindahkan path (hasil `resolve_path`) ke trash
+    pub fn move_to_trash(username: &str, path: &Path) -> ApiResult<TrashItem> {
+        let base_path = Self::canonical_base(username);
+        let original_path = path
+            .strip_prefix(&base_path)
+            .map_err(|_| ApiError::FilePermissionDenied)?
+            .to_string_lossy()
+            .to_string();
+        let metadata = fs::symlink_metadata(path)
+            .map_err(|_| ApiError::FileNotFound(original_path.clone()))?;
+
+        let file_type = if metadata.file_type().is_symlink() {
+            FileType::Symlink
+        } else if metadata.is_dir() {
+            FileType::Directory
+        } else {
+            FileType::File
+        };
+
+        let item = TrashItem {
+            id: Uuid::new_v4().to_string(),
+            name: path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_default(),
+            original_path,
+            file_type,
+            size: FileService::tree_size(path),
+            deleted_at: Utc::now(),
+        };
+
+        let dir = TRASH.ensure_root(username)?.join(&item.id);
+        fs::create_dir(&dir).map_err(|e| {
+            tracing::error!("Failed to create trash item directory: {}", e);
+            ApiError::FilePermissionDenied
+        })?;
+        TRASH.save(&dir, &item)?;
+
+        if let Err(e) = fs::rename(path, dir.join(DATA_FILE)) {
+            tracing::error!("Failed to move {:?} to trash: {}", path, e);
+            let _ = fs::remove_dir_all(&dir);
+            return Err(ApiError::FilePermissionDenied);
+        }
+
+        tracing::info!(
+            "Moved to trash: {} ({}) by user {}",
+            item.original_path,
+            item.id,
+            username
+        );
+
+        Ok(item)
+    }
+
+    /// Daftar item trash user, terbaru lebih dulu
+    pub fn list(username: &str) -> ApiResult<TrashListResponse> {
+        let retention_days = TrashConfig::default().retention_days;
+        let dir = TRASH.root(username)?;
+
+        let mut items: Vec<TrashItem> = match fs::read_dir(&dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| TRASH.load(&entry.path()).ok())
+                .collect(),
+            Err(_) => Vec::new(),
+        };
+        items.sort_by_key(|item| std::cmp::Reverse(item.deleted_at));
+
+        Ok(TrashListResponse {
+            total_size: items.iter().map(|item| item.size).sum(),
+            items,
+            retention_days,
+        })
+    }
+
+    /// Kembalikan item ke path asli
+    ///
+    /// Gagal dengan `AlreadyExists` jika path asli sudah terisi; directory
+    /// induk yang sudah tidak ada dibuat ulang.
+    pub fn restore(username: &str, id: &str) -> ApiResult<FileInfo> {
+        let dir = TRASH.item_dir(username, id)?;
+        let item: TrashItem = TRASH.load(&dir)?;
+        let base_path = Self::canonical_base(username);
+
+        let target = FileService::resolve_path(username, &item.original_path)?;
+        if fs::symlink_metadata(&target).is_ok() {
+            return Err(ApiError::AlreadyExists(item.original_path));
+        }
+        if let Some(parent) = target.parent() {
+            Self::ensure_parent(parent, &base_path)?;
+        }
+
+        fs::rename(dir.join(DATA_FILE), &target).map_err(|e| {
+            tracing::error!("Failed to restore trash item {}: {}", item.id, e);
+            ApiError::FilePermissionDenied
+        })?;
+        let _ = fs::remove_dir_all(&dir);
+
+        let metadata = fs::symlink_metadata(&target)
+            .map_err(|_| ApiError::InternalError("Failed to get file metadata".to_string()))?;
+
+        tracing::info!(
+            "Restored from trash: {} ({}) by user {}",
+            item.original_path,
+            item.id,
+            username
+        );
+
+        // Path yang belum ada tidak di-canonicalize oleh `resolve_path`
+        let info_base = if target.starts_with(&base_path) {
+            base_path
+        } else {
+            FileService::get_user_base_path(username)
+        };
+        Ok(FileService::build_file_info(&target, &metadata, &info_base))
+    }
+
+    /// Buat directory induk tujuan restore tanpa keluar dari sandbox
+    fn ensure_parent(parent: &Path, base_path: &Path) -> ApiResult<()> {
+        let mut existing = parent;
+        while fs::symlink_metadata(existing).is_err() {
+            existing = existing.parent().ok_or(ApiError::FilePermissionDenied)?;
+        }
+
+        let canonical = existing
+            .canonicalize()
+            .map_err(|_| ApiError::FilePermissionDenied)?;
+        if !canonical.starts_with(base_path) {
+            return Err(ApiError::FilePermissionDenied);
+        }
+
+        fs::create_dir_all(parent).map_err(|e| {
+            tracing::error!("Failed to recreate parent directory: {}", e);
+            ApiError::FilePermissionDenied
+        })
+    }
+
+    /// Hapus permanen satu item trash
+    pub fn purge(username: &str, id: &str) -> ApiResult<()> {
+        let dir = TRASH.item_dir(username, id)?;
+
+        fs::remove_dir_all(&dir).map_err(|e| {
+            tracing::error!("Failed to purge trash item {}: {}", id, e);
+            ApiError::FilePermissionDenied
+        })?;
+
+        tracing::info!("Trash item {} purged by user {}", id, username);
+        Ok(())
+    }
+
+    /// Kosongkan trash user, mengembalikan jumlah item yang dihapus
+    pub fn empty(username: &str) -> ApiResult<usize> {
+        let dir = TRASH.root(username)?;
+        let Ok(entries) = fs::read_dir(&dir) else {
+            return Ok(0);
+        };
+
+        let mut removed = 0;
+        for entry in entries.filter_map(|e| e.ok()) {
+            let result = if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                fs::remove_dir_all(entry.path())
+            } else {
+                fs::remove_file(entry.path())
+            };
+            match result {
+                Ok(()) => removed += 1,
+                Err(e) => tracing::warn!("Failed to remove trash entry {:?}: {}", entry.path(), e),
+            }
+        }
+
+        tracing::info!("Trash emptied ({} items) by user {}", removed, username);
+        Ok(removed)
+    }
+
+    // ==========================================
+    // RETENTION
+    // ==========================================
+
+    /// Hapus item trash yang melewati retention di semua user home
+    pub fn purge_expired() -> usize {
+        let retention_days = TrashConfig::default().retention_days;
+        if retention_days == 0 {
+            return 0;
+        }
+
+        let cutoff = Utc::now() - chrono::Duration::days(retention_days as i64);
+        TRASH.sweep_homes(|trash| {
+            TRASH.remove_expired(
+                trash,
+                cutoff,
+                |item: &TrashItem| item.deleted_at,
+                |_| Some(()),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_purge_trash_before() {
+        let trash = std::env::temp_dir().join(format!("nusa-trash-test-{}", Uuid::new_v4()));
+        fs::create_dir_all(&trash).unwrap();
+
+        let item = |days_ago: i64| {
+            let item = TrashItem {
+                id: Uuid::new_v4().to_string(),
+                name: "index.php".to_string(),
+                original_path: "public_html/index.php".to_string(),
+                file_type: FileType::File,
+                size: 5,
+                deleted_at: Utc::now() - chrono::Duration::days(days_ago),
+            };
+            let dir = trash.join(&item.id);
+            fs::create_dir(&dir).unwrap();
+            fs::write(dir.join(DATA_FILE), "halo!").unwrap();
+            TRASH.save(&dir, &item).unwrap();
+            dir
+        };
+        let old = item(40);
+        let recent = item(1);
+
+        let cutoff = Utc::now() - chrono::Duration::days(30);
+        let removed = TRASH.remove_expired(
+            &trash,
+            cutoff,
+            |item: &TrashItem| item.deleted_at,
+            |_| Some(()),
+        );
+        assert_eq!(removed, 1);
+        assert!(!old.exists());
+        assert_eq!(TRASH.load::<TrashItem>(&recent).unwrap().name, "index.php");
+
+        fs::remove_dir_all(trash).ok();
+    }
+}
@@ -0,0 +1,438 @@
+//! # Version Service
+//!
+//! Riwayat versi file yang disimpan lewat editor (`write_file`). Sebelum file
+//! ditimpa, konten lamanya disimpan terkompresi gzip di
+//! `~/.nusa-versions/<sha256 path>/` (di luar document root) bersama
+//! `index.json`. Hanya `max_revisions` revisi terakhir yang disimpan per file.
+
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use chrono::Utc;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use similar::TextDiff;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{
+    DiskQuota, FileDiffResponse, FileInfo, FileRevision, FileVersionsResponse,
+    RestoreVersionRequest, VersionConfig, VERSIONS_DIR,
+};
+use crate::services::{FileService, QuotaService};
+
+/// Index revisi satu file
+const INDEX_FILE: &str = "index.json";
+
+/// Ukuran maksimum `index.json` yang dibaca
+const MAX_INDEX_SIZE: u64 = 1024 * 1024;
+
+/// Jumlah baris konteks di unified diff
+const DIFF_CONTEXT_LINES: usize = 3;
+
+/// Serialisasi update index (write paralel ke file yang sama)
+static INDEX_LOCK: Lazy<Mutex<()>> = Lazy::new(|| Mutex::new(()));
+
+/// Isi `index.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct VersionIndex {
+    /// Path file relatif dari user home
+    path: String,
+
+    /// Revisi, terlama lebih dulu
+    revisions: Vec<FileRevision>,
+}
+
+/// Service untuk versioning file
+pub struct VersionService;
+
+impl VersionService {
+    // ==========================================
+    // STORAGE
+    // ==========================================
+
+    /// Path relatif dari user home untuk path hasil `resolve_path`
+    fn relative_path(username: &str, full_path: &Path) -> ApiResult<String> {
+        let base_path = FileService::get_user_base_path(username);
+        let canonical_base = base_path
+            .canonicalize()
+            .unwrap_or_else(|_| base_path.clone());
+
+        full_path
+            .strip_prefix(&canonical_base)
+            .or_else(|_| full_path.strip_prefix(&base_path))
+            .map(|path| path.to_string_lossy().to_string())
+            .map_err(|_| ApiError::FilePermissionDenied)
+    }
+
+    /// Directory riwayat untuk satu file
+    fn history_dir(username: &str, relative_path: &str) -> ApiResult<PathBuf> {
+        let key = hex::encode(Sha256::digest(relative_path.as_bytes()));
+        FileService::resolve_internal_path(username, &format!("{}/{}", VERSIONS_DIR, key))
+    }
+
+    fn load_index(dir: &Path) -> ApiResult<Option<VersionIndex>> {
+        let file = match fs::File::open(dir.join(INDEX_FILE)) {
+            Ok(file) => file,
+            Err(_) => return Ok(None),
+        };
+        let content = read_limited(file, MAX_INDEX_SIZE).map_err(|e| match e {
+            ApiError::FileTooLarge(_) => {
+                ApiError::InternalError("Version index terlalu besar".to_string())
+            }
+            e => e,
+        })?;
+
+        serde_json::from_slice(&content)
+            .map(Some)
+            .map_err(|e| ApiError::InternalError(format!("Invalid version index: {}", e)))
+    }
+
+    fn save_index(dir: &Path, index: &VersionIndex) -> ApiResult<()> {
+        let content = serde_json::to_vec(index).map_err(|e| {
+            ApiError::InternalError(format!("Failed to encode version index: {}", e))
+        })?;
+
+        fs::write(dir.join(INDEX_FILE), content).map_err(|e| {
+            tracing::error!("Failed to write version index: {}", e);
+            ApiError::FilePermissionDenied
+        })
+    }
+
+    /// File revisi; id dari `index.json` bisa diubah user sehingga wajib UUID
+    fn revision_file(dir: &Path, id: &str) -> ApiResult<PathBuf> {
+        let id = Uuid::parse_str(id).map_err(|_| ApiError::NotFound("Revisi".to_string()))?;
+        Ok(dir.join(format!("{}.gz", id)))
+    }
+
+    /// Simpan konten sebagai revisi baru di `dir`
+    ///
+    /// Konten yang sama dengan revisi terakhir tidak disimpan ulang. Revisi
+    /// terlama dihapus jika melebihi `max_revisions`.
+    fn record(
+        dir: &Path,
+        relative_path: &str,
+        content: &[u8],
+        max_revisions: usize,
+    ) -> ApiResult<Option<FileRevision>> {
+        let _guard = INDEX_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        let mut index = Self::load_index(dir)?.unwrap_or_default();
+        let sha256 = hex::encode(Sha256::digest(content));
+        if index
+            .revisions
+            .last()
+            .is_some_and(|last| last.sha256 == sha256)
+        {
+            return Ok(None);
+        }
+
+        fs::create_dir_all(dir).map_err(|e| {
+            tracing::error!("Failed to create version directory: {}", e);
+            ApiError::FilePermissionDenied
+        })?;
+
+        let revision = FileRevision {
+            id: Uuid::new_v4().to_string(),
+            size: content.len() as u64,
+            sha256,
+            created_at: Utc::now(),
+        };
+
+        let file = fs::File::create(Self::revision_file(dir, &revision.id)?).map_err(|e| {
+            tracing::error!("Failed to create revision file: {}", e);
+            ApiError::FilePermissionDenied
+        })?;
+        let mut encoder = GzEncoder::new(file, Compression::default());
+        encoder
+            .write_all(content)
+            .and_then(|_| encoder.finish().map(|_| ()))
+            .map_err(|e| {
+                tracing::error!("Failed to write revision: {}", e);
+                ApiError::InternalError("Failed to write revision".to_string())
+            })?;
+
+        index.path = relative_path.to_string();
+        index.revisions.push(revision.clone());
+
+        let excess = index.revisions.len().saturating_sub(max_revisions);
+        for old in index.revisions.drain(..excess) {
+            match Self::revision_file(dir, &old.id) {
+                Ok(file) => {
+                    let _ = fs::remove_file(file);
+                }
+                Err(_) => tracing::warn!("Skipping invalid revision id in {:?}: {}", dir, old.id),
+            }
+        }
+
+        Self::save_index(dir, &index)?;
+        Ok(Some(revision))
+    }
+
+    /// Baca dan dekompresi revisi, maksimal `max_size` bytes
+    fn read_revision(dir: &Path, id: &str, max_size: u64) -> ApiResult<Vec<u8>> {
+        let file = fs::File::open(Self::revision_file(dir, id)?)
+            .map_err(|_| ApiError::NotFound("Revisi".to_string()))?;
+
+        read_limited(GzDecoder::new(file), max_size)
+    }
+
+    // ==========================================
+    // OPERATIONS
+    // ==========================================
+
+    /// Simpan konten file saat ini sebagai revisi sebelum ditimpa
+    ///
+    /// File yang belum ada, terlalu besar, atau versioning nonaktif dilewati.
+    pub fn snapshot(username: &str, full_path: &Path) -> ApiResult<Option<FileRevision>> {
+        let config = VersionConfig::default();
+        if config.max_revisions == 0 {
+            return Ok(None);
+        }
+
+        let metadata = match fs::metadata(full_path) {
+            Ok(metadata) if metadata.is_file() => metadata,
+            _ => return Ok(None),
+        };
+        if metadata.len() > config.max_file_size {
+            return Ok(None);
+        }
+
+        let content = fs::read(full_path).map_err(|e| {
+            tracing::error!("Failed to read file for revision: {}", e);
+            ApiError::FilePermissionDenied
+        })?;
+
+        let relative_path = Self::relative_path(username, full_path)?;
+        let dir = Self::history_dir(username, &relative_path)?;
+        Self::record(&dir, &relative_path, &content, config.max_revisions)
+    }
+
+    /// Daftar revisi file, terbaru lebih dulu
+    pub fn list(username: &str, path: &str) -> ApiResult<FileVersionsResponse> {
+        let full_path = FileService::resolve_path(username, path)?;
+        let relative_path = Self::relative_path(username, &full_path)?;
+        let dir = Self::history_dir(username, &relative_path)?;
+
+        let mut revisions = Self::load_index(&dir)?
+            .map(|index| index.revisions)
+            .unwrap_or_default();
+        revisions.reverse();
+
+        Ok(FileVersionsResponse {
+            path: relative_path,
+            revisions,
+            max_revisions: VersionConfig::default().max_revisions,
+        })
+    }
+
+    /// Unified diff dari revisi `from` ke revisi `to` (atau konten saat ini)
+    pub fn diff(
+        username: &str,
+        path: &str,
+        from: &str,
+        to: Option<&str>,
+    ) -> ApiResult<FileDiffResponse> {
+        let full_path = FileService::resolve_path(username, path)?;
+        let relative_path = Self::relative_path(username, &full_path)?;
+        let dir = Self::history_dir(username, &relative_path)?;
+
+        let max_size = VersionConfig::default().max_file_size;
+        let old = Self::read_revision(&dir, from, max_size)?;
+        let new = match to {
+            Some(to) => Self::read_revision(&dir, to, max_size)?,
+            None => {
+                let file = fs::File::open(&full_path)
+                    .map_err(|_| ApiError::FileNotFound(path.to_string()))?;
+                read_limited(file, max_size)?
+            }
+        };
+
+        let to_label = to.unwrap_or("current");
+        let diff = unified_diff(&old, &new, from, to_label)?;
+
+        Ok(FileDiffResponse {
+            path: relative_path,
+            from: from.to_string(),
+            to: to.map(str::to_string),
+            diff,
+        })
+    }
+
+    /// Kembalikan file ke konten revisi
+    ///
+    /// Konten saat ini disimpan sebagai revisi baru lebih dulu sehingga
+    /// restore bisa dibatalkan.
+    pub fn restore(
+        username: &str,
+        request: RestoreVersionRequest,
+        quota: &DiskQuota,
+    ) -> ApiResult<FileInfo> {
+        request
+            .validate()
+            .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+        let full_path = FileService::resolve_path(username, &request.path)?;
+        let relative_path = Self::relative_path(username, &full_path)?;
+        let dir = Self::history_dir(username, &relative_path)?;
+        let content = Self::read_revision(
+            &dir,
+            &request.revision,
+            VersionConfig::default().max_file_size,
+        )?;
+
+        if full_path.is_dir() {
+            return Err(ApiError::ValidationError(
+                "Path adalah directory".to_string(),
+            ));
+        }
+        if !full_path.parent().is_some_and(Path::exists) {
+            return Err(ApiError::FileNotFound(
+                "Parent directory does not exist".to_string(),
+            ));
+        }
+
+        let existing_size = fs::metadata(&full_path).map(|m| m.len()).unwrap_or(0);
+        quota.ensure_available((content.len() as u64).saturating_sub(existing_size))?;
+
+        Self::snapshot(username, &full_path)?;
+        fs::write(&full_path, &content).map_err(|e| {
+            tracing::error!("Failed to restore revision: {}", e);
+            ApiError::FilePermissionDenied
+        })?;
+        QuotaService::invalidate(username);
+
+        let metadata = fs::metadata(&full_path)
+            .map_err(|_| ApiError::InternalError("Failed to get file metadata".to_string()))?;
+
+        tracing::info!(
+            "Revision {} restored to {} by user {}",
+            request.revision,
+            relative_path,
+            username
+        );
+
+        Ok(FileService::build_file_info(
+            &full_path,
+            &metadata,
+            &FileService::get_user_base_path(username),
+        ))
+    }
+}
+
+/// Baca seluruh isi reader, `FileTooLarge` jika melebihi `max_size` bytes
+fn read_limited<R: Read>(reader: R, max_size: u64) -> ApiResult<Vec<u8>> {
+    let mut content = Vec::new();
+    reader
+        .take(max_size.saturating_add(1))
+        .read_to_end(&mut content)
+        .map_err(|e| ApiError::InternalError(format!("Failed to read revision: {}", e)))?;
+
+    if content.len() as u64 > max_size {
+        return Err(ApiError::FileTooLarge(max_size));
+    }
+    Ok(content)
+}
+
+/// Unified diff dua konten teks
+fn unified_diff(old: &[u8], new: &[u8], old_label: &str, new_label: &str) -> ApiResult<String> {
+    let (Ok(old), Ok(new)) = (std::str::from_utf8(old), std::str::from_utf8(new)) else {
+        return Err(ApiError::ValidationError(
+            "File biner tidak bisa di-diff".to_string(),
+        ));
+    };
+
+    Ok(TextDiff::from_lines(old, new)
+        .unified_diff()
+        .context_radius(DIFF_CONTEXT_LINES)
+        .header(old_label, new_label)
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_dedup_and_prune() {
+        let dir = std::env::temp_dir().join(format!("nusa-versions-test-{}", Uuid::new_v4()));
+
+        let first = VersionService::record(&dir, "public_html/.htaccess", b"v1", 2)
+            .unwrap()
+            .unwrap();
+        // Konten sama dengan revisi terakhir tidak disimpan ulang
+        assert!(
+            VersionService::record(&dir, "public_html/.htaccess", b"v1", 2)
+                .unwrap()
+                .is_none()
+        );
+
+        let second = VersionService::record(&dir, "public_html/.htaccess", b"v2", 2)
+            .unwrap()
+            .unwrap();
+        let third = VersionService::record(&dir, "public_html/.htaccess", b"v3", 2)
+            .unwrap()
+            .unwrap();
+
+        let index = VersionService::load_index(&dir).unwrap().unwrap();
+        let ids: Vec<&str> = index.revisions.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(ids, vec![second.id.as_str(), third.id.as_str()]);
+        assert!(!VersionService::revision_file(&dir, &first.id)
+            .unwrap()
+            .exists());
+        assert_eq!(
+            VersionService::read_revision(&dir, &second.id, 1024).unwrap(),
+            b"v2"
+        );
+        // Revisi yang melebihi batas (mis. gzip bomb) tidak dibaca penuh
+        assert!(matches!(
+            VersionService::read_revision(&dir, &third.id, 1),
+            Err(ApiError::FileTooLarge(1))
+        ));
+
+        fs::remove_dir_all(dir).ok();
+    }
+
+    #[test]
+    fn test_record_ignores_tampered_revision_ids() {
+        let root = std::env::temp_dir().join(format!("nusa-versions-test-{}", Uuid::new_v4()));
+        let dir = root.join("history");
+        fs::create_dir_all(&dir).unwrap();
+        let victim = root.join("backup.gz");
+        fs::write(&victim, b"backup").unwrap();
+
+        // Index yang diubah user lewat file manager
+        let index = VersionIndex {
+            path: "public_html/index.php".to_string(),
+            revisions: vec![FileRevision {
+                id: "../backup".to_string(),
+                size: 6,
+                sha256: String::new(),
+                created_at: Utc::now(),
+            }],
+        };
+        VersionService::save_index(&dir, &index).unwrap();
+
+        VersionService::record(&dir, "public_html/index.php", b"v1", 1).unwrap();
+        assert!(victim.exists());
+        assert!(VersionService::read_revision(&dir, "../backup", 1024).is_err());
+
+        fs::remove_dir_all(root).ok();
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let diff = unified_diff(b"a\nb\nc\n", b"a\nB\nc\n", "rev1", "current").unwrap();
+        assert!(diff.starts_with("--- rev1\n+++ current\n"));
+        assert!(diff.contains("-b\n+B\n"));
+
+        assert!(unified_diff(&[0xff, 0xfe], b"text", "rev1", "current").is_err());
+    }
+}
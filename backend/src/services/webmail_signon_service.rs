@@ -0,0 +1,211 @@
+//! # Webmail Signon Service
+//!
+//! Business logic untuk Webmail SSO (Roundcube / SnappyMail).
+//! Menggunakan in-memory storage untuk token dengan auto-cleanup,
+//! sama seperti phpMyAdmin signon.
+
+use std::collections::HashMap;
+use std::sync::RwLock;
+
+use chrono::Utc;
+use once_cell::sync::Lazy;
+use sqlx::MySqlPool;
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{
+    EmailAccount, ValidateTokenRequest, WebmailConfig, WebmailCredentials, WebmailSignonResponse,
+    WebmailSignonToken,
+};
+
+/// In-memory storage untuk webmail signon tokens
+/// Menggunakan RwLock untuk thread-safe access
+static TOKEN_STORE: Lazy<RwLock<HashMap<String, WebmailSignonToken>>> =
+    Lazy::new(|| RwLock::new(HashMap::new()));
+
+/// Service untuk Webmail SSO operations
+pub struct WebmailSignonService;
+
+impl WebmailSignonService {
+    /// Generate signon token untuk mailbox milik user
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    /// * `email_account_id` - ID email account yang akan di-SSO
+    /// * `panel_user_id` - ID panel user yang melakukan request
+    ///
+    /// # Returns
+    /// WebmailSignonResponse dengan URL untuk redirect ke webmail
+    ///
+    /// # Errors
+    /// - `NotFound` jika email account tidak ditemukan
+    /// - `Forbidden` jika mailbox bukan milik user atau tidak aktif
+    /// - `ServiceUnavailable` jika master user webmail belum dikonfigurasi
+    pub async fn generate_token(
+        pool: &MySqlPool,
+        email_account_id: &str,
+        panel_user_id: &str,
+    ) -> ApiResult<WebmailSignonResponse> {
+        let config = WebmailConfig::default();
+        if !config.has_master_user() {
+            tracing::warn!("Webmail SSO requested but WEBMAIL_MASTER_USER is not configured");
+            return Err(ApiError::ServiceUnavailable);
+        }
+
+        // 1. Validasi ownership mailbox
+        let account = sqlx::query_as::<_, EmailAccount>(
+            "SELECT * FROM email_accounts WHERE id = ?",
+        )
+        .bind(email_account_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(ApiError::NotFound("Email Account".to_string()))?;
+
+        if account.user_id != panel_user_id {
+            return Err(ApiError::Forbidden);
+        }
+
+        // 2. Cek apakah mailbox aktif
+        if !account.is_active {
+            return Err(ApiError::Forbidden);
+        }
+
+        // 3. Buat token baru
+        let token = WebmailSignonToken::new(email_account_id, panel_user_id, &account.email_address);
+        let token_id = token.id.clone();
+        let expires_in = (token.expires_at - Utc::now()).num_seconds();
+
+        // 4. Simpan token ke storage
+        {
+            let mut store = TOKEN_STORE
+                .write()
+                .map_err(|_| ApiError::InternalError("Failed to acquire token store lock".into()))?;
+
+            // Cleanup expired tokens saat menambah token baru
+            store.retain(|_, t| !t.is_expired());
+
+            store.insert(token_id.clone(), token);
+        }
+
+        tracing::info!("Webmail signon token issued for {}", account.email_address);
+
+        // 5. Build signon URL
+        let separator = if config.signon_path.contains('?') { '&' } else { '?' };
+        let signon_url = format!(
+            "{}{}{}token={}",
+            config.base_url, config.signon_path, separator, token_id
+        );
+
+        Ok(WebmailSignonResponse {
+            signon_url,
+            expires_in,
+        })
+    }
+
+    /// Validasi token dan kembalikan kredensial master user
+    /// Token akan dihapus setelah validasi (one-time use)
+    ///
+    /// # Arguments
+    /// * `pool` - Database connection pool
+    /// * `request` - Request berisi token yang akan divalidasi
+    /// * `internal_key` - Internal key dari header untuk verifikasi
+    ///
+    /// # Errors
+    /// - `Unauthorized` jika token tidak valid, expired, atau internal key salah
+    /// - `Forbidden` jika mailbox sudah dinonaktifkan setelah token dibuat
+    pub async fn validate_token(
+        pool: &MySqlPool,
+        request: &ValidateTokenRequest,
+        internal_key: &str,
+    ) -> ApiResult<WebmailCredentials> {
+        // 1. Validasi internal key
+        let config = WebmailConfig::default();
+        if internal_key != config.internal_key {
+            return Err(ApiError::Unauthorized);
+        }
+
+        // 2. Ambil dan hapus token dari storage (one-time use)
+        let token = {
+            let mut store = TOKEN_STORE.write().map_err(|_| {
+                ApiError::InternalError("Failed to acquire token store lock".into())
+            })?;
+
+            store.remove(&request.token)
+        };
+
+        let token = token.ok_or(ApiError::Unauthorized)?;
+
+        // 3. Cek expiry
+        if token.is_expired() {
+            return Err(ApiError::Unauthorized);
+        }
+
+        // 4. Pastikan mailbox masih ada dan aktif
+        let is_active = sqlx::query_scalar::<_, bool>(
+            "SELECT is_active FROM email_accounts WHERE id = ? AND user_id = ?",
+        )
+        .bind(&token.email_account_id)
+        .bind(&token.panel_user_id)
+        .fetch_optional(pool)
+        .await?
+        .ok_or(ApiError::NotFound("Email Account".to_string()))?;
+
+        if !is_active {
+            return Err(ApiError::Forbidden);
+        }
+
+        if !config.has_master_user() {
+            return Err(ApiError::ServiceUnavailable);
+        }
+
+        Ok(Self::build_credentials(&config, &token.email_address))
+    }
+
+    /// Build kredensial master user untuk mailbox
+    fn build_credentials(config: &WebmailConfig, email_address: &str) -> WebmailCredentials {
+        WebmailCredentials {
+            email: email_address.to_string(),
+            login: config.master_login(email_address),
+            password: config.master_password.clone(),
+            imap_host: config.imap_host.clone(),
+            imap_port: config.imap_port,
+            smtp_host: config.smtp_host.clone(),
+            smtp_port: config.smtp_port,
+        }
+    }
+
+    /// Cleanup expired tokens secara manual
+    pub fn cleanup_expired_tokens() -> usize {
+        if let Ok(mut store) = TOKEN_STORE.write() {
+            let before = store.len();
+            store.retain(|_, t| !t.is_expired());
+            before - store.len()
+        } else {
+            0
+        }
+    }
+
+    /// Get jumlah token aktif (untuk monitoring)
+    pub fn get_active_token_count() -> usize {
+        TOKEN_STORE.read().map(|s| s.len()).unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_credentials_uses_master_login() {
+        let config = WebmailConfig {
+            master_user: "nusapanel".to_string(),
+            master_password: "secret".to_string(),
+            master_separator: "*".to_string(),
+            ..WebmailConfig::default()
+        };
+
+        let credentials = WebmailSignonService::build_credentials(&config, "info@example.com");
+        assert_eq!(credentials.email, "info@example.com");
+        assert_eq!(credentials.login, "info@example.com*nusapanel");
+        assert_eq!(credentials.password, "secret");
+    }
+}
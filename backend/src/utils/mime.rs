@@ -0,0 +1,164 @@
+//! # MIME Utilities
+//!
+//! Deteksi MIME type dari magic bytes dengan fallback ke ekstensi file, serta
+//! penentuan apakah konten file berupa text (bisa dibuka di editor).
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+/// Jumlah byte awal file yang dibaca untuk sniffing
+pub const SNIFF_LENGTH: usize = 8192;
+
+/// MIME default untuk konten binary yang tidak dikenali
+const OCTET_STREAM: &str = "application/octet-stream";
+
+/// Subtype `application/*` yang berupa text
+const TEXT_APPLICATION_TYPES: &[&str] = &[
+    "application/json",
+    "application/javascript",
+    "application/xml",
+    "application/sql",
+    "application/toml",
+    "application/x-sh",
+    "application/x-httpd-php",
+    "application/x-yaml",
+    "application/yaml",
+];
+
+/// Hasil deteksi konten file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sniffed {
+    /// MIME type hasil deteksi
+    pub mime_type: String,
+    /// Apakah konten berupa text UTF-8
+    pub is_text: bool,
+}
+
+/// Baca byte awal file untuk sniffing
+pub fn read_head(path: &Path) -> std::io::Result<Vec<u8>> {
+    let mut head = Vec::with_capacity(SNIFF_LENGTH);
+    File::open(path)?
+        .take(SNIFF_LENGTH as u64)
+        .read_to_end(&mut head)?;
+    Ok(head)
+}
+
+/// Cek apakah bytes berupa text UTF-8 (tanpa NUL byte)
+///
+/// Karakter multi-byte yang terpotong di akhir buffer sniffing tetap dianggap text.
+pub fn looks_like_text(bytes: &[u8]) -> bool {
+    if bytes.contains(&0) {
+        return false;
+    }
+
+    match std::str::from_utf8(bytes) {
+        Ok(_) => true,
+        Err(e) => e.error_len().is_none() && bytes.len() - e.valid_up_to() < 4,
+    }
+}
+
+/// Cek apakah MIME type berupa format text
+fn is_text_mime(mime: &str) -> bool {
+    mime.starts_with("text/")
+        || mime.ends_with("+xml")
+        || mime.ends_with("+json")
+        || TEXT_APPLICATION_TYPES.contains(&mime)
+}
+
+/// Deteksi MIME type dan text/binary dari nama file dan byte awal konten
+///
+/// Urutan: magic bytes, lalu sniffing text, lalu ekstensi file.
+pub fn detect(filename: &str, head: &[u8]) -> Sniffed {
+    let guessed = mime_guess::from_path(filename)
+        .first_raw()
+        .map(|mime| mime.to_string());
+
+    if let Some(kind) = infer::get(head) {
+        return Sniffed {
+            mime_type: kind.mime_type().to_string(),
+            is_text: kind.matcher_type() == infer::MatcherType::Text && looks_like_text(head),
+        };
+    }
+
+    if looks_like_text(head) {
+        return Sniffed {
+            mime_type: guessed
+                .filter(|mime| is_text_mime(mime))
+                .unwrap_or_else(|| "text/plain".to_string()),
+            is_text: true,
+        };
+    }
+
+    Sniffed {
+        mime_type: guessed
+            .filter(|mime| !is_text_mime(mime))
+            .unwrap_or_else(|| OCTET_STREAM.to_string()),
+        is_text: false,
+    }
+}
+
+/// Deteksi konten file langsung dari disk
+pub fn detect_file(path: &Path) -> Option<Sniffed> {
+    let filename = path.file_name()?.to_str()?;
+    let head = read_head(path).ok()?;
+    Some(detect(filename, &head))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_magic_bytes() {
+        let png = [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0, 0, 0, 0x0D];
+        let sniffed = detect("photo.txt", &png);
+        assert_eq!(sniffed.mime_type, "image/png");
+        assert!(!sniffed.is_text);
+
+        let sniffed = detect("doc", b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n");
+        assert_eq!(sniffed.mime_type, "application/pdf");
+        assert!(!sniffed.is_text);
+    }
+
+    #[test]
+    fn test_detect_text() {
+        let sniffed = detect("app.js", b"console.log('halo');\n");
+        assert_eq!(sniffed.mime_type, "text/javascript");
+        assert!(sniffed.is_text);
+
+        let sniffed = detect("composer.json", b"{\"name\": \"nusa/site\"}");
+        assert_eq!(sniffed.mime_type, "application/json");
+        assert!(sniffed.is_text);
+
+        let sniffed = detect("Makefile", b"all:\n\tcargo build\n");
+        assert_eq!(sniffed.mime_type, "text/plain");
+        assert!(sniffed.is_text);
+
+        let sniffed = detect("logo.png", b"bukan gambar");
+        assert_eq!(sniffed.mime_type, "text/plain");
+        assert!(sniffed.is_text);
+
+        let sniffed = detect("empty.css", b"");
+        assert_eq!(sniffed.mime_type, "text/css");
+        assert!(sniffed.is_text);
+    }
+
+    #[test]
+    fn test_detect_binary_fallback() {
+        let sniffed = detect("data.txt", &[0x00, 0x01, 0x02, 0xFF]);
+        assert_eq!(sniffed.mime_type, OCTET_STREAM);
+        assert!(!sniffed.is_text);
+
+        let sniffed = detect("font.woff2", &[0x00, 0x01, 0x02, 0xFF]);
+        assert_eq!(sniffed.mime_type, "font/woff2");
+    }
+
+    #[test]
+    fn test_looks_like_text_truncated_utf8() {
+        // "é" (0xC3 0xA9) terpotong di akhir buffer
+        assert!(looks_like_text(b"caf\xC3"));
+        assert!(!looks_like_text(b"caf\xC3x"));
+        assert!(!looks_like_text(b"\xFF\xFE"));
+    }
+}
@@ -3,6 +3,11 @@
 //! Utility functions dan helpers untuk NusaPanel.
 
 pub mod jwt;
+pub mod mime;
+pub mod mysql;
 pub mod password;
+pub mod postgres;
 pub mod response;
+pub mod search;
+pub mod secrets;
 pub mod system;
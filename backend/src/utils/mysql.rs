@@ -0,0 +1,448 @@
+//! # MySQL Administration Utilities
+//!
+//! Builder statement administrasi MySQL (CREATE/DROP DATABASE, CREATE/ALTER/DROP USER,
+//! GRANT/REVOKE) untuk managed database customer.
+//!
+//! Statement ini tidak mendukung placeholder, jadi setiap nilai divalidasi:
+//! - Identifier (nama database, username) harus cocok dengan grammar ketat
+//! - Host harus `%`, `localhost`, IP, IP/netmask, pola wildcard IPv4, atau hostname
+//! - Password di-escape sebagai string literal
+//! - Privilege hanya berasal dari enum `DatabasePrivilege`
+
+use std::net::{IpAddr, Ipv4Addr};
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::DatabasePrivilege;
+
+/// Panjang maksimum nama database MySQL
+pub const MAX_DATABASE_NAME_LENGTH: usize = 64;
+
+/// Panjang maksimum username MySQL
+pub const MAX_USERNAME_LENGTH: usize = 32;
+
+/// Panjang maksimum host MySQL
+pub const MAX_HOST_LENGTH: usize = 255;
+
+/// Host akun utama database user
+pub const LOCAL_HOST: &str = "localhost";
+
+/// Prefix CIDR IPv4 terkecil yang boleh dipakai untuk remote MySQL
+pub const MIN_REMOTE_IPV4_PREFIX: u32 = 8;
+
+/// Grammar identifier: huruf, angka, underscore
+static IDENTIFIER_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^[A-Za-z0-9_]+$").unwrap());
+
+/// Pola wildcard IPv4 (e.g., `192.168.1.%`)
+static IPV4_WILDCARD_REGEX: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(\d{1,3}\.){1,3}%$").unwrap());
+
+/// Hostname (RFC 1123)
+static HOSTNAME_REGEX: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[A-Za-z0-9]([A-Za-z0-9-]{0,61}[A-Za-z0-9])?(\.[A-Za-z0-9]([A-Za-z0-9-]{0,61}[A-Za-z0-9])?)*$")
+        .unwrap()
+});
+
+/// Cek nama database
+pub fn is_valid_database_name(name: &str) -> bool {
+    name.len() <= MAX_DATABASE_NAME_LENGTH && IDENTIFIER_REGEX.is_match(name)
+}
+
+/// Cek username MySQL
+pub fn is_valid_username(username: &str) -> bool {
+    username.len() <= MAX_USERNAME_LENGTH && IDENTIFIER_REGEX.is_match(username)
+}
+
+/// Cek host MySQL (`%`, `localhost`, IP, IP/netmask, wildcard IPv4, hostname)
+pub fn is_valid_host(host: &str) -> bool {
+    if host.is_empty() || host.len() > MAX_HOST_LENGTH {
+        return false;
+    }
+
+    if host == "%" || host == "localhost" || host.parse::<IpAddr>().is_ok() {
+        return true;
+    }
+
+    if let Some((ip, mask)) = host.split_once('/') {
+        return ip.parse::<Ipv4Addr>().is_ok() && mask.parse::<Ipv4Addr>().is_ok();
+    }
+
+    if IPV4_WILDCARD_REGEX.is_match(host) {
+        return host
+            .trim_end_matches(".%")
+            .split('.')
+            .all(|octet| octet.parse::<u8>().is_ok());
+    }
+
+    HOSTNAME_REGEX.is_match(host)
+}
+
+/// Entry allow-list remote MySQL
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RemoteHost {
+    /// Bentuk normal (IP atau `network/prefix`), dipakai untuk disimpan dan firewall
+    pub source: String,
+
+    /// Bentuk host account MySQL (IP atau `network/netmask`)
+    pub mysql_host: String,
+
+    /// Alamat IPv6 (firewall lewat `ip6tables`)
+    pub is_ipv6: bool,
+}
+
+/// Parse IP atau CIDR IPv4 untuk allow-list remote MySQL
+///
+/// MySQL hanya mengenal `network/netmask` untuk IPv4, jadi CIDR IPv6 ditolak.
+pub fn parse_remote_host(value: &str) -> Option<RemoteHost> {
+    let value = value.trim();
+
+    if let Ok(ip) = value.parse::<IpAddr>() {
+        return Some(RemoteHost {
+            source: ip.to_string(),
+            mysql_host: ip.to_string(),
+            is_ipv6: ip.is_ipv6(),
+        });
+    }
+
+    let (ip, prefix) = value.split_once('/')?;
+    let ip = ip.parse::<Ipv4Addr>().ok()?;
+    let prefix = prefix.parse::<u32>().ok()?;
+    if !(MIN_REMOTE_IPV4_PREFIX..=32).contains(&prefix) {
+        return None;
+    }
+
+    let mask = u32::MAX << (32 - prefix);
+    let network = Ipv4Addr::from(u32::from(ip) & mask);
+
+    if prefix == 32 {
+        return Some(RemoteHost {
+            source: network.to_string(),
+            mysql_host: network.to_string(),
+            is_ipv6: false,
+        });
+    }
+
+    Some(RemoteHost {
+        source: format!("{}/{}", network, prefix),
+        mysql_host: format!("{}/{}", network, Ipv4Addr::from(mask)),
+        is_ipv6: false,
+    })
+}
+
+/// Cek password MySQL (karakter kontrol ditolak)
+pub fn is_valid_password(password: &str) -> bool {
+    !password.is_empty() && !password.chars().any(char::is_control)
+}
+
+/// Escape nilai sebagai string literal MySQL (`'...'`)
+///
+/// Mengikuti `mysql_real_escape_string` untuk sql_mode default.
+pub fn quote_string(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('\'');
+    for c in value.chars() {
+        match c {
+            '\'' => quoted.push_str("\\'"),
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\0' => quoted.push_str("\\0"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\x1a' => quoted.push_str("\\Z"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('\'');
+    quoted
+}
+
+/// Identifier database dalam backtick
+pub fn quote_database(name: &str) -> ApiResult<String> {
+    if !is_valid_database_name(name) {
+        return Err(ApiError::ValidationError(
+            "Nama database tidak valid".to_string(),
+        ));
+    }
+    Ok(format!("`{}`", name))
+}
+
+/// Nama database sebagai pola GRANT/REVOKE
+///
+/// Di level database `_` adalah wildcard (seperti LIKE), sehingga di-escape agar
+/// grant hanya berlaku untuk database itu sendiri.
+fn quote_grant_database(name: &str) -> ApiResult<String> {
+    Ok(quote_database(name)?.replace('_', "\\_"))
+}
+
+/// Account MySQL `'user'@'host'`
+pub fn account(username: &str, host: &str) -> ApiResult<String> {
+    if !is_valid_username(username) {
+        return Err(ApiError::ValidationError(
+            "Username database tidak valid".to_string(),
+        ));
+    }
+    if !is_valid_host(host) {
+        return Err(ApiError::ValidationError(
+            "Host database tidak valid".to_string(),
+        ));
+    }
+    Ok(format!("{}@{}", quote_string(username), quote_string(host)))
+}
+
+/// `CREATE DATABASE IF NOT EXISTS ... CHARACTER SET ... COLLATE ...`
+pub fn create_database(name: &str, charset: &str, collation: &str) -> ApiResult<String> {
+    if !IDENTIFIER_REGEX.is_match(charset) || !IDENTIFIER_REGEX.is_match(collation) {
+        return Err(ApiError::ValidationError(
+            "Charset atau collation tidak valid".to_string(),
+        ));
+    }
+    Ok(format!(
+        "CREATE DATABASE IF NOT EXISTS {} CHARACTER SET {} COLLATE {}",
+        quote_database(name)?,
+        charset,
+        collation
+    ))
+}
+
+/// `DROP DATABASE IF EXISTS ...`
+pub fn drop_database(name: &str) -> ApiResult<String> {
+    Ok(format!("DROP DATABASE IF EXISTS {}", quote_database(name)?))
+}
+
+/// `CREATE USER IF NOT EXISTS ... IDENTIFIED BY ...`
+pub fn create_user(username: &str, host: &str, password: &str) -> ApiResult<String> {
+    Ok(format!(
+        "CREATE USER IF NOT EXISTS {} IDENTIFIED BY {}",
+        account(username, host)?,
+        password_literal(password)?
+    ))
+}
+
+/// `CREATE USER IF NOT EXISTS ... IDENTIFIED WITH <plugin> AS 0x<hash>`
+///
+/// Dipakai untuk menyalin password akun lain (`mysql.user.authentication_string`).
+pub fn create_user_with_auth(
+    username: &str,
+    host: &str,
+    plugin: &str,
+    auth_hex: &str,
+) -> ApiResult<String> {
+    if !IDENTIFIER_REGEX.is_match(plugin) || !auth_hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(ApiError::ValidationError(
+            "Authentication string tidak valid".to_string(),
+        ));
+    }
+    let auth = if auth_hex.is_empty() {
+        "''".to_string()
+    } else {
+        format!("0x{}", auth_hex)
+    };
+    Ok(format!(
+        "CREATE USER IF NOT EXISTS {} IDENTIFIED WITH {} AS {}",
+        account(username, host)?,
+        plugin,
+        auth
+    ))
+}
+
+/// `RENAME USER ... TO ...`
+pub fn rename_user(username: &str, from_host: &str, to_host: &str) -> ApiResult<String> {
+    Ok(format!(
+        "RENAME USER {} TO {}",
+        account(username, from_host)?,
+        account(username, to_host)?
+    ))
+}
+
+/// `ALTER USER ... IDENTIFIED BY ...`
+pub fn alter_user_password(username: &str, host: &str, password: &str) -> ApiResult<String> {
+    Ok(format!(
+        "ALTER USER {} IDENTIFIED BY {}",
+        account(username, host)?,
+        password_literal(password)?
+    ))
+}
+
+/// `DROP USER IF EXISTS ...`
+pub fn drop_user(username: &str, host: &str) -> ApiResult<String> {
+    Ok(format!("DROP USER IF EXISTS {}", account(username, host)?))
+}
+
+/// `GRANT <privileges> ON db.* TO ...`
+pub fn grant(
+    privileges: &[DatabasePrivilege],
+    database: &str,
+    username: &str,
+    host: &str,
+) -> ApiResult<String> {
+    if privileges.is_empty() {
+        return Err(ApiError::ValidationError(
+            "Privileges tidak boleh kosong".to_string(),
+        ));
+    }
+    Ok(format!(
+        "GRANT {} ON {}.* TO {}",
+        DatabasePrivilege::join(privileges),
+        quote_grant_database(database)?,
+        account(username, host)?
+    ))
+}
+
+/// `REVOKE ALL PRIVILEGES ON db.* FROM ...`
+pub fn revoke_all(database: &str, username: &str, host: &str) -> ApiResult<String> {
+    Ok(format!(
+        "REVOKE ALL PRIVILEGES ON {}.* FROM {}",
+        quote_grant_database(database)?,
+        account(username, host)?
+    ))
+}
+
+/// `SHOW GRANTS FOR ...`
+pub fn show_grants(username: &str, host: &str) -> ApiResult<String> {
+    Ok(format!("SHOW GRANTS FOR {}", account(username, host)?))
+}
+
+/// Parse dan validasi string privileges dari request
+pub fn parse_privileges(value: &str) -> ApiResult<Vec<DatabasePrivilege>> {
+    DatabasePrivilege::parse_list(value)
+        .ok_or_else(|| ApiError::ValidationError(format!("Privileges '{}' tidak valid", value)))
+}
+
+fn password_literal(password: &str) -> ApiResult<String> {
+    if !is_valid_password(password) {
+        return Err(ApiError::ValidationError(
+            "Password mengandung karakter tidak valid".to_string(),
+        ));
+    }
+    Ok(quote_string(password))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Cek literal hasil `quote_string` tidak pernah berakhir sebelum karakter terakhir
+    fn is_single_literal(literal: &str) -> bool {
+        let inner = &literal[1..literal.len() - 1];
+        let mut escaped = false;
+        for c in inner.chars() {
+            match (escaped, c) {
+                (true, _) => escaped = false,
+                (false, '\\') => escaped = true,
+                (false, '\'') => return false,
+                _ => {}
+            }
+        }
+        !escaped && literal.starts_with('\'') && literal.ends_with('\'')
+    }
+
+    #[test]
+    fn test_identifier_injection_rejected() {
+        assert!(is_valid_username("a1b2c3d4_wp"));
+        assert!(!is_valid_username("wp'@'%' IDENTIFIED BY 'x'; --"));
+        assert!(!is_valid_username("wp`"));
+        assert!(!is_valid_username(&"a".repeat(33)));
+
+        assert!(quote_database("a1b2c3d4_shop").is_ok());
+        assert!(quote_database("shop`; DROP DATABASE mysql; --").is_err());
+        assert!(drop_database("x` ; DROP TABLE users; -- ").is_err());
+    }
+
+    #[test]
+    fn test_host_grammar() {
+        for host in [
+            "%",
+            "localhost",
+            "10.0.0.5",
+            "::1",
+            "192.168.1.%",
+            "10.0.0.0/255.0.0.0",
+            "db.example.com",
+        ] {
+            assert!(is_valid_host(host), "{}", host);
+        }
+        for host in [
+            "",
+            "%' IDENTIFIED BY 'x",
+            "10.0.0.1'; --",
+            "a b",
+            "300.1.%",
+            "10.0.0.0/8/8",
+            "-bad.com",
+        ] {
+            assert!(!is_valid_host(host), "{}", host);
+        }
+    }
+
+    #[test]
+    fn test_password_is_escaped() {
+        for payload in [
+            "x'; DROP DATABASE mysql; --",
+            "a\\",
+            "\\'; GRANT ALL ON *.* TO 'x'@'%",
+            "p\"w",
+        ] {
+            let sql = create_user("a1b2c3d4_wp", "localhost", payload).unwrap();
+            let literal = sql.split(" IDENTIFIED BY ").nth(1).unwrap();
+            assert!(is_single_literal(literal), "{}", sql);
+        }
+
+        assert_eq!(quote_string("it's"), "'it\\'s'");
+        assert!(create_user("a1b2c3d4_wp", "localhost", "line\nbreak").is_err());
+        assert!(create_user("a1b2c3d4_wp", "localhost", "nul\0byte").is_err());
+    }
+
+    #[test]
+    fn test_parse_remote_host() {
+        let host = parse_remote_host("203.0.113.77/24").unwrap();
+        assert_eq!(host.source, "203.0.113.0/24");
+        assert_eq!(host.mysql_host, "203.0.113.0/255.255.255.0");
+        assert!(is_valid_host(&host.mysql_host));
+
+        let host = parse_remote_host(" 198.51.100.7 ").unwrap();
+        assert_eq!(host.source, "198.51.100.7");
+        assert_eq!(host.mysql_host, "198.51.100.7");
+        assert_eq!(parse_remote_host("198.51.100.7/32"), Some(host));
+
+        let host = parse_remote_host("2001:db8::1").unwrap();
+        assert!(host.is_ipv6);
+
+        for value in [
+            "%",
+            "0.0.0.0/0",
+            "10.0.0.0/7",
+            "2001:db8::/64",
+            "db.example.com",
+            "1.2.3.4/x",
+        ] {
+            assert!(parse_remote_host(value).is_none(), "{}", value);
+        }
+
+        assert_eq!(
+            create_user_with_auth("a1b2c3d4_wp", "10.0.0.5", "caching_sha2_password", "2441").unwrap(),
+            "CREATE USER IF NOT EXISTS 'a1b2c3d4_wp'@'10.0.0.5' IDENTIFIED WITH caching_sha2_password AS 0x2441"
+        );
+        assert!(create_user_with_auth("a1b2c3d4_wp", "10.0.0.5", "x", "'; --").is_err());
+    }
+
+    #[test]
+    fn test_grant_statement() {
+        let privileges = parse_privileges("select, insert").unwrap();
+        assert_eq!(
+            grant(&privileges, "a1b2c3d4_shop", "a1b2c3d4_wp", "%").unwrap(),
+            "GRANT SELECT, INSERT ON `a1b2c3d4\\_shop`.* TO 'a1b2c3d4_wp'@'%'"
+        );
+        assert_eq!(
+            revoke_all("a1b2c3d4_shop", "a1b2c3d4_wp", "%").unwrap(),
+            "REVOKE ALL PRIVILEGES ON `a1b2c3d4\\_shop`.* FROM 'a1b2c3d4_wp'@'%'"
+        );
+        assert!(parse_privileges("ALL PRIVILEGES ON *.* TO 'evil'@'%' --").is_err());
+        assert!(parse_privileges("FILE").is_err());
+        assert!(grant(&[], "a1b2c3d4_shop", "a1b2c3d4_wp", "%").is_err());
+        assert_eq!(
+            show_grants("a1b2c3d4_wp", "localhost").unwrap(),
+            "SHOW GRANTS FOR 'a1b2c3d4_wp'@'localhost'"
+        );
+    }
+}
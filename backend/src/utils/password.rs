@@ -7,6 +7,7 @@ use argon2::{
     password_hash::{rand_core::OsRng, PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
     Argon2,
 };
+use sha_crypt::{sha512_simple, Sha512Params, ROUNDS_DEFAULT};
 
 use crate::config::CONFIG;
 use crate::errors::ApiError;
@@ -196,10 +197,34 @@ pub fn generate_random_password(length: usize) -> String {
     password.into_iter().collect()
 }
 
+/// Hash password dalam format SHA-512 crypt (`$6$salt$hash`)
+///
+/// Dipakai untuk service yang memverifikasi password lewat `crypt(3)` libc
+/// (mis. Pure-FTPd `MYSQLCrypt crypt`), yang tidak mengenal hash Argon2 PHC.
+///
+/// # Errors
+/// Returns ApiError jika hashing gagal
+pub fn hash_crypt_password(password: &str) -> Result<String, ApiError> {
+    let params = Sha512Params::new(ROUNDS_DEFAULT)
+        .map_err(|_| ApiError::InternalError("Invalid crypt rounds".to_string()))?;
+
+    sha512_simple(password, &params).map_err(|e| {
+        tracing::error!("Crypt password hashing failed: {:?}", e);
+        ApiError::InternalError("Failed to hash password".to_string())
+    })
+}
+
+/// Cek apakah hash bisa diverifikasi `crypt(3)` (bukan hash Argon2 PHC)
+pub fn is_crypt_hash(hash: &str) -> bool {
+    hash.starts_with("$6$")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use sha_crypt::sha512_check;
+
     #[test]
     fn test_hash_and_verify_password() {
         let password = "TestP@ssword123";
@@ -212,6 +237,24 @@ mod tests {
         assert!(!verify_password("WrongPassword", &hash).expect("Verification failed"));
     }
 
+    #[test]
+    fn test_hash_crypt_password() {
+        let hash = hash_crypt_password("TestP@ssword123").unwrap();
+        assert!(is_crypt_hash(&hash));
+        assert_eq!(hash.len(), 3 + 16 + 1 + 86);
+        assert!(sha512_check("TestP@ssword123", &hash).is_ok());
+        assert!(sha512_check("WrongPassword", &hash).is_err());
+        // Test vector dari spesifikasi SHA-crypt
+        assert!(sha512_check(
+            "Hello world!",
+            "$6$saltstring$svn8UoSVapNtMuq1ukKS4tPQd8iKwSMHWjl/O817G3uBnIFNjnQJuesI68u4OTLiBFdcbYEdFCoEOfaS35inz1"
+        )
+        .is_ok());
+
+        // Hash Argon2 lama tidak bisa dipakai `crypt(3)`
+        assert!(!is_crypt_hash(&hash_password("TestP@ssword123").unwrap()));
+    }
+
     #[test]
     fn test_validate_password_strength() {
         // Valid password
@@ -0,0 +1,455 @@
+//! # PostgreSQL Administration Utilities
+//!
+//! Builder statement administrasi PostgreSQL (CREATE/DROP DATABASE, CREATE/ALTER/DROP ROLE,
+//! GRANT/REVOKE) untuk managed database customer.
+//!
+//! Setiap database dimiliki role `NOLOGIN` `<db_name>_owner`. Database user dengan
+//! privilege `ALL` menjadi anggota role tersebut dan sesinya di database itu berjalan
+//! sebagai owner (`ALTER ROLE ... IN DATABASE ... SET role`), sehingga object yang dibuat
+//! user mana pun tetap dimiliki role owner yang sama.
+//!
+//! Aturan validasi sama dengan `utils::mysql`: identifier mengikuti grammar ketat,
+//! password di-escape sebagai string literal, privilege hanya dari `DatabasePrivilege`.
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::DatabasePrivilege;
+use crate::utils::mysql;
+
+/// Panjang maksimum identifier PostgreSQL (NAMEDATALEN - 1)
+pub const MAX_IDENTIFIER_LENGTH: usize = 63;
+
+/// Suffix role owner database
+pub const OWNER_ROLE_SUFFIX: &str = "_owner";
+
+/// Schema default tempat object customer dibuat
+pub const DEFAULT_SCHEMA: &str = "public";
+
+/// Query ukuran database dalam bytes
+pub const DATABASE_SIZE_QUERY: &str = "SELECT pg_database_size($1)::BIGINT";
+
+/// Query statistik tabel di schema `public` (dijalankan di database tujuan)
+pub const TABLE_STATS_QUERY: &str = r#"
+    SELECT c.relname::TEXT,
+           COALESCE(am.amname, 'heap')::TEXT,
+           GREATEST(c.reltuples, 0)::BIGINT,
+           pg_table_size(c.oid)::BIGINT,
+           pg_indexes_size(c.oid)::BIGINT,
+           COALESCE(s.n_live_tup, 0)::BIGINT,
+           COALESCE(s.n_dead_tup, 0)::BIGINT
+    FROM pg_class c
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    LEFT JOIN pg_am am ON am.oid = c.relam
+    LEFT JOIN pg_stat_user_tables s ON s.relid = c.oid
+    WHERE n.nspname = 'public' AND c.relkind IN ('r', 'p')
+"#;
+
+/// Query privilege level database yang dimiliki role (`aclexplode(datacl)`)
+pub const DATABASE_PRIVILEGES_QUERY: &str = r#"
+    SELECT d.datname::TEXT, a.privilege_type::TEXT
+    FROM pg_database d
+    CROSS JOIN LATERAL aclexplode(d.datacl) a
+    JOIN pg_roles r ON r.oid = a.grantee
+    WHERE r.rolname = $1
+    ORDER BY d.datname, a.privilege_type
+"#;
+
+/// Query role owner yang diwarisi role (`GRANT <owner> TO <user>`)
+pub const ROLE_MEMBERSHIPS_QUERY: &str = r#"
+    SELECT g.rolname::TEXT
+    FROM pg_auth_members m
+    JOIN pg_roles g ON g.oid = m.roleid
+    JOIN pg_roles u ON u.oid = m.member
+    WHERE u.rolname = $1
+    ORDER BY g.rolname
+"#;
+
+/// Cek identifier (nama database / role)
+pub fn is_valid_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && name.len() <= MAX_IDENTIFIER_LENGTH
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Identifier dalam double quote
+pub fn quote_identifier(name: &str) -> ApiResult<String> {
+    if !is_valid_identifier(name) {
+        return Err(ApiError::ValidationError(
+            "Identifier PostgreSQL tidak valid".to_string(),
+        ));
+    }
+    Ok(format!("\"{}\"", name))
+}
+
+/// Escape nilai sebagai string literal PostgreSQL (`'...'`)
+///
+/// Mengasumsikan `standard_conforming_strings = on` (default sejak 9.1), sehingga
+/// hanya single quote yang perlu digandakan.
+pub fn quote_string(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "''"))
+}
+
+/// Nama role owner database
+pub fn owner_role(database: &str) -> String {
+    format!("{}{}", database, OWNER_ROLE_SUFFIX)
+}
+
+/// Statement membuat role owner dan database
+pub fn create_database(name: &str, encoding: &str) -> ApiResult<Vec<String>> {
+    if !encoding
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '_')
+    {
+        return Err(ApiError::ValidationError(
+            "Encoding tidak valid".to_string(),
+        ));
+    }
+    let database = quote_identifier(name)?;
+    let owner = quote_identifier(&owner_role(name))?;
+
+    Ok(vec![
+        format!("CREATE ROLE {} NOLOGIN", owner),
+        format!(
+            "CREATE DATABASE {} OWNER {} ENCODING {} TEMPLATE template0",
+            database,
+            owner,
+            quote_string(encoding)
+        ),
+        format!("REVOKE ALL ON DATABASE {} FROM PUBLIC", database),
+    ])
+}
+
+/// Statement di dalam database baru: schema default dimiliki role owner
+pub fn setup_database_schema(name: &str) -> ApiResult<Vec<String>> {
+    let owner = quote_identifier(&owner_role(name))?;
+    Ok(vec![
+        format!("ALTER SCHEMA {} OWNER TO {}", DEFAULT_SCHEMA, owner),
+        format!("REVOKE ALL ON SCHEMA {} FROM PUBLIC", DEFAULT_SCHEMA),
+    ])
+}
+
+/// Statement menghapus database beserta role owner
+pub fn drop_database(name: &str) -> ApiResult<Vec<String>> {
+    Ok(vec![
+        format!(
+            "DROP DATABASE IF EXISTS {} WITH (FORCE)",
+            quote_identifier(name)?
+        ),
+        format!(
+            "DROP ROLE IF EXISTS {}",
+            quote_identifier(&owner_role(name))?
+        ),
+    ])
+}
+
+/// `CREATE ROLE ... LOGIN PASSWORD ...`
+pub fn create_role(username: &str, password: &str) -> ApiResult<String> {
+    Ok(format!(
+        "CREATE ROLE {} LOGIN NOINHERIT PASSWORD {}",
+        quote_identifier(username)?,
+        password_literal(password)?
+    ))
+}
+
+/// `ALTER ROLE ... PASSWORD ...`
+pub fn alter_role_password(username: &str, password: &str) -> ApiResult<String> {
+    Ok(format!(
+        "ALTER ROLE {} PASSWORD {}",
+        quote_identifier(username)?,
+        password_literal(password)?
+    ))
+}
+
+/// `DROP ROLE IF EXISTS ...`
+pub fn drop_role(username: &str) -> ApiResult<String> {
+    Ok(format!(
+        "DROP ROLE IF EXISTS {}",
+        quote_identifier(username)?
+    ))
+}
+
+/// Privilege yang tidak punya padanan di PostgreSQL
+pub fn unsupported_privileges(privileges: &[DatabasePrivilege]) -> Vec<DatabasePrivilege> {
+    privileges
+        .iter()
+        .copied()
+        .filter(|privilege| {
+            matches!(
+                privilege,
+                DatabasePrivilege::Drop
+                    | DatabasePrivilege::Index
+                    | DatabasePrivilege::Alter
+                    | DatabasePrivilege::LockTables
+                    | DatabasePrivilege::CreateView
+                    | DatabasePrivilege::ShowView
+                    | DatabasePrivilege::CreateRoutine
+                    | DatabasePrivilege::AlterRoutine
+                    | DatabasePrivilege::Event
+            )
+        })
+        .collect()
+}
+
+/// Statement GRANT, dipisah antara level cluster dan level database
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GrantStatements {
+    /// Dijalankan di koneksi admin (database apa pun)
+    pub cluster: Vec<String>,
+
+    /// Dijalankan di koneksi ke database tujuan
+    pub database: Vec<String>,
+}
+
+/// Statement GRANT privileges database ke role
+///
+/// `ALL` menjadikan role anggota role owner; selain itu privilege dipetakan ke
+/// privilege tabel/sequence/function pada schema `public`, termasuk default
+/// privileges untuk object baru milik role owner.
+pub fn grant(
+    privileges: &[DatabasePrivilege],
+    database: &str,
+    username: &str,
+) -> ApiResult<GrantStatements> {
+    if privileges.is_empty() {
+        return Err(ApiError::ValidationError(
+            "Privileges tidak boleh kosong".to_string(),
+        ));
+    }
+
+    let unsupported = unsupported_privileges(privileges);
+    if !unsupported.is_empty() {
+        return Err(ApiError::ValidationError(format!(
+            "Privileges tidak didukung PostgreSQL: {}",
+            DatabasePrivilege::join(&unsupported)
+        )));
+    }
+
+    let db = quote_identifier(database)?;
+    let role = quote_identifier(username)?;
+    let owner = quote_identifier(&owner_role(database))?;
+
+    if privileges.contains(&DatabasePrivilege::All) {
+        return Ok(GrantStatements {
+            cluster: vec![
+                format!("GRANT CONNECT, TEMPORARY ON DATABASE {} TO {}", db, role),
+                format!("GRANT {} TO {}", owner, role),
+                format!(
+                    "ALTER ROLE {} IN DATABASE {} SET role = {}",
+                    role,
+                    db,
+                    quote_string(&owner_role(database))
+                ),
+            ],
+            database: Vec::new(),
+        });
+    }
+
+    let database_privileges = if privileges.contains(&DatabasePrivilege::CreateTemporaryTables) {
+        "CONNECT, TEMPORARY"
+    } else {
+        "CONNECT"
+    };
+
+    let table_privileges: Vec<&str> = privileges
+        .iter()
+        .filter_map(|privilege| match privilege {
+            DatabasePrivilege::Select => Some("SELECT"),
+            DatabasePrivilege::Insert => Some("INSERT"),
+            DatabasePrivilege::Update => Some("UPDATE"),
+            DatabasePrivilege::Delete => Some("DELETE"),
+            DatabasePrivilege::References => Some("REFERENCES"),
+            DatabasePrivilege::Trigger => Some("TRIGGER"),
+            _ => None,
+        })
+        .collect();
+
+    let mut sequence_privileges = Vec::new();
+    if privileges.contains(&DatabasePrivilege::Select) {
+        sequence_privileges.push("SELECT");
+    }
+    if privileges.contains(&DatabasePrivilege::Insert) {
+        sequence_privileges.push("USAGE");
+    }
+    if privileges.contains(&DatabasePrivilege::Update) {
+        sequence_privileges.push("UPDATE");
+    }
+
+    let schema_privileges = if privileges.contains(&DatabasePrivilege::Create) {
+        "USAGE, CREATE"
+    } else {
+        "USAGE"
+    };
+
+    let mut statements = vec![format!(
+        "GRANT {} ON SCHEMA {} TO {}",
+        schema_privileges, DEFAULT_SCHEMA, role
+    )];
+
+    let mut grant_objects = |privileges: &str, objects: &str| {
+        statements.push(format!(
+            "GRANT {} ON ALL {} IN SCHEMA {} TO {}",
+            privileges, objects, DEFAULT_SCHEMA, role
+        ));
+        statements.push(format!(
+            "ALTER DEFAULT PRIVILEGES FOR ROLE {} IN SCHEMA {} GRANT {} ON {} TO {}",
+            owner, DEFAULT_SCHEMA, privileges, objects, role
+        ));
+    };
+
+    if !table_privileges.is_empty() {
+        grant_objects(&table_privileges.join(", "), "TABLES");
+    }
+    if !sequence_privileges.is_empty() {
+        grant_objects(&sequence_privileges.join(", "), "SEQUENCES");
+    }
+    if privileges.contains(&DatabasePrivilege::Execute) {
+        grant_objects("EXECUTE", "FUNCTIONS");
+    }
+
+    Ok(GrantStatements {
+        cluster: vec![format!(
+            "GRANT {} ON DATABASE {} TO {}",
+            database_privileges, db, role
+        )],
+        database: statements,
+    })
+}
+
+/// Statement mencabut semua privilege role pada database
+pub fn revoke_all(database: &str, username: &str) -> ApiResult<GrantStatements> {
+    let db = quote_identifier(database)?;
+    let role = quote_identifier(username)?;
+    let owner = quote_identifier(&owner_role(database))?;
+
+    let mut statements = Vec::new();
+    for objects in ["TABLES", "SEQUENCES", "FUNCTIONS"] {
+        statements.push(format!(
+            "REVOKE ALL ON ALL {} IN SCHEMA {} FROM {}",
+            objects, DEFAULT_SCHEMA, role
+        ));
+        statements.push(format!(
+            "ALTER DEFAULT PRIVILEGES FOR ROLE {} IN SCHEMA {} REVOKE ALL ON {} FROM {}",
+            owner, DEFAULT_SCHEMA, objects, role
+        ));
+    }
+    statements.push(format!(
+        "REVOKE ALL ON SCHEMA {} FROM {}",
+        DEFAULT_SCHEMA, role
+    ));
+
+    Ok(GrantStatements {
+        cluster: vec![
+            format!("REVOKE ALL ON DATABASE {} FROM {}", db, role),
+            format!("REVOKE {} FROM {}", owner, role),
+            format!("ALTER ROLE {} IN DATABASE {} RESET role", role, db),
+        ],
+        database: statements,
+    })
+}
+
+/// Parse dan validasi privileges untuk PostgreSQL
+pub fn parse_privileges(value: &str) -> ApiResult<Vec<DatabasePrivilege>> {
+    let privileges = mysql::parse_privileges(value)?;
+    let unsupported = unsupported_privileges(&privileges);
+    if !unsupported.is_empty() {
+        return Err(ApiError::ValidationError(format!(
+            "Privileges tidak didukung PostgreSQL: {}",
+            DatabasePrivilege::join(&unsupported)
+        )));
+    }
+    Ok(privileges)
+}
+
+fn password_literal(password: &str) -> ApiResult<String> {
+    if !mysql::is_valid_password(password) {
+        return Err(ApiError::ValidationError(
+            "Password mengandung karakter tidak valid".to_string(),
+        ));
+    }
+    Ok(quote_string(password))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_identifier_and_string() {
+        assert_eq!(
+            quote_identifier("a1b2c3d4_shop").unwrap(),
+            "\"a1b2c3d4_shop\""
+        );
+        assert!(quote_identifier("shop\"; DROP DATABASE x; --").is_err());
+        assert!(quote_identifier("").is_err());
+        assert!(quote_identifier(&"a".repeat(64)).is_err());
+
+        assert_eq!(quote_string("it's"), "'it''s'");
+        assert_eq!(
+            create_role("a1b2c3d4_app", "p'ss\\word").unwrap(),
+            "CREATE ROLE \"a1b2c3d4_app\" LOGIN NOINHERIT PASSWORD 'p''ss\\word'"
+        );
+        assert!(create_role("a1b2c3d4_app", "nul\0byte").is_err());
+    }
+
+    #[test]
+    fn test_create_and_drop_database() {
+        assert_eq!(
+            create_database("a1b2c3d4_shop", "UTF8").unwrap(),
+            vec![
+                "CREATE ROLE \"a1b2c3d4_shop_owner\" NOLOGIN",
+                "CREATE DATABASE \"a1b2c3d4_shop\" OWNER \"a1b2c3d4_shop_owner\" ENCODING 'UTF8' TEMPLATE template0",
+                "REVOKE ALL ON DATABASE \"a1b2c3d4_shop\" FROM PUBLIC",
+            ]
+        );
+        assert!(create_database("a1b2c3d4_shop", "UTF8'; --").is_err());
+        assert_eq!(
+            drop_database("a1b2c3d4_shop").unwrap()[1],
+            "DROP ROLE IF EXISTS \"a1b2c3d4_shop_owner\""
+        );
+    }
+
+    #[test]
+    fn test_grant_statements() {
+        use DatabasePrivilege::*;
+
+        let all = grant(&[All], "a1b2c3d4_shop", "a1b2c3d4_app").unwrap();
+        assert!(all.database.is_empty());
+        assert_eq!(
+            all.cluster[1],
+            "GRANT \"a1b2c3d4_shop_owner\" TO \"a1b2c3d4_app\""
+        );
+        assert_eq!(
+            all.cluster[2],
+            "ALTER ROLE \"a1b2c3d4_app\" IN DATABASE \"a1b2c3d4_shop\" SET role = 'a1b2c3d4_shop_owner'"
+        );
+
+        let read = grant(&[Select], "a1b2c3d4_shop", "a1b2c3d4_ro").unwrap();
+        assert_eq!(
+            read.cluster,
+            vec!["GRANT CONNECT ON DATABASE \"a1b2c3d4_shop\" TO \"a1b2c3d4_ro\""]
+        );
+        assert_eq!(
+            read.database,
+            vec![
+                "GRANT USAGE ON SCHEMA public TO \"a1b2c3d4_ro\"",
+                "GRANT SELECT ON ALL TABLES IN SCHEMA public TO \"a1b2c3d4_ro\"",
+                "ALTER DEFAULT PRIVILEGES FOR ROLE \"a1b2c3d4_shop_owner\" IN SCHEMA public GRANT SELECT ON TABLES TO \"a1b2c3d4_ro\"",
+                "GRANT SELECT ON ALL SEQUENCES IN SCHEMA public TO \"a1b2c3d4_ro\"",
+                "ALTER DEFAULT PRIVILEGES FOR ROLE \"a1b2c3d4_shop_owner\" IN SCHEMA public GRANT SELECT ON SEQUENCES TO \"a1b2c3d4_ro\"",
+            ]
+        );
+
+        assert!(grant(&[Select, LockTables], "a1b2c3d4_shop", "a1b2c3d4_ro").is_err());
+        assert!(parse_privileges("SELECT, EVENT").is_err());
+        assert_eq!(
+            parse_privileges("insert, select").unwrap(),
+            vec![Select, Insert]
+        );
+
+        let revoke = revoke_all("a1b2c3d4_shop", "a1b2c3d4_ro").unwrap();
+        assert_eq!(
+            revoke.cluster[2],
+            "ALTER ROLE \"a1b2c3d4_ro\" IN DATABASE \"a1b2c3d4_shop\" RESET role"
+        );
+        assert_eq!(revoke.database.len(), 7);
+    }
+}
@@ -0,0 +1,190 @@
+//! # Search Utilities
+//!
+//! Helper pencarian file: matcher literal/regex, filter glob include/exclude
+//! dan pencarian baris yang cocok beserta konteksnya.
+
+use std::path::Path;
+
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use regex::{Regex, RegexBuilder};
+
+use crate::errors::{ApiError, ApiResult};
+use crate::models::{SearchMatch, SearchMode, SEARCH_MAX_LINE_LENGTH, SEARCH_MAX_MATCHES_PER_FILE};
+
+/// Batas ukuran regex yang sudah dikompilasi (mencegah pola raksasa)
+const REGEX_SIZE_LIMIT: usize = 1024 * 1024;
+
+/// Buat matcher untuk query
+///
+/// Mode `Name` dan `Literal` mencocokkan teks apa adanya; mode `Regex` memakai
+/// sintaks crate `regex` (tanpa backtracking, aman dari ReDoS).
+pub fn build_matcher(query: &str, mode: SearchMode, case_sensitive: bool) -> ApiResult<Regex> {
+    let pattern = match mode {
+        SearchMode::Regex => query.to_string(),
+        SearchMode::Name | SearchMode::Literal => regex::escape(query),
+    };
+
+    RegexBuilder::new(&pattern)
+        .case_insensitive(!case_sensitive)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+        .map_err(|e| ApiError::ValidationError(format!("Regex tidak valid: {}", e)))
+}
+
+/// Kumpulan glob dari daftar pola yang dipisah koma
+#[derive(Debug, Clone)]
+pub struct GlobFilter {
+    set: GlobSet,
+}
+
+impl GlobFilter {
+    /// Parse daftar glob, `None` jika kosong
+    pub fn parse(patterns: Option<&str>) -> ApiResult<Option<Self>> {
+        let patterns: Vec<&str> = patterns
+            .unwrap_or("")
+            .split(',')
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .collect();
+
+        if patterns.is_empty() {
+            return Ok(None);
+        }
+
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let glob = Glob::new(pattern).map_err(|e| {
+                ApiError::ValidationError(format!("Glob tidak valid '{}': {}", pattern, e))
+            })?;
+            builder.add(glob);
+        }
+
+        let set = builder
+            .build()
+            .map_err(|e| ApiError::ValidationError(format!("Glob tidak valid: {}", e)))?;
+
+        Ok(Some(Self { set }))
+    }
+
+    /// Cek apakah nama file atau path relatif terhadap `root` cocok
+    pub fn matches(&self, path: &Path, root: &Path) -> bool {
+        let relative = path.strip_prefix(root).unwrap_or(path);
+        self.set.is_match(relative)
+            || path
+                .file_name()
+                .is_some_and(|name| self.set.is_match(Path::new(name)))
+    }
+}
+
+/// Potong baris yang terlalu panjang
+fn truncate_line(line: &str) -> String {
+    match line.char_indices().nth(SEARCH_MAX_LINE_LENGTH) {
+        Some((index, _)) => format!("{}…", &line[..index]),
+        None => line.to_string(),
+    }
+}
+
+/// Cari baris yang cocok beserta `context` baris sebelum dan sesudahnya
+///
+/// Maksimum [`SEARCH_MAX_MATCHES_PER_FILE`] match per file.
+pub fn find_matches(text: &str, matcher: &Regex, context: usize) -> Vec<SearchMatch> {
+    let lines: Vec<&str> = text.lines().collect();
+    let mut matches = Vec::new();
+
+    for (index, line) in lines.iter().enumerate() {
+        if !matcher.is_match(line) {
+            continue;
+        }
+
+        let after_end = (index + 1 + context).min(lines.len());
+        matches.push(SearchMatch {
+            line_number: index + 1,
+            line: truncate_line(line),
+            before: lines[index.saturating_sub(context)..index]
+                .iter()
+                .map(|line| truncate_line(line))
+                .collect(),
+            after: lines[index + 1..after_end]
+                .iter()
+                .map(|line| truncate_line(line))
+                .collect(),
+        });
+
+        if matches.len() >= SEARCH_MAX_MATCHES_PER_FILE {
+            break;
+        }
+    }
+
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_build_matcher() {
+        let literal = build_matcher("DB_PASS.*", SearchMode::Literal, false).unwrap();
+        assert!(literal.is_match("db_pass.* = 1"));
+        assert!(!literal.is_match("DB_PASSWORD=rahasia"));
+
+        let regex = build_matcher(r"DB_PASS\w*=", SearchMode::Regex, true).unwrap();
+        assert!(regex.is_match("DB_PASSWORD=rahasia"));
+        assert!(!regex.is_match("db_password=rahasia"));
+
+        assert!(build_matcher("(unclosed", SearchMode::Regex, false).is_err());
+    }
+
+    #[test]
+    fn test_glob_filter() {
+        let root = Path::new("/home/budi/public_html");
+        let filter = GlobFilter::parse(Some("*.php, node_modules ,vendor/**"))
+            .unwrap()
+            .unwrap();
+
+        assert!(filter.matches(&root.join("app/index.php"), root));
+        assert!(filter.matches(&root.join("assets/node_modules"), root));
+        assert!(filter.matches(&root.join("vendor/autoload.js"), root));
+        assert!(!filter.matches(&root.join("app/main.js"), root));
+
+        assert!(GlobFilter::parse(Some(" , ")).unwrap().is_none());
+        assert!(GlobFilter::parse(Some("[unclosed")).is_err());
+    }
+
+    #[test]
+    fn test_find_matches_with_context() {
+        let text = "<?php\n$host = 'localhost';\n$pass = 'rahasia';\n$db = 'toko';\n";
+        let matcher = build_matcher("$pass", SearchMode::Literal, true).unwrap();
+
+        let matches = find_matches(text, &matcher, 1);
+        assert_eq!(
+            matches,
+            vec![SearchMatch {
+                line_number: 3,
+                line: "$pass = 'rahasia';".to_string(),
+                before: vec!["$host = 'localhost';".to_string()],
+                after: vec!["$db = 'toko';".to_string()],
+            }]
+        );
+
+        // Line ending CRLF tidak ikut di hasil
+        let matches = find_matches("$pass = 1\r\nlain\r\n", &matcher, 3);
+        assert_eq!(matches[0].line, "$pass = 1");
+        assert_eq!(matches[0].after, vec!["lain".to_string()]);
+    }
+
+    #[test]
+    fn test_find_matches_limits() {
+        let text = "x\n".repeat(SEARCH_MAX_MATCHES_PER_FILE + 5);
+        let matcher = build_matcher("x", SearchMode::Literal, false).unwrap();
+        assert_eq!(
+            find_matches(&text, &matcher, 0).len(),
+            SEARCH_MAX_MATCHES_PER_FILE
+        );
+
+        let long = "é".repeat(SEARCH_MAX_LINE_LENGTH + 10);
+        let matcher = build_matcher("é", SearchMode::Literal, false).unwrap();
+        let matches = find_matches(&long, &matcher, 0);
+        assert_eq!(matches[0].line.chars().count(), SEARCH_MAX_LINE_LENGTH + 1);
+    }
+}
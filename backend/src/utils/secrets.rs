@@ -0,0 +1,249 @@
+//! # Secret Utilities
+//!
+//! Enkripsi kredensial yang harus bisa dibaca kembali (mis. password database
+//! user untuk phpMyAdmin SSO) menggunakan AES-256-GCM dengan master key dari
+//! konfigurasi.
+//!
+//! Format ciphertext: `v1:<key_id>:<base64(nonce || ciphertext)>`. ID key ikut
+//! disimpan sehingga master key bisa dirotasi: key lama tetap dipakai untuk
+//! dekripsi sampai semua kredensial dienkripsi ulang dengan key aktif.
+
+use std::collections::HashMap;
+
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use crate::config::{SecretsConfig, CONFIG};
+use crate::errors::ApiError;
+
+/// Versi format ciphertext
+const FORMAT_VERSION: &str = "v1";
+
+/// Panjang nonce AES-GCM (bytes)
+const NONCE_LENGTH: usize = 12;
+
+/// Panjang master key AES-256 (bytes)
+const KEY_LENGTH: usize = 32;
+
+/// Kumpulan master key: key aktif untuk enkripsi, semua key untuk dekripsi
+pub struct Keyring {
+    current_id: String,
+    keys: HashMap<String, Aes256Gcm>,
+}
+
+impl Keyring {
+    /// Buat keyring dari konfigurasi (`None` jika master key belum diset)
+    pub fn from_config(config: &SecretsConfig) -> Result<Option<Self>, ApiError> {
+        let Some(master_key) = &config.master_key else {
+            return Ok(None);
+        };
+
+        let mut keyring = Self {
+            current_id: config.master_key_id.clone(),
+            keys: HashMap::new(),
+        };
+        keyring.add_key(&config.master_key_id, master_key)?;
+
+        for entry in &config.previous_keys {
+            let (id, key) = entry.split_once(':').ok_or_else(|| {
+                ApiError::InternalError("SECRETS_PREVIOUS_KEYS must use id:key format".to_string())
+            })?;
+            keyring.add_key(id.trim(), key.trim())?;
+        }
+
+        Ok(Some(keyring))
+    }
+
+    fn add_key(&mut self, id: &str, encoded: &str) -> Result<(), ApiError> {
+        if id.is_empty() || id.contains(':') {
+            return Err(ApiError::InternalError(format!(
+                "Invalid secrets key id: {:?}",
+                id
+            )));
+        }
+
+        let bytes = STANDARD
+            .decode(encoded.trim())
+            .ok()
+            .filter(|bytes| bytes.len() == KEY_LENGTH)
+            .ok_or_else(|| {
+                ApiError::InternalError(format!(
+                    "Secrets key {} must be {} bytes encoded as base64",
+                    id, KEY_LENGTH
+                ))
+            })?;
+
+        let cipher = Aes256Gcm::new_from_slice(&bytes)
+            .map_err(|_| ApiError::InternalError("Invalid secrets key".to_string()))?;
+        self.keys.insert(id.to_string(), cipher);
+
+        Ok(())
+    }
+
+    /// Enkripsi plaintext dengan key aktif
+    ///
+    /// `context` (mis. ID record) di-bind sebagai associated data sehingga
+    /// ciphertext tidak bisa dipindahkan ke record lain.
+    pub fn encrypt(&self, plaintext: &str, context: &str) -> Result<String, ApiError> {
+        let cipher = &self.keys[&self.current_id];
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+        let ciphertext = cipher
+            .encrypt(
+                &nonce,
+                Payload {
+                    msg: plaintext.as_bytes(),
+                    aad: context.as_bytes(),
+                },
+            )
+            .map_err(|_| ApiError::InternalError("Failed to encrypt secret".to_string()))?;
+
+        let mut sealed = nonce.to_vec();
+        sealed.extend_from_slice(&ciphertext);
+
+        Ok(format!(
+            "{}:{}:{}",
+            FORMAT_VERSION,
+            self.current_id,
+            STANDARD.encode(sealed)
+        ))
+    }
+
+    /// Dekripsi ciphertext dengan key sesuai ID yang tersimpan
+    pub fn decrypt(&self, stored: &str, context: &str) -> Result<String, ApiError> {
+        let invalid = || ApiError::InternalError("Invalid encrypted secret".to_string());
+
+        let (key_id, payload) = parse(stored).ok_or_else(invalid)?;
+        let cipher = self.keys.get(key_id).ok_or_else(|| {
+            ApiError::InternalError(format!("Secrets key {} is not configured", key_id))
+        })?;
+
+        let sealed = STANDARD.decode(payload).map_err(|_| invalid())?;
+        if sealed.len() <= NONCE_LENGTH {
+            return Err(invalid());
+        }
+        let (nonce, ciphertext) = sealed.split_at(NONCE_LENGTH);
+
+        let plaintext = cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: context.as_bytes(),
+                },
+            )
+            .map_err(|_| ApiError::InternalError("Failed to decrypt secret".to_string()))?;
+
+        String::from_utf8(plaintext).map_err(|_| invalid())
+    }
+
+    /// Cek apakah ciphertext belum memakai key aktif
+    pub fn needs_rotation(&self, stored: &str) -> bool {
+        !matches!(parse(stored), Some((key_id, _)) if key_id == self.current_id)
+    }
+}
+
+/// Pisahkan ID key dan payload dari ciphertext
+fn parse(stored: &str) -> Option<(&str, &str)> {
+    let rest = stored.strip_prefix(FORMAT_VERSION)?.strip_prefix(':')?;
+    rest.split_once(':')
+}
+
+/// Keyring dari konfigurasi global
+///
+/// # Errors
+/// Returns ApiError jika master key belum dikonfigurasi atau tidak valid
+pub fn keyring() -> Result<Keyring, ApiError> {
+    Keyring::from_config(&CONFIG.secrets)?
+        .ok_or_else(|| ApiError::InternalError("SECRETS_MASTER_KEY is not configured".to_string()))
+}
+
+/// Cek apakah master key sudah dikonfigurasi
+pub fn is_enabled() -> bool {
+    CONFIG.secrets.master_key.is_some()
+}
+
+/// Enkripsi secret jika master key tersedia (`None` jika enkripsi nonaktif)
+///
+/// # Example
+/// ```rust
+/// let encrypted = encrypt_if_enabled("MySecureP@ss123", &db_user_id)?;
+/// ```
+pub fn encrypt_if_enabled(plaintext: &str, context: &str) -> Result<Option<String>, ApiError> {
+    if !is_enabled() {
+        return Ok(None);
+    }
+
+    keyring()?.encrypt(plaintext, context).map(Some)
+}
+
+/// Dekripsi secret dengan master key dari konfigurasi
+pub fn decrypt(stored: &str, context: &str) -> Result<String, ApiError> {
+    keyring()?.decrypt(stored, context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(id: &str, key: u8, previous: &[(&str, u8)]) -> SecretsConfig {
+        SecretsConfig {
+            master_key: Some(STANDARD.encode([key; KEY_LENGTH])),
+            master_key_id: id.to_string(),
+            previous_keys: previous
+                .iter()
+                .map(|(id, key)| format!("{}:{}", id, STANDARD.encode([*key; KEY_LENGTH])))
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_encrypt_decrypt_roundtrip() {
+        let keyring = Keyring::from_config(&config("1", 7, &[])).unwrap().unwrap();
+
+        let sealed = keyring.encrypt("S3cret!pass", "user-1").unwrap();
+        assert!(sealed.starts_with("v1:1:"));
+        assert_ne!(sealed, keyring.encrypt("S3cret!pass", "user-1").unwrap());
+        assert_eq!(keyring.decrypt(&sealed, "user-1").unwrap(), "S3cret!pass");
+
+        // Associated data berbeda atau ciphertext diubah harus gagal
+        assert!(keyring.decrypt(&sealed, "user-2").is_err());
+        let mut tampered = sealed.clone();
+        tampered.replace_range(sealed.len() - 4.., "AAAA");
+        assert!(keyring.decrypt(&tampered, "user-1").is_err());
+        assert!(keyring.decrypt("plaintext", "user-1").is_err());
+    }
+
+    #[test]
+    fn test_key_rotation() {
+        let old = Keyring::from_config(&config("1", 7, &[])).unwrap().unwrap();
+        let sealed = old.encrypt("S3cret!pass", "user-1").unwrap();
+
+        let rotated = Keyring::from_config(&config("2", 9, &[("1", 7)]))
+            .unwrap()
+            .unwrap();
+        assert!(rotated.needs_rotation(&sealed));
+        assert_eq!(rotated.decrypt(&sealed, "user-1").unwrap(), "S3cret!pass");
+
+        let resealed = rotated.encrypt("S3cret!pass", "user-1").unwrap();
+        assert!(!rotated.needs_rotation(&resealed));
+
+        // Tanpa key lama ciphertext lama tidak bisa dibuka
+        let without_old = Keyring::from_config(&config("2", 9, &[])).unwrap().unwrap();
+        assert!(without_old.decrypt(&sealed, "user-1").is_err());
+    }
+
+    #[test]
+    fn test_invalid_keys() {
+        let mut short = config("1", 7, &[]);
+        short.master_key = Some(STANDARD.encode([7u8; 16]));
+        assert!(Keyring::from_config(&short).is_err());
+
+        assert!(Keyring::from_config(&config("a:b", 7, &[])).is_err());
+
+        let mut disabled = config("1", 7, &[]);
+        disabled.master_key = None;
+        assert!(Keyring::from_config(&disabled).unwrap().is_none());
+    }
+}
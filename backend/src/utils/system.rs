@@ -1,7 +1,14 @@
+use std::collections::HashMap;
 use std::process::{Command, Stdio};
 use std::io::Write;
+use std::sync::RwLock;
+use once_cell::sync::Lazy;
 use crate::errors::{ApiError, ApiResult};
 
+/// Cache nama user (uid) dan group (gid) dari /etc/passwd dan /etc/group
+static USER_NAMES: Lazy<RwLock<HashMap<u32, String>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+static GROUP_NAMES: Lazy<RwLock<HashMap<u32, String>>> = Lazy::new(|| RwLock::new(HashMap::new()));
+
 /// Ensure Linux system user exists for the given panel user.
 /// If not, it creates it.
 /// 
@@ -119,3 +126,86 @@ pub fn ensure_directory(path: &str, owner: &str) -> ApiResult<()> {
 
     Ok(())
 }
+
+/// Ubah owner/group file atau directory menggunakan sudo chown
+///
+/// Symlink tidak diikuti (`-h`) sehingga target di luar sandbox tidak ikut berubah.
+pub fn change_ownership(path: &str, owner: &str, group: &str, recursive: bool) -> ApiResult<()> {
+    let mut command = Command::new("sudo");
+    command.arg("chown").arg("-h");
+    if recursive {
+        command.arg("-R");
+    }
+
+    let output = command
+        .arg(format!("{}:{}", owner, group))
+        .arg("--")
+        .arg(path)
+        .output()
+        .map_err(|e| ApiError::InternalError(format!("Failed to execute chown: {}", e)))?;
+
+    if !output.status.success() {
+        let error_msg = String::from_utf8_lossy(&output.stderr);
+        return Err(ApiError::InternalError(format!("Failed to set ownership for '{}': {}", path, error_msg.trim())));
+    }
+
+    Ok(())
+}
+
+/// Nama system user untuk uid (fallback ke angka uid)
+pub fn user_name(uid: u32) -> String {
+    lookup_name(&USER_NAMES, "/etc/passwd", uid)
+}
+
+/// Nama system group untuk gid (fallback ke angka gid)
+pub fn group_name(gid: u32) -> String {
+    lookup_name(&GROUP_NAMES, "/etc/group", gid)
+}
+
+/// Cari nama id di cache, baca ulang database jika belum ada (user baru)
+fn lookup_name(cache: &RwLock<HashMap<u32, String>>, database: &str, id: u32) -> String {
+    if let Some(name) = cache.read().ok().and_then(|names| names.get(&id).cloned()) {
+        return name;
+    }
+
+    let entries = std::fs::read_to_string(database)
+        .map(|content| parse_id_names(&content))
+        .unwrap_or_default();
+    let name = entries.get(&id).cloned().unwrap_or_else(|| id.to_string());
+
+    if let Ok(mut names) = cache.write() {
+        names.extend(entries);
+    }
+
+    name
+}
+
+/// Parse format `name:password:id:...` milik /etc/passwd dan /etc/group
+fn parse_id_names(content: &str) -> HashMap<u32, String> {
+    content
+        .lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let mut fields = line.split(':');
+            let name = fields.next()?;
+            let id = fields.nth(1)?.parse().ok()?;
+            Some((id, name.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_id_names() {
+        let names = parse_id_names(
+            "# comment\nroot:x:0:0:root:/root:/bin/bash\nuser_budi:x:1001:1001::/home/user_budi:/bin/false\nbroken\n",
+        );
+
+        assert_eq!(names.len(), 2);
+        assert_eq!(names[&0], "root");
+        assert_eq!(names[&1001], "user_budi");
+    }
+}
@@ -1,9 +1,17 @@
 import api from './api';
 import type {
     ApiResponse,
+    DiskUsage,
     FileInfo,
     FileListResponse,
     FileContentResponse,
+    FileDiffResponse,
+    FileVersionsResponse,
+    Job,
+    SearchOptions,
+    SearchResponse,
+    TrashItem,
+    TrashListResponse,
 } from '@/types';
 
 /**
@@ -61,14 +69,14 @@ export const fileService = {
         api.put<ApiResponse<FileInfo>>('/files/rename', data),
 
     /**
-     * Menyalin file atau direktori
+     * Menyalin file atau direktori (background job)
      * @param data Path sumber dan tujuan
      */
     copy: (data: {
         source: string;
         destination: string;
         overwrite?: boolean;
-    }) => api.post<ApiResponse<FileInfo>>('/files/copy', data),
+    }) => api.post<ApiResponse<Job<FileInfo>>>('/files/copy', data),
 
     /**
      * Memindahkan file atau direktori
@@ -81,41 +89,96 @@ export const fileService = {
     }) => api.post<ApiResponse<FileInfo>>('/files/move', data),
 
     /**
-     * Menghapus file atau direktori
-     * @param data Path dan opsi rekursif
+     * Menghapus file atau direktori (default dipindahkan ke trash)
+     * @param data Path, opsi rekursif dan permanent (tanpa trash)
      */
-    delete: (data: { path: string; recursive?: boolean }) =>
-        api.post<ApiResponse<void>>('/files/delete', data),
+    delete: (data: { path: string; recursive?: boolean; permanent?: boolean }) =>
+        api.post<ApiResponse<TrashItem | null>>('/files/delete', data),
 
     /**
-     * Compress files/directories ke zip archive
+     * Mendapatkan isi trash (terbaru lebih dulu)
+     */
+    listTrash: () => api.get<ApiResponse<TrashListResponse>>('/files/trash'),
+
+    /**
+     * Mengembalikan item trash ke path asli
+     * @param id ID item trash
+     */
+    restoreTrash: (id: string) =>
+        api.post<ApiResponse<FileInfo>>(`/files/trash/${id}/restore`),
+
+    /**
+     * Menghapus permanen satu item trash
+     * @param id ID item trash
+     */
+    purgeTrash: (id: string) => api.delete<ApiResponse<void>>(`/files/trash/${id}`),
+
+    /**
+     * Mengosongkan trash
+     */
+    emptyTrash: () => api.delete<ApiResponse<void>>('/files/trash'),
+
+    /**
+     * Mendapatkan pemakaian dan kuota disk user
+     */
+    getQuota: () => api.get<ApiResponse<DiskUsage>>('/files/quota'),
+
+    /**
+     * Mendapatkan riwayat revisi file yang disimpan lewat editor
+     * @param path Path file
+     */
+    listVersions: (path: string) =>
+        api.get<ApiResponse<FileVersionsResponse>>('/files/versions', { params: { path } }),
+
+    /**
+     * Unified diff antar revisi atau terhadap konten saat ini
+     * @param path Path file
+     * @param from ID revisi asal
+     * @param to ID revisi tujuan (kosong = konten saat ini)
+     */
+    diffVersions: (path: string, from: string, to?: string) =>
+        api.get<ApiResponse<FileDiffResponse>>('/files/versions/diff', {
+            params: { path, from, to },
+        }),
+
+    /**
+     * Mengembalikan file ke revisi sebelumnya
+     * @param path Path file
+     * @param revision ID revisi
+     */
+    restoreVersion: (path: string, revision: string) =>
+        api.post<ApiResponse<FileInfo>>('/files/versions/restore', { path, revision }),
+
+    /**
+     * Compress files/directories ke archive (background job)
      * @param data Files yang akan di-compress dan nama archive
      */
     compress: (data: {
         paths: string[];
         archive_name: string;
         format?: string;
-    }) => api.post<ApiResponse<FileInfo>>('/files/compress', data),
+    }) => api.post<ApiResponse<Job<FileInfo>>>('/files/compress', data),
 
     /**
-     * Extract zip archive
+     * Extract archive (background job)
      * @param data Path archive dan destination
      */
     extract: (data: {
         archive_path: string;
         destination: string;
         overwrite?: boolean;
-    }) => api.post<ApiResponse<FileInfo>>('/files/extract', data),
+    }) => api.post<ApiResponse<Job<FileInfo>>>('/files/extract', data),
 
     /**
-     * Mencari file berdasarkan nama
+     * Mencari file berdasarkan nama atau isi file (background job)
      * @param query Query pencarian
      * @param path Optional path untuk memulai pencarian
      * @param limit Maximum hasil (default 50)
+     * @param options Mode (name, literal, regex), filter glob dan konteks
      */
-    search: (query: string, path?: string, limit: number = 50) =>
-        api.get<ApiResponse<FileInfo[]>>('/files/search', {
-            params: { query, path, limit },
+    search: (query: string, path?: string, limit: number = 50, options: SearchOptions = {}) =>
+        api.get<ApiResponse<Job<SearchResponse>>>('/files/search', {
+            params: { query, path, limit, ...options },
         }),
 
     /**
@@ -1,5 +1,6 @@
 import { authService } from './auth.service';
 import { fileService } from './file.service';
+import { jobService } from './job.service';
 import { userService } from './user.service';
 import { systemService } from './system.service';
 import { domainService } from './domain.service';
@@ -19,6 +20,7 @@ import { nodejsService } from './nodejs.service';
 export {
     authService,
     fileService,
+    jobService,
     userService,
     systemService,
     domainService,
@@ -36,6 +38,7 @@ export {
 export default {
     auth: authService,
     files: fileService,
+    jobs: jobService,
     users: userService,
     system: systemService,
     domains: domainService,
@@ -0,0 +1,56 @@
+import api from './api';
+import type { ApiResponse, Job } from '@/types';
+
+/** Interval polling status job (ms) */
+const POLL_INTERVAL = 1000;
+
+/**
+ * Background Job Service
+ * Status, progress dan pembatalan operasi file yang berjalan di background.
+ */
+export const jobService = {
+    /**
+     * Mendapatkan daftar job user (terbaru lebih dulu)
+     * @param limit Maximum job (default 50)
+     */
+    list: (limit: number = 50) =>
+        api.get<ApiResponse<Job[]>>('/jobs', { params: { limit } }),
+
+    /**
+     * Mendapatkan status dan progress job
+     * @param id ID job
+     */
+    get: <T = unknown>(id: string) => api.get<ApiResponse<Job<T>>>(`/jobs/${id}`),
+
+    /**
+     * Membatalkan job yang masih pending atau running
+     * @param id ID job
+     */
+    cancel: (id: string) => api.post<ApiResponse<Job>>(`/jobs/${id}/cancel`),
+
+    /**
+     * Menunggu job selesai dengan polling
+     * @param id ID job
+     * @param onProgress Callback untuk progress update (0-100)
+     * @returns Job yang sudah selesai; error jika gagal atau dibatalkan
+     */
+    wait: async <T = unknown>(
+        id: string,
+        onProgress?: (percent: number) => void,
+    ): Promise<Job<T>> => {
+        for (;;) {
+            const response = await jobService.get<T>(id);
+            const job = response.data.data;
+            onProgress?.(job.progress_percent);
+
+            if (job.status === 'completed') return job;
+            if (job.status === 'failed' || job.status === 'cancelled') {
+                throw new Error(job.error_message || `Job ${job.status}`);
+            }
+
+            await new Promise((resolve) => setTimeout(resolve, POLL_INTERVAL));
+        }
+    },
+};
+
+export default jobService;
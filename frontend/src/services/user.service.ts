@@ -4,7 +4,8 @@ import type {
   PaginatedResponse, 
   UserResponse, 
   UpdateUserRequest,
-  UserResourceUsage
+  UserResourceUsage,
+  DiskUsage
 } from '@/types'
 
 /**
@@ -63,7 +64,15 @@ export const userService = {
    * @param id User ID
    */
   getUsage: (id: string) => 
-    api.get<ApiResponse<UserResourceUsage>>(`/users/${id}/usage`)
+    api.get<ApiResponse<UserResourceUsage>>(`/users/${id}/usage`),
+
+  /**
+   * Mengubah kuota disk user (Admin)
+   * @param id User ID
+   * @param diskQuotaMb Kuota dalam MB (null = default server, 0 = unlimited)
+   */
+  updateQuota: (id: string, diskQuotaMb: number | null) =>
+    api.put<ApiResponse<DiskUsage>>(`/users/${id}/quota`, { disk_quota_mb: diskQuotaMb })
 }
 
 export default userService
@@ -105,6 +105,99 @@ export interface FileContentResponse {
     mime_type?: string;
 }
 
+// File Search Interface
+export interface SearchMatch {
+    line_number: number;
+    line: string;
+    before: string[];
+    after: string[];
+}
+
+export interface SearchResult extends FileInfo {
+    matches?: SearchMatch[];
+}
+
+export interface SearchResponse {
+    results: SearchResult[];
+    files_scanned: number;
+    files_skipped: number;
+    truncated: boolean;
+    timed_out: boolean;
+}
+
+export interface SearchOptions {
+    mode?: 'name' | 'literal' | 'regex';
+    case_sensitive?: boolean;
+    include?: string;
+    exclude?: string;
+    context?: number;
+}
+
+// Disk Quota Interface
+export interface DiskUsage {
+    used_bytes: number;
+    limit_bytes: number | null;
+    available_bytes: number | null;
+    usage_percent: number | null;
+    source: 'quota' | 'du';
+}
+
+// Trash Interface
+export interface TrashItem {
+    id: string;
+    name: string;
+    original_path: string;
+    file_type: 'file' | 'directory' | 'symlink';
+    size: number;
+    deleted_at: string;
+}
+
+export interface TrashListResponse {
+    items: TrashItem[];
+    total_size: number;
+    retention_days: number;
+}
+
+// File Version Interface
+export interface FileRevision {
+    id: string;
+    size: number;
+    sha256: string;
+    created_at: string;
+}
+
+export interface FileVersionsResponse {
+    path: string;
+    revisions: FileRevision[];
+    max_revisions: number;
+}
+
+export interface FileDiffResponse {
+    path: string;
+    from: string;
+    to: string | null;
+    diff: string;
+}
+
+// Background Job Interface
+export type JobStatus = 'pending' | 'running' | 'completed' | 'failed' | 'cancelled';
+
+export interface Job<T = unknown> {
+    id: string;
+    user_id: string;
+    kind: 'compress' | 'extract' | 'copy' | 'search';
+    description: string;
+    status: JobStatus;
+    total_bytes: number;
+    processed_bytes: number;
+    progress_percent: number;
+    result: T | null;
+    error_message: string | null;
+    created_at: string;
+    started_at: string | null;
+    completed_at: string | null;
+}
+
 // System Interface
 export interface CronJob {
     id: string;